@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use super::less_than::BigIntLessThanInstruction;
+use super::mul_mod::BigIntMulModInstruction;
+use crate::air::AirConstraint;
+use crate::chip::ec::scalar::LimbBitInstruction;
+use crate::chip::instruction::Instruction;
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::uint::bytes::decode::ByteDecodeInstruction;
+use crate::chip::uint::bytes::lookup_table::{ByteInstructionSet, ByteInstructions};
+use crate::chip::uint::bytes::operations::instruction::ByteOperationInstruction;
+use crate::chip::uint::bytes::operations::value::ByteOperationDigestConstraint;
+use crate::chip::uint::operations::add::ByteArrayAdd;
+use crate::chip::uint::operations::instruction::{UintInstruction, UintInstructions};
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BigIntInstruction {
+    MulMod(BigIntMulModInstruction),
+    LessThan(BigIntLessThanInstruction),
+}
+
+pub trait FromBigIntInstruction:
+    From<BigIntMulModInstruction> + From<BigIntLessThanInstruction>
+{
+}
+
+impl FromBigIntInstruction for BigIntInstruction {}
+
+impl<AP: PolynomialParser> AirConstraint<AP> for BigIntInstruction {
+    fn eval(&self, parser: &mut AP) {
+        match self {
+            BigIntInstruction::MulMod(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+            BigIntInstruction::LessThan(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+        }
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for BigIntInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        match self {
+            BigIntInstruction::MulMod(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            BigIntInstruction::LessThan(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+        }
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        match self {
+            BigIntInstruction::MulMod(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            BigIntInstruction::LessThan(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+        }
+    }
+}
+
+impl From<BigIntMulModInstruction> for BigIntInstruction {
+    fn from(instr: BigIntMulModInstruction) -> Self {
+        BigIntInstruction::MulMod(instr)
+    }
+}
+
+impl From<BigIntLessThanInstruction> for BigIntInstruction {
+    fn from(instr: BigIntLessThanInstruction) -> Self {
+        BigIntInstruction::LessThan(instr)
+    }
+}
+
+/// Instructions for RSA signature verification: the byte operations of SHA-256, the big integer
+/// arithmetic and the bit decomposition of the exponentiation schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RSAInstruction {
+    Uint(UintInstruction),
+    BigInt(BigIntInstruction),
+    Bit(LimbBitInstruction),
+}
+
+impl ByteInstructions for RSAInstruction {}
+
+impl UintInstructions for RSAInstruction {}
+
+impl FromBigIntInstruction for RSAInstruction {}
+
+impl<AP: PolynomialParser> AirConstraint<AP> for RSAInstruction {
+    fn eval(&self, parser: &mut AP) {
+        match self {
+            RSAInstruction::Uint(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+            RSAInstruction::BigInt(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+            RSAInstruction::Bit(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+        }
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for RSAInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        match self {
+            RSAInstruction::Uint(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            RSAInstruction::BigInt(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            RSAInstruction::Bit(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+        }
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        match self {
+            RSAInstruction::Uint(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            RSAInstruction::BigInt(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            RSAInstruction::Bit(instruction) => Instruction::<F>::write_to_air(instruction, writer),
+        }
+    }
+}
+
+impl From<UintInstruction> for RSAInstruction {
+    fn from(i: UintInstruction) -> Self {
+        Self::Uint(i)
+    }
+}
+
+impl From<ByteInstructionSet> for RSAInstruction {
+    fn from(i: ByteInstructionSet) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteArrayAdd<4>> for RSAInstruction {
+    fn from(i: ByteArrayAdd<4>) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteOperationInstruction> for RSAInstruction {
+    fn from(i: ByteOperationInstruction) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteDecodeInstruction> for RSAInstruction {
+    fn from(i: ByteDecodeInstruction) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteOperationDigestConstraint> for RSAInstruction {
+    fn from(i: ByteOperationDigestConstraint) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<BigIntMulModInstruction> for RSAInstruction {
+    fn from(i: BigIntMulModInstruction) -> Self {
+        Self::BigInt(i.into())
+    }
+}
+
+impl From<BigIntLessThanInstruction> for RSAInstruction {
+    fn from(i: BigIntLessThanInstruction) -> Self {
+        Self::BigInt(i.into())
+    }
+}
+
+impl From<LimbBitInstruction> for RSAInstruction {
+    fn from(i: LimbBitInstruction) -> Self {
+        Self::Bit(i)
+    }
+}
@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+
+use super::NB_BITS_PER_LIMB;
+use crate::air::parser::AirParser;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+
+/// Comparison of big integers. Checks that `a < b`.
+///
+/// This is done by witnessing the difference `d = b - a - 1` and constraining the limbs of `a`
+/// and `d` to add up to the limbs of `b - 1` with carries, so that `a + d + 1 = b` in the
+/// integers. As the limbs of `d` are range checked, this is only possible if `a < b`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BigIntLessThanInstruction {
+    pub a: ArrayRegister<U16Register>,
+    pub b: ArrayRegister<U16Register>,
+    pub(crate) difference: ArrayRegister<U16Register>,
+    pub(crate) carry: ArrayRegister<BitRegister>,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Asserts that the integer `a` is less than the integer `b`, with the same number of limbs.
+    pub fn bigint_assert_less_than(
+        &mut self,
+        a: &ArrayRegister<U16Register>,
+        b: &ArrayRegister<U16Register>,
+    ) where
+        L::Instruction: From<BigIntLessThanInstruction>,
+    {
+        let nb_limbs = b.len();
+        assert_eq!(a.len(), nb_limbs, "Operand lengths mismatch");
+
+        let is_trace = a.is_trace() || b.is_trace();
+
+        let difference: ArrayRegister<U16Register>;
+        let carry: ArrayRegister<BitRegister>;
+
+        if is_trace {
+            difference = self.alloc_array::<U16Register>(nb_limbs);
+            carry = self.alloc_array::<BitRegister>(nb_limbs - 1);
+        } else {
+            difference = self.alloc_array_public::<U16Register>(nb_limbs);
+            carry = self.alloc_array_public::<BitRegister>(nb_limbs - 1);
+        }
+
+        let instr = BigIntLessThanInstruction {
+            a: *a,
+            b: *b,
+            difference,
+            carry,
+        };
+
+        self.assert_range_check_bits(NB_BITS_PER_LIMB);
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+    }
+}
+
+impl<AP: AirParser> AirConstraint<AP> for BigIntLessThanInstruction {
+    fn eval(&self, parser: &mut AP) {
+        let limb = AP::Field::from_canonical_u32(1 << NB_BITS_PER_LIMB);
+        let nb_limbs = self.b.len();
+
+        // The carry into the first limb is the one of `a + d + 1`.
+        let mut carry_in = parser.one();
+        for i in 0..nb_limbs {
+            let a = self.a.get(i).eval(parser);
+            let b = self.b.get(i).eval(parser);
+            let d = self.difference.get(i).eval(parser);
+
+            let a_plus_d = parser.add(a, d);
+            let sum = parser.add(a_plus_d, carry_in);
+            let mut constraint = parser.sub(sum, b);
+            if i < nb_limbs - 1 {
+                let carry_out = self.carry.get(i).eval(parser);
+                let carry_out_shifted = parser.mul_const(carry_out, limb);
+                constraint = parser.sub(constraint, carry_out_shifted);
+                carry_in = carry_out;
+            }
+            parser.constraint(constraint);
+        }
+    }
+}
+
+impl BigIntLessThanInstruction {
+    /// Computes the values of `difference` and `carry` from the values of `a` and `b`.
+    fn witness<F: PrimeField64>(a: &[F], b: &[F]) -> (Vec<F>, Vec<F>) {
+        let nb_limbs = b.len();
+        let mut difference = Vec::with_capacity(nb_limbs);
+        let mut carry = Vec::with_capacity(nb_limbs - 1);
+
+        // Compute `d = b - a - 1` limb by limb, with the borrows of the subtraction being the
+        // carries of the addition `a + d + 1`.
+        let mut borrow = 1u64;
+        for (a_limb, b_limb) in a.iter().zip(b.iter()) {
+            let subtrahend = a_limb.as_canonical_u64() + borrow;
+            let b_limb = b_limb.as_canonical_u64();
+            let (d, next_borrow) = if b_limb >= subtrahend {
+                (b_limb - subtrahend, 0)
+            } else {
+                (b_limb + (1 << NB_BITS_PER_LIMB) - subtrahend, 1)
+            };
+            difference.push(F::from_canonical_u64(d));
+            carry.push(F::from_canonical_u64(next_borrow));
+            borrow = next_borrow;
+        }
+        assert_eq!(borrow, 0, "The first integer is not less than the second");
+        carry.pop();
+
+        (difference, carry)
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for BigIntLessThanInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let a = writer.read_vec(&self.a, row_index);
+        let b = writer.read_vec(&self.b, row_index);
+        let (difference, carry) = Self::witness(&a, &b);
+
+        writer.write_array(&self.difference, &difference, row_index);
+        writer.write_array(&self.carry, &carry, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let a = writer.read_vec(&self.a);
+        let b = writer.read_vec(&self.b);
+        let (difference, carry) = Self::witness(&a, &b);
+
+        writer.write_array(&self.difference, &difference);
+        writer.write_array(&self.carry, &carry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::BigUint;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct BigIntLessThanTest;
+
+    impl AirParameters for BigIntLessThanTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 96;
+        const NUM_FREE_COLUMNS: usize = 33;
+        const EXTENDED_COLUMNS: usize = 153;
+
+        type Instruction = BigIntLessThanInstruction;
+    }
+
+    #[test]
+    fn test_bigint_less_than() {
+        type F = GoldilocksField;
+        type L = BigIntLessThanTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        let nb_limbs = 32;
+        let nb_limbs_pub = 128;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_array_public::<U16Register>(nb_limbs_pub);
+        let b_pub = builder.alloc_array_public::<U16Register>(nb_limbs_pub);
+        builder.bigint_assert_less_than(&a_pub, &b_pub);
+
+        let a = builder.alloc_array::<U16Register>(nb_limbs);
+        let b = builder.alloc_array::<U16Register>(nb_limbs);
+        builder.bigint_assert_less_than(&a, &b);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            let b_int = rng.gen_biguint(16 * nb_limbs as u64 - 1) + 1u32;
+            // Include the largest possible value of `a`.
+            let a_int = if i % 4 == 0 {
+                &b_int - 1u32
+            } else {
+                rng.gen_biguint_below(&b_int)
+            };
+            for (register, value) in [(a, &a_int), (b, &b_int)] {
+                let p_value = Polynomial::<F>::from_biguint_field(value, 16, nb_limbs);
+                writer.write_array(&register, p_value.coefficients(), i);
+            }
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let writer = generator.new_writer();
+        let b_int = rng.gen_biguint(16 * nb_limbs_pub as u64 - 1) + 1u32;
+        let a_int: BigUint = rng.gen_biguint_below(&b_int);
+        for (register, value) in [(a_pub, &a_int), (b_pub, &b_int)] {
+            let p_value = Polynomial::<F>::from_biguint_field(value, 16, nb_limbs_pub);
+            writer.write_array(&register, p_value.coefficients(), 0);
+        }
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -0,0 +1,25 @@
+//! Arithmetic of big integers modulo a modulus given by a register, as in RSA.
+//!
+//! The integers are arrays of `nb_limbs` little-endian 16-bit limbs, whose number is only known
+//! when the instructions are built. The products are checked with the same technique as the
+//! field operations of `chip::field`, with a quotient witnessed in place of the carry. In
+//! memory, the integers are stored by blocks of limbs of a fixed size.
+
+use plonky2::util::log2_ceil;
+
+pub mod instruction;
+pub mod less_than;
+pub mod mul_mod;
+pub mod register;
+
+/// The number of bits of the limbs of the integers.
+pub const NB_BITS_PER_LIMB: usize = 16;
+
+/// The offset of the coefficients of the witness polynomial of a product of integers with
+/// `nb_limbs` limbs.
+///
+/// The coefficients are less than `2^(16 + log2(nb_limbs) + 1)` in absolute value, so they are
+/// positive after the shift and less than `2^32` for up to `2^14` limbs.
+pub fn witness_offset(nb_limbs: usize) -> usize {
+    1 << (17 + log2_ceil(nb_limbs))
+}
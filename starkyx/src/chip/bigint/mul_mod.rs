@@ -0,0 +1,301 @@
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::{witness_offset, NB_BITS_PER_LIMB};
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::util;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::RegisterSerializable;
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::{digits_to_biguint, split_witness_limbs};
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+use crate::polynomial::Polynomial;
+
+/// Modular multiplication of big integers. Computes `a * b = result mod modulus`.
+///
+/// The quotient is witnessed such that `a * b - result - quotient * modulus = 0` in the integers,
+/// which is checked as a polynomial identity as in `FpMulInstruction`. The result is reduced by
+/// the writer, but it is not constrained to be less than the modulus.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BigIntMulModInstruction {
+    pub a: ArrayRegister<U16Register>,
+    pub b: ArrayRegister<U16Register>,
+    pub modulus: ArrayRegister<U16Register>,
+    pub result: ArrayRegister<U16Register>,
+    pub(crate) quotient: ArrayRegister<U16Register>,
+    pub(crate) witness_low: ArrayRegister<U16Register>,
+    pub(crate) witness_high: ArrayRegister<U16Register>,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Given integers `a` and `b` with the same number of limbs as `modulus`, computes the product
+    /// `a * b mod modulus`.
+    pub fn bigint_mul_mod(
+        &mut self,
+        a: &ArrayRegister<U16Register>,
+        b: &ArrayRegister<U16Register>,
+        modulus: &ArrayRegister<U16Register>,
+    ) -> ArrayRegister<U16Register>
+    where
+        L::Instruction: From<BigIntMulModInstruction>,
+    {
+        let nb_limbs = modulus.len();
+        assert_eq!(a.len(), nb_limbs, "Operand and modulus lengths mismatch");
+        assert_eq!(b.len(), nb_limbs, "Operand and modulus lengths mismatch");
+        let nb_witness_limbs = 2 * nb_limbs - 2;
+
+        let is_trace = a.is_trace() || b.is_trace() || modulus.is_trace();
+
+        let result: ArrayRegister<U16Register>;
+        let quotient: ArrayRegister<U16Register>;
+        let witness_low: ArrayRegister<U16Register>;
+        let witness_high: ArrayRegister<U16Register>;
+
+        if is_trace {
+            result = self.alloc_array::<U16Register>(nb_limbs);
+            quotient = self.alloc_array::<U16Register>(nb_limbs);
+            witness_low = self.alloc_array::<U16Register>(nb_witness_limbs);
+            witness_high = self.alloc_array::<U16Register>(nb_witness_limbs);
+        } else {
+            result = self.alloc_array_public::<U16Register>(nb_limbs);
+            quotient = self.alloc_array_public::<U16Register>(nb_limbs);
+            witness_low = self.alloc_array_public::<U16Register>(nb_witness_limbs);
+            witness_high = self.alloc_array_public::<U16Register>(nb_witness_limbs);
+        }
+        let instr = BigIntMulModInstruction {
+            a: *a,
+            b: *b,
+            modulus: *modulus,
+            result,
+            quotient,
+            witness_low,
+            witness_high,
+        };
+
+        self.assert_range_check_bits(NB_BITS_PER_LIMB);
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+        result
+    }
+}
+
+impl<AP: PolynomialParser> AirConstraint<AP> for BigIntMulModInstruction {
+    fn eval(&self, parser: &mut AP) {
+        let p_a = Polynomial::from_coefficients(self.a.eval_vec(parser));
+        let p_b = Polynomial::from_coefficients(self.b.eval_vec(parser));
+        let p_modulus = Polynomial::from_coefficients(self.modulus.eval_vec(parser));
+        let p_result = Polynomial::from_coefficients(self.result.eval_vec(parser));
+        let p_quotient = Polynomial::from_coefficients(self.quotient.eval_vec(parser));
+
+        // Compute the vanishing polynomial a(x) * b(x) - result(x) - quotient(x) * modulus(x).
+        let p_a_mul_b = parser.poly_mul(&p_a, &p_b);
+        let p_a_mul_b_minus_result = parser.poly_sub(&p_a_mul_b, &p_result);
+        let p_quotient_mul_modulus = parser.poly_mul(&p_quotient, &p_modulus);
+        let p_vanishing = parser.poly_sub(&p_a_mul_b_minus_result, &p_quotient_mul_modulus);
+
+        let p_witness_low = Polynomial::from_coefficients(self.witness_low.eval_vec(parser));
+        let p_witness_high = Polynomial::from_coefficients(self.witness_high.eval_vec(parser));
+
+        util::eval_root_quotient(
+            parser,
+            &p_vanishing,
+            &p_witness_low,
+            &p_witness_high,
+            witness_offset(self.modulus.len()),
+            NB_BITS_PER_LIMB,
+        )
+    }
+}
+
+impl BigIntMulModInstruction {
+    /// Computes the values of `result`, `quotient`, `witness_low` and `witness_high` from the
+    /// values of `a`, `b` and `modulus`.
+    fn witness<F: PrimeField64>(
+        a: &[F],
+        b: &[F],
+        modulus: &[F],
+    ) -> (Vec<F>, Vec<F>, Vec<F>, Vec<F>) {
+        let to_biguint = |limbs: &[F]| {
+            let digits = limbs
+                .iter()
+                .map(|x| x.as_canonical_u64() as u16)
+                .collect::<Vec<_>>();
+            digits_to_biguint(&digits)
+        };
+        let nb_limbs = modulus.len();
+        let a_int = to_biguint(a);
+        let b_int = to_biguint(b);
+        let modulus_int = to_biguint(modulus);
+        assert!(!modulus_int.is_zero(), "Modulus must be nonzero");
+
+        // Compute the modular multiplication in the integers.
+        let product = &a_int * &b_int;
+        let result = &product % &modulus_int;
+        let quotient: BigUint = (&product - &result) / &modulus_int;
+        debug_assert_eq!(&quotient * &modulus_int + &result, product);
+
+        let p_a = Polynomial::from_coefficients_slice(a);
+        let p_b = Polynomial::from_coefficients_slice(b);
+        let p_modulus = Polynomial::from_coefficients_slice(modulus);
+        let p_result = Polynomial::<F>::from_biguint_field(&result, NB_BITS_PER_LIMB, nb_limbs);
+        let p_quotient = Polynomial::<F>::from_biguint_field(&quotient, NB_BITS_PER_LIMB, nb_limbs);
+
+        // Compute the vanishing polynomial and the witness.
+        let p_vanishing = &p_a * &p_b - &p_result - &p_quotient * &p_modulus;
+        debug_assert_eq!(p_vanishing.degree(), 2 * nb_limbs - 2);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            witness_offset(nb_limbs),
+            NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, NB_BITS_PER_LIMB);
+
+        (
+            p_result.coefficients,
+            p_quotient.coefficients,
+            p_witness_low,
+            p_witness_high,
+        )
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for BigIntMulModInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let a = writer.read_vec(&self.a, row_index);
+        let b = writer.read_vec(&self.b, row_index);
+        let modulus = writer.read_vec(&self.modulus, row_index);
+        let (result, quotient, witness_low, witness_high) = Self::witness(&a, &b, &modulus);
+
+        writer.write_array(&self.result, &result, row_index);
+        writer.write_array(&self.quotient, &quotient, row_index);
+        writer.write_array(&self.witness_low, &witness_low, row_index);
+        writer.write_array(&self.witness_high, &witness_high, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let a = writer.read_vec(&self.a);
+        let b = writer.read_vec(&self.b);
+        let modulus = writer.read_vec(&self.modulus);
+        let (result, quotient, witness_low, witness_high) = Self::witness(&a, &b, &modulus);
+
+        writer.write_array(&self.result, &result);
+        writer.write_array(&self.quotient, &quotient);
+        writer.write_array(&self.witness_low, &witness_low);
+        writer.write_array(&self.witness_high, &witness_high);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::One;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct BigIntMulModTest;
+
+    impl AirParameters for BigIntMulModTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 284;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 435;
+
+        type Instruction = BigIntMulModInstruction;
+    }
+
+    #[test]
+    fn test_bigint_mul_mod() {
+        type F = GoldilocksField;
+        type L = BigIntMulModTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        // A 512-bit multiplication in the trace and a 2048-bit one in the public inputs.
+        let nb_limbs = 32;
+        let nb_limbs_pub = 128;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_array_public::<U16Register>(nb_limbs_pub);
+        let b_pub = builder.alloc_array_public::<U16Register>(nb_limbs_pub);
+        let modulus_pub = builder.alloc_array_public::<U16Register>(nb_limbs_pub);
+        let result_pub = builder.bigint_mul_mod(&a_pub, &b_pub, &modulus_pub);
+
+        let a = builder.alloc_array::<U16Register>(nb_limbs);
+        let b = builder.alloc_array::<U16Register>(nb_limbs);
+        let modulus = builder.alloc_array::<U16Register>(nb_limbs);
+        let result = builder.bigint_mul_mod(&a, &b, &modulus);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        // Returns a random modulus of `nb_limbs` limbs and two integers less than it.
+        let random_values = |nb_limbs: usize| {
+            let mut rng = thread_rng();
+            let nb_bits = 16 * nb_limbs as u64;
+            let modulus = rng.gen_biguint(nb_bits) | (BigUint::one() << (nb_bits - 1));
+            let a = rng.gen_biguint_below(&modulus);
+            let b = rng.gen_biguint_below(&modulus);
+            (a, b, modulus)
+        };
+
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            let (a_int, b_int, modulus_int) = random_values(nb_limbs);
+            for (register, value) in [(a, &a_int), (b, &b_int), (modulus, &modulus_int)] {
+                let p_value = Polynomial::<F>::from_biguint_field(value, 16, nb_limbs);
+                writer.write_array(&register, p_value.coefficients(), i);
+            }
+            writer.write_row_instructions(&generator.air_data, i);
+
+            let expected = Polynomial::<F>::from_biguint_field(
+                &(&a_int * &b_int % &modulus_int),
+                16,
+                nb_limbs,
+            );
+            assert_eq!(writer.read_vec(&result, i), expected.coefficients);
+        }
+
+        let writer = generator.new_writer();
+        let (a_int, b_int, modulus_int) = random_values(nb_limbs_pub);
+        for (register, value) in [
+            (a_pub, &a_int),
+            (b_pub, &b_int),
+            (modulus_pub, &modulus_int),
+        ] {
+            let p_value = Polynomial::<F>::from_biguint_field(value, 16, nb_limbs_pub);
+            writer.write_array(&register, p_value.coefficients(), 0);
+        }
+        writer.write_global_instructions(&generator.air_data);
+
+        let expected = Polynomial::<F>::from_biguint_field(
+            &(&a_int * &b_int % &modulus_int),
+            16,
+            nb_limbs_pub,
+        );
+        assert_eq!(writer.read_vec(&result_pub, 0), expected.coefficients);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
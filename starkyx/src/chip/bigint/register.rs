@@ -0,0 +1,109 @@
+use core::iter::once;
+
+use serde::{Deserialize, Serialize};
+
+use super::NB_BITS_PER_LIMB;
+use crate::chip::builder::AirBuilder;
+use crate::chip::memory::pointer::raw::RawPointer;
+use crate::chip::memory::time::Time;
+use crate::chip::memory::value::MemoryValue;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::cell::CellType;
+use crate::chip::register::cubic::CubicRegister;
+use crate::chip::register::memory::MemorySlice;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::math::prelude::*;
+
+/// The number of limbs of a `BigIntBlockRegister`.
+pub const NB_LIMBS_PER_BLOCK: usize = 16;
+
+/// A register for a block of `NB_LIMBS_PER_BLOCK` consecutive limbs of a big integer.
+///
+/// As the number of limbs of an integer is only known when the instructions are built, integers
+/// are stored in memory block by block.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BigIntBlockRegister(MemorySlice);
+
+impl BigIntBlockRegister {
+    /// Splits the limbs of `integer` into blocks, from the least significant one.
+    ///
+    /// Panics if the number of limbs is not a multiple of `NB_LIMBS_PER_BLOCK`.
+    pub fn split(integer: &ArrayRegister<U16Register>) -> Vec<Self> {
+        assert_eq!(
+            integer.len() % NB_LIMBS_PER_BLOCK,
+            0,
+            "The number of limbs must be a multiple of the block size"
+        );
+        (0..integer.len())
+            .step_by(NB_LIMBS_PER_BLOCK)
+            .map(|i| {
+                let block = integer.get_subarray(i..i + NB_LIMBS_PER_BLOCK);
+                Self::from_register_unsafe(*block.register())
+            })
+            .collect()
+    }
+}
+
+impl RegisterSerializable for BigIntBlockRegister {
+    const CELL: CellType = CellType::U16;
+
+    fn register(&self) -> &MemorySlice {
+        &self.0
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(register)
+    }
+}
+
+impl RegisterSized for BigIntBlockRegister {
+    fn size_of() -> usize {
+        NB_LIMBS_PER_BLOCK
+    }
+}
+
+impl Register for BigIntBlockRegister {
+    type Value<T> = [T; NB_LIMBS_PER_BLOCK];
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+}
+
+impl MemoryValue for BigIntBlockRegister {
+    fn num_challenges() -> usize {
+        NB_LIMBS_PER_BLOCK / 2 + 1
+    }
+
+    fn compress<L: crate::chip::AirParameters>(
+        &self,
+        builder: &mut AirBuilder<L>,
+        ptr: RawPointer,
+        time: &Time<L::Field>,
+        challenges: &ArrayRegister<CubicRegister>,
+    ) -> CubicRegister {
+        let limb_array = ArrayRegister::<U16Register>::from_register_unsafe(self.0);
+        let expressions = (0..NB_LIMBS_PER_BLOCK)
+            .step_by(2)
+            .map(|i| {
+                limb_array.get(i).expr()
+                    + limb_array.get(i + 1).expr()
+                        * L::Field::from_canonical_u32(1 << NB_BITS_PER_LIMB)
+            })
+            .chain(once(time.expr()))
+            .collect::<Vec<_>>();
+        let compressed = if self.is_trace() {
+            builder.accumulate_expressions(challenges, &expressions)
+        } else {
+            builder.accumulate_public_expressions(challenges, &expressions)
+        };
+
+        ptr.accumulate_cubic(builder, compressed.ext_expr())
+    }
+}
@@ -147,7 +147,12 @@ impl<L: AirParameters> AirBuilder<L> {
                 }
                 register
             }
-            CellType::Bit => self.get_public_memory(T::size_of()),
+            CellType::Bit => {
+                let reg = self.get_public_memory(T::size_of());
+                let constraint = AirInstruction::bits(&reg);
+                self.register_global_air_instruction_internal(constraint);
+                reg
+            }
         };
         T::from_register(register)
     }
@@ -164,7 +169,12 @@ impl<L: AirParameters> AirBuilder<L> {
                 }
                 register
             }
-            CellType::Bit => self.get_public_memory(size_of),
+            CellType::Bit => {
+                let reg = self.get_public_memory(size_of);
+                let constraint = AirInstruction::bits(&reg);
+                self.register_global_air_instruction_internal(constraint);
+                reg
+            }
         };
         ArrayRegister::<T>::from_register_unsafe(register)
     }
@@ -314,6 +314,7 @@ pub(crate) mod tests {
     pub use crate::air::parser::AirParser;
     pub use crate::air::RAir;
     pub use crate::chip::instruction::empty::EmptyInstruction;
+    use crate::chip::register::bit::BitRegister;
     pub use crate::chip::register::u16::U16Register;
     pub use crate::chip::register::RegisterSerializable;
     pub use crate::chip::trace::generator::ArithmeticGenerator;
@@ -532,4 +533,38 @@ pub(crate) mod tests {
         // Test the recursive proof.
         test_recursive_starky(stark, config, generator, &public_inputs);
     }
+
+    #[test]
+    #[should_panic]
+    fn test_builder_public_bit_not_valid() {
+        type F = GoldilocksField;
+        type L = SimpleTestPublicParameters;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        let mut builder = AirBuilder::<L>::new();
+        let bit = builder.alloc_public::<BitRegister>();
+
+        let clk = builder.clock();
+        let clk_expected = builder.alloc::<ElementRegister>();
+
+        builder.assert_equal(&clk, &clk_expected);
+
+        let num_rows = 1 << 14;
+
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        writer.write(&bit, &F::TWO, 0);
+        for i in 0..num_rows {
+            writer.write(&clk_expected, &F::from_canonical_usize(i), i);
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        let public_inputs = writer.public.read().unwrap().clone();
+        test_starky(&stark, &config, &generator, &public_inputs);
+    }
 }
@@ -9,6 +9,20 @@ use crate::chip::table::lookup::values::LookupValues;
 use crate::chip::AirParameters;
 
 impl<L: AirParameters> AirBuilder<L> {
+    /// Asserts that the range checks of the arithmetic columns are of `nb_bits_per_limb` bits.
+    ///
+    /// Called by the instructions whose limbs are range checked, as a table of a different size
+    /// would either reject valid limbs or accept limbs which are out of range.
+    pub(crate) fn assert_range_check_bits(&self, nb_bits_per_limb: usize) {
+        assert_eq!(
+            nb_bits_per_limb,
+            L::RANGE_CHECK_BITS,
+            "Limbs of {} bits are range checked to {} bits",
+            nb_bits_per_limb,
+            L::RANGE_CHECK_BITS
+        );
+    }
+
     pub(crate) fn arithmetic_range_checks(&mut self) {
         let table = self.alloc::<ElementRegister>();
 
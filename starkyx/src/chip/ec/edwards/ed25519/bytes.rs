@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+
+use super::params::Ed25519BaseField;
+use crate::air::parser::AirParser;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::field::sub::FpSubInstruction;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+
+/// The number of bytes in the encoding of an element of the Ed25519 base field.
+pub const ED25519_FP_NUM_BYTES: usize = 32;
+
+/// Converts a field element to its little-endian bytes, constraining the element to be less than
+/// the modulus.
+///
+/// Each limb is constrained to be `low + 2^8 * high` for a pair of bytes. The value is constrained
+/// to be canonical by `fp_assert_canonical` when the instruction is registered.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Ed25519FpBytesInstruction {
+    value: FieldRegister<Ed25519BaseField>,
+    /// The bytes of `value`, allocated as range-checked `u16` limbs.
+    bytes: ArrayRegister<ByteRegister>,
+    /// The low byte of each limb multiplied by `2^8`, whose range check bounds the low byte.
+    shifted_low_bytes: ArrayRegister<U16Register>,
+    /// The least significant bit of `value`.
+    parity: BitRegister,
+    /// The value `(bytes[0] - parity) / 2`.
+    half_low_byte: U16Register,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Returns the little-endian bytes of `value`, constraining `value` to be less than the
+    /// modulus.
+    pub fn ed25519_fp_to_bytes(
+        &mut self,
+        value: &FieldRegister<Ed25519BaseField>,
+    ) -> ArrayRegister<ByteRegister>
+    where
+        L::Instruction: From<FpSubInstruction<Ed25519BaseField>> + From<Ed25519FpBytesInstruction>,
+    {
+        self.ed25519_fp_to_bytes_with_parity(value).0
+    }
+
+    /// Returns the little-endian bytes of `value` together with its least significant bit,
+    /// constraining `value` to be less than the modulus.
+    pub(crate) fn ed25519_fp_to_bytes_with_parity(
+        &mut self,
+        value: &FieldRegister<Ed25519BaseField>,
+    ) -> (ArrayRegister<ByteRegister>, BitRegister)
+    where
+        L::Instruction: From<FpSubInstruction<Ed25519BaseField>> + From<Ed25519FpBytesInstruction>,
+    {
+        let num_limbs = Ed25519BaseField::NB_LIMBS;
+        let is_trace = value.is_trace();
+
+        self.fp_assert_canonical(value);
+
+        let bytes_limbs: ArrayRegister<U16Register>;
+        let shifted_low_bytes: ArrayRegister<U16Register>;
+        let parity: BitRegister;
+        let half_low_byte: U16Register;
+
+        if is_trace {
+            bytes_limbs = self.alloc_array::<U16Register>(ED25519_FP_NUM_BYTES);
+            shifted_low_bytes = self.alloc_array::<U16Register>(num_limbs);
+            parity = self.alloc::<BitRegister>();
+            half_low_byte = self.alloc::<U16Register>();
+        } else {
+            bytes_limbs = self.alloc_array_public::<U16Register>(ED25519_FP_NUM_BYTES);
+            shifted_low_bytes = self.alloc_array_public::<U16Register>(num_limbs);
+            parity = self.alloc_public::<BitRegister>();
+            half_low_byte = self.alloc_public::<U16Register>();
+        }
+        let bytes = ArrayRegister::<ByteRegister>::from_register_unsafe(*bytes_limbs.register());
+
+        let instr = Ed25519FpBytesInstruction {
+            value: *value,
+            bytes,
+            shifted_low_bytes,
+            parity,
+            half_low_byte,
+        };
+
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+
+        (bytes, parity)
+    }
+}
+
+impl<AP: AirParser> AirConstraint<AP> for Ed25519FpBytesInstruction {
+    fn eval(&self, parser: &mut AP) {
+        let value = self.value.eval(parser).coefficients;
+        let two_8 = AP::Field::from_canonical_u32(1 << 8);
+
+        // Assert that `limb = low + 2^8 * high` and that `shifted_low = 2^8 * low`. As all of
+        // these are range-checked `u16` values, the first equation holds over the integers and
+        // the second one implies `low < 2^8`, which in turn implies `high < 2^8`.
+        for (i, limb) in value.iter().enumerate() {
+            let low = self.bytes.get(2 * i).eval(parser);
+            let high = self.bytes.get(2 * i + 1).eval(parser);
+            let shifted_low = self.shifted_low_bytes.get(i).eval(parser);
+
+            let high_shifted = parser.mul_const(high, two_8);
+            let limb_from_bytes = parser.add(low, high_shifted);
+            parser.assert_eq(*limb, limb_from_bytes);
+
+            let low_shifted = parser.mul_const(low, two_8);
+            parser.assert_eq(shifted_low, low_shifted);
+        }
+
+        // Assert that `bytes[0] = parity + 2 * half_low_byte`.
+        let low_byte = self.bytes.get(0).eval(parser);
+        let parity = self.parity.eval(parser);
+        let half_low_byte = self.half_low_byte.eval(parser);
+        let half_doubled = parser.mul_const(half_low_byte, AP::Field::from_canonical_u8(2));
+        let low_byte_from_parity = parser.add(parity, half_doubled);
+        parser.assert_eq(low_byte, low_byte_from_parity);
+    }
+}
+
+impl Ed25519FpBytesInstruction {
+    /// Computes the values of the witness registers from the limbs of `value`.
+    ///
+    /// Returns the bytes, the shifted low bytes, the parity and the half of the low byte, in this
+    /// order.
+    fn witness<F: PrimeField64>(value: &[F]) -> (Vec<F>, Vec<F>, F, F) {
+        let limbs = value
+            .iter()
+            .map(|x| x.as_canonical_u64() as u32)
+            .collect::<Vec<_>>();
+
+        let bytes = limbs
+            .iter()
+            .flat_map(|limb| [limb & 0xff, limb >> 8])
+            .collect::<Vec<_>>();
+        let shifted_low_bytes = limbs
+            .iter()
+            .map(|limb| F::from_canonical_u32((limb & 0xff) << 8))
+            .collect();
+
+        let parity = F::from_canonical_u32(bytes[0] & 1);
+        let half_low_byte = F::from_canonical_u32(bytes[0] >> 1);
+        let bytes = bytes.into_iter().map(F::from_canonical_u32).collect();
+
+        (bytes, shifted_low_bytes, parity, half_low_byte)
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for Ed25519FpBytesInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let value = writer.read(&self.value, row_index);
+        let (bytes, shifted_low_bytes, parity, half_low_byte) = Self::witness(&value.coefficients);
+
+        writer.write_array(&self.bytes, bytes, row_index);
+        writer.write_array(&self.shifted_low_bytes, shifted_low_bytes, row_index);
+        writer.write(&self.parity, &parity, row_index);
+        writer.write(&self.half_low_byte, &half_low_byte, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let value = writer.read(&self.value);
+        let (bytes, shifted_low_bytes, parity, half_low_byte) = Self::witness(&value.coefficients);
+
+        writer.write_array(&self.bytes, bytes);
+        writer.write_array(&self.shifted_low_bytes, shifted_low_bytes);
+        writer.write(&self.parity, &parity);
+        writer.write(&self.half_low_byte, &half_low_byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::BigUint;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519FpInstruction;
+    use crate::chip::field::parameters::FieldParameters;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpBytesTest;
+
+    impl AirParameters for FpBytesTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 157;
+        const NUM_FREE_COLUMNS: usize = 18;
+        const EXTENDED_COLUMNS: usize = 243;
+
+        type Instruction = Ed25519FpInstruction;
+    }
+
+    #[test]
+    fn test_fp_to_bytes() {
+        type F = GoldilocksField;
+        type L = FpBytesTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Ed25519BaseField;
+
+        let p = Ed25519BaseField::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let bytes_pub = builder.ed25519_fp_to_bytes(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let bytes = builder.ed25519_fp_to_bytes(&a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        // Include the edge cases `0` and `p - 1`.
+        let mut rng = thread_rng();
+        let a_pub_int = &p - 1u32;
+        let p_a_pub = Polynomial::<F>::from_biguint_field(&a_pub_int, 16, 16);
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            let a_int = match i {
+                0 => BigUint::from(0u32),
+                1 => &p - 1u32,
+                _ => rng.gen_biguint(256) % &p,
+            };
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+            writer.write(&a, &p_a, i);
+            writer.write(&a_pub, &p_a_pub, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            let bytes_value = writer
+                .read_array::<_, 32>(&bytes, i)
+                .map(|b| b.as_canonical_u64() as u8);
+            assert_eq!(BigUint::from_bytes_le(&bytes_value), a_int);
+        }
+        writer.write_global_instructions(&generator.air_data);
+
+        let public = writer.public().unwrap().clone();
+        let bytes_pub_value = bytes_pub
+            .iter()
+            .map(|b| public[b.register().index()].as_canonical_u64() as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(BigUint::from_bytes_le(&bytes_pub_value), a_pub_int);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
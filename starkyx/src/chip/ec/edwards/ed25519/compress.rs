@@ -0,0 +1,131 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+
+use super::bytes::Ed25519FpBytesInstruction;
+use super::params::{Ed25519, Ed25519BaseField, Ed25519Parameters};
+use super::point::CompressedPointRegister;
+use crate::chip::builder::AirBuilder;
+use crate::chip::ec::edwards::EdwardsCurve;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::field::sub::FpSubInstruction;
+use crate::chip::AirParameters;
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Compresses a point into the sign bit of its x-coordinate and its y-coordinate, the
+    /// counterpart of `ed25519_decompress`.
+    ///
+    /// Both coordinates are constrained to be less than the modulus, so that the sign bit is the
+    /// least significant bit of the canonical x-coordinate and the encoding is unique. The point is
+    /// assumed to be on the curve.
+    pub fn ed25519_compress(
+        &mut self,
+        p: &AffinePointRegister<EdwardsCurve<Ed25519Parameters>>,
+    ) -> CompressedPointRegister
+    where
+        L::Instruction: From<FpSubInstruction<Ed25519BaseField>> + From<Ed25519FpBytesInstruction>,
+    {
+        let (_, sign) = self.ed25519_fp_to_bytes_with_parity(&p.x);
+        self.ed25519_fp_to_bytes(&p.y);
+
+        CompressedPointRegister::new(sign, p.y)
+    }
+}
+
+pub fn compress(point: &AffinePoint<Ed25519>) -> CompressedEdwardsY {
+    let mut point_bytes = [0u8; 32];
+    let y_bytes = point.y.to_bytes_le();
+    point_bytes[..y_bytes.len()].copy_from_slice(&y_bytes);
+    // set the sign bit to the least significant bit of x
+    point_bytes[31] |= (point.x.bit(0) as u8) << 7;
+
+    CompressedEdwardsY(point_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+    use curve25519_dalek::scalar::Scalar;
+    use num::BigUint;
+    use rand::{thread_rng, Rng};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::edwards::ed25519::decompress::decompress;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519FpInstruction;
+    use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
+    use crate::chip::ec::EllipticCurve;
+    use crate::chip::utils::digits_to_biguint;
+    use crate::math::prelude::*;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct Ed25519CompressTest;
+
+    impl AirParameters for Ed25519CompressTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 314;
+        const NUM_FREE_COLUMNS: usize = 34;
+        const EXTENDED_COLUMNS: usize = 480;
+        type Instruction = Ed25519FpInstruction;
+    }
+
+    #[test]
+    fn test_ed25519_compress() {
+        type L = Ed25519CompressTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = Ed25519;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+        let compressed_p = builder.ed25519_compress(&p);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::ec_generator();
+        let mut rng = thread_rng();
+        let points = (0..256)
+            .map(|_| {
+                let scalar_bytes = rng.gen::<[u8; 32]>();
+                let p_int = &base * &BigUint::from_bytes_le(&scalar_bytes);
+
+                let expected = ED25519_BASEPOINT_POINT * Scalar::from_bytes_mod_order(scalar_bytes);
+                let compressed_int = compress(&p_int);
+                assert_eq!(compressed_int, expected.compress());
+                assert_eq!(decompress(&compressed_int).0, p_int);
+
+                (p_int, compressed_int)
+            })
+            .collect::<Vec<_>>();
+
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            let (p_int, compressed_int) = &points[i % points.len()];
+            writer.write_ec_point(&p, p_int, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            let sign = writer.read(&compressed_p.sign, i).as_canonical_u64();
+            let y_digits = writer
+                .read(&compressed_p.y, i)
+                .coefficients
+                .iter()
+                .map(|x| x.as_canonical_u64() as u16)
+                .collect::<Vec<_>>();
+            assert_eq!(sign, (compressed_int.as_bytes()[31] >> 7) as u64);
+            assert_eq!(digits_to_biguint(&y_digits), p_int.y);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -9,7 +9,7 @@ use crate::chip::register::bit::BitRegister;
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
-use crate::polynomial::to_u16_le_limbs_polynomial;
+use crate::polynomial::to_le_limbs_polynomial;
 
 pub trait CompressedPointGadget {
     fn alloc_local_ec_compressed_point(&mut self) -> CompressedPointRegister;
@@ -50,7 +50,7 @@ pub trait CompressedPointAirWriter: AirWriter {
 
         let y = BigUint::from_bytes_le(&value_bytes);
 
-        let value_y = to_u16_le_limbs_polynomial::<Self::Field, Ed25519BaseField>(&y);
+        let value_y = to_le_limbs_polynomial::<Self::Field, Ed25519BaseField>(&y);
         self.write(&data.y, &value_y);
     }
 }
@@ -96,7 +96,7 @@ impl<F: PrimeField64> CompressedPointWriter for TraceWriter<F> {
 
         let y = BigUint::from_bytes_le(&value_bytes);
 
-        let value_y = to_u16_le_limbs_polynomial::<F, Ed25519BaseField>(&y);
+        let value_y = to_le_limbs_polynomial::<F, Ed25519BaseField>(&y);
         self.write(&data.y, &value_y, row_index);
     }
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
-use super::params::{Ed25519, Ed25519BaseField};
+use super::bytes::Ed25519FpBytesInstruction;
+use super::invsqrt::Ed25519FpInvSqrtInstruction;
+use super::params::{Ed25519, Ed25519BaseField, Ed25519ScalarField};
 use super::sqrt::Ed25519FpSqrtInstruction;
 use crate::air::AirConstraint;
 use crate::chip::ec::scalar::LimbBitInstruction;
@@ -9,12 +11,18 @@ use crate::chip::field::add::FpAddInstruction;
 use crate::chip::field::den::FpDenInstruction;
 use crate::chip::field::div::FpDivInstruction;
 use crate::chip::field::inner_product::FpInnerProductInstruction;
-use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::instruction::{FpInstruction, FromFieldInstruction};
 use crate::chip::field::mul::FpMulInstruction;
 use crate::chip::field::mul_const::FpMulConstInstruction;
 use crate::chip::field::sub::FpSubInstruction;
 use crate::chip::instruction::Instruction;
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::uint::bytes::decode::ByteDecodeInstruction;
+use crate::chip::uint::bytes::lookup_table::{ByteInstructionSet, ByteInstructions};
+use crate::chip::uint::bytes::operations::instruction::ByteOperationInstruction;
+use crate::chip::uint::bytes::operations::value::ByteOperationDigestConstraint;
+use crate::chip::uint::operations::add::ByteArrayAdd;
+use crate::chip::uint::operations::instruction::{UintInstruction, UintInstructions};
 use crate::math::field::PrimeField64;
 use crate::polynomial::parser::PolynomialParser;
 
@@ -23,6 +31,8 @@ use crate::polynomial::parser::PolynomialParser;
 pub enum Ed25519FpInstruction {
     EC(ECInstruction<Ed25519>),
     Sqrt(Ed25519FpSqrtInstruction),
+    Bytes(Ed25519FpBytesInstruction),
+    InvSqrt(Ed25519FpInvSqrtInstruction),
 }
 
 impl FromFieldInstruction<Ed25519BaseField> for Ed25519FpInstruction {}
@@ -33,6 +43,18 @@ impl From<Ed25519FpSqrtInstruction> for Ed25519FpInstruction {
     }
 }
 
+impl From<Ed25519FpBytesInstruction> for Ed25519FpInstruction {
+    fn from(i: Ed25519FpBytesInstruction) -> Self {
+        Self::Bytes(i)
+    }
+}
+
+impl From<Ed25519FpInvSqrtInstruction> for Ed25519FpInstruction {
+    fn from(i: Ed25519FpInvSqrtInstruction) -> Self {
+        Self::InvSqrt(i)
+    }
+}
+
 impl<AP: PolynomialParser> AirConstraint<AP> for Ed25519FpInstruction {
     fn eval(&self, parser: &mut AP) {
         match self {
@@ -40,6 +62,12 @@ impl<AP: PolynomialParser> AirConstraint<AP> for Ed25519FpInstruction {
             Ed25519FpInstruction::Sqrt(instruction) => {
                 AirConstraint::<AP>::eval(instruction, parser)
             }
+            Ed25519FpInstruction::Bytes(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+            Ed25519FpInstruction::InvSqrt(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
         }
     }
 }
@@ -53,6 +81,12 @@ impl<F: PrimeField64> Instruction<F> for Ed25519FpInstruction {
             Ed25519FpInstruction::Sqrt(instruction) => {
                 Instruction::<F>::write(instruction, writer, row_index)
             }
+            Ed25519FpInstruction::Bytes(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            Ed25519FpInstruction::InvSqrt(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
         }
     }
 
@@ -64,6 +98,12 @@ impl<F: PrimeField64> Instruction<F> for Ed25519FpInstruction {
             Ed25519FpInstruction::Sqrt(instruction) => {
                 Instruction::<F>::write_to_air(instruction, writer)
             }
+            Ed25519FpInstruction::Bytes(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            Ed25519FpInstruction::InvSqrt(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
         }
     }
 }
@@ -115,3 +155,211 @@ impl From<FpMulConstInstruction<Ed25519BaseField>> for Ed25519FpInstruction {
         Self::EC(i.into())
     }
 }
+
+/// Instructions for Ed25519 signature verification: the byte operations of SHA-512, the curve
+/// operations and point decompression, and arithmetic in the scalar field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum Ed25519VerifyInstruction {
+    Uint(UintInstruction),
+    EC(Ed25519FpInstruction),
+    Scalar(FpInstruction<Ed25519ScalarField>),
+}
+
+impl ByteInstructions for Ed25519VerifyInstruction {}
+
+impl UintInstructions for Ed25519VerifyInstruction {}
+
+impl FromFieldInstruction<Ed25519BaseField> for Ed25519VerifyInstruction {}
+
+impl FromFieldInstruction<Ed25519ScalarField> for Ed25519VerifyInstruction {}
+
+impl<AP: PolynomialParser> AirConstraint<AP> for Ed25519VerifyInstruction {
+    fn eval(&self, parser: &mut AP) {
+        match self {
+            Ed25519VerifyInstruction::Uint(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+            Ed25519VerifyInstruction::EC(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+            Ed25519VerifyInstruction::Scalar(instruction) => {
+                AirConstraint::<AP>::eval(instruction, parser)
+            }
+        }
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for Ed25519VerifyInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        match self {
+            Ed25519VerifyInstruction::Uint(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            Ed25519VerifyInstruction::EC(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            Ed25519VerifyInstruction::Scalar(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+        }
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        match self {
+            Ed25519VerifyInstruction::Uint(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            Ed25519VerifyInstruction::EC(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            Ed25519VerifyInstruction::Scalar(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+        }
+    }
+}
+
+impl From<UintInstruction> for Ed25519VerifyInstruction {
+    fn from(i: UintInstruction) -> Self {
+        Self::Uint(i)
+    }
+}
+
+impl From<ByteInstructionSet> for Ed25519VerifyInstruction {
+    fn from(i: ByteInstructionSet) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteArrayAdd<4>> for Ed25519VerifyInstruction {
+    fn from(i: ByteArrayAdd<4>) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteOperationInstruction> for Ed25519VerifyInstruction {
+    fn from(i: ByteOperationInstruction) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteDecodeInstruction> for Ed25519VerifyInstruction {
+    fn from(i: ByteDecodeInstruction) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<ByteOperationDigestConstraint> for Ed25519VerifyInstruction {
+    fn from(i: ByteOperationDigestConstraint) -> Self {
+        Self::Uint(i.into())
+    }
+}
+
+impl From<Ed25519FpSqrtInstruction> for Ed25519VerifyInstruction {
+    fn from(i: Ed25519FpSqrtInstruction) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<Ed25519FpBytesInstruction> for Ed25519VerifyInstruction {
+    fn from(i: Ed25519FpBytesInstruction) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<Ed25519FpInvSqrtInstruction> for Ed25519VerifyInstruction {
+    fn from(i: Ed25519FpInvSqrtInstruction) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<LimbBitInstruction> for Ed25519VerifyInstruction {
+    fn from(i: LimbBitInstruction) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpAddInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpAddInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpMulInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpMulInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpSubInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpSubInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpDivInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpDivInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpDenInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpDenInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpInnerProductInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpInnerProductInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpMulConstInstruction<Ed25519BaseField>> for Ed25519VerifyInstruction {
+    fn from(i: FpMulConstInstruction<Ed25519BaseField>) -> Self {
+        Self::EC(i.into())
+    }
+}
+
+impl From<FpAddInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpAddInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpMulInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpMulInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpSubInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpSubInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpDivInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpDivInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpDenInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpDenInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpInnerProductInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpInnerProductInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
+
+impl From<FpMulConstInstruction<Ed25519ScalarField>> for Ed25519VerifyInstruction {
+    fn from(i: FpMulConstInstruction<Ed25519ScalarField>) -> Self {
+        Self::Scalar(i.into())
+    }
+}
@@ -0,0 +1,241 @@
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::params::Ed25519BaseField;
+use super::sqrt::sqrt;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::mul::FpMulInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::RegisterSerializable;
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::digits_to_biguint;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// Fp Inverse Square Root. Witnesses `r = 1 / sqrt(a)`, or `r = 0` if `a = 0`.
+///
+/// The instruction only constrains the square `r * r`. The relation `a * a * r * r = a` is
+/// constrained by `ed25519_inv_sqrt` with regular field multiplications.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Ed25519FpInvSqrtInstruction {
+    /// The value whose inverse square root is computed.
+    a: FieldRegister<Ed25519BaseField>,
+    /// a `FpMulInstruction` to compute `r * r`.
+    square: FpMulInstruction<Ed25519BaseField>,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Given a field element `a`, computes `r` such that `a * r^2 = 1`, or `r = 0` if `a = 0`.
+    ///
+    /// The constraint is `a^2 * r^2 = a`, which implies `a * r^2 = 1` whenever `a` is nonzero and
+    /// in particular that `a` is a square. If `a = 0`, the value of `r` is not constrained. The
+    /// sign of `r` is not constrained either.
+    pub fn ed25519_inv_sqrt(
+        &mut self,
+        a: &FieldRegister<Ed25519BaseField>,
+    ) -> FieldRegister<Ed25519BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Ed25519BaseField> + From<Ed25519FpInvSqrtInstruction>,
+    {
+        let is_trace = a.is_trace();
+
+        let result: FieldRegister<Ed25519BaseField>;
+        let square_result: FieldRegister<Ed25519BaseField>;
+        let square_carry: FieldRegister<Ed25519BaseField>;
+        let square_witness_low: ArrayRegister<U16Register>;
+        let square_witness_high: ArrayRegister<U16Register>;
+
+        if is_trace {
+            result = self.alloc::<FieldRegister<Ed25519BaseField>>();
+            square_result = self.alloc::<FieldRegister<Ed25519BaseField>>();
+            square_carry = self.alloc::<FieldRegister<Ed25519BaseField>>();
+            square_witness_low =
+                self.alloc_array::<U16Register>(Ed25519BaseField::NB_WITNESS_LIMBS);
+            square_witness_high =
+                self.alloc_array::<U16Register>(Ed25519BaseField::NB_WITNESS_LIMBS);
+        } else {
+            result = self.alloc_public::<FieldRegister<Ed25519BaseField>>();
+            square_result = self.alloc_public::<FieldRegister<Ed25519BaseField>>();
+            square_carry = self.alloc_public::<FieldRegister<Ed25519BaseField>>();
+            square_witness_low =
+                self.alloc_array_public::<U16Register>(Ed25519BaseField::NB_WITNESS_LIMBS);
+            square_witness_high =
+                self.alloc_array_public::<U16Register>(Ed25519BaseField::NB_WITNESS_LIMBS);
+        }
+
+        let square = FpMulInstruction {
+            a: result,
+            b: result,
+            result: square_result,
+            carry: square_carry,
+            witness_low: square_witness_low,
+            witness_high: square_witness_high,
+        };
+
+        let instr = Ed25519FpInvSqrtInstruction { a: *a, square };
+
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+
+        // check that a * a * r * r == a
+        let ratio = self.fp_mul(a, &square_result);
+        let check = self.fp_mul(a, &ratio);
+        self.assert_equal(&check, a);
+
+        result
+    }
+}
+
+impl<AP: PolynomialParser> AirConstraint<AP> for Ed25519FpInvSqrtInstruction {
+    fn eval(&self, parser: &mut AP) {
+        self.square.eval(parser);
+    }
+}
+
+impl<F: PrimeField64> Instruction<F> for Ed25519FpInvSqrtInstruction {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let p_a = writer.read(&self.a, row_index);
+
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+
+        let a = digits_to_biguint(&a_digits);
+
+        let r = inv_sqrt(&a);
+        let p_r = to_le_limbs_polynomial::<F, Ed25519BaseField>(&r);
+
+        writer.write(&self.square.a, &p_r, row_index);
+        self.square.write(writer, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let p_a = writer.read(&self.a);
+
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+
+        let a = digits_to_biguint(&a_digits);
+
+        let r = inv_sqrt(&a);
+        let p_r = to_le_limbs_polynomial::<F, Ed25519BaseField>(&r);
+
+        writer.write(&self.square.a, &p_r);
+        self.square.write_to_air(writer);
+    }
+}
+
+/// Returns the non-negative square root of `1 / a`, or zero if `a = 0`.
+///
+/// Panics if `a` is not a square.
+pub fn inv_sqrt(a: &BigUint) -> BigUint {
+    if a.is_zero() {
+        return BigUint::zero();
+    }
+    let modulus = Ed25519BaseField::modulus();
+    let a_inv = a.modpow(&(&modulus - BigUint::from(2u32)), &modulus);
+    sqrt(a_inv)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519FpInstruction;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpInvSqrtTest;
+
+    impl AirParameters for FpInvSqrtTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 308;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 471;
+
+        type Instruction = Ed25519FpInstruction;
+    }
+
+    #[test]
+    fn test_fp_inv_sqrt() {
+        type F = GoldilocksField;
+        type L = FpInvSqrtTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Ed25519BaseField;
+
+        let p = Ed25519BaseField::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let result_pub = builder.ed25519_inv_sqrt(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let result = builder.ed25519_inv_sqrt(&a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        let a_pub_int = BigUint::zero();
+        let p_a_pub = Polynomial::<F>::from_biguint_field(&a_pub_int, 16, 16);
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            let root = rng.gen_biguint(256) % &p;
+            let a_int = (&root * &root) % &p;
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&a_pub, &p_a_pub, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            if i < 256 {
+                let r_digits = writer
+                    .read(&result, i)
+                    .coefficients
+                    .iter()
+                    .map(|x| x.as_canonical_u64() as u16)
+                    .collect::<Vec<_>>();
+                let r = digits_to_biguint(&r_digits);
+                assert_eq!((&a_int * &r * &r) % &p, BigUint::from(1u32));
+            }
+        }
+        writer.write_global_instructions(&generator.air_data);
+
+        let public = writer.public().unwrap().clone();
+        let r_pub = writer.read(&result_pub, 0);
+        assert!(r_pub.coefficients.iter().all(|x| *x == F::ZERO));
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -1,6 +1,10 @@
+pub mod bytes;
+pub mod compress;
 pub mod decompress;
 pub mod gadget;
 pub mod instruction;
+pub mod invsqrt;
 pub mod params;
 pub mod point;
+pub mod ristretto;
 pub mod sqrt;
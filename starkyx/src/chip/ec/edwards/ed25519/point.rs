@@ -1,7 +1,11 @@
-use super::params::Ed25519BaseField;
+use serde::{Deserialize, Serialize};
+
+use super::params::{Ed25519, Ed25519BaseField};
+use crate::chip::ec::point::AffinePointRegister;
 use crate::chip::field::register::FieldRegister;
 use crate::chip::register::bit::BitRegister;
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CompressedPointRegister {
     pub sign: BitRegister,
     pub y: FieldRegister<Ed25519BaseField>,
@@ -12,3 +16,15 @@ impl CompressedPointRegister {
         Self { sign, y }
     }
 }
+
+/// A ristretto255 element, represented by any of the Edwards points of its equivalence class.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RistrettoPointRegister {
+    pub point: AffinePointRegister<Ed25519>,
+}
+
+impl RistrettoPointRegister {
+    pub fn new(point: AffinePointRegister<Ed25519>) -> Self {
+        Self { point }
+    }
+}
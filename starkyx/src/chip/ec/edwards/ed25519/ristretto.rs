@@ -0,0 +1,483 @@
+use core::str::FromStr;
+
+use curve25519_dalek::ristretto::CompressedRistretto;
+use num::{BigUint, One, Zero};
+
+use super::bytes::Ed25519FpBytesInstruction;
+use super::invsqrt::{inv_sqrt, Ed25519FpInvSqrtInstruction};
+use super::params::{Ed25519, Ed25519BaseField, Ed25519Parameters};
+use super::point::RistrettoPointRegister;
+use crate::chip::builder::AirBuilder;
+use crate::chip::ec::edwards::EdwardsParameters;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::utils::bigint_into_u16_digits;
+use crate::chip::AirParameters;
+
+/// The square root of -1 in the field.
+const SQRT_M1: &str =
+    "19681161376707505956807079304988542015446066515923890162744021073123829784752";
+
+/// The constant `1 / sqrt(a - d)`, where `a = -1` and `d` are the parameters of the curve.
+const INVSQRT_A_MINUS_D: &str =
+    "54469307008909316920995813868745141605393597292927456921205312896311721017578";
+
+fn constant_limbs(value: &str) -> [u16; MAX_NB_LIMBS] {
+    let value = BigUint::from_str(value).unwrap();
+    let mut limbs = [0; MAX_NB_LIMBS];
+    limbs[..Ed25519BaseField::NB_LIMBS]
+        .copy_from_slice(&bigint_into_u16_digits(&value, Ed25519BaseField::NB_LIMBS));
+    limbs
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Decodes the ristretto255 encoding `s` of a point, following RFC 9496, section 4.3.1.
+    ///
+    /// The encoding is constrained to be valid: `s` is canonical and non-negative, the inverse
+    /// square root exists, `x * y` is non-negative and `y` is nonzero.
+    pub fn ristretto_decode(
+        &mut self,
+        s: &FieldRegister<Ed25519BaseField>,
+    ) -> RistrettoPointRegister
+    where
+        L::Instruction: FromFieldInstruction<Ed25519BaseField>
+            + From<Ed25519FpInvSqrtInstruction>
+            + From<Ed25519FpBytesInstruction>,
+    {
+        let one = self.fp_one::<Ed25519BaseField>();
+
+        let s_is_negative = self.ed25519_fp_is_negative(s);
+        self.assert_zero(&s_is_negative);
+
+        let ss = self.fp_mul(s, s);
+        let u1 = self.fp_sub(&one, &ss);
+        let u2 = self.fp_add(&one, &ss);
+        let u2_sqr = self.fp_mul(&u2, &u2);
+
+        // v = -(d * u1^2) - u2^2.
+        let u1_sqr = self.fp_mul(&u1, &u1);
+        let d_u1_sqr = self.fp_mul_const(&u1_sqr, Ed25519Parameters::D);
+        let minus_v = self.fp_add(&d_u1_sqr, &u2_sqr);
+        let zero = self.fp_zero::<Ed25519BaseField>();
+        let v = self.fp_sub(&zero, &minus_v);
+
+        // If `v * u2^2` is zero, the value of `invsqrt` is not constrained, but then `y = 0`.
+        let v_u2_sqr = self.fp_mul(&v, &u2_sqr);
+        let invsqrt = self.ed25519_inv_sqrt(&v_u2_sqr);
+
+        let den_x = self.fp_mul(&invsqrt, &u2);
+        let invsqrt_den_x = self.fp_mul(&invsqrt, &den_x);
+        let den_y = self.fp_mul(&invsqrt_den_x, &v);
+
+        // x = |2 * s * den_x|, y = u1 * den_y, t = x * y.
+        let s_den_x = self.fp_mul(s, &den_x);
+        let two_s_den_x = self.fp_add(&s_den_x, &s_den_x);
+        let x = self.ed25519_fp_abs(&two_s_den_x);
+        let y = self.fp_mul(&u1, &den_y);
+        let t = self.fp_mul(&x, &y);
+
+        let t_is_negative = self.ed25519_fp_is_negative(&t);
+        self.assert_zero(&t_is_negative);
+        // Check that `y` is nonzero by computing its inverse.
+        self.fp_div(&one, &y);
+
+        RistrettoPointRegister::new(AffinePointRegister::new(x, y))
+    }
+
+    /// Computes the canonical ristretto255 encoding of a point, following RFC 9496, section 4.3.2.
+    ///
+    /// The point is assumed to be a representative of a ristretto255 element, i.e. an Edwards
+    /// point of the form `2 * P`. The encoding is constrained to be canonical and non-negative.
+    pub fn ristretto_encode(
+        &mut self,
+        p: &RistrettoPointRegister,
+    ) -> FieldRegister<Ed25519BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Ed25519BaseField>
+            + From<Ed25519FpInvSqrtInstruction>
+            + From<Ed25519FpBytesInstruction>,
+    {
+        let x = p.point.x;
+        let y = p.point.y;
+        let one = self.fp_one::<Ed25519BaseField>();
+        let zero = self.fp_zero::<Ed25519BaseField>();
+
+        // The point is affine, so that `z = 1` and `t = x * y = u2`.
+        let y_sqr = self.fp_mul(&y, &y);
+        let u1 = self.fp_sub(&one, &y_sqr);
+        let u2 = self.fp_mul(&x, &y);
+
+        // If `u1 * u2^2` is zero, the value of `invsqrt` is not constrained, but then `u2 = 0` and
+        // the encoding is zero regardless.
+        let u2_sqr = self.fp_mul(&u2, &u2);
+        let u1_u2_sqr = self.fp_mul(&u1, &u2_sqr);
+        let invsqrt = self.ed25519_inv_sqrt(&u1_u2_sqr);
+
+        let den1 = self.fp_mul(&invsqrt, &u1);
+        let den2 = self.fp_mul(&invsqrt, &u2);
+        let den1_den2 = self.fp_mul(&den1, &den2);
+        let z_inv = self.fp_mul(&den1_den2, &u2);
+
+        let ix = self.fp_mul_const(&x, constant_limbs(SQRT_M1));
+        let iy = self.fp_mul_const(&y, constant_limbs(SQRT_M1));
+        let enchanted_denominator = self.fp_mul_const(&den1, constant_limbs(INVSQRT_A_MINUS_D));
+
+        let t_z_inv = self.fp_mul(&u2, &z_inv);
+        let rotate = self.ed25519_fp_is_negative(&t_z_inv);
+
+        let x = self.select(&rotate, &iy, &x);
+        let y = self.select(&rotate, &ix, &y);
+        let den_inv = self.select(&rotate, &enchanted_denominator, &den2);
+
+        let x_z_inv = self.fp_mul(&x, &z_inv);
+        let y_is_negated = self.ed25519_fp_is_negative(&x_z_inv);
+        let minus_y = self.fp_sub(&zero, &y);
+        let y = self.select(&y_is_negated, &minus_y, &y);
+
+        // s = |den_inv * (z - y)|.
+        let z_minus_y = self.fp_sub(&one, &y);
+        let den_inv_z_minus_y = self.fp_mul(&den_inv, &z_minus_y);
+        let s = self.ed25519_fp_abs(&den_inv_z_minus_y);
+        self.ed25519_fp_to_bytes(&s);
+
+        s
+    }
+
+    /// Returns the least significant bit of `a`, constraining `a` to be less than the modulus.
+    fn ed25519_fp_is_negative(&mut self, a: &FieldRegister<Ed25519BaseField>) -> BitRegister
+    where
+        L::Instruction: FromFieldInstruction<Ed25519BaseField> + From<Ed25519FpBytesInstruction>,
+    {
+        self.ed25519_fp_to_bytes_with_parity(a).1
+    }
+
+    /// Returns `-a` if `a` is negative and `a` otherwise.
+    ///
+    /// The input is constrained to be less than the modulus, but the output is not.
+    fn ed25519_fp_abs(
+        &mut self,
+        a: &FieldRegister<Ed25519BaseField>,
+    ) -> FieldRegister<Ed25519BaseField>
+    where
+        L::Instruction: FromFieldInstruction<Ed25519BaseField> + From<Ed25519FpBytesInstruction>,
+    {
+        let is_negative = self.ed25519_fp_is_negative(a);
+        let zero = self.fp_zero::<Ed25519BaseField>();
+        let minus_a = self.fp_sub(&zero, a);
+        self.select(&is_negative, &minus_a, a)
+    }
+}
+
+fn is_negative(a: &BigUint) -> bool {
+    a.bit(0)
+}
+
+fn abs(a: BigUint) -> BigUint {
+    if is_negative(&a) {
+        Ed25519BaseField::modulus() - a
+    } else {
+        a
+    }
+}
+
+/// Decodes a ristretto255 encoding into a representative Edwards point, or returns `None` if the
+/// encoding is invalid.
+pub fn decode(encoding: &CompressedRistretto) -> Option<AffinePoint<Ed25519>> {
+    let modulus = &Ed25519BaseField::modulus();
+    let s = BigUint::from_bytes_le(encoding.as_bytes());
+    if &s >= modulus || is_negative(&s) {
+        return None;
+    }
+
+    let ss = &s * &s % modulus;
+    let u1 = (BigUint::one() + modulus - &ss) % modulus;
+    let u2 = (BigUint::one() + &ss) % modulus;
+    let u2_sqr = &u2 * &u2 % modulus;
+    let minus_v = (Ed25519Parameters::d_biguint() * &u1 * &u1 + &u2_sqr) % modulus;
+    let v = (modulus - minus_v) % modulus;
+
+    // `v * u2^2` must be a nonzero square.
+    let v_u2_sqr = &v * &u2_sqr % modulus;
+    let exponent = (modulus - BigUint::one()) >> 1;
+    if v_u2_sqr.modpow(&exponent, modulus) != BigUint::one() {
+        return None;
+    }
+    let invsqrt = inv_sqrt(&v_u2_sqr);
+
+    let den_x = &invsqrt * &u2 % modulus;
+    let den_y = &invsqrt * &den_x * &v % modulus;
+
+    let x = abs(BigUint::from(2u32) * &s * &den_x % modulus);
+    let y = &u1 * &den_y % modulus;
+    let t = &x * &y % modulus;
+
+    if is_negative(&t) || y.is_zero() {
+        return None;
+    }
+
+    Some(AffinePoint::new(x, y))
+}
+
+/// Computes the canonical ristretto255 encoding of a representative Edwards point.
+pub fn encode(point: &AffinePoint<Ed25519>) -> CompressedRistretto {
+    let modulus = &Ed25519BaseField::modulus();
+    let sqrt_m1 = BigUint::from_str(SQRT_M1).unwrap();
+    let invsqrt_a_minus_d = BigUint::from_str(INVSQRT_A_MINUS_D).unwrap();
+
+    let (x, y) = (&point.x, &point.y);
+    let u1 = (BigUint::one() + modulus - y * y % modulus) % modulus;
+    let u2 = x * y % modulus;
+    let invsqrt = inv_sqrt(&(&u1 * &u2 * &u2 % modulus));
+
+    let den1 = &invsqrt * &u1 % modulus;
+    let den2 = &invsqrt * &u2 % modulus;
+    let z_inv = &den1 * &den2 * &u2 % modulus;
+
+    let rotate = is_negative(&(&u2 * &z_inv % modulus));
+    let (x, y, den_inv) = if rotate {
+        (
+            y * &sqrt_m1 % modulus,
+            x * &sqrt_m1 % modulus,
+            &den1 * &invsqrt_a_minus_d % modulus,
+        )
+    } else {
+        (x.clone(), y.clone(), den2)
+    };
+
+    let y = if is_negative(&(&x * &z_inv % modulus)) {
+        (modulus - y) % modulus
+    } else {
+        y
+    };
+
+    let s = abs(den_inv * ((BigUint::one() + modulus - y) % modulus) % modulus);
+
+    let mut encoding = [0u8; 32];
+    let s_bytes = s.to_bytes_le();
+    encoding[..s_bytes.len()].copy_from_slice(&s_bytes);
+    CompressedRistretto(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+    use curve25519_dalek::scalar::Scalar;
+    use rand::{thread_rng, Rng};
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519FpInstruction;
+    use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
+    use crate::chip::ec::EllipticCurve;
+    use crate::chip::trace::writer::TraceWriter;
+    use crate::chip::utils::digits_to_biguint;
+    use crate::math::prelude::*;
+    use crate::polynomial::to_le_limbs_polynomial;
+
+    /// The encodings of the multiples `0, B, ..., 15 * B` of the generator, from RFC 9496,
+    /// appendix A.1.
+    const MULTIPLES_OF_GENERATOR: [&str; 16] = [
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+        "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+        "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+        "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+        "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+        "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+        "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+        "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+        "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+        "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+        "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+        "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+        "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+        "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e",
+    ];
+
+    /// Invalid encodings from RFC 9496, appendix A.2.
+    const BAD_ENCODINGS: [&str; 29] = [
+        // Non-canonical field encodings.
+        "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        // Negative field elements.
+        "0100000000000000000000000000000000000000000000000000000000000000",
+        "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+        "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+        "c34c4e1826e5d403b78e246e88aa051c36ccf0aafebffe137d148a2bf9104562",
+        "c940e5a4404157cfb1628b108db051a8d439e1a421394ec4ebccb9ec92a8ac78",
+        "47cfc5497c53dc8e61c91d17fd626ffb1c49e2bca94eed052281b510b1117a24",
+        "f1c6165d33367351b0da8f6e4511010c68174a03b6581212c71c0e1d026c3c72",
+        "87260f7a2f12495118360f02c26a470f450dadf34a413d21042b43b9d93e1309",
+        // Non-square x^2.
+        "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+        "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+        "de6a7b00deadc788eb6b6c8d20c0ae96c2f2019078fa604fee5b87d6e989ad7b",
+        "bcab477be20861e01e4a0e295284146a510150d9817763caf1a6f4b422d67042",
+        "2a292df7e32cababbd9de088d1d1abec9fc0440f637ed2fba145094dc14bea08",
+        "f4a9e534fc0d216c44b218fa0c42d99635a0127ee2e53c712f70609649fdff22",
+        "8268436f8c4126196cf64b3c7ddbda90746a378625f9813dd9b8457077256731",
+        "2810e5cbc2cc4d4eece54f61c6f69758e289aa7ab440b3cbeaa21995c2f4232b",
+        // Negative xy value.
+        "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+        "a45fdc55c76448c049a1ab33f17023edfb2be3581e9c7aade8a6125215e04220",
+        "d483fe813c6ba647ebbfd3ec41adca1c6130c2beeee9d9bf065c8d151c5f396e",
+        "8a2e1d30050198c65a54483123960ccc38aef6848e1ec8f5f780e8523769ba32",
+        "32888462f8b486c68ad7dd9610be5192bbeaf3b443951ac1a8118419d9fa097b",
+        "227142501b9d4355ccba290404bde41575b037693cef1f438c47f8fbf35d1165",
+        "5c37cc491da847cfeb9281d407efc41e15144c876e0170b499a96a22ed31e01e",
+        "445425117cb8c90edcbc7c1cc0e74f747f2c1efa5630a967c64f287792a48a4b",
+        // s = -1, which causes y = 0.
+        "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ];
+
+    fn compressed_ristretto(encoding: &str) -> CompressedRistretto {
+        CompressedRistretto::from_slice(&hex::decode(encoding).unwrap()).unwrap()
+    }
+
+    fn read_encoding<F: PrimeField64>(
+        writer: &TraceWriter<F>,
+        register: &FieldRegister<Ed25519BaseField>,
+        row_index: usize,
+    ) -> CompressedRistretto {
+        let digits = writer
+            .read(register, row_index)
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+        let mut encoding = [0u8; 32];
+        let bytes = digits_to_biguint(&digits).to_bytes_le();
+        encoding[..bytes.len()].copy_from_slice(&bytes);
+        CompressedRistretto(encoding)
+    }
+
+    #[test]
+    fn test_ristretto_native() {
+        let base = Ed25519::ec_generator();
+        let mut multiple = Ed25519::ec_neutral().unwrap();
+        for (k, encoding) in MULTIPLES_OF_GENERATOR.iter().enumerate() {
+            let compressed = compressed_ristretto(encoding);
+
+            assert_eq!(encode(&multiple), compressed);
+            let decoded = decode(&compressed).unwrap();
+            assert_eq!(encode(&decoded), compressed);
+
+            let expected = RISTRETTO_BASEPOINT_POINT * Scalar::from(k as u64);
+            assert_eq!(compressed.decompress().unwrap(), expected);
+            assert_eq!(expected.compress(), compressed);
+
+            multiple = &multiple + &base;
+        }
+
+        for encoding in BAD_ENCODINGS {
+            let compressed = compressed_ristretto(encoding);
+            assert!(decode(&compressed).is_none());
+            assert!(compressed.decompress().is_none());
+        }
+
+        let mut rng = thread_rng();
+        for _ in 0..16 {
+            let scalar_bytes = rng.gen::<[u8; 32]>();
+            let point = &base * &BigUint::from_bytes_le(&scalar_bytes);
+            let expected = RISTRETTO_BASEPOINT_POINT * Scalar::from_bytes_mod_order(scalar_bytes);
+            assert_eq!(encode(&point), expected.compress());
+            assert_eq!(
+                encode(&decode(&expected.compress()).unwrap()),
+                expected.compress()
+            );
+        }
+    }
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct RistrettoTest;
+
+    impl AirParameters for RistrettoTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 7711;
+        const NUM_FREE_COLUMNS: usize = 178;
+        const EXTENDED_COLUMNS: usize = 11574;
+        type Instruction = Ed25519FpInstruction;
+    }
+
+    #[test]
+    fn test_ristretto_decode_encode() {
+        type L = RistrettoTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = Ed25519;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        // Decode an encoding and encode the result back.
+        let s = builder.alloc::<FieldRegister<Ed25519BaseField>>();
+        let decoded = builder.ristretto_decode(&s);
+        let s_encoded = builder.ristretto_encode(&decoded);
+
+        // Encode a multiple of the generator.
+        let q = RistrettoPointRegister::new(builder.alloc_ec_point());
+        let q_encoded = builder.ristretto_encode(&q);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::ec_generator();
+        let mut rng = thread_rng();
+        let encodings = MULTIPLES_OF_GENERATOR
+            .iter()
+            .map(|encoding| compressed_ristretto(encoding))
+            .chain((0..16).map(|_| {
+                let scalar = Scalar::from_bytes_mod_order(rng.gen::<[u8; 32]>());
+                (RISTRETTO_BASEPOINT_POINT * scalar).compress()
+            }))
+            .collect::<Vec<_>>();
+        let points = (0..16)
+            .map(|_| {
+                let scalar_bytes = rng.gen::<[u8; 32]>();
+                let point = &base * &BigUint::from_bytes_le(&scalar_bytes);
+                let expected =
+                    RISTRETTO_BASEPOINT_POINT * Scalar::from_bytes_mod_order(scalar_bytes);
+                (point, expected.compress())
+            })
+            .collect::<Vec<_>>();
+
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            let encoding = &encodings[i % encodings.len()];
+            let s_value = BigUint::from_bytes_le(encoding.as_bytes());
+            writer.write(
+                &s,
+                &to_le_limbs_polynomial::<GoldilocksField, Ed25519BaseField>(&s_value),
+                i,
+            );
+            let (point, point_encoding) = &points[i % points.len()];
+            writer.write_ec_point(&q.point, point, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            if i < 64 {
+                let decoded_value = writer.read_ec_point(&decoded.point, i);
+                assert_eq!(Some(decoded_value), decode(encoding));
+                assert_eq!(read_encoding(&writer, &s_encoded, i), *encoding);
+                assert_eq!(read_encoding(&writer, &q_encoded, i), *point_encoding);
+            }
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -19,7 +19,7 @@ use crate::chip::utils::digits_to_biguint;
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::to_u16_le_limbs_polynomial;
+use crate::polynomial::to_le_limbs_polynomial;
 
 /// Fp Square Root. Computes `sqrt(a) = result`.
 ///
@@ -142,7 +142,7 @@ impl<F: PrimeField64> Instruction<F> for Ed25519FpSqrtInstruction {
         let a = digits_to_biguint(&a_digits);
 
         let beta = sqrt(a);
-        let p_beta = to_u16_le_limbs_polynomial::<F, Ed25519BaseField>(&beta);
+        let p_beta = to_le_limbs_polynomial::<F, Ed25519BaseField>(&beta);
         let a = &self.square.a;
 
         let limb = p_beta.coefficients[0].as_canonical_u64();
@@ -168,7 +168,7 @@ impl<F: PrimeField64> Instruction<F> for Ed25519FpSqrtInstruction {
         let a = digits_to_biguint(&a_digits);
 
         let beta = sqrt(a);
-        let p_beta = to_u16_le_limbs_polynomial::<F, Ed25519BaseField>(&beta);
+        let p_beta = to_le_limbs_polynomial::<F, Ed25519BaseField>(&beta);
         let a = &self.square.a;
 
         let limb = p_beta.coefficients[0].as_canonical_u64();
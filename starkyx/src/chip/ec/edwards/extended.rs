@@ -0,0 +1,168 @@
+use super::{EdwardsCurve, EdwardsParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::ec::point::{AffinePointRegister, ExtendedPointRegister};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::AirParameters;
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Converts an affine point `(x, y)` to the extended coordinates `(x : y : 1 : x * y)`.
+    pub fn ed_to_extended<E: EdwardsParameters>(
+        &mut self,
+        p: &AffinePointRegister<EdwardsCurve<E>>,
+    ) -> ExtendedPointRegister<EdwardsCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let z = self.fp_one();
+        let t = self.fp_mul(&p.x, &p.y);
+
+        ExtendedPointRegister::new(p.x, p.y, z, t)
+    }
+
+    /// Adds two points in extended coordinates, without any field division.
+    pub fn ed_extended_add<E: EdwardsParameters>(
+        &mut self,
+        p: &ExtendedPointRegister<EdwardsCurve<E>>,
+        q: &ExtendedPointRegister<EdwardsCurve<E>>,
+    ) -> ExtendedPointRegister<EdwardsCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        // Unified addition formula in extended coordinates for `a = -1`
+        //
+        // Given two points (X1 : Y1 : Z1 : T1) and (X2 : Y2 : Z2 : T2), compute the sum
+        // (X3 : Y3 : Z3 : T3) with
+        //
+        // E = X1 * Y2 + Y1 * X2
+        // F = Z1 * Z2 - d * T1 * T2
+        // G = Z1 * Z2 + d * T1 * T2
+        // H = Y1 * Y2 + X1 * X2
+        //
+        // X3 = E * F, Y3 = G * H, Z3 = F * G, T3 = E * H.
+        //
+        // Reference: https://eprint.iacr.org/2008/522
+
+        let e = self.fp_inner_product(&[p.x, p.y], &[q.y, q.x]);
+        let h = self.fp_inner_product(&[p.y, p.x], &[q.y, q.x]);
+
+        let t1_mul_t2 = self.fp_mul(&p.t, &q.t);
+        let d_mul_t = self.fp_mul_const(&t1_mul_t2, E::D);
+        let z1_mul_z2 = self.fp_mul(&p.z, &q.z);
+
+        let f = self.fp_sub(&z1_mul_z2, &d_mul_t);
+        let g = self.fp_add(&z1_mul_z2, &d_mul_t);
+
+        let x3 = self.fp_mul(&e, &f);
+        let y3 = self.fp_mul(&g, &h);
+        let z3 = self.fp_mul(&f, &g);
+        let t3 = self.fp_mul(&e, &h);
+
+        ExtendedPointRegister::new(x3, y3, z3, t3)
+    }
+
+    /// Doubles a point in extended coordinates. As with `ed_double`, the unified addition formula
+    /// is used under the hood.
+    pub fn ed_extended_double<E: EdwardsParameters>(
+        &mut self,
+        p: &ExtendedPointRegister<EdwardsCurve<E>>,
+    ) -> ExtendedPointRegister<EdwardsCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        self.ed_extended_add(p, p)
+    }
+
+    /// Converts a point in extended coordinates back to the affine point `(X / Z, Y / Z)`.
+    pub fn ed_extended_to_affine<E: EdwardsParameters>(
+        &mut self,
+        p: &ExtendedPointRegister<EdwardsCurve<E>>,
+    ) -> AffinePointRegister<EdwardsCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let x = self.fp_div(&p.x, &p.z);
+        let y = self.fp_div(&p.y, &p.z);
+
+        AffinePointRegister::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::edwards::ed25519::params::{Ed25519, Ed25519BaseField};
+    use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
+    use crate::chip::ec::EllipticCurve;
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct Ed25519ExtendedTest;
+
+    impl AirParameters for Ed25519ExtendedTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 2916;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 4383;
+        type Instruction = FpInstruction<Ed25519BaseField>;
+    }
+
+    #[test]
+    fn test_ed25519_extended_add_double() {
+        type L = Ed25519ExtendedTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = Ed25519;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+        let q = builder.alloc_ec_point();
+
+        let p_ext = builder.ed_to_extended(&p);
+        let q_ext = builder.ed_to_extended(&q);
+        let sum_ext = builder.ed_extended_add(&p_ext, &q_ext);
+        let double_ext = builder.ed_extended_double(&sum_ext);
+
+        let sum = builder.ed_extended_to_affine(&sum_ext);
+        let double = builder.ed_extended_to_affine(&double_ext);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::ec_generator();
+        let mut rng = thread_rng();
+        let a = rng.gen_biguint(256);
+        let b = rng.gen_biguint(256);
+        let p_int = &base * &a;
+        let q_int = &base * &b;
+        let sum_int = &p_int + &q_int;
+        let double_int = E::ec_double(&sum_int);
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+        (0..num_rows).into_par_iter().for_each(|i| {
+            writer.write_ec_point(&p, &p_int, i);
+            writer.write_ec_point(&q, &q_int, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        });
+
+        assert_eq!(writer.read_ec_point(&sum, 0), sum_int);
+        assert_eq!(writer.read_ec_point(&double, 0), double_int);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -1,17 +1,23 @@
 use num::{BigUint, Zero};
 use serde::{Deserialize, Serialize};
 
-use super::point::{AffinePoint, AffinePointRegister};
-use super::{EllipticCurve, EllipticCurveAir, EllipticCurveParameters};
+use super::point::{AffinePoint, AffinePointRegister, ExtendedPointRegister};
+use super::{EllipticCurve, EllipticCurveAir, EllipticCurveParameters, ProjectiveEllipticCurveAir};
 use crate::chip::builder::AirBuilder;
 use crate::chip::field::instruction::FromFieldInstruction;
 use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+use crate::chip::field::register::FieldRegister;
 use crate::chip::AirParameters;
 pub mod add;
 pub mod assert_valid;
 pub mod bigint_operations;
 pub mod ed25519;
+pub mod extended;
 
+/// Parameters of a twisted Edwards curve `-x^2 + y^2 = 1 + d * x^2 * y^2`.
+///
+/// The coefficient `a` is fixed to `-1`, which the affine and extended addition formulas rely on,
+/// so a curve with another `a` cannot be described by these parameters.
 pub trait EdwardsParameters: EllipticCurveParameters {
     const D: [u16; MAX_NB_LIMBS];
 
@@ -22,7 +28,7 @@ pub trait EdwardsParameters: EllipticCurveParameters {
     fn d_biguint() -> BigUint {
         let mut modulus = BigUint::zero();
         for (i, limb) in Self::D.iter().enumerate() {
-            modulus += BigUint::from(*limb) << (16 * i);
+            modulus += BigUint::from(*limb) << (Self::BaseField::NB_BITS_PER_LIMB * i);
         }
         modulus
     }
@@ -140,3 +146,47 @@ where
         AffinePointRegister::new(x, y)
     }
 }
+
+impl<L: AirParameters, E: EdwardsParameters> ProjectiveEllipticCurveAir<L> for EdwardsCurve<E>
+where
+    L::Instruction: FromFieldInstruction<E::BaseField>,
+{
+    type ProjectivePointRegister = ExtendedPointRegister<Self>;
+
+    fn projective_coordinates(p: &ExtendedPointRegister<Self>) -> Vec<FieldRegister<E::BaseField>> {
+        vec![p.x, p.y, p.z, p.t]
+    }
+
+    fn projective_from_coordinates(
+        coordinates: &[FieldRegister<E::BaseField>],
+    ) -> ExtendedPointRegister<Self> {
+        ExtendedPointRegister::new(
+            coordinates[0],
+            coordinates[1],
+            coordinates[2],
+            coordinates[3],
+        )
+    }
+
+    fn ec_to_projective_air(
+        builder: &mut AirBuilder<L>,
+        p: &AffinePointRegister<Self>,
+    ) -> ExtendedPointRegister<Self> {
+        builder.ed_to_extended::<E>(p)
+    }
+
+    fn ec_projective_add_air(
+        builder: &mut AirBuilder<L>,
+        p: &ExtendedPointRegister<Self>,
+        q: &ExtendedPointRegister<Self>,
+    ) -> ExtendedPointRegister<Self> {
+        builder.ed_extended_add::<E>(p, q)
+    }
+
+    fn ec_projective_to_affine_air(
+        builder: &mut AirBuilder<L>,
+        p: &ExtendedPointRegister<Self>,
+    ) -> AffinePointRegister<Self> {
+        builder.ed_extended_to_affine::<E>(p)
+    }
+}
@@ -8,7 +8,7 @@ use crate::chip::trace::writer::{AirWriter, TraceWriter};
 use crate::chip::utils::field_limbs_to_biguint;
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
-use crate::polynomial::to_u16_le_limbs_polynomial;
+use crate::polynomial::to_le_limbs_polynomial;
 
 pub trait EllipticCurveGadget<E: EllipticCurve> {
     fn alloc_unchecked_ec_point(&mut self) -> AffinePointRegister<E>;
@@ -43,15 +43,15 @@ pub trait EllipticCurveAirWriter<E: EllipticCurve>: AirWriter {
         let p_x = self.read(&data.x);
         let p_y = self.read(&data.y);
 
-        let x = field_limbs_to_biguint(p_x.coefficients());
-        let y = field_limbs_to_biguint(p_y.coefficients());
+        let x = field_limbs_to_biguint(p_x.coefficients(), E::BaseField::NB_BITS_PER_LIMB);
+        let y = field_limbs_to_biguint(p_y.coefficients(), E::BaseField::NB_BITS_PER_LIMB);
 
         AffinePoint::<E>::new(x, y)
     }
 
     fn write_ec_point(&mut self, data: &AffinePointRegister<E>, value: &AffinePoint<E>) {
-        let value_x = to_u16_le_limbs_polynomial::<Self::Field, E::BaseField>(&value.x);
-        let value_y = to_u16_le_limbs_polynomial::<Self::Field, E::BaseField>(&value.y);
+        let value_x = to_le_limbs_polynomial::<Self::Field, E::BaseField>(&value.x);
+        let value_y = to_le_limbs_polynomial::<Self::Field, E::BaseField>(&value.y);
         self.write(&data.x, &value_x);
         self.write(&data.y, &value_y);
     }
@@ -95,8 +95,8 @@ impl<F: PrimeField64, E: EllipticCurve> EllipticCurveWriter<E> for TraceWriter<F
         let p_x = self.read(&data.x, row_index);
         let p_y = self.read(&data.y, row_index);
 
-        let x = field_limbs_to_biguint(p_x.coefficients());
-        let y = field_limbs_to_biguint(p_y.coefficients());
+        let x = field_limbs_to_biguint(p_x.coefficients(), E::BaseField::NB_BITS_PER_LIMB);
+        let y = field_limbs_to_biguint(p_y.coefficients(), E::BaseField::NB_BITS_PER_LIMB);
 
         AffinePoint::<E>::new(x, y)
     }
@@ -107,8 +107,8 @@ impl<F: PrimeField64, E: EllipticCurve> EllipticCurveWriter<E> for TraceWriter<F
         value: &AffinePoint<E>,
         row_index: usize,
     ) {
-        let value_x = to_u16_le_limbs_polynomial::<F, E::BaseField>(&value.x);
-        let value_y = to_u16_le_limbs_polynomial::<F, E::BaseField>(&value.y);
+        let value_x = to_le_limbs_polynomial::<F, E::BaseField>(&value.x);
+        let value_y = to_le_limbs_polynomial::<F, E::BaseField>(&value.y);
         self.write(&data.x, &value_x, row_index);
         self.write(&data.y, &value_y, row_index);
     }
@@ -6,6 +6,7 @@ use serde::Serialize;
 use self::point::{AffinePoint, AffinePointRegister};
 use super::builder::AirBuilder;
 use super::field::parameters::FieldParameters;
+use super::field::register::FieldRegister;
 use super::AirParameters;
 use crate::machine::builder::ops::{Add, Double};
 use crate::machine::builder::Builder;
@@ -13,6 +14,7 @@ use crate::machine::builder::Builder;
 pub mod edwards;
 pub mod gadget;
 mod instruction_set;
+pub mod montgomery;
 pub mod point;
 pub mod scalar;
 pub mod scalar_mul;
@@ -67,6 +69,46 @@ pub trait EllipticCurveAir<L: AirParameters>: EllipticCurve {
     fn ec_generator_air(builder: &mut AirBuilder<L>) -> AffinePointRegister<Self>;
 }
 
+/// Division-free point arithmetic in projective coordinates.
+///
+/// This allows to keep a running sum of points in projective coordinates over many rows, and to
+/// convert it back to affine coordinates only once at the end.
+pub trait ProjectiveEllipticCurveAir<L: AirParameters>: EllipticCurveAir<L> {
+    /// The register of a point in projective coordinates.
+    type ProjectivePointRegister: Debug + Clone + Copy;
+
+    /// Returns the coordinates of a point in projective coordinates.
+    fn projective_coordinates(
+        p: &Self::ProjectivePointRegister,
+    ) -> Vec<FieldRegister<Self::BaseField>>;
+
+    /// Returns the point with the given coordinates, in the order of `projective_coordinates`.
+    fn projective_from_coordinates(
+        coordinates: &[FieldRegister<Self::BaseField>],
+    ) -> Self::ProjectivePointRegister;
+
+    /// Converts an affine point to projective coordinates.
+    fn ec_to_projective_air(
+        builder: &mut AirBuilder<L>,
+        p: &AffinePointRegister<Self>,
+    ) -> Self::ProjectivePointRegister;
+
+    /// Adds two points in projective coordinates.
+    ///
+    /// Warning: As with `ec_add_air`, this method may assume that the two points are different.
+    fn ec_projective_add_air(
+        builder: &mut AirBuilder<L>,
+        p: &Self::ProjectivePointRegister,
+        q: &Self::ProjectivePointRegister,
+    ) -> Self::ProjectivePointRegister;
+
+    /// Converts a point in projective coordinates back to affine coordinates.
+    fn ec_projective_to_affine_air(
+        builder: &mut AirBuilder<L>,
+        p: &Self::ProjectivePointRegister,
+    ) -> AffinePointRegister<Self>;
+}
+
 impl<L: AirParameters> AirBuilder<L> {
     pub fn ec_add<E: EllipticCurveAir<L>>(
         &mut self,
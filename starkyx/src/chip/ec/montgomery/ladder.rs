@@ -0,0 +1,67 @@
+use super::{MontgomeryParameters, MontgomeryPointRegister};
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::AirParameters;
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Computes a step of the Montgomery ladder on projective u-coordinates.
+    ///
+    /// Given `p`, `q` and the u-coordinate `u` of `q - p`, returns `(2 * p, p + q)`.
+    pub fn montgomery_ladder_step<M: MontgomeryParameters>(
+        &mut self,
+        u: &FieldRegister<M::BaseField>,
+        p: &MontgomeryPointRegister<M>,
+        q: &MontgomeryPointRegister<M>,
+    ) -> (MontgomeryPointRegister<M>, MontgomeryPointRegister<M>)
+    where
+        L::Instruction: FromFieldInstruction<M::BaseField>,
+    {
+        // Montgomery Ladder Step Formula
+        //
+        // Given p = (X2 : Z2) and q = (X3 : Z3), compute 2 * p = (X4 : Z4) and
+        // p + q = (X5 : Z5) with
+        //
+        // A = X2 + Z2, B = X2 - Z2, C = X3 + Z3, D = X3 - Z3,
+        // E = A^2 - B^2,
+        //
+        // X4 = A^2 * B^2
+        // Z4 = E * (A^2 + a24 * E)
+        // X5 = (D * A + C * B)^2
+        // Z5 = u * (D * A - C * B)^2.
+        //
+        // Reference: https://datatracker.ietf.org/doc/html/rfc7748#section-5
+
+        let a = self.fp_add(&p.x, &p.z);
+        let a_sq = self.fp_mul(&a, &a);
+        let b = self.fp_sub(&p.x, &p.z);
+        let b_sq = self.fp_mul(&b, &b);
+        let e = self.fp_sub(&a_sq, &b_sq);
+        let c = self.fp_add(&q.x, &q.z);
+        let d = self.fp_sub(&q.x, &q.z);
+        let d_mul_a = self.fp_mul(&d, &a);
+        let c_mul_b = self.fp_mul(&c, &b);
+
+        // x5 = (d * a + c * b)^2.
+        let sum = self.fp_add(&d_mul_a, &c_mul_b);
+        let x5 = self.fp_mul(&sum, &sum);
+
+        // z5 = u * (d * a - c * b)^2.
+        let diff = self.fp_sub(&d_mul_a, &c_mul_b);
+        let diff_sq = self.fp_mul(&diff, &diff);
+        let z5 = self.fp_mul(u, &diff_sq);
+
+        // x4 = a^2 * b^2.
+        let x4 = self.fp_mul(&a_sq, &b_sq);
+
+        // z4 = e * (a^2 + a24 * e).
+        let a24_mul_e = self.fp_mul_const(&e, M::A24);
+        let a_sq_plus_a24_mul_e = self.fp_add(&a_sq, &a24_mul_e);
+        let z4 = self.fp_mul(&e, &a_sq_plus_a24_mul_e);
+
+        (
+            MontgomeryPointRegister::new(x4, z4),
+            MontgomeryPointRegister::new(x5, z5),
+        )
+    }
+}
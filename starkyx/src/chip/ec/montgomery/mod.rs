@@ -0,0 +1,99 @@
+use core::fmt::Debug;
+
+use num::{BigUint, One, Zero};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+use crate::chip::field::register::FieldRegister;
+
+pub mod ladder;
+pub mod x25519;
+
+/// Parameters that specify a Montgomery curve : B * v^2 = u^3 + A * u^2 + u.
+///
+/// Only the u-coordinates of points are used, so the ladder only depends on `A` through the
+/// constant `A24 = (A - 2) / 4`.
+pub trait MontgomeryParameters:
+    Debug + Send + Sync + Copy + Serialize + DeserializeOwned + 'static
+{
+    type BaseField: FieldParameters;
+
+    const A24: [u16; MAX_NB_LIMBS];
+
+    fn a24() -> BigUint {
+        let mut a24 = BigUint::zero();
+        for (i, limb) in Self::A24.iter().enumerate() {
+            a24 += BigUint::from(*limb) << (Self::BaseField::NB_BITS_PER_LIMB * i);
+        }
+        a24
+    }
+}
+
+/// The u-coordinate of a point in projective coordinates `(X : Z)`, representing `u = X / Z`.
+///
+/// The point at infinity is represented by `(1 : 0)`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MontgomeryPointRegister<M: MontgomeryParameters> {
+    pub x: FieldRegister<M::BaseField>,
+    pub z: FieldRegister<M::BaseField>,
+}
+
+impl<M: MontgomeryParameters> MontgomeryPointRegister<M> {
+    pub fn new(x: FieldRegister<M::BaseField>, z: FieldRegister<M::BaseField>) -> Self {
+        Self { x, z }
+    }
+}
+
+/// Computes a step of the Montgomery ladder on projective u-coordinates.
+///
+/// Given `p`, `q` and the u-coordinate `u` of `q - p`, returns `(2 * p, p + q)`.
+pub fn montgomery_ladder_step<M: MontgomeryParameters>(
+    u: &BigUint,
+    p: &(BigUint, BigUint),
+    q: &(BigUint, BigUint),
+) -> ((BigUint, BigUint), (BigUint, BigUint)) {
+    let modulus = M::BaseField::modulus();
+    let (x2, z2) = p;
+    let (x3, z3) = q;
+
+    let a = (x2 + z2) % &modulus;
+    let aa = (&a * &a) % &modulus;
+    let b = (&modulus + x2 - z2) % &modulus;
+    let bb = (&b * &b) % &modulus;
+    let e = (&modulus + &aa - &bb) % &modulus;
+    let c = (x3 + z3) % &modulus;
+    let d = (&modulus + x3 - z3) % &modulus;
+    let da = (d * &a) % &modulus;
+    let cb = (c * &b) % &modulus;
+
+    let sum = (&da + &cb) % &modulus;
+    let diff = (&modulus + &da - &cb) % &modulus;
+    let x5 = (&sum * &sum) % &modulus;
+    let z5 = (u * &diff * &diff) % &modulus;
+
+    let x4 = (&aa * &bb) % &modulus;
+    let z4 = (&e * ((&aa + M::a24() * &e) % &modulus)) % &modulus;
+
+    ((x4, z4), (x5, z5))
+}
+
+/// Runs the Montgomery ladder over the bits of a scalar, most significant bit first, and returns
+/// the projective u-coordinate of the product of the scalar with the point of u-coordinate `u`.
+///
+/// Every step doubles one of the two points of the ladder and adds them, so that the sequence of
+/// operations does not depend on the bits.
+pub fn montgomery_ladder<M: MontgomeryParameters>(
+    u: &BigUint,
+    bits: impl IntoIterator<Item = bool>,
+) -> (BigUint, BigUint) {
+    let mut r0 = (BigUint::one(), BigUint::zero());
+    let mut r1 = (u.clone(), BigUint::one());
+    for bit in bits {
+        let (p, q) = if bit { (&r1, &r0) } else { (&r0, &r1) };
+        let (double, sum) = montgomery_ladder_step::<M>(u, p, q);
+        (r0, r1) = if bit { (sum, double) } else { (double, sum) };
+    }
+    r0
+}
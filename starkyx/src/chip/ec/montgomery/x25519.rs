@@ -0,0 +1,112 @@
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use super::{montgomery_ladder, MontgomeryParameters};
+use crate::chip::ec::edwards::ed25519::params::Ed25519BaseField;
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+
+/// The number of bits of an X25519 scalar, all of which are processed by the ladder.
+///
+/// Clamping clears the most significant bit, so that the first step of the ladder leaves it
+/// unchanged and the ladder has the 255 steps of RFC 7748.
+pub const X25519_SCALAR_BITS: usize = 256;
+
+/// The Montgomery form of Curve25519 : v^2 = u^3 + 486662 * u^2 + u.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Curve25519Parameters;
+
+impl MontgomeryParameters for Curve25519Parameters {
+    type BaseField = Ed25519BaseField;
+
+    const A24: [u16; MAX_NB_LIMBS] = [
+        56129, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0,
+    ];
+}
+
+/// Clamps an X25519 scalar as in RFC 7748, clearing the three least significant bits and the
+/// most significant bit, and setting the second most significant bit.
+pub fn x25519_clamp(scalar: &[u8; 32]) -> [u8; 32] {
+    let mut clamped = *scalar;
+    clamped[0] &= 248;
+    clamped[31] &= 127;
+    clamped[31] |= 64;
+    clamped
+}
+
+/// Decodes a u-coordinate as in RFC 7748, ignoring the most significant bit and reducing the
+/// result modulo `p`.
+pub fn x25519_decode_u(u: &[u8; 32]) -> BigUint {
+    let mut bytes = *u;
+    bytes[31] &= 127;
+    BigUint::from_bytes_le(&bytes) % Ed25519BaseField::modulus()
+}
+
+/// The bits of the clamped scalar in the order of the ladder, most significant bit first.
+pub fn x25519_ladder_bits(scalar: &[u8; 32]) -> Vec<bool> {
+    let clamped = x25519_clamp(scalar);
+    (0..X25519_SCALAR_BITS)
+        .rev()
+        .map(|i| (clamped[i / 8] >> (i % 8)) & 1 == 1)
+        .collect()
+}
+
+/// Computes the projective u-coordinate `(X : Z)` of the ladder of `scalar` on `u`, before the
+/// final division.
+pub fn x25519_projective(scalar: &[u8; 32], u: &BigUint) -> (BigUint, BigUint) {
+    montgomery_ladder::<Curve25519Parameters>(u, x25519_ladder_bits(scalar))
+}
+
+/// The X25519 function of RFC 7748.
+pub fn x25519(scalar: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let modulus = Ed25519BaseField::modulus();
+    let (x, z) = x25519_projective(scalar, &x25519_decode_u(u));
+    let z_inv = z.modpow(&(&modulus - 2u32), &modulus);
+    let result = (x * z_inv) % &modulus;
+
+    let mut bytes = result.to_bytes_le();
+    bytes.resize(32, 0);
+    bytes.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::montgomery::MontgomeryPoint;
+    use rand::{thread_rng, Rng};
+
+    use super::*;
+
+    fn bytes(hex: &str) -> [u8; 32] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_x25519_rfc7748_vectors() {
+        let vectors = [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ];
+        for (scalar, u, expected) in vectors {
+            assert_eq!(x25519(&bytes(scalar), &bytes(u)), bytes(expected));
+        }
+    }
+
+    #[test]
+    fn test_x25519_dalek() {
+        let mut rng = thread_rng();
+        for _ in 0..10 {
+            let scalar = rng.gen::<[u8; 32]>();
+            let u = rng.gen::<[u8; 32]>();
+            let expected = MontgomeryPoint(u).mul_clamped(scalar);
+            assert_eq!(x25519(&scalar, &u), expected.to_bytes());
+        }
+    }
+}
@@ -36,6 +36,48 @@ impl<E: EllipticCurve> AffinePointRegister<E> {
     }
 }
 
+/// A point in projective coordinates `(X : Y : Z)`.
+///
+/// On short Weierstrass curves, the coordinates are Jacobian, representing the affine point
+/// `(X / Z^2, Y / Z^3)`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ProjectivePointRegister<E: EllipticCurve> {
+    pub x: FieldRegister<E::BaseField>,
+    pub y: FieldRegister<E::BaseField>,
+    pub z: FieldRegister<E::BaseField>,
+}
+
+impl<E: EllipticCurve> ProjectivePointRegister<E> {
+    pub fn new(
+        x: FieldRegister<E::BaseField>,
+        y: FieldRegister<E::BaseField>,
+        z: FieldRegister<E::BaseField>,
+    ) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// A point on a twisted Edwards curve in extended coordinates `(X : Y : Z : T)`, representing
+/// the affine point `(X / Z, Y / Z)` with `T = X * Y / Z`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ExtendedPointRegister<E: EllipticCurve> {
+    pub x: FieldRegister<E::BaseField>,
+    pub y: FieldRegister<E::BaseField>,
+    pub z: FieldRegister<E::BaseField>,
+    pub t: FieldRegister<E::BaseField>,
+}
+
+impl<E: EllipticCurve> ExtendedPointRegister<E> {
+    pub fn new(
+        x: FieldRegister<E::BaseField>,
+        y: FieldRegister<E::BaseField>,
+        z: FieldRegister<E::BaseField>,
+        t: FieldRegister<E::BaseField>,
+    ) -> Self {
+        Self { x, y, z, t }
+    }
+}
+
 impl<E: EllipticCurve> Add<&AffinePoint<E>> for &AffinePoint<E> {
     type Output = AffinePoint<E>;
 
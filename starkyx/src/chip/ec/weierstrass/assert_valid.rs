@@ -0,0 +1,107 @@
+use num::Zero;
+
+use super::{SWCurve, WeierstrassParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::ec::point::AffinePointRegister;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::AirParameters;
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Asserts that the point `p` lies on the short Weierstrass curve y^2 = x^3 + ax + b.
+    pub fn sw_assert_valid<E: WeierstrassParameters>(&mut self, p: &AffinePointRegister<SWCurve<E>>)
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let b = self.fp_constant(&E::b_int());
+
+        let y_squared = self.fp_mul(&p.y, &p.y);
+        let x_squared = self.fp_mul(&p.x, &p.x);
+        let x_cubed = self.fp_mul(&x_squared, &p.x);
+        let mut rhs = self.fp_add(&x_cubed, &b);
+        // The linear term vanishes on curves such as bn254 and secp256k1.
+        if !E::a_int().is_zero() {
+            let a_x = self.fp_mul_const(&p.x, E::A);
+            rhs = self.fp_add(&rhs, &a_x);
+        }
+
+        self.assert_equal(&y_squared, &rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::BigUint;
+    use rand::thread_rng;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
+    use crate::chip::ec::point::AffinePoint;
+    use crate::chip::ec::weierstrass::secp256k1::{
+        Secp256k1, Secp256k1BaseField, Secp256k1Parameters,
+    };
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct Secp256k1AssertValidTest;
+
+    impl AirParameters for Secp256k1AssertValidTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 400;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 612;
+        type Instruction = FpInstruction<Secp256k1BaseField>;
+    }
+
+    fn test_secp256k1_assert_valid_point(point: AffinePoint<Secp256k1>) {
+        type L = Secp256k1AssertValidTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+        builder.sw_assert_valid::<Secp256k1Parameters>(&p);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+
+        (0..num_rows).into_par_iter().for_each(|i| {
+            writer.write_ec_point(&p, &point, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        });
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+
+    #[test]
+    fn test_secp256k1_assert_valid() {
+        let mut rng = thread_rng();
+        let k = rng.gen_biguint(256);
+        let point = Secp256k1::generator().sw_scalar_mul(&k);
+        test_secp256k1_assert_valid_point(point);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_secp256k1_assert_not_valid() {
+        let base = Secp256k1::generator();
+        let point = AffinePoint::new(base.x, base.y + BigUint::from(1u32));
+        test_secp256k1_assert_valid_point(point);
+    }
+}
@@ -0,0 +1,158 @@
+use num::{BigUint, Num, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::{SWCurve, WeierstrassParameters};
+use crate::chip::ec::EllipticCurveParameters;
+use crate::chip::field::extension::fp2::Fp2;
+use crate::chip::field::extension::TowerFieldParameters;
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// BLS12-381 G1 curve parameter
+pub struct Bls12381Parameters;
+
+pub type Bls12381 = SWCurve<Bls12381Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// BLS12-381 base field parameter
+pub struct Bls12381BaseField;
+
+impl FieldParameters for Bls12381BaseField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 24;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Base field modulus:
+    //  4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559787
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        43691, 65535, 65535, 47614, 65535, 45395, 65534, 7851, 63012, 63152, 53920, 26416, 4799,
+        62341, 19332, 25719, 44247, 17227, 42934, 19227, 59034, 14719, 4586, 6657, 0, 0, 0, 0, 0,
+        0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 22;
+
+    fn modulus() -> BigUint {
+        BigUint::from_str_radix(
+            "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+            16,
+        )
+        .unwrap()
+    }
+}
+
+impl TowerFieldParameters for Bls12381BaseField {
+    fn fp2_non_residue() -> BigUint {
+        Self::modulus() - 1u32
+    }
+
+    fn fp6_non_residue() -> Fp2<Self> {
+        Fp2::new(BigUint::from(1u32), BigUint::from(1u32))
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// BLS12-381 scalar field parameter
+pub struct Bls12381ScalarField;
+
+impl FieldParameters for Bls12381ScalarField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 16;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Scalar field modulus:
+    //  52435875175126190479447740508185965837690552500527637822603658699938581184513
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        1, 0, 65535, 65535, 23550, 65534, 41986, 21437, 55301, 2465, 55304, 13113, 32072, 10653,
+        42835, 29677, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 21;
+}
+
+impl EllipticCurveParameters for Bls12381Parameters {
+    type BaseField = Bls12381BaseField;
+}
+
+impl WeierstrassParameters for Bls12381Parameters {
+    const A: [u16; MAX_NB_LIMBS] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+
+    const B: [u16; MAX_NB_LIMBS] = [
+        4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str_radix(
+            "3685416753713387016781088315183077757961620795782546409894578378688607592378376318836054947676345821548104185464507",
+            10,
+        )
+        .unwrap();
+        let y = BigUint::from_str_radix(
+            "1339506544944476473020471379941921221584933875938349620426543736416511423956333506472724655353366534992391756441569",
+            10,
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> BigUint {
+        Bls12381ScalarField::modulus()
+    }
+
+    fn a_int() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn b_int() -> BigUint {
+        BigUint::from(4u32)
+    }
+
+    /// The group order is a 255-bit prime, so scalars fit in 256 bits rather than the 384 bits
+    /// of the base field limbs.
+    fn nb_scalar_bits() -> usize {
+        256
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::One;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::ec::EllipticCurve;
+
+    #[test]
+    fn test_bls12_381_generator() {
+        let base = Bls12381::generator();
+        let p = Bls12381BaseField::modulus();
+        let rhs = (&base.x * &base.x * &base.x + Bls12381::b_int()) % &p;
+        assert_eq!((&base.y * &base.y) % &p, rhs);
+
+        // The limbs agree with the modulus.
+        let mut modulus = BigUint::zero();
+        for (i, limb) in Bls12381BaseField::MODULUS.iter().enumerate() {
+            modulus += BigUint::from(*limb) << (Bls12381BaseField::NB_BITS_PER_LIMB * i);
+        }
+        assert_eq!(modulus, p);
+
+        // (r - 1) * G = -G and (r + k) * G = k * G.
+        let r = Bls12381Parameters::prime_group_order();
+        assert!(r < BigUint::one() << Bls12381::nb_scalar_bits());
+        let minus_base = base.sw_scalar_mul(&(&r - 1u32));
+        assert_eq!(minus_base, Bls12381::ec_neg(&base));
+
+        let mut rng = thread_rng();
+        let k = rng.gen_biguint(128);
+        assert_eq!(base.sw_scalar_mul(&(&r + &k)), base.sw_scalar_mul(&k));
+    }
+}
@@ -2,8 +2,14 @@ use num::{BigUint, Num, Zero};
 use serde::{Deserialize, Serialize};
 
 use super::{SWCurve, WeierstrassParameters};
+use crate::chip::builder::AirBuilder;
 use crate::chip::ec::EllipticCurveParameters;
+use crate::chip::field::extension::fp2::{Fp2, Fp2Register};
+use crate::chip::field::extension::TowerFieldParameters;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::inverse::FpInverseInstruction;
 use crate::chip::field::parameters::FieldParameters;
+use crate::chip::AirParameters;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 /// Bn254 curve parameter
@@ -32,6 +38,16 @@ impl FieldParameters for Bn254BaseField {
     const WITNESS_OFFSET: usize = 1usize << 20;
 }
 
+impl TowerFieldParameters for Bn254BaseField {
+    fn fp2_non_residue() -> BigUint {
+        Self::modulus() - 1u32
+    }
+
+    fn fp6_non_residue() -> Fp2<Self> {
+        Fp2::new(BigUint::from(9u32), BigUint::from(1u32))
+    }
+}
+
 impl EllipticCurveParameters for Bn254Parameters {
     type BaseField = Bn254BaseField;
 }
@@ -68,3 +84,319 @@ impl WeierstrassParameters for Bn254Parameters {
         BigUint::from(3u32)
     }
 }
+
+/// A point on the sextic twist `E'(Fp2): y^2 = x^3 + 3 / (9 + u)` of bn254, whose subgroup of
+/// prime order is G2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bn254G2AffinePoint {
+    pub x: Fp2<Bn254BaseField>,
+    pub y: Fp2<Bn254BaseField>,
+}
+
+impl Bn254G2AffinePoint {
+    pub fn new(x: Fp2<Bn254BaseField>, y: Fp2<Bn254BaseField>) -> Self {
+        Self { x, y }
+    }
+
+    /// The coefficient `b' = 3 / (9 + u)` of the twist.
+    pub fn twist_b() -> Fp2<Bn254BaseField> {
+        let three = Fp2::new(BigUint::from(3u32), BigUint::zero());
+        &three * &Bn254BaseField::fp6_non_residue().inverse()
+    }
+
+    pub fn generator() -> Self {
+        let coordinate = |c0: &str, c1: &str| {
+            Fp2::new(
+                BigUint::from_str_radix(c0, 10).unwrap(),
+                BigUint::from_str_radix(c1, 10).unwrap(),
+            )
+        };
+        let x = coordinate(
+            "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+            "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+        );
+        let y = coordinate(
+            "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+            "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+        );
+        Self::new(x, y)
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        let x_cubed = &(&self.x * &self.x) * &self.x;
+        &self.y * &self.y == &x_cubed + &Self::twist_b()
+    }
+
+    /// Adds two points with different x-coordinates.
+    pub fn add(&self, other: &Self) -> Self {
+        let slope = &(&other.y - &self.y) * &(&other.x - &self.x).inverse();
+        self.add_with_slope(other, &slope)
+    }
+
+    /// Doubles a point whose y-coordinate is nonzero.
+    pub fn double(&self) -> Self {
+        let three = Fp2::new(BigUint::from(3u32), BigUint::zero());
+        let x_squared = &self.x * &self.x;
+        let slope = &(&three * &x_squared) * &(&self.y + &self.y).inverse();
+        self.add_with_slope(self, &slope)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self::new(self.x.clone(), -&self.y)
+    }
+
+    /// Computes `scalar * self` with the double-and-add algorithm, for a nonzero `scalar` less
+    /// than the order of `self`.
+    pub fn scalar_mul(&self, scalar: &BigUint) -> Self {
+        assert!(!scalar.is_zero(), "The scalar must be nonzero");
+        let mut result = self.clone();
+        for i in (0..scalar.bits() - 1).rev() {
+            result = result.double();
+            if scalar.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+
+    fn add_with_slope(&self, other: &Self, slope: &Fp2<Bn254BaseField>) -> Self {
+        let x = &(&(slope * slope) - &self.x) - &other.x;
+        let y = &(slope * &(&self.x - &x)) - &self.y;
+        Self::new(x, y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Bn254G2AffinePointRegister {
+    pub x: Fp2Register<Bn254BaseField>,
+    pub y: Fp2Register<Bn254BaseField>,
+}
+
+impl Bn254G2AffinePointRegister {
+    pub fn new(x: Fp2Register<Bn254BaseField>, y: Fp2Register<Bn254BaseField>) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    pub fn alloc_bn254_g2_point(&mut self) -> Bn254G2AffinePointRegister {
+        let x = self.alloc_extension();
+        let y = self.alloc_extension();
+        Bn254G2AffinePointRegister::new(x, y)
+    }
+
+    pub fn alloc_public_bn254_g2_point(&mut self) -> Bn254G2AffinePointRegister {
+        let x = self.alloc_public_extension();
+        let y = self.alloc_public_extension();
+        Bn254G2AffinePointRegister::new(x, y)
+    }
+
+    /// Asserts that the point `p` lies on the twist `y^2 = x^3 + 3 / (9 + u)`.
+    ///
+    /// This does not check that `p` lies in the subgroup G2.
+    pub fn bn254_g2_assert_valid(&mut self, p: &Bn254G2AffinePointRegister)
+    where
+        L::Instruction: FromFieldInstruction<Bn254BaseField>,
+    {
+        let b =
+            self.extension_constant::<Fp2Register<Bn254BaseField>>(&Bn254G2AffinePoint::twist_b());
+
+        let y_squared = self.fp2_mul(&p.y, &p.y);
+        let x_squared = self.fp2_mul(&p.x, &p.x);
+        let x_cubed = self.fp2_mul(&x_squared, &p.x);
+        let rhs = self.fp2_add(&x_cubed, &b);
+
+        self.assert_extension_equal(&y_squared, &rhs);
+    }
+
+    /// Adds two points `p` and `q` of the twist with different x-coordinates.
+    ///
+    /// The constraints are not satisfiable if `p` and `q` have the same x-coordinate.
+    pub fn bn254_g2_add(
+        &mut self,
+        p: &Bn254G2AffinePointRegister,
+        q: &Bn254G2AffinePointRegister,
+    ) -> Bn254G2AffinePointRegister
+    where
+        L::Instruction:
+            FromFieldInstruction<Bn254BaseField> + From<FpInverseInstruction<Bn254BaseField>>,
+    {
+        let y_difference = self.fp2_sub(&q.y, &p.y);
+        let x_difference = self.fp2_sub(&q.x, &p.x);
+        let x_difference_inv = self.fp2_inverse(&x_difference);
+        let slope = self.fp2_mul(&y_difference, &x_difference_inv);
+        self.bn254_g2_add_with_slope(p, q, &slope)
+    }
+
+    /// Doubles a point `p` of the twist.
+    ///
+    /// The constraints are not satisfiable if the y-coordinate of `p` is zero.
+    pub fn bn254_g2_double(&mut self, p: &Bn254G2AffinePointRegister) -> Bn254G2AffinePointRegister
+    where
+        L::Instruction:
+            FromFieldInstruction<Bn254BaseField> + From<FpInverseInstruction<Bn254BaseField>>,
+    {
+        let three = self.fp_constant::<Bn254BaseField>(&BigUint::from(3u32));
+        let x_squared = self.fp2_mul(&p.x, &p.x);
+        let three_x_squared = self.fp2_mul_by_fp(&x_squared, &three);
+        let two_y = self.fp2_add(&p.y, &p.y);
+        let two_y_inv = self.fp2_inverse(&two_y);
+        let slope = self.fp2_mul(&three_x_squared, &two_y_inv);
+        self.bn254_g2_add_with_slope(p, p, &slope)
+    }
+
+    pub fn bn254_g2_neg(&mut self, p: &Bn254G2AffinePointRegister) -> Bn254G2AffinePointRegister
+    where
+        L::Instruction: FromFieldInstruction<Bn254BaseField>,
+    {
+        let y = self.fp2_neg(&p.y);
+        Bn254G2AffinePointRegister::new(p.x, y)
+    }
+
+    /// Given two points `p` and `q` and the slope of the line through them, computes the third
+    /// point of intersection reflected over the x-axis.
+    fn bn254_g2_add_with_slope(
+        &mut self,
+        p: &Bn254G2AffinePointRegister,
+        q: &Bn254G2AffinePointRegister,
+        slope: &Fp2Register<Bn254BaseField>,
+    ) -> Bn254G2AffinePointRegister
+    where
+        L::Instruction: FromFieldInstruction<Bn254BaseField>,
+    {
+        let slope_squared = self.fp2_mul(slope, slope);
+        let mut x = self.fp2_sub(&slope_squared, &p.x);
+        x = self.fp2_sub(&x, &q.x);
+
+        let mut y = self.fp2_sub(&p.x, &x);
+        y = self.fp2_mul(slope, &y);
+        y = self.fp2_sub(&y, &p.y);
+
+        Bn254G2AffinePointRegister::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::field::extension::ExtensionFieldWriter;
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct Bn254G2Test;
+
+    impl AirParameters for Bn254G2Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 0;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 9;
+
+        type Instruction = FpInstruction<Bn254BaseField>;
+    }
+
+    fn test_bn254_g2_assert_valid_point(point: Bn254G2AffinePoint) {
+        type L = Bn254G2Test;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_public_bn254_g2_point();
+        builder.bn254_g2_assert_valid(&p);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        writer.write_extension(&p.x, &point.x, 0);
+        writer.write_extension(&p.y, &point.y, 0);
+        writer.write_global_instructions(&generator.air_data);
+
+        for i in 0..num_rows {
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+
+    #[test]
+    fn test_bn254_g2_assert_valid() {
+        let point = Bn254G2AffinePoint::generator();
+        assert!(point.is_on_curve());
+        test_bn254_g2_assert_valid_point(point);
+    }
+
+    #[test]
+    fn test_bn254_g2_group_operations() {
+        type L = Bn254G2Test;
+        type SC = PoseidonGoldilocksStarkConfig;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_public_bn254_g2_point();
+        let q = builder.alloc_public_bn254_g2_point();
+        let sum = builder.bn254_g2_add(&p, &q);
+        let double = builder.bn254_g2_double(&p);
+        let neg = builder.bn254_g2_neg(&q);
+        builder.bn254_g2_assert_valid(&sum);
+        builder.bn254_g2_assert_valid(&double);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = Bn254G2AffinePoint::generator();
+        let p_value = base.scalar_mul(&BigUint::from(5u32));
+        let q_value = base.scalar_mul(&BigUint::from(7u32));
+        let writer = generator.new_writer();
+        writer.write_extension(&p.x, &p_value.x, 0);
+        writer.write_extension(&p.y, &p_value.y, 0);
+        writer.write_extension(&q.x, &q_value.x, 0);
+        writer.write_extension(&q.y, &q_value.y, 0);
+        writer.write_global_instructions(&generator.air_data);
+
+        for i in 0..num_rows {
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let sum_value = p_value.add(&q_value);
+        assert_eq!(sum_value, base.scalar_mul(&BigUint::from(12u32)));
+        assert_eq!(p_value.double(), base.scalar_mul(&BigUint::from(10u32)));
+        assert_eq!(writer.read_extension(&sum.x, 0), sum_value.x);
+        assert_eq!(writer.read_extension(&sum.y, 0), sum_value.y);
+        assert_eq!(writer.read_extension(&double.x, 0), p_value.double().x);
+        assert_eq!(writer.read_extension(&double.y, 0), p_value.double().y);
+        assert_eq!(writer.read_extension(&neg.y, 0), q_value.neg().y);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bn254_g2_assert_not_valid() {
+        let base = Bn254G2AffinePoint::generator();
+        let y = &base.y + &Fp2::one();
+        let point = Bn254G2AffinePoint::new(base.x, y);
+        assert!(!point.is_on_curve());
+        test_bn254_g2_assert_valid_point(point);
+    }
+}
@@ -69,6 +69,7 @@ mod tests {
     use crate::chip::builder::tests::*;
     use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
     use crate::chip::ec::weierstrass::bn254::{Bn254, Bn254BaseField};
+    use crate::chip::ec::weierstrass::p256::{P256BaseField, P256};
     use crate::chip::field::instruction::FpInstruction;
 
     #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
@@ -161,4 +162,99 @@ mod tests {
         // Test the recursive proof.
         test_recursive_starky(stark, config, generator, &public_inputs);
     }
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct P256GroupTest;
+
+    impl AirParameters for P256GroupTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 1152;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 1737;
+        type Instruction = FpInstruction<P256BaseField>;
+    }
+
+    #[test]
+    fn test_p256_add() {
+        type L = P256GroupTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = P256;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+        let q = builder.alloc_ec_point();
+
+        let res = builder.ec_add(&p, &q);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::generator();
+        let mut rng = thread_rng();
+        let p_int = base.sw_scalar_mul(&rng.gen_biguint(256));
+        let q_int = base.sw_scalar_mul(&rng.gen_biguint(256));
+        let writer = generator.new_writer();
+        (0..num_rows).for_each(|i| {
+            writer.write_ec_point(&p, &p_int, i);
+            writer.write_ec_point(&q, &q_int, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        });
+
+        assert_eq!(writer.read_ec_point(&res, 0), p_int.sw_add(&q_int));
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        let public_inputs = writer.0.public.read().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public_inputs);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public_inputs);
+    }
+
+    #[test]
+    fn test_p256_double() {
+        type L = P256GroupTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = P256;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+
+        let res = builder.ec_double(&p);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::generator();
+        let mut rng = thread_rng();
+        let p_int = base.sw_scalar_mul(&rng.gen_biguint(256));
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+        (0..num_rows).for_each(|i| {
+            writer.write_ec_point(&p, &p_int, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        });
+
+        // The tangent slope depends on the nonzero `a` coefficient of P-256.
+        assert_eq!(writer.read_ec_point(&res, 0), p_int.sw_double());
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        let public_inputs = writer.0.public.read().unwrap().clone();
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public_inputs);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public_inputs);
+    }
 }
@@ -0,0 +1,178 @@
+/// Defines the instruction enum of a Weierstrass curve for signature verification.
+///
+/// The enum has an `EC` variant for the curve operations over the base field, a `Scalar` variant
+/// for the arithmetic in the scalar field and, optionally, a `Uint` variant for the byte
+/// operations of a hash. The macro implements `AirConstraint` and `Instruction` by dispatching to
+/// the variants, together with the `From` conversions of the field, limb bit and byte
+/// instructions.
+macro_rules! weierstrass_instruction {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident {
+            $($uint:ident(UintInstruction),)?
+            EC(ECInstruction<$curve:ty>),
+            Scalar(FpInstruction<$scalar:ty>),
+        }
+        base_field = $base:ty;
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        #[serde(bound = "")]
+        pub enum $name {
+            $($uint($crate::chip::uint::operations::instruction::UintInstruction),)?
+            EC($crate::chip::ec::ECInstruction<$curve>),
+            Scalar($crate::chip::field::instruction::FpInstruction<$scalar>),
+        }
+
+        $(
+            impl $crate::chip::uint::bytes::lookup_table::ByteInstructions for $name {}
+
+            impl $crate::chip::uint::operations::instruction::UintInstructions for $name {}
+
+            impl From<$crate::chip::uint::operations::instruction::UintInstruction> for $name {
+                fn from(i: $crate::chip::uint::operations::instruction::UintInstruction) -> Self {
+                    Self::$uint(i)
+                }
+            }
+
+            $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+                @from $name, $uint, $crate::chip::uint::bytes::lookup_table::ByteInstructionSet
+            );
+            $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+                @from $name, $uint, $crate::chip::uint::operations::add::ByteArrayAdd<4>
+            );
+            $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+                @from $name, $uint,
+                $crate::chip::uint::bytes::operations::instruction::ByteOperationInstruction
+            );
+            $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+                @from $name, $uint, $crate::chip::uint::bytes::decode::ByteDecodeInstruction
+            );
+            $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+                @from $name, $uint,
+                $crate::chip::uint::bytes::operations::value::ByteOperationDigestConstraint
+            );
+        )?
+
+        impl $crate::chip::field::instruction::FromFieldInstruction<$base> for $name {}
+
+        impl $crate::chip::field::instruction::FromFieldInstruction<$scalar> for $name {}
+
+        impl<AP: $crate::polynomial::parser::PolynomialParser> $crate::air::AirConstraint<AP>
+            for $name
+        {
+            fn eval(&self, parser: &mut AP) {
+                match self {
+                    $($name::$uint(instruction) => {
+                        $crate::air::AirConstraint::<AP>::eval(instruction, parser)
+                    })?
+                    $name::EC(instruction) => {
+                        $crate::air::AirConstraint::<AP>::eval(instruction, parser)
+                    }
+                    $name::Scalar(instruction) => {
+                        $crate::air::AirConstraint::<AP>::eval(instruction, parser)
+                    }
+                }
+            }
+        }
+
+        impl<F: $crate::math::field::PrimeField64> $crate::chip::instruction::Instruction<F>
+            for $name
+        {
+            fn write(&self, writer: &$crate::chip::trace::writer::TraceWriter<F>, row_index: usize) {
+                match self {
+                    $($name::$uint(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write(
+                            instruction,
+                            writer,
+                            row_index,
+                        )
+                    })?
+                    $name::EC(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write(
+                            instruction,
+                            writer,
+                            row_index,
+                        )
+                    }
+                    $name::Scalar(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write(
+                            instruction,
+                            writer,
+                            row_index,
+                        )
+                    }
+                }
+            }
+
+            fn write_to_air(
+                &self,
+                writer: &mut impl $crate::chip::trace::writer::AirWriter<Field = F>,
+            ) {
+                match self {
+                    $($name::$uint(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write_to_air(
+                            instruction,
+                            writer,
+                        )
+                    })?
+                    $name::EC(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write_to_air(
+                            instruction,
+                            writer,
+                        )
+                    }
+                    $name::Scalar(instruction) => {
+                        $crate::chip::instruction::Instruction::<F>::write_to_air(
+                            instruction,
+                            writer,
+                        )
+                    }
+                }
+            }
+        }
+
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, EC, $crate::chip::ec::scalar::LimbBitInstruction
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from_field $name, EC, $base
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from_field $name, Scalar, $scalar
+        );
+    };
+    (@from_field $name:ident, $variant:ident, $field:ty) => {
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::add::FpAddInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::mul::FpMulInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::sub::FpSubInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::div::FpDivInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::den::FpDenInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant,
+            $crate::chip::field::inner_product::FpInnerProductInstruction<$field>
+        );
+        $crate::chip::ec::weierstrass::instruction::weierstrass_instruction!(
+            @from $name, $variant, $crate::chip::field::mul_const::FpMulConstInstruction<$field>
+        );
+    };
+    (@from $name:ident, $variant:ident, $instruction:ty) => {
+        impl From<$instruction> for $name {
+            fn from(i: $instruction) -> Self {
+                Self::$variant(i.into())
+            }
+        }
+    };
+}
+
+pub(crate) use weierstrass_instruction;
@@ -0,0 +1,234 @@
+use super::{SWCurve, WeierstrassParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::ec::point::{AffinePointRegister, ProjectivePointRegister};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::MAX_NB_LIMBS;
+use crate::chip::AirParameters;
+
+/// Returns the limbs of a small constant, to be used with `fp_mul_const`.
+fn small_constant(value: u16) -> [u16; MAX_NB_LIMBS] {
+    let mut limbs = [0; MAX_NB_LIMBS];
+    limbs[0] = value;
+    limbs
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Converts an affine point `(x, y)` to the Jacobian coordinates `(x : y : 1)`.
+    pub fn sw_to_jacobian<E: WeierstrassParameters>(
+        &mut self,
+        p: &AffinePointRegister<SWCurve<E>>,
+    ) -> ProjectivePointRegister<SWCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let z = self.fp_one();
+        ProjectivePointRegister::new(p.x, p.y, z)
+    }
+
+    /// Adds two different points `p` and `q` in Jacobian coordinates, without any field division.
+    pub fn sw_jacobian_add<E: WeierstrassParameters>(
+        &mut self,
+        p: &ProjectivePointRegister<SWCurve<E>>,
+        q: &ProjectivePointRegister<SWCurve<E>>,
+    ) -> ProjectivePointRegister<SWCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        // Addition formula in Jacobian coordinates
+        //
+        // Given two points (X1 : Y1 : Z1) and (X2 : Y2 : Z2), compute the sum (X3 : Y3 : Z3) with
+        //
+        // U1 = X1 * Z2^2, U2 = X2 * Z1^2, S1 = Y1 * Z2^3, S2 = Y2 * Z1^3,
+        // H = U2 - U1, r = S2 - S1, V = U1 * H^2,
+        //
+        // X3 = r^2 - H^3 - 2 * V
+        // Y3 = r * (V - X3) - S1 * H^3
+        // Z3 = Z1 * Z2 * H.
+        //
+        // Reference: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#addition-add-1998-cmo-2
+
+        let z1_sq = self.fp_mul(&p.z, &p.z);
+        let z2_sq = self.fp_mul(&q.z, &q.z);
+
+        let u1 = self.fp_mul(&p.x, &z2_sq);
+        let u2 = self.fp_mul(&q.x, &z1_sq);
+
+        let y1_mul_z2 = self.fp_mul(&p.y, &q.z);
+        let s1 = self.fp_mul(&y1_mul_z2, &z2_sq);
+        let y2_mul_z1 = self.fp_mul(&q.y, &p.z);
+        let s2 = self.fp_mul(&y2_mul_z1, &z1_sq);
+
+        let h = self.fp_sub(&u2, &u1);
+        let r = self.fp_sub(&s2, &s1);
+
+        let h_sq = self.fp_mul(&h, &h);
+        let h_cube = self.fp_mul(&h, &h_sq);
+        let v = self.fp_mul(&u1, &h_sq);
+
+        // x3 = r^2 - h^3 - 2 * v.
+        let r_sq = self.fp_mul(&r, &r);
+        let mut x3 = self.fp_sub(&r_sq, &h_cube);
+        x3 = self.fp_sub(&x3, &v);
+        x3 = self.fp_sub(&x3, &v);
+
+        // y3 = r * (v - x3) - s1 * h^3.
+        let v_minus_x3 = self.fp_sub(&v, &x3);
+        let r_mul_v_minus_x3 = self.fp_mul(&r, &v_minus_x3);
+        let s1_mul_h_cube = self.fp_mul(&s1, &h_cube);
+        let y3 = self.fp_sub(&r_mul_v_minus_x3, &s1_mul_h_cube);
+
+        // z3 = z1 * z2 * h.
+        let z1_mul_z2 = self.fp_mul(&p.z, &q.z);
+        let z3 = self.fp_mul(&z1_mul_z2, &h);
+
+        ProjectivePointRegister::new(x3, y3, z3)
+    }
+
+    /// Doubles a point `p` in Jacobian coordinates, without any field division.
+    pub fn sw_jacobian_double<E: WeierstrassParameters>(
+        &mut self,
+        p: &ProjectivePointRegister<SWCurve<E>>,
+    ) -> ProjectivePointRegister<SWCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        // Doubling formula in Jacobian coordinates
+        //
+        // Given a point (X1 : Y1 : Z1), compute the double (X3 : Y3 : Z3) with
+        //
+        // S = 4 * X1 * Y1^2, M = 3 * X1^2 + a * Z1^4,
+        //
+        // X3 = M^2 - 2 * S
+        // Y3 = M * (S - X3) - 8 * Y1^4
+        // Z3 = 2 * Y1 * Z1.
+        //
+        // Reference: https://hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html#doubling-dbl-1998-cmo-2
+
+        let x1_sq = self.fp_mul(&p.x, &p.x);
+        let y1_sq = self.fp_mul(&p.y, &p.y);
+        let z1_sq = self.fp_mul(&p.z, &p.z);
+
+        // s = 4 * x1 * y1^2.
+        let x1_mul_y1_sq = self.fp_mul(&p.x, &y1_sq);
+        let s = self.fp_mul_const(&x1_mul_y1_sq, small_constant(4));
+
+        // m = 3 * x1^2 + a * z1^4.
+        let three_x1_sq = self.fp_mul_const(&x1_sq, small_constant(3));
+        let z1_pow_4 = self.fp_mul(&z1_sq, &z1_sq);
+        let a_z1_pow_4 = self.fp_mul_const(&z1_pow_4, E::A);
+        let m = self.fp_add(&three_x1_sq, &a_z1_pow_4);
+
+        // x3 = m^2 - 2 * s.
+        let m_sq = self.fp_mul(&m, &m);
+        let mut x3 = self.fp_sub(&m_sq, &s);
+        x3 = self.fp_sub(&x3, &s);
+
+        // y3 = m * (s - x3) - 8 * y1^4.
+        let s_minus_x3 = self.fp_sub(&s, &x3);
+        let m_mul_s_minus_x3 = self.fp_mul(&m, &s_minus_x3);
+        let y1_pow_4 = self.fp_mul(&y1_sq, &y1_sq);
+        let eight_y1_pow_4 = self.fp_mul_const(&y1_pow_4, small_constant(8));
+        let y3 = self.fp_sub(&m_mul_s_minus_x3, &eight_y1_pow_4);
+
+        // z3 = 2 * y1 * z1.
+        let y1_mul_z1 = self.fp_mul(&p.y, &p.z);
+        let z3 = self.fp_add(&y1_mul_z1, &y1_mul_z1);
+
+        ProjectivePointRegister::new(x3, y3, z3)
+    }
+
+    /// Converts a point in Jacobian coordinates back to the affine point `(X / Z^2, Y / Z^3)`.
+    pub fn sw_jacobian_to_affine<E: WeierstrassParameters>(
+        &mut self,
+        p: &ProjectivePointRegister<SWCurve<E>>,
+    ) -> AffinePointRegister<SWCurve<E>>
+    where
+        L::Instruction: FromFieldInstruction<E::BaseField>,
+    {
+        let z_sq = self.fp_mul(&p.z, &p.z);
+        let z_cube = self.fp_mul(&z_sq, &p.z);
+
+        let x = self.fp_div(&p.x, &z_sq);
+        let y = self.fp_div(&p.y, &z_cube);
+
+        AffinePointRegister::new(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::gadget::{EllipticCurveGadget, EllipticCurveWriter};
+    use crate::chip::ec::weierstrass::p256::{P256BaseField, P256};
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    pub struct P256JacobianTest;
+
+    impl AirParameters for P256JacobianTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 4756;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 7143;
+        type Instruction = FpInstruction<P256BaseField>;
+    }
+
+    #[test]
+    fn test_p256_jacobian_add_double() {
+        type L = P256JacobianTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type E = P256;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let p = builder.alloc_ec_point();
+        let q = builder.alloc_ec_point();
+
+        let p_jac = builder.sw_to_jacobian(&p);
+        let q_jac = builder.sw_to_jacobian(&q);
+        let sum_jac = builder.sw_jacobian_add(&p_jac, &q_jac);
+        let double_jac = builder.sw_jacobian_double(&sum_jac);
+
+        let sum = builder.sw_jacobian_to_affine(&sum_jac);
+        let double = builder.sw_jacobian_to_affine(&double_jac);
+
+        let num_rows = 1 << 16;
+        let (air, trace_data) = builder.build();
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let base = E::generator();
+        let mut rng = thread_rng();
+        let p_int = base.sw_scalar_mul(&rng.gen_biguint(256));
+        let q_int = base.sw_scalar_mul(&rng.gen_biguint(256));
+        let sum_int = p_int.sw_add(&q_int);
+        let double_int = sum_int.sw_double();
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+        (0..num_rows).for_each(|i| {
+            writer.write_ec_point(&p, &p_int, i);
+            writer.write_ec_point(&q, &q_int, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        });
+
+        // The doubling depends on the nonzero `a` coefficient of P-256.
+        assert_eq!(writer.read_ec_point(&sum, 0), sum_int);
+        assert_eq!(writer.read_ec_point(&double, 0), double_int);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+
+        let public_inputs = writer.0.public.read().unwrap().clone();
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public_inputs);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public_inputs);
+    }
+}
@@ -1,16 +1,23 @@
 use num::{BigUint, Zero};
 use serde::{Deserialize, Serialize};
 
-use super::point::{AffinePoint, AffinePointRegister};
-use super::{EllipticCurve, EllipticCurveAir, EllipticCurveParameters};
+use super::point::{AffinePoint, AffinePointRegister, ProjectivePointRegister};
+use super::{EllipticCurve, EllipticCurveAir, EllipticCurveParameters, ProjectiveEllipticCurveAir};
 use crate::chip::builder::AirBuilder;
 use crate::chip::field::instruction::FromFieldInstruction;
 use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+use crate::chip::field::register::FieldRegister;
 use crate::chip::AirParameters;
 
+pub mod assert_valid;
 pub mod biguint_operations;
+pub mod bls12_381;
 pub mod bn254;
 pub mod group;
+pub mod instruction;
+pub mod jacobian;
+pub mod p256;
+pub mod secp256k1;
 pub mod slope;
 
 /// Parameters that specify a short Weierstrass curve : y^2 = x^3 + ax + b.
@@ -25,7 +32,7 @@ pub trait WeierstrassParameters: EllipticCurveParameters {
     fn a_int() -> BigUint {
         let mut modulus = BigUint::zero();
         for (i, limb) in Self::A.iter().enumerate() {
-            modulus += BigUint::from(*limb) << (16 * i);
+            modulus += BigUint::from(*limb) << (Self::BaseField::NB_BITS_PER_LIMB * i);
         }
         modulus
     }
@@ -33,13 +40,13 @@ pub trait WeierstrassParameters: EllipticCurveParameters {
     fn b_int() -> BigUint {
         let mut modulus = BigUint::zero();
         for (i, limb) in Self::B.iter().enumerate() {
-            modulus += BigUint::from(*limb) << (16 * i);
+            modulus += BigUint::from(*limb) << (Self::BaseField::NB_BITS_PER_LIMB * i);
         }
         modulus
     }
 
     fn nb_scalar_bits() -> usize {
-        Self::BaseField::NB_LIMBS * 16
+        Self::BaseField::NB_LIMBS * Self::BaseField::NB_BITS_PER_LIMB
     }
 }
 
@@ -52,7 +59,13 @@ impl<E: WeierstrassParameters> EllipticCurveParameters for SWCurve<E> {
 
 impl<E: WeierstrassParameters> EllipticCurve for SWCurve<E> {
     fn ec_add(p: &AffinePoint<Self>, q: &AffinePoint<Self>) -> AffinePoint<Self> {
-        p.sw_add(q)
+        // The chord formula is undefined for equal points, which the generic scalar
+        // multiplication adds when doubling.
+        if p == q {
+            p.sw_double()
+        } else {
+            p.sw_add(q)
+        }
     }
 
     fn ec_double(p: &AffinePoint<Self>) -> AffinePoint<Self> {
@@ -72,6 +85,10 @@ impl<E: WeierstrassParameters> EllipticCurve for SWCurve<E> {
         let modulus = E::BaseField::modulus();
         AffinePoint::new(p.x.clone(), modulus - &p.y)
     }
+
+    fn nb_scalar_bits() -> usize {
+        E::nb_scalar_bits()
+    }
 }
 
 impl<E: WeierstrassParameters> SWCurve<E> {
@@ -81,6 +98,10 @@ impl<E: WeierstrassParameters> SWCurve<E> {
         AffinePoint::new(x, y)
     }
 
+    pub fn prime_group_order() -> BigUint {
+        E::prime_group_order()
+    }
+
     pub fn a_int() -> BigUint {
         E::a_int()
     }
@@ -123,3 +144,44 @@ where
         AffinePointRegister::new(x, y)
     }
 }
+
+impl<L: AirParameters, E: WeierstrassParameters> ProjectiveEllipticCurveAir<L> for SWCurve<E>
+where
+    L::Instruction: FromFieldInstruction<E::BaseField>,
+{
+    type ProjectivePointRegister = ProjectivePointRegister<Self>;
+
+    fn projective_coordinates(
+        p: &ProjectivePointRegister<Self>,
+    ) -> Vec<FieldRegister<E::BaseField>> {
+        vec![p.x, p.y, p.z]
+    }
+
+    fn projective_from_coordinates(
+        coordinates: &[FieldRegister<E::BaseField>],
+    ) -> ProjectivePointRegister<Self> {
+        ProjectivePointRegister::new(coordinates[0], coordinates[1], coordinates[2])
+    }
+
+    fn ec_to_projective_air(
+        builder: &mut AirBuilder<L>,
+        p: &AffinePointRegister<Self>,
+    ) -> ProjectivePointRegister<Self> {
+        builder.sw_to_jacobian::<E>(p)
+    }
+
+    fn ec_projective_add_air(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self>,
+        q: &ProjectivePointRegister<Self>,
+    ) -> ProjectivePointRegister<Self> {
+        builder.sw_jacobian_add::<E>(p, q)
+    }
+
+    fn ec_projective_to_affine_air(
+        builder: &mut AirBuilder<L>,
+        p: &ProjectivePointRegister<Self>,
+    ) -> AffinePointRegister<Self> {
+        builder.sw_jacobian_to_affine::<E>(p)
+    }
+}
@@ -0,0 +1,12 @@
+use super::{P256BaseField, P256ScalarField, P256};
+use crate::chip::ec::weierstrass::instruction::weierstrass_instruction;
+
+weierstrass_instruction! {
+    /// Instructions for P-256 curve operations together with arithmetic in its scalar field, as
+    /// needed for signature verification.
+    pub enum P256Instruction {
+        EC(ECInstruction<P256>),
+        Scalar(FpInstruction<P256ScalarField>),
+    }
+    base_field = P256BaseField;
+}
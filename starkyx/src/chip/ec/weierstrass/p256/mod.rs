@@ -0,0 +1,170 @@
+use num::{BigUint, Num};
+use serde::{Deserialize, Serialize};
+
+use super::{SWCurve, WeierstrassParameters};
+use crate::chip::ec::EllipticCurveParameters;
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+
+pub mod instruction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// NIST P-256 (secp256r1) curve parameter
+pub struct P256Parameters;
+
+pub type P256 = SWCurve<P256Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// P-256 base field parameter
+pub struct P256BaseField;
+
+impl FieldParameters for P256BaseField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 16;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Base field modulus: 2^256 - 2^224 + 2^192 + 2^96 - 1
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        65535, 65535, 65535, 65535, 65535, 65535, 0, 0, 0, 0, 0, 0, 1, 0, 65535, 65535, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 21;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// P-256 scalar field parameter
+pub struct P256ScalarField;
+
+impl FieldParameters for P256ScalarField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 16;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Scalar field modulus:
+    //  115792089210356248762697446949407573529996955224135760342422259061068512044369
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        9553, 64611, 51906, 62393, 40580, 42775, 64173, 48358, 65535, 65535, 65535, 65535, 0, 0,
+        65535, 65535, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 21;
+}
+
+impl EllipticCurveParameters for P256Parameters {
+    type BaseField = P256BaseField;
+}
+
+impl WeierstrassParameters for P256Parameters {
+    // a = p - 3
+    const A: [u16; MAX_NB_LIMBS] = [
+        65532, 65535, 65535, 65535, 65535, 65535, 0, 0, 0, 0, 0, 0, 1, 0, 65535, 65535, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const B: [u16; MAX_NB_LIMBS] = [
+        24651, 10194, 15422, 15310, 45302, 52307, 1712, 25885, 34492, 30360, 48469, 46059, 37863,
+        43578, 13784, 23238, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str_radix(
+            "48439561293906451759052585252797914202762949526041747995844080717082404635286",
+            10,
+        )
+        .unwrap();
+        let y = BigUint::from_str_radix(
+            "36134250956749795798585127919587881956611106672985015071877198253568414405109",
+            10,
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> BigUint {
+        P256ScalarField::modulus()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::ec::point::AffinePoint;
+    use crate::chip::ec::EllipticCurve;
+    use crate::machine::ec::ecdsa::ecdsa_verify;
+
+    #[test]
+    fn test_p256_generator() {
+        let base = P256::generator();
+        let p = P256BaseField::modulus();
+        assert_eq!(P256::a_int(), &p - 3u32);
+        let rhs = (&base.x * &base.x * &base.x + P256::a_int() * &base.x + P256::b_int()) % &p;
+        assert_eq!((&base.y * &base.y) % &p, rhs);
+
+        // (n - 1) * G = -G and (n + k) * G = k * G.
+        let n = P256Parameters::prime_group_order();
+        let minus_base = base.sw_scalar_mul(&(&n - 1u32));
+        assert_eq!(minus_base, P256::ec_neg(&base));
+
+        let mut rng = thread_rng();
+        let k = rng.gen_biguint(128);
+        assert_eq!(base.sw_scalar_mul(&(&n + &k)), base.sw_scalar_mul(&k));
+    }
+
+    /// The P-256 with SHA-256 test vectors of RFC 6979, appendix A.2.5.
+    pub(crate) fn rfc6979_vectors() -> (AffinePoint<P256>, Vec<(BigUint, BigUint, BigUint)>) {
+        let hex = |s: &str| BigUint::from_str_radix(s, 16).unwrap();
+
+        let public_key = AffinePoint::new(
+            hex("60FED4BA255A9D31C961EB74C6356D68C049B8923B61FA6CE669622E60F29FB6"),
+            hex("7903FE1008B8BC99A41AE9E95628BC64F2F1B20C2D7E9F5177A3C294D4462299"),
+        );
+        let signatures = vec![
+            // SHA-256("sample")
+            (
+                hex("AF2BDBE1AA9B6EC1E2ADE1D694F41FC71A831D0268E9891562113D8A62ADD1BF"),
+                hex("EFD48B2AACB6A8FD1140DD9CD45E81D69D2C877B56AAF991C34D0EA84EAF3716"),
+                hex("F7CB1C942D657C41D436C7A1B6E29F65F3E900DBB9AFF4064DC4AB2F843ACDA8"),
+            ),
+            // SHA-256("test")
+            (
+                hex("9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08"),
+                hex("F1ABB023518351CD71D881567B1EA663ED3EFCF6C5132B354F28D3B0B7D38367"),
+                hex("019F4113742A2B14BD25926B49C649155F267E60D3814B4C0CC84250E46F0083"),
+            ),
+        ];
+        (public_key, signatures)
+    }
+
+    #[test]
+    fn test_p256_ecdsa_pure() {
+        let (public_key, signatures) = rfc6979_vectors();
+        let secret_key = BigUint::from_str_radix(
+            "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721",
+            16,
+        )
+        .unwrap();
+        assert_eq!(P256::generator().sw_scalar_mul(&secret_key), public_key);
+
+        for (message_hash, r, s) in signatures.iter() {
+            assert!(ecdsa_verify::<P256, P256ScalarField>(
+                message_hash,
+                r,
+                s,
+                &public_key
+            ));
+            assert!(!ecdsa_verify::<P256, P256ScalarField>(
+                &(message_hash + 1u32),
+                r,
+                s,
+                &public_key
+            ));
+        }
+    }
+}
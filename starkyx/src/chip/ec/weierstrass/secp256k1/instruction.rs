@@ -0,0 +1,23 @@
+use super::{Secp256k1, Secp256k1BaseField, Secp256k1ScalarField};
+use crate::chip::ec::weierstrass::instruction::weierstrass_instruction;
+
+weierstrass_instruction! {
+    /// Instructions for secp256k1 curve operations together with arithmetic in its scalar field,
+    /// as needed for signature verification.
+    pub enum Secp256k1Instruction {
+        EC(ECInstruction<Secp256k1>),
+        Scalar(FpInstruction<Secp256k1ScalarField>),
+    }
+    base_field = Secp256k1BaseField;
+}
+
+weierstrass_instruction! {
+    /// Instructions for BIP-340 Schnorr signature verification: the byte operations of SHA-256,
+    /// the curve operations, and arithmetic in the scalar field.
+    pub enum Secp256k1SchnorrInstruction {
+        Uint(UintInstruction),
+        EC(ECInstruction<Secp256k1>),
+        Scalar(FpInstruction<Secp256k1ScalarField>),
+    }
+    base_field = Secp256k1BaseField;
+}
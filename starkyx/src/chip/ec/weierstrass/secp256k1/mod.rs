@@ -0,0 +1,127 @@
+use num::{BigUint, Num, One, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::{SWCurve, WeierstrassParameters};
+use crate::chip::ec::EllipticCurveParameters;
+use crate::chip::field::parameters::{FieldParameters, MAX_NB_LIMBS};
+
+pub mod instruction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Secp256k1 curve parameter
+pub struct Secp256k1Parameters;
+
+pub type Secp256k1 = SWCurve<Secp256k1Parameters>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Secp256k1 base field parameter
+pub struct Secp256k1BaseField;
+
+impl FieldParameters for Secp256k1BaseField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 16;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Base field modulus: 2^256 - 2^32 - 977
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        64559, 65535, 65534, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535, 65535,
+        65535, 65535, 65535, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 21;
+
+    fn modulus() -> BigUint {
+        (BigUint::one() << 256) - (BigUint::one() << 32) - BigUint::from(977u32)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// Secp256k1 scalar field parameter
+pub struct Secp256k1ScalarField;
+
+impl FieldParameters for Secp256k1ScalarField {
+    const NB_BITS_PER_LIMB: usize = 16;
+
+    const NB_LIMBS: usize = 16;
+
+    const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+
+    // Scalar field modulus:
+    //  115792089237316195423570985008687907852837564279074904382605163141518161494337
+    const MODULUS: [u16; MAX_NB_LIMBS] = [
+        16705, 53302, 24204, 49106, 41019, 44872, 56550, 47790, 65534, 65535, 65535, 65535, 65535,
+        65535, 65535, 65535, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+
+    const WITNESS_OFFSET: usize = 1usize << 21;
+}
+
+impl EllipticCurveParameters for Secp256k1Parameters {
+    type BaseField = Secp256k1BaseField;
+}
+
+impl WeierstrassParameters for Secp256k1Parameters {
+    const A: [u16; MAX_NB_LIMBS] = [
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+
+    const B: [u16; MAX_NB_LIMBS] = [
+        7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+
+    fn generator() -> (BigUint, BigUint) {
+        let x = BigUint::from_str_radix(
+            "55066263022277343669578718895168534326250603453777594175500187360389116729240",
+            10,
+        )
+        .unwrap();
+        let y = BigUint::from_str_radix(
+            "32670510020758816978083085130507043184471273380659243275938904335757337482424",
+            10,
+        )
+        .unwrap();
+        (x, y)
+    }
+
+    fn prime_group_order() -> BigUint {
+        Secp256k1ScalarField::modulus()
+    }
+
+    fn a_int() -> BigUint {
+        BigUint::zero()
+    }
+
+    fn b_int() -> BigUint {
+        BigUint::from(7u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::ec::EllipticCurve;
+
+    #[test]
+    fn test_secp256k1_generator() {
+        let base = Secp256k1::generator();
+        let p = Secp256k1BaseField::modulus();
+        let rhs = (&base.x * &base.x * &base.x + Secp256k1::b_int()) % &p;
+        assert_eq!((&base.y * &base.y) % &p, rhs);
+
+        // (n - 1) * G = -G and (n + k) * G = k * G.
+        let n = Secp256k1Parameters::prime_group_order();
+        let minus_base = base.sw_scalar_mul(&(&n - 1u32));
+        assert_eq!(minus_base, Secp256k1::ec_neg(&base));
+
+        let mut rng = thread_rng();
+        let k = rng.gen_biguint(128);
+        assert_eq!(base.sw_scalar_mul(&(&n + &k)), base.sw_scalar_mul(&k));
+    }
+}
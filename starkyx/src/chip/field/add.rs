@@ -10,11 +10,11 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::u16::U16Register;
 use crate::chip::register::{Register, RegisterSerializable};
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
-use crate::chip::utils::{digits_to_biguint, split_u32_limbs_to_u16_limbs};
+use crate::chip::utils::{limbs_to_biguint, split_witness_limbs};
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::{to_u16_le_limbs_polynomial, Polynomial};
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -84,6 +84,7 @@ impl<L: AirParameters> AirBuilder<L> {
             witness_low,
             witness_high,
         };
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr);
         } else {
@@ -134,8 +135,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpAddInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let a = digits_to_biguint(&a_digits);
-        let b = digits_to_biguint(&b_digits);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         // Compute field addition in the integers.
         let modulus = P::modulus();
@@ -146,17 +147,21 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpAddInstruction<P>
         debug_assert_eq!(&carry * &modulus, a + b - &result);
 
         // Make little endian polynomial limbs.
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(&modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(&modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let p_vanishing = &p_a + &p_b - &p_result - &p_carry * &p_modulus;
         debug_assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result, row_index);
         writer.write(&self.carry, &p_carry, row_index);
@@ -179,8 +184,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpAddInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let a = digits_to_biguint(&a_digits);
-        let b = digits_to_biguint(&b_digits);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         // Compute field addition in the integers.
         let modulus = P::modulus();
@@ -191,17 +196,21 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpAddInstruction<P>
         debug_assert_eq!(&carry * &modulus, a + b - &result);
 
         // Make little endian polynomial limbs.
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(&modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(&modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let p_vanishing = &p_a + &p_b - &p_result - &p_carry * &p_modulus;
         debug_assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result);
         writer.write(&self.carry, &p_carry);
@@ -0,0 +1,148 @@
+use super::add::FpAddInstruction;
+use super::parameters::FieldParameters;
+use super::register::FieldRegister;
+use super::sub::FpSubInstruction;
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::builder::AirBuilder;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::utils::bigint_into_limbs;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// Asserts that the field register `value` is canonical, i.e. less than the modulus as an
+    /// integer.
+    ///
+    /// The difference `d = (p - 1) - value` is computed with `fp_sub`, and the limbs of `value`
+    /// and `d` are constrained to add up to `p - 1` with carries, so that `value <= p - 1` as
+    /// integers.
+    pub fn fp_assert_canonical<P: FieldParameters>(&mut self, value: &FieldRegister<P>)
+    where
+        L::Instruction: From<FpSubInstruction<P>>,
+    {
+        let max_value = P::modulus() - 1u32;
+        let max_value_register = self.fp_constant::<P>(&max_value);
+        let difference = self.fp_sub(&max_value_register, value);
+
+        let value_limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+        let difference_limbs =
+            ArrayRegister::<U16Register>::from_register_unsafe(*difference.register());
+        let max_value_limbs = bigint_into_limbs(&max_value, P::NB_BITS_PER_LIMB, P::NB_LIMBS);
+        let limb_inverse = L::Field::from_canonical_u32(1 << P::NB_BITS_PER_LIMB).inverse();
+
+        let mut carry = ArithmeticExpression::zero();
+        for (i, max_value_limb) in max_value_limbs.into_iter().enumerate() {
+            let sum = value_limbs.get(i).expr() + difference_limbs.get(i).expr() + carry
+                - L::Field::from_canonical_u16(max_value_limb);
+            if i == P::NB_LIMBS - 1 {
+                self.assert_expression_zero(sum);
+            } else {
+                let carry_bit = if value.is_trace() {
+                    let carry_bit = self.alloc::<BitRegister>();
+                    self.set_to_expression(&carry_bit, sum * limb_inverse);
+                    carry_bit
+                } else {
+                    let carry_bit = self.alloc_public::<BitRegister>();
+                    self.set_to_expression_public(&carry_bit, sum * limb_inverse);
+                    carry_bit
+                };
+                carry = carry_bit.expr();
+            }
+        }
+    }
+
+    /// Returns the canonical representative of `value` modulo `p`.
+    ///
+    /// The limbs of `value` may encode any integer of `NB_LIMBS` limbs. The result is constrained
+    /// to be congruent to `value` by an addition with zero, and to be canonical by
+    /// `fp_assert_canonical`, which makes it unique.
+    pub fn fp_reduce<P: FieldParameters>(&mut self, value: &FieldRegister<P>) -> FieldRegister<P>
+    where
+        L::Instruction: From<FpAddInstruction<P>> + From<FpSubInstruction<P>>,
+    {
+        let zero = self.fp_zero::<P>();
+        let result = self.fp_add(value, &zero);
+        self.fp_assert_canonical(&result);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use num::BigUint;
+    use rand::thread_rng;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::field::parameters::tests::Fp25519;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpCanonicalTest;
+
+    impl AirParameters for FpCanonicalTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 108;
+        const NUM_FREE_COLUMNS: usize = 17;
+        const EXTENDED_COLUMNS: usize = 171;
+
+        type Instruction = FpSubInstruction<Fp25519>;
+    }
+
+    #[test]
+    fn test_fp_assert_canonical() {
+        type F = GoldilocksField;
+        type L = FpCanonicalTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Fp25519;
+
+        let p = Fp25519::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        builder.fp_assert_canonical(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        builder.fp_assert_canonical(&a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            let a_int = match i % 4 {
+                0 => BigUint::from(0u32),
+                1 => &p - 1u32,
+                _ => rng.gen_biguint_below(&p),
+            };
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&a_pub, &p_a, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
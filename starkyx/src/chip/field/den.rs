@@ -12,12 +12,12 @@ use crate::chip::register::u16::U16Register;
 use crate::chip::register::{Register, RegisterSerializable};
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
 use crate::chip::utils::{
-    compute_root_quotient_and_shift, field_limbs_to_biguint, split_u32_limbs_to_u16_limbs,
+    compute_root_quotient_and_shift, field_limbs_to_biguint, split_witness_limbs,
 };
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::{to_u16_le_limbs_polynomial, Polynomial};
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -75,6 +75,7 @@ impl<L: AirParameters> AirBuilder<L> {
             witness_low,
             witness_high,
         };
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr);
         } else {
@@ -124,8 +125,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         let p_a = writer.read(&self.a, row_index);
         let p_b = writer.read(&self.b, row_index);
 
-        let a = field_limbs_to_biguint(p_a.coefficients());
-        let b = field_limbs_to_biguint(p_b.coefficients());
+        let a = field_limbs_to_biguint(p_a.coefficients(), P::NB_BITS_PER_LIMB);
+        let b = field_limbs_to_biguint(p_b.coefficients(), P::NB_BITS_PER_LIMB);
 
         let p = P::modulus();
         let minus_b_int = &p - &b;
@@ -147,11 +148,11 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         debug_assert_eq!(&carry * &p, &equation_lhs - &equation_rhs);
 
         // Make little endian polynomial limbs.
-        let p_a = to_u16_le_limbs_polynomial::<F, P>(&a);
-        let p_b = to_u16_le_limbs_polynomial::<F, P>(&b);
-        let p_p = to_u16_le_limbs_polynomial::<F, P>(&p);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_a = to_le_limbs_polynomial::<F, P>(&a);
+        let p_b = to_le_limbs_polynomial::<F, P>(&b);
+        let p_p = to_le_limbs_polynomial::<F, P>(&p);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let vanishing_poly = if self.sign {
@@ -162,8 +163,13 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         debug_assert_eq!(vanishing_poly.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness_shifted = compute_root_quotient_and_shift(&vanishing_poly, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs::<F>(&p_witness_shifted);
+        let p_witness_shifted = compute_root_quotient_and_shift(
+            &vanishing_poly,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) =
+            split_witness_limbs::<F>(&p_witness_shifted, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result, row_index);
         writer.write(&self.carry, &p_carry, row_index);
@@ -175,8 +181,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         let p_a = writer.read(&self.a);
         let p_b = writer.read(&self.b);
 
-        let a = field_limbs_to_biguint(p_a.coefficients());
-        let b = field_limbs_to_biguint(p_b.coefficients());
+        let a = field_limbs_to_biguint(p_a.coefficients(), P::NB_BITS_PER_LIMB);
+        let b = field_limbs_to_biguint(p_b.coefficients(), P::NB_BITS_PER_LIMB);
 
         let p = P::modulus();
         let minus_b_int = &p - &b;
@@ -198,11 +204,11 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         debug_assert_eq!(&carry * &p, &equation_lhs - &equation_rhs);
 
         // Make little endian polynomial limbs.
-        let p_a = to_u16_le_limbs_polynomial::<F, P>(&a);
-        let p_b = to_u16_le_limbs_polynomial::<F, P>(&b);
-        let p_p = to_u16_le_limbs_polynomial::<F, P>(&p);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_a = to_le_limbs_polynomial::<F, P>(&a);
+        let p_b = to_le_limbs_polynomial::<F, P>(&b);
+        let p_p = to_le_limbs_polynomial::<F, P>(&p);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let vanishing_poly = if self.sign {
@@ -213,8 +219,13 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDenInstruction<P>
         debug_assert_eq!(vanishing_poly.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness_shifted = compute_root_quotient_and_shift(&vanishing_poly, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs::<F>(&p_witness_shifted);
+        let p_witness_shifted = compute_root_quotient_and_shift(
+            &vanishing_poly,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) =
+            split_witness_limbs::<F>(&p_witness_shifted, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result);
         writer.write(&self.carry, &p_carry);
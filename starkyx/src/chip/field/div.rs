@@ -11,11 +11,11 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::u16::U16Register;
 use crate::chip::register::RegisterSerializable;
 use crate::chip::trace::writer::TraceWriter;
-use crate::chip::utils::digits_to_biguint;
+use crate::chip::utils::limbs_to_biguint;
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::to_u16_le_limbs_polynomial;
+use crate::polynomial::to_le_limbs_polynomial;
 
 /// Fp Division. Computes `a / b = result`.
 ///
@@ -115,6 +115,7 @@ impl<L: AirParameters> AirBuilder<L> {
             multiplication,
         };
 
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr);
         } else {
@@ -140,11 +141,11 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDivInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let b = digits_to_biguint(&b_digits);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         let modulus = P::modulus();
         let b_inv_int = b.modpow(&(&modulus - BigUint::from(2u64)), &modulus);
-        let p_b_inv = to_u16_le_limbs_polynomial::<F, P>(&b_inv_int);
+        let p_b_inv = to_le_limbs_polynomial::<F, P>(&b_inv_int);
 
         let b_inv = &self.denominator.b;
 
@@ -163,11 +164,11 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpDivInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let b = digits_to_biguint(&b_digits);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         let modulus = P::modulus();
         let b_inv_int = b.modpow(&(&modulus - BigUint::from(2u64)), &modulus);
-        let p_b_inv = to_u16_le_limbs_polynomial::<F, P>(&b_inv_int);
+        let p_b_inv = to_le_limbs_polynomial::<F, P>(&b_inv_int);
 
         let b_inv = &self.denominator.b;
 
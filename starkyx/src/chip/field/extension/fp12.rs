@@ -0,0 +1,350 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use super::fp2::Fp2Register;
+use super::fp6::{Fp6, Fp6Register};
+use super::{frobenius_coefficient, ExtensionRegister, TowerFieldParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::inverse::FpInverseInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::AirParameters;
+
+/// An element `c0 + c1 * w` of `Fp12`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp12<P> {
+    pub c0: Fp6<P>,
+    pub c1: Fp6<P>,
+}
+
+impl<P: FieldParameters> Fp12<P> {
+    pub fn new(c0: Fp6<P>, c1: Fp6<P>) -> Self {
+        Self { c0, c1 }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(Fp6::zero(), Fp6::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(Fp6::one(), Fp6::zero())
+    }
+
+    pub fn rand() -> Self {
+        Self::new(Fp6::rand(), Fp6::rand())
+    }
+
+    /// Returns `c0 - c1 * w`, which is the Frobenius map `x -> x^(p^6)`.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.c0.clone(), -&self.c1)
+    }
+}
+
+impl<P: TowerFieldParameters> Fp12<P> {
+    pub fn inverse(&self) -> Self {
+        let norm = &(&self.c0 * &self.c0) - &(&self.c1 * &self.c1).mul_by_non_residue();
+        let norm_inv = norm.inverse();
+        Self::new(&self.c0 * &norm_inv, -&(&self.c1 * &norm_inv))
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        let mut temp = self.clone();
+        for i in 0..exponent.bits() {
+            if exponent.bit(i) {
+                result = &result * &temp;
+            }
+            temp = &temp * &temp;
+        }
+        result
+    }
+
+    /// Returns the `power`-th Frobenius map `x -> x^(p^power)`.
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        let c1 = Fp6::new(
+            &self.c1.c0.frobenius_map(power) * &frobenius_coefficient(power, 1),
+            &self.c1.c1.frobenius_map(power) * &frobenius_coefficient(power, 3),
+            &self.c1.c2.frobenius_map(power) * &frobenius_coefficient(power, 5),
+        );
+        Self::new(self.c0.frobenius_map(power), c1)
+    }
+}
+
+impl<P: FieldParameters> Add<&Fp12<P>> for &Fp12<P> {
+    type Output = Fp12<P>;
+
+    fn add(self, other: &Fp12<P>) -> Fp12<P> {
+        Fp12::new(&self.c0 + &other.c0, &self.c1 + &other.c1)
+    }
+}
+
+impl<P: FieldParameters> Sub<&Fp12<P>> for &Fp12<P> {
+    type Output = Fp12<P>;
+
+    fn sub(self, other: &Fp12<P>) -> Fp12<P> {
+        Fp12::new(&self.c0 - &other.c0, &self.c1 - &other.c1)
+    }
+}
+
+impl<P: FieldParameters> Neg for &Fp12<P> {
+    type Output = Fp12<P>;
+
+    fn neg(self) -> Fp12<P> {
+        Fp12::new(-&self.c0, -&self.c1)
+    }
+}
+
+impl<P: TowerFieldParameters> Mul<&Fp12<P>> for &Fp12<P> {
+    type Output = Fp12<P>;
+
+    fn mul(self, other: &Fp12<P>) -> Fp12<P> {
+        let a0_b0 = &self.c0 * &other.c0;
+        let a1_b1 = &self.c1 * &other.c1;
+        let c0 = &a0_b0 + &a1_b1.mul_by_non_residue();
+        let c1 = &(&self.c0 * &other.c1) + &(&self.c1 * &other.c0);
+        Fp12::new(c0, c1)
+    }
+}
+
+/// A register for an element `c0 + c1 * w` of `Fp12`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Fp12Register<P: FieldParameters> {
+    pub c0: Fp6Register<P>,
+    pub c1: Fp6Register<P>,
+}
+
+impl<P: FieldParameters> Fp12Register<P> {
+    pub fn new(c0: Fp6Register<P>, c1: Fp6Register<P>) -> Self {
+        Self { c0, c1 }
+    }
+}
+
+impl<P: FieldParameters> ExtensionRegister for Fp12Register<P> {
+    type BaseField = P;
+    type Value = Fp12<P>;
+
+    const DEGREE: usize = 12;
+
+    fn from_coordinates(coordinates: &[FieldRegister<P>]) -> Self {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Self::new(
+            Fp6Register::<P>::from_coordinates(&coordinates[0..6]),
+            Fp6Register::<P>::from_coordinates(&coordinates[6..12]),
+        )
+    }
+
+    fn coordinates(&self) -> Vec<FieldRegister<P>> {
+        let mut coordinates = self.c0.coordinates();
+        coordinates.extend(self.c1.coordinates());
+        coordinates
+    }
+
+    fn value_from_coordinates(coordinates: &[BigUint]) -> Fp12<P> {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Fp12::new(
+            Fp6Register::<P>::value_from_coordinates(&coordinates[0..6]),
+            Fp6Register::<P>::value_from_coordinates(&coordinates[6..12]),
+        )
+    }
+
+    fn value_coordinates(value: &Fp12<P>) -> Vec<BigUint> {
+        let mut coordinates = Fp6Register::<P>::value_coordinates(&value.c0);
+        coordinates.extend(Fp6Register::<P>::value_coordinates(&value.c1));
+        coordinates
+    }
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    pub fn fp12_add<P: FieldParameters>(
+        &mut self,
+        a: &Fp12Register<P>,
+        b: &Fp12Register<P>,
+    ) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp6_add(&a.c0, &b.c0);
+        let c1 = self.fp6_add(&a.c1, &b.c1);
+        Fp12Register::new(c0, c1)
+    }
+
+    pub fn fp12_sub<P: FieldParameters>(
+        &mut self,
+        a: &Fp12Register<P>,
+        b: &Fp12Register<P>,
+    ) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp6_sub(&a.c0, &b.c0);
+        let c1 = self.fp6_sub(&a.c1, &b.c1);
+        Fp12Register::new(c0, c1)
+    }
+
+    /// Computes the conjugate `c0 - c1 * w` of `a = c0 + c1 * w`, which is the Frobenius map
+    /// `a -> a^(p^6)`. On the cyclotomic subgroup, this is the inverse of `a`.
+    pub fn fp12_conjugate<P: FieldParameters>(&mut self, a: &Fp12Register<P>) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c1 = self.fp6_neg(&a.c1);
+        Fp12Register::new(a.c0, c1)
+    }
+
+    /// Computes `a * b` with the Karatsuba formula, using `w^2 = v`:
+    ///
+    /// c0 = a0 * b0 + v * a1 * b1,
+    /// c1 = (a0 + a1) * (b0 + b1) - a0 * b0 - a1 * b1.
+    pub fn fp12_mul<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp12Register<P>,
+        b: &Fp12Register<P>,
+    ) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let a0_b0 = self.fp6_mul(&a.c0, &b.c0);
+        let a1_b1 = self.fp6_mul(&a.c1, &b.c1);
+        let v_a1_b1 = self.fp6_mul_by_non_residue(&a1_b1);
+        let c0 = self.fp6_add(&a0_b0, &v_a1_b1);
+
+        let a0_plus_a1 = self.fp6_add(&a.c0, &a.c1);
+        let b0_plus_b1 = self.fp6_add(&b.c0, &b.c1);
+        let product = self.fp6_mul(&a0_plus_a1, &b0_plus_b1);
+        let product_minus_a0_b0 = self.fp6_sub(&product, &a0_b0);
+        let c1 = self.fp6_sub(&product_minus_a0_b0, &a1_b1);
+
+        Fp12Register::new(c0, c1)
+    }
+
+    /// Computes the inverse of `a` as `conjugate(a) / (a0^2 - v * a1^2)`.
+    ///
+    /// The constraints are not satisfiable if `a` is zero.
+    pub fn fp12_inverse<P: TowerFieldParameters>(&mut self, a: &Fp12Register<P>) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P> + From<FpInverseInstruction<P>>,
+    {
+        let a0_squared = self.fp6_mul(&a.c0, &a.c0);
+        let a1_squared = self.fp6_mul(&a.c1, &a.c1);
+        let v_a1_squared = self.fp6_mul_by_non_residue(&a1_squared);
+        let norm = self.fp6_sub(&a0_squared, &v_a1_squared);
+        let norm_inv = self.fp6_inverse(&norm);
+
+        let c0 = self.fp6_mul(&a.c0, &norm_inv);
+        let a1_norm_inv = self.fp6_mul(&a.c1, &norm_inv);
+        let c1 = self.fp6_neg(&a1_norm_inv);
+        Fp12Register::new(c0, c1)
+    }
+
+    /// Computes the `power`-th Frobenius map `a -> a^(p^power)`, using that
+    /// `(w^j)^(p^power) = ξ^(j * (p^power - 1) / 6) * w^j`.
+    pub fn fp12_frobenius_map<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp12Register<P>,
+        power: usize,
+    ) -> Fp12Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp6_frobenius_map(&a.c0, power);
+
+        let mut c1 = Vec::with_capacity(3);
+        for (x, j) in [a.c1.c0, a.c1.c1, a.c1.c2].iter().zip([1, 3, 5]) {
+            let x_frobenius = self.fp2_frobenius_map(x, power);
+            let gamma = self.extension_constant::<Fp2Register<P>>(&frobenius_coefficient(power, j));
+            c1.push(self.fp2_mul(&x_frobenius, &gamma));
+        }
+        Fp12Register::new(c0, Fp6Register::new(c1[0], c1[1], c1[2]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::chip::field::extension::ExtensionFieldWriter;
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct Fp12Test;
+
+    impl AirParameters for Fp12Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 0;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 9;
+
+        type Instruction = FpInstruction<Bn254BaseField>;
+    }
+
+    #[test]
+    fn test_fp12_frobenius_map() {
+        type P = Bn254BaseField;
+
+        let p = P::modulus();
+        let a = Fp12::<P>::rand();
+        assert_eq!(a.frobenius_map(1), a.pow(&p));
+        assert_eq!(a.frobenius_map(2), a.frobenius_map(1).frobenius_map(1));
+        assert_eq!(a.frobenius_map(6), a.conjugate());
+        assert_eq!(a.frobenius_map(12), a);
+    }
+
+    #[test]
+    fn test_fp12_mul_and_inverse() {
+        type L = Fp12Test;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Bn254BaseField;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a = builder.alloc_public_extension::<Fp12Register<P>>();
+        let b = builder.alloc_public_extension::<Fp12Register<P>>();
+        let a_mul_b = builder.fp12_mul(&a, &b);
+        let a_inv = builder.fp12_inverse(&a);
+        let a_frobenius = builder.fp12_frobenius_map(&a, 1);
+        let a_conjugate = builder.fp12_conjugate(&a);
+        let a_plus_b = builder.fp12_add(&a, &b);
+        let a_plus_b_minus_b = builder.fp12_sub(&a_plus_b, &b);
+        builder.assert_extension_equal(&a_plus_b_minus_b, &a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        let a_val = Fp12::<P>::rand();
+        let b_val = Fp12::<P>::rand();
+        writer.write_extension(&a, &a_val, 0);
+        writer.write_extension(&b, &b_val, 0);
+        writer.write_global_instructions(&generator.air_data);
+
+        assert_eq!(writer.read_extension(&a_mul_b, 0), &a_val * &b_val);
+        assert_eq!(writer.read_extension(&a_inv, 0), a_val.inverse());
+        assert_eq!(&a_val * &a_val.inverse(), Fp12::one());
+        assert_eq!(
+            writer.read_extension(&a_frobenius, 0),
+            a_val.frobenius_map(1)
+        );
+        assert_eq!(writer.read_extension(&a_conjugate, 0), a_val.conjugate());
+
+        for i in 0..num_rows {
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
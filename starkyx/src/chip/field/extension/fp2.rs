@@ -0,0 +1,377 @@
+use core::marker::PhantomData;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use num::{BigUint, One, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::{ExtensionRegister, TowerFieldParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::inverse::FpInverseInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::AirParameters;
+
+/// An element `c0 + c1 * u` of `Fp2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp2<P> {
+    pub c0: BigUint,
+    pub c1: BigUint,
+    _marker: PhantomData<P>,
+}
+
+impl<P: FieldParameters> Fp2<P> {
+    pub fn new(c0: BigUint, c1: BigUint) -> Self {
+        Self {
+            c0,
+            c1,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(BigUint::zero(), BigUint::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(BigUint::one(), BigUint::zero())
+    }
+
+    pub fn rand() -> Self {
+        Self::new(P::rand(), P::rand())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero()
+    }
+
+    /// Returns `c0 - c1 * u`.
+    pub fn conjugate(&self) -> Self {
+        let p = P::modulus();
+        Self::new(self.c0.clone(), (&p - &self.c1) % &p)
+    }
+}
+
+impl<P: TowerFieldParameters> Fp2<P> {
+    pub fn inverse(&self) -> Self {
+        assert!(!self.is_zero(), "Cannot invert zero");
+        let p = P::modulus();
+        let beta = P::fp2_non_residue();
+        // The norm `c0^2 - β * c1^2` of the element lies in `Fp`.
+        let norm = (&self.c0 * &self.c0 + (&p - &beta) * &self.c1 * &self.c1) % &p;
+        let norm_inv = norm.modpow(&(&p - 2u32), &p);
+        Self::new(
+            (&self.c0 * &norm_inv) % &p,
+            ((&p - &self.c1) * &norm_inv) % &p,
+        )
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let mut result = Self::one();
+        let mut temp = self.clone();
+        for i in 0..exponent.bits() {
+            if exponent.bit(i) {
+                result = &result * &temp;
+            }
+            temp = &temp * &temp;
+        }
+        result
+    }
+
+    /// Returns the `power`-th Frobenius map `x -> x^(p^power)`, which is the conjugation for odd
+    /// powers and the identity for even ones.
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        if power % 2 == 1 {
+            self.conjugate()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl<P: FieldParameters> Add<&Fp2<P>> for &Fp2<P> {
+    type Output = Fp2<P>;
+
+    fn add(self, other: &Fp2<P>) -> Fp2<P> {
+        let p = P::modulus();
+        Fp2::new((&self.c0 + &other.c0) % &p, (&self.c1 + &other.c1) % &p)
+    }
+}
+
+impl<P: FieldParameters> Sub<&Fp2<P>> for &Fp2<P> {
+    type Output = Fp2<P>;
+
+    fn sub(self, other: &Fp2<P>) -> Fp2<P> {
+        let p = P::modulus();
+        Fp2::new(
+            (&self.c0 + &p - &other.c0) % &p,
+            (&self.c1 + &p - &other.c1) % &p,
+        )
+    }
+}
+
+impl<P: FieldParameters> Neg for &Fp2<P> {
+    type Output = Fp2<P>;
+
+    fn neg(self) -> Fp2<P> {
+        &Fp2::zero() - self
+    }
+}
+
+impl<P: TowerFieldParameters> Mul<&Fp2<P>> for &Fp2<P> {
+    type Output = Fp2<P>;
+
+    fn mul(self, other: &Fp2<P>) -> Fp2<P> {
+        let p = P::modulus();
+        let beta = P::fp2_non_residue();
+        let c0 = &self.c0 * &other.c0 + beta * &self.c1 * &other.c1;
+        let c1 = &self.c0 * &other.c1 + &self.c1 * &other.c0;
+        Fp2::new(c0 % &p, c1 % &p)
+    }
+}
+
+/// A register for an element `c0 + c1 * u` of `Fp2`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Fp2Register<P: FieldParameters> {
+    pub c0: FieldRegister<P>,
+    pub c1: FieldRegister<P>,
+}
+
+impl<P: FieldParameters> Fp2Register<P> {
+    pub fn new(c0: FieldRegister<P>, c1: FieldRegister<P>) -> Self {
+        Self { c0, c1 }
+    }
+}
+
+impl<P: FieldParameters> ExtensionRegister for Fp2Register<P> {
+    type BaseField = P;
+    type Value = Fp2<P>;
+
+    const DEGREE: usize = 2;
+
+    fn from_coordinates(coordinates: &[FieldRegister<P>]) -> Self {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Self::new(coordinates[0], coordinates[1])
+    }
+
+    fn coordinates(&self) -> Vec<FieldRegister<P>> {
+        vec![self.c0, self.c1]
+    }
+
+    fn value_from_coordinates(coordinates: &[BigUint]) -> Fp2<P> {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Fp2::new(coordinates[0].clone(), coordinates[1].clone())
+    }
+
+    fn value_coordinates(value: &Fp2<P>) -> Vec<BigUint> {
+        vec![value.c0.clone(), value.c1.clone()]
+    }
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    pub fn fp2_add<P: FieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+        b: &Fp2Register<P>,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp_add(&a.c0, &b.c0);
+        let c1 = self.fp_add(&a.c1, &b.c1);
+        Fp2Register::new(c0, c1)
+    }
+
+    pub fn fp2_sub<P: FieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+        b: &Fp2Register<P>,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp_sub(&a.c0, &b.c0);
+        let c1 = self.fp_sub(&a.c1, &b.c1);
+        Fp2Register::new(c0, c1)
+    }
+
+    pub fn fp2_neg<P: FieldParameters>(&mut self, a: &Fp2Register<P>) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let zero = self.fp_zero();
+        let c0 = self.fp_sub(&zero, &a.c0);
+        let c1 = self.fp_sub(&zero, &a.c1);
+        Fp2Register::new(c0, c1)
+    }
+
+    /// Computes the conjugate `c0 - c1 * u` of `a = c0 + c1 * u`.
+    pub fn fp2_conjugate<P: FieldParameters>(&mut self, a: &Fp2Register<P>) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let zero = self.fp_zero();
+        let c1 = self.fp_sub(&zero, &a.c1);
+        Fp2Register::new(a.c0, c1)
+    }
+
+    /// Computes `a * b` as `(a0 * b0 + β * a1 * b1) + (a0 * b1 + a1 * b0) * u`.
+    pub fn fp2_mul<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+        b: &Fp2Register<P>,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let beta = self.fp_constant(&P::fp2_non_residue());
+        let a1_b1 = self.fp_mul(&a.c1, &b.c1);
+        let c0 = self.fp_inner_product(&[a.c0, a1_b1], &[b.c0, beta]);
+        let c1 = self.fp_inner_product(&[a.c0, a.c1], &[b.c1, b.c0]);
+        Fp2Register::new(c0, c1)
+    }
+
+    /// Computes the product `a * c` of an element of `Fp2` with an element `c` of `Fp`.
+    pub fn fp2_mul_by_fp<P: FieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+        c: &FieldRegister<P>,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp_mul(&a.c0, c);
+        let c1 = self.fp_mul(&a.c1, c);
+        Fp2Register::new(c0, c1)
+    }
+
+    /// Computes `ξ * a`, where `ξ` is the non-residue defining `Fp6`.
+    pub fn fp2_mul_by_non_residue<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let xi = self.extension_constant::<Fp2Register<P>>(&P::fp6_non_residue());
+        self.fp2_mul(a, &xi)
+    }
+
+    /// Computes the inverse of `a` as `conjugate(a) / (a0^2 - β * a1^2)`.
+    ///
+    /// The constraints are not satisfiable if `a` is zero.
+    pub fn fp2_inverse<P: TowerFieldParameters>(&mut self, a: &Fp2Register<P>) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P> + From<FpInverseInstruction<P>>,
+    {
+        let p = P::modulus();
+        let minus_beta = self.fp_constant(&((&p - P::fp2_non_residue()) % &p));
+        let a1_squared = self.fp_mul(&a.c1, &a.c1);
+        let norm = self.fp_inner_product(&[a.c0, a1_squared], &[a.c0, minus_beta]);
+        let norm_inv = self.fp_inverse(&norm);
+        let conjugate = self.fp2_conjugate(a);
+        self.fp2_mul_by_fp(&conjugate, &norm_inv)
+    }
+
+    /// Computes the `power`-th Frobenius map `a -> a^(p^power)`.
+    pub fn fp2_frobenius_map<P: FieldParameters>(
+        &mut self,
+        a: &Fp2Register<P>,
+        power: usize,
+    ) -> Fp2Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        if power % 2 == 1 {
+            self.fp2_conjugate(a)
+        } else {
+            *a
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::chip::field::extension::ExtensionFieldWriter;
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct Fp2Test;
+
+    impl AirParameters for Fp2Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 340;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 519;
+
+        type Instruction = FpInstruction<Bn254BaseField>;
+    }
+
+    #[test]
+    fn test_fp2_mul_and_inverse() {
+        type L = Fp2Test;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Bn254BaseField;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a = builder.alloc_extension::<Fp2Register<P>>();
+        let b = builder.alloc_extension::<Fp2Register<P>>();
+        let a_mul_b = builder.fp2_mul(&a, &b);
+
+        let a_pub = builder.alloc_public_extension::<Fp2Register<P>>();
+        let b_pub = builder.alloc_public_extension::<Fp2Register<P>>();
+        let a_mul_b_pub = builder.fp2_mul(&a_pub, &b_pub);
+        let a_inv_pub = builder.fp2_inverse(&a_pub);
+        let a_conj_pub = builder.fp2_frobenius_map(&a_pub, 1);
+        let sum_pub = builder.fp2_add(&a_pub, &b_pub);
+        let diff_pub = builder.fp2_sub(&sum_pub, &b_pub);
+        builder.assert_extension_equal(&diff_pub, &a_pub);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        let a_pub_val = Fp2::<P>::rand();
+        let b_pub_val = Fp2::<P>::rand();
+        writer.write_extension(&a_pub, &a_pub_val, 0);
+        writer.write_extension(&b_pub, &b_pub_val, 0);
+        writer.write_global_instructions(&generator.air_data);
+
+        assert_eq!(
+            writer.read_extension(&a_mul_b_pub, 0),
+            &a_pub_val * &b_pub_val
+        );
+        assert_eq!(writer.read_extension(&a_inv_pub, 0), a_pub_val.inverse());
+        assert_eq!(
+            writer.read_extension(&a_conj_pub, 0),
+            a_pub_val.frobenius_map(1)
+        );
+        assert_eq!(&a_pub_val * &a_pub_val.inverse(), Fp2::one());
+
+        for i in 0..num_rows {
+            let a_val = Fp2::<P>::rand();
+            let b_val = Fp2::<P>::rand();
+            writer.write_extension(&a, &a_val, i);
+            writer.write_extension(&b, &b_val, i);
+            writer.write_row_instructions(&generator.air_data, i);
+            assert_eq!(writer.read_extension(&a_mul_b, i), &a_val * &b_val);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
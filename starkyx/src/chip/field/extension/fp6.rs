@@ -0,0 +1,410 @@
+use core::ops::{Add, Mul, Neg, Sub};
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use super::fp2::{Fp2, Fp2Register};
+use super::{frobenius_coefficient, ExtensionRegister, TowerFieldParameters};
+use crate::chip::builder::AirBuilder;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::inverse::FpInverseInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::AirParameters;
+
+/// An element `c0 + c1 * v + c2 * v^2` of `Fp6`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fp6<P> {
+    pub c0: Fp2<P>,
+    pub c1: Fp2<P>,
+    pub c2: Fp2<P>,
+}
+
+impl<P: FieldParameters> Fp6<P> {
+    pub fn new(c0: Fp2<P>, c1: Fp2<P>, c2: Fp2<P>) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(Fp2::zero(), Fp2::zero(), Fp2::zero())
+    }
+
+    pub fn one() -> Self {
+        Self::new(Fp2::one(), Fp2::zero(), Fp2::zero())
+    }
+
+    pub fn rand() -> Self {
+        Self::new(Fp2::rand(), Fp2::rand(), Fp2::rand())
+    }
+}
+
+impl<P: TowerFieldParameters> Fp6<P> {
+    /// Returns `v * self`, using `v^3 = ξ`.
+    pub fn mul_by_non_residue(&self) -> Self {
+        let c0 = &self.c2 * &P::fp6_non_residue();
+        Self::new(c0, self.c0.clone(), self.c1.clone())
+    }
+
+    pub fn inverse(&self) -> Self {
+        let xi = P::fp6_non_residue();
+        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+        let t0 = &(a0 * a0) - &(&xi * &(a1 * a2));
+        let t1 = &(&xi * &(a2 * a2)) - &(a0 * a1);
+        let t2 = &(a1 * a1) - &(a0 * a2);
+        let norm = &(a0 * &t0) + &(&xi * &(&(a2 * &t1) + &(a1 * &t2)));
+        let norm_inv = norm.inverse();
+        Self::new(&t0 * &norm_inv, &t1 * &norm_inv, &t2 * &norm_inv)
+    }
+
+    /// Returns the `power`-th Frobenius map `x -> x^(p^power)`.
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        Self::new(
+            self.c0.frobenius_map(power),
+            &self.c1.frobenius_map(power) * &frobenius_coefficient(power, 2),
+            &self.c2.frobenius_map(power) * &frobenius_coefficient(power, 4),
+        )
+    }
+}
+
+impl<P: FieldParameters> Add<&Fp6<P>> for &Fp6<P> {
+    type Output = Fp6<P>;
+
+    fn add(self, other: &Fp6<P>) -> Fp6<P> {
+        Fp6::new(
+            &self.c0 + &other.c0,
+            &self.c1 + &other.c1,
+            &self.c2 + &other.c2,
+        )
+    }
+}
+
+impl<P: FieldParameters> Sub<&Fp6<P>> for &Fp6<P> {
+    type Output = Fp6<P>;
+
+    fn sub(self, other: &Fp6<P>) -> Fp6<P> {
+        Fp6::new(
+            &self.c0 - &other.c0,
+            &self.c1 - &other.c1,
+            &self.c2 - &other.c2,
+        )
+    }
+}
+
+impl<P: FieldParameters> Neg for &Fp6<P> {
+    type Output = Fp6<P>;
+
+    fn neg(self) -> Fp6<P> {
+        Fp6::new(-&self.c0, -&self.c1, -&self.c2)
+    }
+}
+
+impl<P: TowerFieldParameters> Mul<&Fp6<P>> for &Fp6<P> {
+    type Output = Fp6<P>;
+
+    fn mul(self, other: &Fp6<P>) -> Fp6<P> {
+        let xi = P::fp6_non_residue();
+        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+        let (b0, b1, b2) = (&other.c0, &other.c1, &other.c2);
+        let c0 = &(a0 * b0) + &(&xi * &(&(a1 * b2) + &(a2 * b1)));
+        let c1 = &(&(a0 * b1) + &(a1 * b0)) + &(&xi * &(a2 * b2));
+        let c2 = &(&(a0 * b2) + &(a1 * b1)) + &(a2 * b0);
+        Fp6::new(c0, c1, c2)
+    }
+}
+
+/// A register for an element `c0 + c1 * v + c2 * v^2` of `Fp6`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Fp6Register<P: FieldParameters> {
+    pub c0: Fp2Register<P>,
+    pub c1: Fp2Register<P>,
+    pub c2: Fp2Register<P>,
+}
+
+impl<P: FieldParameters> Fp6Register<P> {
+    pub fn new(c0: Fp2Register<P>, c1: Fp2Register<P>, c2: Fp2Register<P>) -> Self {
+        Self { c0, c1, c2 }
+    }
+}
+
+impl<P: FieldParameters> ExtensionRegister for Fp6Register<P> {
+    type BaseField = P;
+    type Value = Fp6<P>;
+
+    const DEGREE: usize = 6;
+
+    fn from_coordinates(coordinates: &[FieldRegister<P>]) -> Self {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Self::new(
+            Fp2Register::<P>::from_coordinates(&coordinates[0..2]),
+            Fp2Register::<P>::from_coordinates(&coordinates[2..4]),
+            Fp2Register::<P>::from_coordinates(&coordinates[4..6]),
+        )
+    }
+
+    fn coordinates(&self) -> Vec<FieldRegister<P>> {
+        [self.c0, self.c1, self.c2]
+            .iter()
+            .flat_map(|c| c.coordinates())
+            .collect()
+    }
+
+    fn value_from_coordinates(coordinates: &[BigUint]) -> Fp6<P> {
+        assert_eq!(coordinates.len(), Self::DEGREE);
+        Fp6::new(
+            Fp2Register::<P>::value_from_coordinates(&coordinates[0..2]),
+            Fp2Register::<P>::value_from_coordinates(&coordinates[2..4]),
+            Fp2Register::<P>::value_from_coordinates(&coordinates[4..6]),
+        )
+    }
+
+    fn value_coordinates(value: &Fp6<P>) -> Vec<BigUint> {
+        [&value.c0, &value.c1, &value.c2]
+            .into_iter()
+            .flat_map(Fp2Register::<P>::value_coordinates)
+            .collect()
+    }
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    pub fn fp6_add<P: FieldParameters>(
+        &mut self,
+        a: &Fp6Register<P>,
+        b: &Fp6Register<P>,
+    ) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp2_add(&a.c0, &b.c0);
+        let c1 = self.fp2_add(&a.c1, &b.c1);
+        let c2 = self.fp2_add(&a.c2, &b.c2);
+        Fp6Register::new(c0, c1, c2)
+    }
+
+    pub fn fp6_sub<P: FieldParameters>(
+        &mut self,
+        a: &Fp6Register<P>,
+        b: &Fp6Register<P>,
+    ) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp2_sub(&a.c0, &b.c0);
+        let c1 = self.fp2_sub(&a.c1, &b.c1);
+        let c2 = self.fp2_sub(&a.c2, &b.c2);
+        Fp6Register::new(c0, c1, c2)
+    }
+
+    pub fn fp6_neg<P: FieldParameters>(&mut self, a: &Fp6Register<P>) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp2_neg(&a.c0);
+        let c1 = self.fp2_neg(&a.c1);
+        let c2 = self.fp2_neg(&a.c2);
+        Fp6Register::new(c0, c1, c2)
+    }
+
+    /// Computes `a * b` with the schoolbook formula, using `v^3 = ξ`:
+    ///
+    /// c0 = a0 * b0 + ξ * (a1 * b2 + a2 * b1),
+    /// c1 = a0 * b1 + a1 * b0 + ξ * a2 * b2,
+    /// c2 = a0 * b2 + a1 * b1 + a2 * b0.
+    pub fn fp6_mul<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp6Register<P>,
+        b: &Fp6Register<P>,
+    ) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let a0_b0 = self.fp2_mul(&a.c0, &b.c0);
+        let a0_b1 = self.fp2_mul(&a.c0, &b.c1);
+        let a0_b2 = self.fp2_mul(&a.c0, &b.c2);
+        let a1_b0 = self.fp2_mul(&a.c1, &b.c0);
+        let a1_b1 = self.fp2_mul(&a.c1, &b.c1);
+        let a1_b2 = self.fp2_mul(&a.c1, &b.c2);
+        let a2_b0 = self.fp2_mul(&a.c2, &b.c0);
+        let a2_b1 = self.fp2_mul(&a.c2, &b.c1);
+        let a2_b2 = self.fp2_mul(&a.c2, &b.c2);
+
+        let t0 = self.fp2_add(&a1_b2, &a2_b1);
+        let xi_t0 = self.fp2_mul_by_non_residue(&t0);
+        let c0 = self.fp2_add(&a0_b0, &xi_t0);
+
+        let t1 = self.fp2_add(&a0_b1, &a1_b0);
+        let xi_a2_b2 = self.fp2_mul_by_non_residue(&a2_b2);
+        let c1 = self.fp2_add(&t1, &xi_a2_b2);
+
+        let t2 = self.fp2_add(&a0_b2, &a1_b1);
+        let c2 = self.fp2_add(&t2, &a2_b0);
+
+        Fp6Register::new(c0, c1, c2)
+    }
+
+    /// Computes `v * a = ξ * a2 + a0 * v + a1 * v^2`.
+    pub fn fp6_mul_by_non_residue<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp6Register<P>,
+    ) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp2_mul_by_non_residue(&a.c2);
+        Fp6Register::new(c0, a.c0, a.c1)
+    }
+
+    /// Computes the inverse of `a` as `(t0 + t1 * v + t2 * v^2) / n`, where
+    ///
+    /// t0 = a0^2 - ξ * a1 * a2,
+    /// t1 = ξ * a2^2 - a0 * a1,
+    /// t2 = a1^2 - a0 * a2,
+    /// n = a0 * t0 + ξ * (a2 * t1 + a1 * t2).
+    ///
+    /// The constraints are not satisfiable if `a` is zero.
+    pub fn fp6_inverse<P: TowerFieldParameters>(&mut self, a: &Fp6Register<P>) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P> + From<FpInverseInstruction<P>>,
+    {
+        let a0_squared = self.fp2_mul(&a.c0, &a.c0);
+        let a1_squared = self.fp2_mul(&a.c1, &a.c1);
+        let a2_squared = self.fp2_mul(&a.c2, &a.c2);
+        let a0_a1 = self.fp2_mul(&a.c0, &a.c1);
+        let a0_a2 = self.fp2_mul(&a.c0, &a.c2);
+        let a1_a2 = self.fp2_mul(&a.c1, &a.c2);
+
+        let xi_a1_a2 = self.fp2_mul_by_non_residue(&a1_a2);
+        let t0 = self.fp2_sub(&a0_squared, &xi_a1_a2);
+        let xi_a2_squared = self.fp2_mul_by_non_residue(&a2_squared);
+        let t1 = self.fp2_sub(&xi_a2_squared, &a0_a1);
+        let t2 = self.fp2_sub(&a1_squared, &a0_a2);
+
+        let a0_t0 = self.fp2_mul(&a.c0, &t0);
+        let a2_t1 = self.fp2_mul(&a.c2, &t1);
+        let a1_t2 = self.fp2_mul(&a.c1, &t2);
+        let s = self.fp2_add(&a2_t1, &a1_t2);
+        let xi_s = self.fp2_mul_by_non_residue(&s);
+        let norm = self.fp2_add(&a0_t0, &xi_s);
+        let norm_inv = self.fp2_inverse(&norm);
+
+        let c0 = self.fp2_mul(&t0, &norm_inv);
+        let c1 = self.fp2_mul(&t1, &norm_inv);
+        let c2 = self.fp2_mul(&t2, &norm_inv);
+        Fp6Register::new(c0, c1, c2)
+    }
+
+    /// Computes the `power`-th Frobenius map `a -> a^(p^power)`, using that
+    /// `(v^i)^(p^power) = ξ^(i * (p^power - 1) / 3) * v^i`.
+    pub fn fp6_frobenius_map<P: TowerFieldParameters>(
+        &mut self,
+        a: &Fp6Register<P>,
+        power: usize,
+    ) -> Fp6Register<P>
+    where
+        L::Instruction: FromFieldInstruction<P>,
+    {
+        let c0 = self.fp2_frobenius_map(&a.c0, power);
+        let c1 = self.fp2_frobenius_map(&a.c1, power);
+        let c2 = self.fp2_frobenius_map(&a.c2, power);
+
+        let gamma_1 = self.extension_constant::<Fp2Register<P>>(&frobenius_coefficient(power, 2));
+        let gamma_2 = self.extension_constant::<Fp2Register<P>>(&frobenius_coefficient(power, 4));
+        let c1 = self.fp2_mul(&c1, &gamma_1);
+        let c2 = self.fp2_mul(&c2, &gamma_2);
+        Fp6Register::new(c0, c1, c2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::weierstrass::bn254::Bn254BaseField;
+    use crate::chip::field::extension::fp12::Fp12;
+    use crate::chip::field::extension::ExtensionFieldWriter;
+    use crate::chip::field::instruction::FpInstruction;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct Fp6Test;
+
+    impl AirParameters for Fp6Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 0;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 9;
+
+        type Instruction = FpInstruction<Bn254BaseField>;
+    }
+
+    #[test]
+    fn test_fp6_frobenius_map() {
+        type P = Bn254BaseField;
+
+        // `Fp6` is a subfield of `Fp12`, so the Frobenius map is the power by `p` in `Fp12`.
+        let p = P::modulus();
+        let a = Fp6::<P>::rand();
+        let a_pow_p = Fp12::new(a.clone(), Fp6::zero()).pow(&p);
+        assert_eq!(a_pow_p, Fp12::new(a.frobenius_map(1), Fp6::zero()));
+        assert_eq!(a.frobenius_map(2), a.frobenius_map(1).frobenius_map(1));
+        assert_eq!(a.frobenius_map(6), a);
+    }
+
+    #[test]
+    fn test_fp6_mul_and_inverse() {
+        type L = Fp6Test;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Bn254BaseField;
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a = builder.alloc_public_extension::<Fp6Register<P>>();
+        let b = builder.alloc_public_extension::<Fp6Register<P>>();
+        let a_mul_b = builder.fp6_mul(&a, &b);
+        let a_inv = builder.fp6_inverse(&a);
+        let a_frobenius = builder.fp6_frobenius_map(&a, 1);
+        let a_mul_non_residue = builder.fp6_mul_by_non_residue(&a);
+        let a_neg = builder.fp6_neg(&a);
+        let a_plus_b = builder.fp6_add(&a, &b);
+        let a_plus_b_minus_b = builder.fp6_sub(&a_plus_b, &b);
+        builder.assert_extension_equal(&a_plus_b_minus_b, &a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let writer = generator.new_writer();
+        let a_val = Fp6::<P>::rand();
+        let b_val = Fp6::<P>::rand();
+        writer.write_extension(&a, &a_val, 0);
+        writer.write_extension(&b, &b_val, 0);
+        writer.write_global_instructions(&generator.air_data);
+
+        assert_eq!(writer.read_extension(&a_mul_b, 0), &a_val * &b_val);
+        assert_eq!(writer.read_extension(&a_inv, 0), a_val.inverse());
+        assert_eq!(&a_val * &a_val.inverse(), Fp6::one());
+        assert_eq!(
+            writer.read_extension(&a_frobenius, 0),
+            a_val.frobenius_map(1)
+        );
+        assert_eq!(
+            writer.read_extension(&a_mul_non_residue, 0),
+            a_val.mul_by_non_residue()
+        );
+        assert_eq!(writer.read_extension(&a_neg, 0), -&a_val);
+
+        for i in 0..num_rows {
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
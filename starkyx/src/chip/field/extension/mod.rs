@@ -0,0 +1,165 @@
+//! Arithmetic over the tower of extension fields used for pairings:
+//!
+//! Fp2 = Fp[u] / (u^2 - β),
+//! Fp6 = Fp2[v] / (v^3 - ξ),
+//! Fp12 = Fp6[w] / (w^2 - v).
+//!
+//! An extension field element is stored as its coordinates over `Fp`, each of which is a
+//! `FieldRegister<P>`. The extension operations are composed out of the base field instructions,
+//! the main building block being the multiplication in `Fp2` which takes one `fp_mul` and two
+//! `fp_inner_product` instructions. Hence, an AIR only needs to support `FromFieldInstruction<P>`
+//! to use the extension fields, and `FpInverseInstruction<P>` for inversion.
+//!
+//! Inner products are limited to two terms by the size of the carry, so the multiplication in
+//! `Fp6` and `Fp12` is done with the schoolbook and Karatsuba formulas over `Fp2` and `Fp6`.
+
+pub mod fp12;
+pub mod fp2;
+pub mod fp6;
+
+use num::{BigUint, One};
+
+use self::fp2::Fp2;
+use super::parameters::FieldParameters;
+use super::register::FieldRegister;
+use crate::chip::builder::AirBuilder;
+use crate::chip::register::RegisterSerializable;
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::field_limbs_to_biguint;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// Parameters of the tower `Fp12 / Fp6 / Fp2` over the base field.
+///
+/// The tower requires `p = 1 mod 6`, so that the Frobenius coefficients are powers of `ξ`.
+pub trait TowerFieldParameters: FieldParameters {
+    /// The quadratic non-residue `β` of `Fp` such that `u^2 = β`.
+    fn fp2_non_residue() -> BigUint;
+
+    /// The element `ξ` of `Fp2`, which is neither a square nor a cube, such that `v^3 = ξ`.
+    fn fp6_non_residue() -> Fp2<Self>;
+}
+
+/// Returns `ξ^(j * (p^k - 1) / 6)`, so that `(w^j)^(p^k) = frobenius_coefficient(k, j) * w^j`.
+pub fn frobenius_coefficient<P: TowerFieldParameters>(power: usize, j: usize) -> Fp2<P> {
+    let p = P::modulus();
+    assert_eq!(&p % 6u32, BigUint::one(), "The modulus must be 1 mod 6");
+    // `ξ` lies in the multiplicative group of `Fp2`, of order `p^2 - 1`.
+    let exponent = (p.pow(power as u32) - 1u32) / 6u32 * j % (&p * &p - 1u32);
+    P::fp6_non_residue().pow(&exponent)
+}
+
+/// A register for an element of an extension field, stored as its coordinates over the base
+/// field.
+pub trait ExtensionRegister: Copy {
+    type BaseField: FieldParameters;
+
+    /// The value of the register, as an element of the extension field.
+    type Value;
+
+    /// The degree of the extension over the base field.
+    const DEGREE: usize;
+
+    fn from_coordinates(coordinates: &[FieldRegister<Self::BaseField>]) -> Self;
+
+    fn coordinates(&self) -> Vec<FieldRegister<Self::BaseField>>;
+
+    fn value_from_coordinates(coordinates: &[BigUint]) -> Self::Value;
+
+    fn value_coordinates(value: &Self::Value) -> Vec<BigUint>;
+
+    fn is_trace(&self) -> bool {
+        self.coordinates().iter().any(|x| x.is_trace())
+    }
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    pub fn alloc_extension<R: ExtensionRegister>(&mut self) -> R {
+        let coordinates = (0..R::DEGREE)
+            .map(|_| self.alloc::<FieldRegister<R::BaseField>>())
+            .collect::<Vec<_>>();
+        R::from_coordinates(&coordinates)
+    }
+
+    pub fn alloc_public_extension<R: ExtensionRegister>(&mut self) -> R {
+        let coordinates = (0..R::DEGREE)
+            .map(|_| self.alloc_public::<FieldRegister<R::BaseField>>())
+            .collect::<Vec<_>>();
+        R::from_coordinates(&coordinates)
+    }
+
+    pub fn extension_constant<R: ExtensionRegister>(&mut self, value: &R::Value) -> R {
+        let coordinates = R::value_coordinates(value)
+            .iter()
+            .map(|x| self.fp_constant(x))
+            .collect::<Vec<_>>();
+        R::from_coordinates(&coordinates)
+    }
+
+    pub fn assert_extension_equal<R: ExtensionRegister>(&mut self, a: &R, b: &R) {
+        for (x, y) in a.coordinates().iter().zip(b.coordinates().iter()) {
+            self.assert_equal(x, y);
+        }
+    }
+}
+
+pub trait ExtensionFieldWriter {
+    fn read_extension<R: ExtensionRegister>(&self, data: &R, row_index: usize) -> R::Value;
+
+    fn write_extension<R: ExtensionRegister>(&self, data: &R, value: &R::Value, row_index: usize);
+}
+
+pub trait ExtensionFieldAirWriter: AirWriter {
+    fn read_extension<R: ExtensionRegister>(&self, data: &R) -> R::Value
+    where
+        Self::Field: PrimeField64,
+    {
+        let coordinates = data
+            .coordinates()
+            .iter()
+            .map(|x| {
+                field_limbs_to_biguint(self.read(x).coefficients(), R::BaseField::NB_BITS_PER_LIMB)
+            })
+            .collect::<Vec<_>>();
+        R::value_from_coordinates(&coordinates)
+    }
+
+    fn write_extension<R: ExtensionRegister>(&mut self, data: &R, value: &R::Value) {
+        for (x, x_int) in data
+            .coordinates()
+            .iter()
+            .zip(R::value_coordinates(value).iter())
+        {
+            let value_x = to_le_limbs_polynomial::<Self::Field, R::BaseField>(x_int);
+            self.write(x, &value_x);
+        }
+    }
+}
+
+impl<W: AirWriter> ExtensionFieldAirWriter for W {}
+
+impl<F: PrimeField64> ExtensionFieldWriter for TraceWriter<F> {
+    fn read_extension<R: ExtensionRegister>(&self, data: &R, row_index: usize) -> R::Value {
+        let coordinates = data
+            .coordinates()
+            .iter()
+            .map(|x| {
+                let p_x = self.read(x, row_index);
+                field_limbs_to_biguint(p_x.coefficients(), R::BaseField::NB_BITS_PER_LIMB)
+            })
+            .collect::<Vec<_>>();
+        R::value_from_coordinates(&coordinates)
+    }
+
+    fn write_extension<R: ExtensionRegister>(&self, data: &R, value: &R::Value, row_index: usize) {
+        for (x, x_int) in data
+            .coordinates()
+            .iter()
+            .zip(R::value_coordinates(value).iter())
+        {
+            let value_x = to_le_limbs_polynomial::<F, R::BaseField>(x_int);
+            self.write(x, &value_x, row_index);
+        }
+    }
+}
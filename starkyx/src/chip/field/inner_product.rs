@@ -15,11 +15,11 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::u16::U16Register;
 use crate::chip::register::{Register, RegisterSerializable};
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
-use crate::chip::utils::{field_limbs_to_biguint, split_u32_limbs_to_u16_limbs};
+use crate::chip::utils::{field_limbs_to_biguint, split_witness_limbs};
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::{to_u16_le_limbs_polynomial, Polynomial};
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -70,6 +70,7 @@ impl<L: AirParameters> AirBuilder<L> {
             witness_high,
         };
 
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr.clone());
         } else {
@@ -132,8 +133,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
             .zip(p_b_vec.iter())
             .map(|(a, b)| {
                 (
-                    field_limbs_to_biguint(a.coefficients()),
-                    field_limbs_to_biguint(b.coefficients()),
+                    field_limbs_to_biguint(a.coefficients(), P::NB_BITS_PER_LIMB),
+                    field_limbs_to_biguint(b.coefficients(), P::NB_BITS_PER_LIMB),
                 )
             })
             .fold(BigUint::zero(), |acc, (c, d)| acc + c * d);
@@ -144,9 +145,9 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
         assert!(carry < &(2u32 * modulus));
         assert_eq!(carry * modulus, inner_product - result);
 
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(carry);
 
         // Compute the vanishing polynomial.
         let p_inner_product = p_a_vec.into_iter().zip(p_b_vec).fold(
@@ -157,8 +158,12 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
         assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result, row_index);
         writer.write(&self.carry, &p_carry, row_index);
@@ -185,8 +190,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
             .zip(p_b_vec.iter())
             .map(|(a, b)| {
                 (
-                    field_limbs_to_biguint(a.coefficients()),
-                    field_limbs_to_biguint(b.coefficients()),
+                    field_limbs_to_biguint(a.coefficients(), P::NB_BITS_PER_LIMB),
+                    field_limbs_to_biguint(b.coefficients(), P::NB_BITS_PER_LIMB),
                 )
             })
             .fold(BigUint::zero(), |acc, (c, d)| acc + c * d);
@@ -197,9 +202,9 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
         assert!(carry < &(2u32 * modulus));
         assert_eq!(carry * modulus, inner_product - result);
 
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(carry);
 
         // Compute the vanishing polynomial.
         let p_inner_product = p_a_vec.into_iter().zip(p_b_vec).fold(
@@ -210,8 +215,12 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInnerProductInstr
         assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result);
         writer.write(&self.carry, &p_carry);
@@ -4,9 +4,12 @@ use super::add::FpAddInstruction;
 use super::den::FpDenInstruction;
 use super::div::FpDivInstruction;
 use super::inner_product::FpInnerProductInstruction;
+use super::inverse::FpInverseInstruction;
+use super::is_zero::FpIsZeroInstruction;
 use super::mul::FpMulInstruction;
 use super::mul_const::FpMulConstInstruction;
 use super::parameters::FieldParameters;
+use super::sqrt::FpSqrtInstruction;
 use super::sub::FpSubInstruction;
 use crate::air::AirConstraint;
 use crate::chip::instruction::Instruction;
@@ -24,6 +27,9 @@ pub enum FpInstruction<P: FieldParameters> {
     Den(FpDenInstruction<P>),
     Sub(FpSubInstruction<P>),
     Div(FpDivInstruction<P>),
+    Inverse(FpInverseInstruction<P>),
+    Sqrt(FpSqrtInstruction<P>),
+    IsZero(FpIsZeroInstruction<P>),
 }
 
 pub trait FromFieldInstruction<P: FieldParameters>:
@@ -49,6 +55,9 @@ impl<AP: PolynomialParser, P: FieldParameters> AirConstraint<AP> for FpInstructi
             FpInstruction::Den(instruction) => AirConstraint::<AP>::eval(instruction, parser),
             FpInstruction::Sub(instruction) => AirConstraint::<AP>::eval(instruction, parser),
             FpInstruction::Div(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+            FpInstruction::Inverse(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+            FpInstruction::Sqrt(instruction) => AirConstraint::<AP>::eval(instruction, parser),
+            FpInstruction::IsZero(instruction) => AirConstraint::<AP>::eval(instruction, parser),
         }
     }
 }
@@ -77,6 +86,15 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInstruction<P> {
             FpInstruction::Div(instruction) => {
                 Instruction::<F>::write(instruction, writer, row_index)
             }
+            FpInstruction::Inverse(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            FpInstruction::Sqrt(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
+            FpInstruction::IsZero(instruction) => {
+                Instruction::<F>::write(instruction, writer, row_index)
+            }
         }
     }
 
@@ -93,6 +111,13 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInstruction<P> {
             FpInstruction::Den(instruction) => Instruction::<F>::write_to_air(instruction, writer),
             FpInstruction::Sub(instruction) => Instruction::<F>::write_to_air(instruction, writer),
             FpInstruction::Div(instruction) => Instruction::<F>::write_to_air(instruction, writer),
+            FpInstruction::Inverse(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
+            FpInstruction::Sqrt(instruction) => Instruction::<F>::write_to_air(instruction, writer),
+            FpInstruction::IsZero(instruction) => {
+                Instruction::<F>::write_to_air(instruction, writer)
+            }
         }
     }
 }
@@ -138,3 +163,21 @@ impl<P: FieldParameters> From<FpDivInstruction<P>> for FpInstruction<P> {
         FpInstruction::Div(instr)
     }
 }
+
+impl<P: FieldParameters> From<FpInverseInstruction<P>> for FpInstruction<P> {
+    fn from(instr: FpInverseInstruction<P>) -> Self {
+        FpInstruction::Inverse(instr)
+    }
+}
+
+impl<P: FieldParameters> From<FpSqrtInstruction<P>> for FpInstruction<P> {
+    fn from(instr: FpSqrtInstruction<P>) -> Self {
+        FpInstruction::Sqrt(instr)
+    }
+}
+
+impl<P: FieldParameters> From<FpIsZeroInstruction<P>> for FpInstruction<P> {
+    fn from(instr: FpIsZeroInstruction<P>) -> Self {
+        FpInstruction::IsZero(instr)
+    }
+}
@@ -0,0 +1,214 @@
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use super::mul::FpMulInstruction;
+use super::parameters::FieldParameters;
+use super::register::FieldRegister;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::RegisterSerializable;
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::limbs_to_biguint;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// Fp Inversion. Computes `a^(-1) = result`.
+///
+/// This is done by witnessing the inverse and then constraining that `a * result == 1`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FpInverseInstruction<P: FieldParameters> {
+    /// a `FpMulInstruction` to compute `a * result = 1`.
+    multiplication: FpMulInstruction<P>,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// given a field element `a`, computes its inverse `a^(-1)`.
+    ///
+    /// The constraints are not satisfiable if `a` is zero.
+    pub fn fp_inverse<P: FieldParameters>(&mut self, a: &FieldRegister<P>) -> FieldRegister<P>
+    where
+        L::Instruction: From<FpInverseInstruction<P>>,
+    {
+        let result = if a.is_trace() {
+            self.alloc::<FieldRegister<P>>()
+        } else {
+            self.alloc_public::<FieldRegister<P>>()
+        };
+        self.set_fp_inverse(a, &result);
+        result
+    }
+
+    pub fn set_fp_inverse<P: FieldParameters>(
+        &mut self,
+        a: &FieldRegister<P>,
+        result: &FieldRegister<P>,
+    ) where
+        L::Instruction: From<FpInverseInstruction<P>>,
+    {
+        let is_trace = a.is_trace() || result.is_trace();
+
+        let carry: FieldRegister<P>;
+        let witness_low: ArrayRegister<U16Register>;
+        let witness_high: ArrayRegister<U16Register>;
+        let one = self.fp_one();
+
+        if is_trace {
+            carry = self.alloc::<FieldRegister<P>>();
+            witness_low = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+        } else {
+            carry = self.alloc_public::<FieldRegister<P>>();
+            witness_low = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+        }
+
+        // check that a * result = one.
+        let multiplication = FpMulInstruction {
+            a: *a,
+            b: *result,
+            result: one,
+            carry,
+            witness_low,
+            witness_high,
+        };
+
+        let instr = FpInverseInstruction { multiplication };
+
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+    }
+}
+
+impl<AP: PolynomialParser, P: FieldParameters> AirConstraint<AP> for FpInverseInstruction<P> {
+    fn eval(&self, parser: &mut AP) {
+        self.multiplication.eval(parser);
+    }
+}
+
+impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpInverseInstruction<P> {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let p_a = writer.read(&self.multiplication.a, row_index);
+
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+
+        let modulus = P::modulus();
+        let a_inv_int = a.modpow(&(&modulus - BigUint::from(2u64)), &modulus);
+        let p_a_inv = to_le_limbs_polynomial::<F, P>(&a_inv_int);
+
+        writer.write(&self.multiplication.b, &p_a_inv, row_index);
+
+        self.multiplication.write(writer, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let p_a = writer.read(&self.multiplication.a);
+
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+
+        let modulus = P::modulus();
+        let a_inv_int = a.modpow(&(&modulus - BigUint::from(2u64)), &modulus);
+        let p_a_inv = to_le_limbs_polynomial::<F, P>(&a_inv_int);
+
+        writer.write(&self.multiplication.b, &p_a_inv);
+
+        self.multiplication.write_to_air(writer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::field::parameters::tests::Fp25519;
+    use crate::polynomial::Polynomial;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpInverseTest;
+
+    impl AirParameters for FpInverseTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 124;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 195;
+
+        type Instruction = FpInverseInstruction<Fp25519>;
+    }
+
+    #[test]
+    fn test_fpinverse() {
+        type F = GoldilocksField;
+        type L = FpInverseTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Fp25519;
+
+        let p = Fp25519::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let _ = builder.fp_inverse(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let a_inv = builder.fp_inverse(&a);
+        let a_inv_expected = builder.alloc::<FieldRegister<P>>();
+        builder.assert_equal(&a_inv, &a_inv_expected);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            let a_int = rng.gen_biguint_range(&BigUint::from(1u32), &p);
+            let a_inv_int = a_int.modpow(&(&p - BigUint::from(2u32)), &p);
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+            let p_a_inv = Polynomial::<F>::from_biguint_field(&a_inv_int, 16, 16);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&a_inv_expected, &p_a_inv, i);
+            writer.write(&a_pub, &p_a, i);
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
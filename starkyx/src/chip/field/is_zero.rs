@@ -0,0 +1,312 @@
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::mul::FpMulInstruction;
+use super::parameters::FieldParameters;
+use super::register::FieldRegister;
+use super::sub::FpSubInstruction;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::limbs_to_biguint;
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
+
+/// Fp Zero Test. Computes the bit `is_zero`, equal to one if and only if `a = 0 mod p`.
+///
+/// This is done by witnessing the inverse `inv` of `a`, set to zero if `a` is zero, and then
+/// constraining that
+///
+/// a * inv = 1 - is_zero,
+/// a * is_zero = 0.
+///
+/// The field registers `not_zero` and `is_zero_field` hold the values `1 - is_zero` and `is_zero`
+/// as field elements.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FpIsZeroInstruction<P: FieldParameters> {
+    pub is_zero: BitRegister,
+    is_zero_field: FieldRegister<P>,
+    /// a `FpMulInstruction` to compute `a * inv = not_zero`.
+    inverse: FpMulInstruction<P>,
+    /// a `FpMulInstruction` to compute `a * is_zero_field = 0`.
+    zero: FpMulInstruction<P>,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// given a field element `a`, computes a bit which is one if and only if `a` is zero.
+    pub fn fp_is_zero<P: FieldParameters>(&mut self, a: &FieldRegister<P>) -> BitRegister
+    where
+        L::Instruction: From<FpIsZeroInstruction<P>>,
+    {
+        let is_trace = a.is_trace();
+
+        let is_zero: BitRegister;
+        let is_zero_field: FieldRegister<P>;
+        let not_zero: FieldRegister<P>;
+        let inv: FieldRegister<P>;
+
+        if is_trace {
+            is_zero = self.alloc::<BitRegister>();
+            is_zero_field = self.alloc::<FieldRegister<P>>();
+            not_zero = self.alloc::<FieldRegister<P>>();
+            inv = self.alloc::<FieldRegister<P>>();
+        } else {
+            is_zero = self.alloc_public::<BitRegister>();
+            is_zero_field = self.alloc_public::<FieldRegister<P>>();
+            not_zero = self.alloc_public::<FieldRegister<P>>();
+            inv = self.alloc_public::<FieldRegister<P>>();
+        }
+
+        let zero_register = self.fp_zero();
+        let inverse = self.fp_mul_instruction(a, &inv, &not_zero, is_trace);
+        let zero = self.fp_mul_instruction(a, &is_zero_field, &zero_register, is_trace);
+
+        let instr = FpIsZeroInstruction {
+            is_zero,
+            is_zero_field,
+            inverse,
+            zero,
+        };
+
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+        is_zero
+    }
+
+    /// given two field elements `a` and `b`, computes a bit which is one if and only if `a = b`.
+    pub fn fp_is_equal<P: FieldParameters>(
+        &mut self,
+        a: &FieldRegister<P>,
+        b: &FieldRegister<P>,
+    ) -> BitRegister
+    where
+        L::Instruction: From<FpSubInstruction<P>> + From<FpIsZeroInstruction<P>>,
+    {
+        let difference = self.fp_sub(a, b);
+        self.fp_is_zero(&difference)
+    }
+
+    /// Allocates the witnesses of a `FpMulInstruction` checking that `a * b = result`, without
+    /// registering it.
+    fn fp_mul_instruction<P: FieldParameters>(
+        &mut self,
+        a: &FieldRegister<P>,
+        b: &FieldRegister<P>,
+        result: &FieldRegister<P>,
+        is_trace: bool,
+    ) -> FpMulInstruction<P> {
+        let carry: FieldRegister<P>;
+        let witness_low: ArrayRegister<U16Register>;
+        let witness_high: ArrayRegister<U16Register>;
+
+        if is_trace {
+            carry = self.alloc::<FieldRegister<P>>();
+            witness_low = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+        } else {
+            carry = self.alloc_public::<FieldRegister<P>>();
+            witness_low = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+        }
+
+        FpMulInstruction {
+            a: *a,
+            b: *b,
+            result: *result,
+            carry,
+            witness_low,
+            witness_high,
+        }
+    }
+}
+
+impl<AP: PolynomialParser, P: FieldParameters> AirConstraint<AP> for FpIsZeroInstruction<P> {
+    fn eval(&self, parser: &mut AP) {
+        let is_zero = self.is_zero.eval(parser);
+        let one = parser.one();
+        let not_zero = parser.sub(one, is_zero);
+
+        // The field registers are `is_zero` and `1 - is_zero` as integers.
+        let p_is_zero = self.is_zero_field.eval(parser);
+        let p_not_zero = self.inverse.result.eval(parser);
+        for (i, (is_zero_limb, not_zero_limb)) in p_is_zero
+            .coefficients
+            .into_iter()
+            .zip(p_not_zero.coefficients)
+            .enumerate()
+        {
+            if i == 0 {
+                let is_zero_constraint = parser.sub(is_zero_limb, is_zero);
+                parser.constraint(is_zero_constraint);
+                let not_zero_constraint = parser.sub(not_zero_limb, not_zero);
+                parser.constraint(not_zero_constraint);
+            } else {
+                parser.constraint(is_zero_limb);
+                parser.constraint(not_zero_limb);
+            }
+        }
+
+        self.inverse.eval(parser);
+        self.zero.eval(parser);
+    }
+}
+
+impl<P: FieldParameters> FpIsZeroInstruction<P> {
+    /// Computes the values of `is_zero`, `is_zero_field` and `inv` from the value of `a`.
+    fn witness<F: PrimeField64>(p_a: &Polynomial<F>) -> (F, Polynomial<F>, Polynomial<F>) {
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+
+        let modulus = P::modulus();
+        let is_zero = (&a % &modulus).is_zero();
+        let a_inv_int = if is_zero {
+            BigUint::zero()
+        } else {
+            a.modpow(&(&modulus - BigUint::from(2u64)), &modulus)
+        };
+
+        (
+            F::from_canonical_u8(is_zero as u8),
+            to_le_limbs_polynomial::<F, P>(&BigUint::from(is_zero as u8)),
+            to_le_limbs_polynomial::<F, P>(&a_inv_int),
+        )
+    }
+}
+
+impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpIsZeroInstruction<P> {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let p_a = writer.read(&self.inverse.a, row_index);
+        let (is_zero, p_is_zero, p_a_inv) = Self::witness(&p_a);
+
+        writer.write(&self.is_zero, &is_zero, row_index);
+        writer.write(&self.is_zero_field, &p_is_zero, row_index);
+        writer.write(&self.inverse.b, &p_a_inv, row_index);
+
+        self.inverse.write(writer, row_index);
+        self.zero.write(writer, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let p_a = writer.read(&self.inverse.a);
+        let (is_zero, p_is_zero, p_a_inv) = Self::witness(&p_a);
+
+        writer.write(&self.is_zero, &is_zero);
+        writer.write(&self.is_zero_field, &p_is_zero);
+        writer.write(&self.inverse.b, &p_a_inv);
+
+        self.inverse.write_to_air(writer);
+        self.zero.write_to_air(writer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::field::instruction::FpInstruction;
+    use crate::chip::field::parameters::tests::Fp25519;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpIsZeroTest;
+
+    impl AirParameters for FpIsZeroTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 524;
+        const NUM_FREE_COLUMNS: usize = 4;
+        const EXTENDED_COLUMNS: usize = 795;
+
+        type Instruction = FpInstruction<Fp25519>;
+    }
+
+    #[test]
+    fn test_fp_is_zero() {
+        type F = GoldilocksField;
+        type L = FpIsZeroTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Fp25519;
+
+        let p = Fp25519::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let _ = builder.fp_is_zero(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let b = builder.alloc::<FieldRegister<P>>();
+        let is_zero = builder.fp_is_zero(&a);
+        let is_equal = builder.fp_is_equal(&a, &b);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            // The modulus is a non-canonical representation of zero.
+            let a_int = match i % 4 {
+                0 => BigUint::zero(),
+                1 => p.clone(),
+                _ => rng.gen_biguint(256) % &p,
+            };
+            let b_int = if i % 3 == 0 {
+                a_int.clone()
+            } else {
+                rng.gen_biguint(256) % &p
+            };
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+            let p_b = Polynomial::<F>::from_biguint_field(&b_int, 16, 16);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&b, &p_b, i);
+            writer.write(&a_pub, &p_a, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            let a_is_zero = (&a_int % &p).is_zero();
+            assert_eq!(
+                writer.read(&is_zero, i),
+                F::from_canonical_u8(a_is_zero as u8)
+            );
+            let a_is_b = (&a_int % &p) == (&b_int % &p);
+            assert_eq!(
+                writer.read(&is_equal, i),
+                F::from_canonical_u8(a_is_b as u8)
+            );
+        }
+
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
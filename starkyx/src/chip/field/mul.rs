@@ -10,11 +10,11 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::u16::U16Register;
 use crate::chip::register::{Register, RegisterSerializable};
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
-use crate::chip::utils::{digits_to_biguint, split_u32_limbs_to_u16_limbs};
+use crate::chip::utils::{limbs_to_biguint, split_witness_limbs};
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::{to_u16_le_limbs_polynomial, Polynomial};
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(bound = "")]
@@ -64,6 +64,7 @@ impl<L: AirParameters> AirBuilder<L> {
             witness_high,
         };
 
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr);
         } else {
@@ -114,8 +115,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpMulInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let a = digits_to_biguint(&a_digits);
-        let b = digits_to_biguint(&b_digits);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         // Compute field multiplication in the integers.
         let modulus = P::modulus();
@@ -126,17 +127,21 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpMulInstruction<P>
         debug_assert_eq!(&carry * &modulus, a * b - &result);
 
         // Make little endian polynomial limbs.
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(&modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(&modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let p_vanishing = &p_a * &p_b - &p_result - &p_carry * &p_modulus;
         debug_assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result, row_index);
         writer.write(&self.carry, &p_carry, row_index);
@@ -159,8 +164,8 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpMulInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let a = digits_to_biguint(&a_digits);
-        let b = digits_to_biguint(&b_digits);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
 
         // Compute field multiplication in the integers.
         let modulus = P::modulus();
@@ -171,17 +176,21 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpMulInstruction<P>
         debug_assert_eq!(&carry * &modulus, a * b - &result);
 
         // Make little endian polynomial limbs.
-        let p_modulus = to_u16_le_limbs_polynomial::<F, P>(&modulus);
-        let p_result = to_u16_le_limbs_polynomial::<F, P>(&result);
-        let p_carry = to_u16_le_limbs_polynomial::<F, P>(&carry);
+        let p_modulus = to_le_limbs_polynomial::<F, P>(&modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
 
         // Compute the vanishing polynomial.
         let p_vanishing = &p_a * &p_b - &p_result - &p_carry * &p_modulus;
         debug_assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
 
         // Compute the witness.
-        let p_witness = util::compute_root_quotient_and_shift(&p_vanishing, P::WITNESS_OFFSET);
-        let (p_witness_low, p_witness_high) = split_u32_limbs_to_u16_limbs(&p_witness);
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
 
         writer.write(&self.result, &p_result);
         writer.write(&self.carry, &p_carry);
@@ -198,7 +207,7 @@ mod tests {
 
     use super::*;
     use crate::chip::builder::tests::*;
-    use crate::chip::field::parameters::tests::Fp25519;
+    use crate::chip::field::parameters::tests::{Fp25519, FpGoldilocks12};
 
     #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
     struct FpMulTest;
@@ -214,6 +223,21 @@ mod tests {
         type Instruction = FpMulInstruction<Fp25519>;
     }
 
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpMulSmallLimbsTest;
+
+    impl AirParameters for FpMulSmallLimbsTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 44;
+        const NUM_FREE_COLUMNS: usize = 2;
+        const EXTENDED_COLUMNS: usize = 75;
+        const RANGE_CHECK_BITS: usize = 12;
+
+        type Instruction = FpMulInstruction<FpGoldilocks12>;
+    }
+
     #[test]
     fn test_fpmul() {
         type F = GoldilocksField;
@@ -279,4 +303,107 @@ mod tests {
         // Test the recursive proof.
         test_recursive_starky(stark, config, generator, &public);
     }
+
+    #[test]
+    fn test_fpmul_small_limbs() {
+        type F = GoldilocksField;
+        type L = FpMulSmallLimbsTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = FpGoldilocks12;
+
+        let p = P::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let b_pub = builder.alloc_public::<FieldRegister<P>>();
+        let _ = builder.fp_mul(&a_pub, &b_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let b = builder.alloc::<FieldRegister<P>>();
+        let _ = builder.fp_mul(&a, &b);
+
+        let (air, trace_data) = builder.build();
+        // The range check table is given by the rows, so the trace has `2^12` rows.
+        let num_rows = 1 << L::RANGE_CHECK_BITS;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            let a_int = match i % 4 {
+                0 => &p - 1u32,
+                _ => rng.gen_biguint_below(&p),
+            };
+            let b_int = rng.gen_biguint_below(&p);
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 12, 6);
+            let p_b = Polynomial::<F>::from_biguint_field(&b_int, 12, 6);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&b, &p_b, i);
+            writer.write(&a_pub, &p_a, i);
+            writer.write(&b_pub, &p_b, i);
+
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fpmul_small_limbs_out_of_range() {
+        type F = GoldilocksField;
+        type L = FpMulSmallLimbsTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = FpGoldilocks12;
+
+        let p = P::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let b = builder.alloc::<FieldRegister<P>>();
+        let _ = builder.fp_mul(&a, &b);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << L::RANGE_CHECK_BITS;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        let writer = generator.new_writer();
+        for i in 0..num_rows {
+            // The first limb of `a` is `2^12`, which is out of the range of the limbs.
+            let p_a = match i {
+                0 => {
+                    let mut coefficients = vec![F::ZERO; P::NB_LIMBS];
+                    coefficients[0] = F::from_canonical_u32(1 << 12);
+                    Polynomial::from_coefficients(coefficients)
+                }
+                _ => Polynomial::<F>::from_biguint_field(&rng.gen_biguint_below(&p), 12, 6),
+            };
+            let b_int = rng.gen_biguint_below(&p);
+            let p_b = Polynomial::<F>::from_biguint_field(&b_int, 12, 6);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&b, &p_b, i);
+
+            writer.write_row_instructions(&generator.air_data, i);
+        }
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        test_starky(&stark, &config, &generator, &public);
+    }
 }
@@ -12,16 +12,22 @@ pub const LIMB: u32 = 2u32.pow(16);
 pub trait FieldParameters:
     Send + Sync + Copy + 'static + Debug + Serialize + DeserializeOwned + Default
 {
+    /// The number of bits of the limbs, at most 16.
+    ///
+    /// The limbs are range checked by the arithmetic columns of the AIR, whose range check table
+    /// must have `2^NB_BITS_PER_LIMB` entries.
     const NB_BITS_PER_LIMB: usize;
     const NB_LIMBS: usize;
     const NB_WITNESS_LIMBS: usize;
     const MODULUS: [u16; MAX_NB_LIMBS];
+    /// The offset of the coefficients of the witness polynomials, which must be less than
+    /// `2^(2 * NB_BITS_PER_LIMB)` after the shift.
     const WITNESS_OFFSET: usize;
 
     fn modulus() -> BigUint {
         let mut modulus = BigUint::zero();
         for (i, limb) in Self::MODULUS.iter().enumerate() {
-            modulus += BigUint::from(*limb) << (16 * i);
+            modulus += BigUint::from(*limb) << (Self::NB_BITS_PER_LIMB * i);
         }
         modulus
     }
@@ -59,4 +65,27 @@ pub mod tests {
             (BigUint::one() << 255) - BigUint::from(19u32)
         }
     }
+
+    /// The Goldilocks field with 12-bit limbs.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct FpGoldilocks12;
+
+    impl FieldParameters for FpGoldilocks12 {
+        const NB_BITS_PER_LIMB: usize = 12;
+        const NB_LIMBS: usize = 6;
+        const NB_WITNESS_LIMBS: usize = 2 * Self::NB_LIMBS - 2;
+        const MODULUS: [u16; MAX_NB_LIMBS] = [
+            1, 0, 3840, 4095, 4095, 15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0,
+        ];
+        const WITNESS_OFFSET: usize = 1usize << 17;
+    }
+
+    #[test]
+    fn test_modulus_from_limbs() {
+        assert_eq!(
+            FpGoldilocks12::modulus(),
+            BigUint::from(0xffff_ffff_0000_0001u64)
+        );
+    }
 }
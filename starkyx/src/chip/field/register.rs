@@ -17,8 +17,9 @@ use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
 use crate::math::prelude::*;
 use crate::polynomial::Polynomial;
 
-/// A register for representing a field element. The value is decomposed into a series of U16 limbs
-/// which is controlled by `NB_LIMBS` in FieldParameters. Each limb is range checked using a lookup.
+/// A register for representing a field element. The value is decomposed into a series of limbs of
+/// `NB_BITS_PER_LIMB` bits which is controlled by `NB_LIMBS` in FieldParameters. Each limb is
+/// range checked using a lookup, against the range check table of the AIR.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct FieldRegister<P: FieldParameters> {
     register: MemorySlice,
@@ -77,7 +78,8 @@ impl<P: FieldParameters> MemoryValue for FieldRegister<P> {
             .step_by(2)
             .map(|i| {
                 let pair = limb_array.get_subarray(i..i + 2);
-                pair.get(0).expr() + pair.get(1).expr() * L::Field::from_canonical_u32(1 << 16)
+                pair.get(0).expr()
+                    + pair.get(1).expr() * L::Field::from_canonical_u32(1 << P::NB_BITS_PER_LIMB)
             })
             .chain(once(time.expr()))
             .collect::<Vec<_>>();
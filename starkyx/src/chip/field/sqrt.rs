@@ -0,0 +1,380 @@
+use num::{BigUint, One, Zero};
+use serde::{Deserialize, Serialize};
+
+use super::parameters::FieldParameters;
+use super::register::FieldRegister;
+use super::util;
+use crate::air::AirConstraint;
+use crate::chip::builder::AirBuilder;
+use crate::chip::instruction::Instruction;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::{AirWriter, TraceWriter};
+use crate::chip::utils::{limbs_to_biguint, split_witness_limbs};
+use crate::chip::AirParameters;
+use crate::math::prelude::*;
+use crate::polynomial::parser::PolynomialParser;
+use crate::polynomial::{to_le_limbs_polynomial, Polynomial};
+
+/// Fp Square Root. Computes `sqrt(a) = result` if `a` is a square, and `sqrt(n * a) = result`
+/// otherwise, where `n` is the smallest quadratic non-residue of the field.
+///
+/// The register `is_square` is set to one in the first case and to zero in the second. Both
+/// cases are checked by the single constraint
+///
+/// result * result - m * a + (1 - is_square) * n * p - carry * p = 0,
+///
+/// where `m = is_square + (1 - is_square) * n`. The term `(1 - is_square) * n * p` keeps the
+/// carry non-negative.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FpSqrtInstruction<P: FieldParameters> {
+    pub a: FieldRegister<P>,
+    pub result: FieldRegister<P>,
+    pub is_square: BitRegister,
+    pub(crate) carry: FieldRegister<P>,
+    pub(crate) witness_low: ArrayRegister<U16Register>,
+    pub(crate) witness_high: ArrayRegister<U16Register>,
+    /// The smallest quadratic non-residue `n`, computed when the instruction is built.
+    pub(crate) non_residue: u32,
+}
+
+impl<L: AirParameters> AirBuilder<L> {
+    /// given a field element `a`, computes a square root of `a` if it is a square and of `n * a`
+    /// otherwise, together with a bit indicating whether `a` is a square.
+    ///
+    /// WARNING: The square root is not constrained to be canonical or of a given sign, and the bit
+    /// is not determined by the constraints if `a` is zero. Such checks must be done by the caller.
+    pub fn fp_sqrt<P: FieldParameters>(
+        &mut self,
+        a: &FieldRegister<P>,
+    ) -> (FieldRegister<P>, BitRegister)
+    where
+        L::Instruction: From<FpSqrtInstruction<P>>,
+    {
+        let (result, is_square) = if a.is_trace() {
+            (
+                self.alloc::<FieldRegister<P>>(),
+                self.alloc::<BitRegister>(),
+            )
+        } else {
+            (
+                self.alloc_public::<FieldRegister<P>>(),
+                self.alloc_public::<BitRegister>(),
+            )
+        };
+        self.set_fp_sqrt(a, &result, &is_square);
+        (result, is_square)
+    }
+
+    pub fn set_fp_sqrt<P: FieldParameters>(
+        &mut self,
+        a: &FieldRegister<P>,
+        result: &FieldRegister<P>,
+        is_square: &BitRegister,
+    ) where
+        L::Instruction: From<FpSqrtInstruction<P>>,
+    {
+        let is_trace = a.is_trace() || result.is_trace() || is_square.is_trace();
+
+        let carry: FieldRegister<P>;
+        let witness_low: ArrayRegister<U16Register>;
+        let witness_high: ArrayRegister<U16Register>;
+
+        if is_trace {
+            carry = self.alloc::<FieldRegister<P>>();
+            witness_low = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array::<U16Register>(P::NB_WITNESS_LIMBS);
+        } else {
+            carry = self.alloc_public::<FieldRegister<P>>();
+            witness_low = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+            witness_high = self.alloc_array_public::<U16Register>(P::NB_WITNESS_LIMBS);
+        }
+
+        let instr = FpSqrtInstruction {
+            a: *a,
+            result: *result,
+            is_square: *is_square,
+            carry,
+            witness_low,
+            witness_high,
+            non_residue: non_residue::<P>(),
+        };
+
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
+        if is_trace {
+            self.register_instruction(instr);
+        } else {
+            self.register_global_instruction(instr);
+        }
+    }
+}
+
+impl<AP: PolynomialParser, P: FieldParameters> AirConstraint<AP> for FpSqrtInstruction<P> {
+    fn eval(&self, parser: &mut AP) {
+        let n = AP::Field::from_canonical_u32(self.non_residue);
+
+        let p_a = self.a.eval(parser);
+        let p_result = self.result.eval(parser);
+        let p_carry = self.carry.eval(parser);
+        let is_square = self.is_square.eval(parser);
+
+        // m = is_square + (1 - is_square) * n
+        let is_square_one_minus_n = parser.mul_const(is_square, AP::Field::ONE - n);
+        let multiplier = parser.add_const(is_square_one_minus_n, n);
+        // (1 - is_square) * n
+        let n_is_square = parser.mul_const(is_square, n);
+        let shift = parser.sub_const(n_is_square, n);
+        let shift = parser.neg(shift);
+
+        let p_result_squared = parser.poly_mul(&p_result, &p_result);
+        let p_a_mul = parser.poly_scalar_mul(&p_a, &multiplier);
+        let p_limbs = parser.constant_poly(&Polynomial::from_iter(util::modulus_field_iter::<
+            AP::Field,
+            P,
+        >()));
+        let p_shift = parser.poly_scalar_mul(&p_limbs, &shift);
+        let p_carry_mul = parser.poly_mul(&p_carry, &p_limbs);
+
+        let p_difference = parser.poly_sub(&p_result_squared, &p_a_mul);
+        let p_difference_shifted = parser.poly_add(&p_difference, &p_shift);
+        let p_vanishing = parser.poly_sub(&p_difference_shifted, &p_carry_mul);
+
+        let p_witness_low = Polynomial::from_coefficients(self.witness_low.eval_vec(parser));
+        let p_witness_high = Polynomial::from_coefficients(self.witness_high.eval_vec(parser));
+
+        util::eval_field_operation::<AP, P>(parser, &p_vanishing, &p_witness_low, &p_witness_high)
+    }
+}
+
+impl<P: FieldParameters> FpSqrtInstruction<P> {
+    /// Computes the values of `result`, `is_square`, `carry`, `witness_low` and `witness_high`
+    /// from the value of `a`.
+    #[allow(clippy::type_complexity)]
+    fn witness<F: PrimeField64>(
+        &self,
+        p_a: &Polynomial<F>,
+    ) -> (Polynomial<F>, F, Polynomial<F>, Vec<F>, Vec<F>) {
+        let a_digits = p_a
+            .coefficients
+            .iter()
+            .map(|x| x.as_canonical_u64() as u16)
+            .collect::<Vec<_>>();
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
+
+        let modulus = P::modulus();
+        let n = self.non_residue;
+        let (result, is_square) = match sqrt::<P>(&a) {
+            Some(root) => (root, true),
+            None => (sqrt::<P>(&(&a * n)).unwrap(), false),
+        };
+        let (multiplier, shift) = if is_square { (1, 0) } else { (n, n) };
+        let carry = (&result * &result + &modulus * shift - &a * multiplier) / &modulus;
+        debug_assert_eq!(
+            &carry * &modulus + &a * multiplier,
+            &result * &result + &modulus * shift
+        );
+
+        let p_modulus = to_le_limbs_polynomial::<F, P>(&modulus);
+        let p_result = to_le_limbs_polynomial::<F, P>(&result);
+        let p_carry = to_le_limbs_polynomial::<F, P>(&carry);
+        let p_vanishing = &p_result * &p_result - p_a * F::from_canonical_u32(multiplier)
+            + &p_modulus * F::from_canonical_u32(shift)
+            - &p_carry * &p_modulus;
+        debug_assert_eq!(p_vanishing.degree(), P::NB_WITNESS_LIMBS);
+
+        let p_witness = util::compute_root_quotient_and_shift(
+            &p_vanishing,
+            P::WITNESS_OFFSET,
+            P::NB_BITS_PER_LIMB,
+        );
+        let (p_witness_low, p_witness_high) = split_witness_limbs(&p_witness, P::NB_BITS_PER_LIMB);
+
+        (
+            p_result,
+            F::from_canonical_u8(is_square as u8),
+            p_carry,
+            p_witness_low,
+            p_witness_high,
+        )
+    }
+}
+
+impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpSqrtInstruction<P> {
+    fn write(&self, writer: &TraceWriter<F>, row_index: usize) {
+        let p_a = writer.read(&self.a, row_index);
+        let (p_result, is_square, p_carry, p_witness_low, p_witness_high) = self.witness(&p_a);
+
+        writer.write(&self.result, &p_result, row_index);
+        writer.write(&self.is_square, &is_square, row_index);
+        writer.write(&self.carry, &p_carry, row_index);
+        writer.write_array(&self.witness_low, &p_witness_low, row_index);
+        writer.write_array(&self.witness_high, &p_witness_high, row_index);
+    }
+
+    fn write_to_air(&self, writer: &mut impl AirWriter<Field = F>) {
+        let p_a = writer.read(&self.a);
+        let (p_result, is_square, p_carry, p_witness_low, p_witness_high) = self.witness(&p_a);
+
+        writer.write(&self.result, &p_result);
+        writer.write(&self.is_square, &is_square);
+        writer.write(&self.carry, &p_carry);
+        writer.write_array(&self.witness_low, &p_witness_low);
+        writer.write_array(&self.witness_high, &p_witness_high);
+    }
+}
+
+/// Returns the smallest quadratic non-residue of the field.
+pub fn non_residue<P: FieldParameters>() -> u32 {
+    let modulus = P::modulus();
+    let exponent = (&modulus - 1u32) >> 1;
+    let minus_one = &modulus - 1u32;
+    (2u32..)
+        .find(|n| BigUint::from(*n).modpow(&exponent, &modulus) == minus_one)
+        .unwrap()
+}
+
+/// Returns the even square root of `a` if `a` is a square, computed with Tonelli-Shanks.
+pub fn sqrt<P: FieldParameters>(a: &BigUint) -> Option<BigUint> {
+    let modulus = P::modulus();
+    let a = a % &modulus;
+    if a.is_zero() {
+        return Some(a);
+    }
+    if !a.modpow(&((&modulus - 1u32) >> 1), &modulus).is_one() {
+        return None;
+    }
+
+    // Write p - 1 = q * 2^s with q odd.
+    let mut q = &modulus - 1u32;
+    let mut s = 0;
+    while !q.bit(0) {
+        q >>= 1;
+        s += 1;
+    }
+
+    let mut m = s;
+    let mut c = BigUint::from(non_residue::<P>()).modpow(&q, &modulus);
+    let mut t = a.modpow(&q, &modulus);
+    let mut root = a.modpow(&((&q + 1u32) >> 1), &modulus);
+    while !t.is_one() {
+        // Find the least i such that t^(2^i) = 1.
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while !t_pow.is_one() {
+            t_pow = &t_pow * &t_pow % &modulus;
+            i += 1;
+        }
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), &modulus);
+        m = i;
+        c = &b * &b % &modulus;
+        t = t * &c % &modulus;
+        root = root * &b % &modulus;
+    }
+
+    if root.bit(0) {
+        root = &modulus - root;
+    }
+    Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::builder::tests::*;
+    use crate::chip::ec::weierstrass::secp256k1::Secp256k1BaseField;
+    use crate::chip::field::parameters::tests::Fp25519;
+
+    #[derive(Clone, Debug, Copy, Serialize, Deserialize)]
+    struct FpSqrtTest;
+
+    impl AirParameters for FpSqrtTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 108;
+        const NUM_FREE_COLUMNS: usize = 3;
+        const EXTENDED_COLUMNS: usize = 171;
+
+        type Instruction = FpSqrtInstruction<Fp25519>;
+    }
+
+    #[test]
+    fn test_sqrt_pure() {
+        fn check<P: FieldParameters>() {
+            let p = P::modulus();
+            let n = BigUint::from(non_residue::<P>());
+            let mut rng = thread_rng();
+            for _ in 0..64 {
+                let a = rng.gen_biguint(256) % &p;
+                match sqrt::<P>(&a) {
+                    Some(root) => {
+                        assert!(!root.bit(0));
+                        assert_eq!(&root * &root % &p, a);
+                    }
+                    None => {
+                        let root = sqrt::<P>(&(&a * &n)).unwrap();
+                        assert_eq!(&root * &root % &p, &a * &n % &p);
+                    }
+                }
+            }
+        }
+        check::<Fp25519>();
+        check::<Secp256k1BaseField>();
+    }
+
+    #[test]
+    fn test_fpsqrt() {
+        type F = GoldilocksField;
+        type L = FpSqrtTest;
+        type SC = PoseidonGoldilocksStarkConfig;
+        type P = Fp25519;
+
+        let p = Fp25519::modulus();
+
+        let mut builder = AirBuilder::<L>::new();
+
+        let a_pub = builder.alloc_public::<FieldRegister<P>>();
+        let _ = builder.fp_sqrt(&a_pub);
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let (_, is_square) = builder.fp_sqrt(&a);
+
+        let (air, trace_data) = builder.build();
+        let num_rows = 1 << 16;
+        let generator = ArithmeticGenerator::<L>::new(trace_data, num_rows);
+
+        let mut rng = thread_rng();
+        for i in 0..num_rows {
+            let writer = generator.new_writer();
+            // Half of the field elements are non-residues.
+            let a_int = rng.gen_biguint(256) % &p;
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 16, 16);
+
+            writer.write(&a, &p_a, i);
+            writer.write(&a_pub, &p_a, i);
+            writer.write_row_instructions(&generator.air_data, i);
+
+            let expected = F::from_canonical_u8(sqrt::<P>(&a_int).is_some() as u8);
+            assert_eq!(writer.read(&is_square, i), expected);
+        }
+
+        let writer = generator.new_writer();
+        writer.write_global_instructions(&generator.air_data);
+
+        let stark = Starky::new(air);
+        let config = SC::standard_fast_config(num_rows);
+        let public = writer.public().unwrap().clone();
+
+        // Generate proof and verify as a stark
+        test_starky(&stark, &config, &generator, &public);
+
+        // Test the recursive proof.
+        test_recursive_starky(stark, config, generator, &public);
+    }
+}
@@ -10,11 +10,11 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::u16::U16Register;
 use crate::chip::register::RegisterSerializable;
 use crate::chip::trace::writer::{AirWriter, TraceWriter};
-use crate::chip::utils::digits_to_biguint;
+use crate::chip::utils::limbs_to_biguint;
 use crate::chip::AirParameters;
 use crate::math::prelude::*;
 use crate::polynomial::parser::PolynomialParser;
-use crate::polynomial::to_u16_le_limbs_polynomial;
+use crate::polynomial::to_le_limbs_polynomial;
 
 /// Fp subtraction.
 ///
@@ -77,6 +77,7 @@ impl<L: AirParameters> AirBuilder<L> {
         };
 
         let instr = FpSubInstruction { inner: inner_instr };
+        self.assert_range_check_bits(P::NB_BITS_PER_LIMB);
         if is_trace {
             self.register_instruction(instr);
         } else {
@@ -109,12 +110,12 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpSubInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let b = digits_to_biguint(&b_digits);
-        let a = digits_to_biguint(&a_digits);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
 
         let modulus = P::modulus();
         let c = (&modulus + &a - &b) % &modulus;
-        let p_c = to_u16_le_limbs_polynomial::<F, P>(&c);
+        let p_c = to_le_limbs_polynomial::<F, P>(&c);
 
         writer.write(&self.inner.a, &p_c, row_index);
 
@@ -137,12 +138,12 @@ impl<F: PrimeField64, P: FieldParameters> Instruction<F> for FpSubInstruction<P>
             .map(|x| x.as_canonical_u64() as u16)
             .collect::<Vec<_>>();
 
-        let b = digits_to_biguint(&b_digits);
-        let a = digits_to_biguint(&a_digits);
+        let b = limbs_to_biguint(&b_digits, P::NB_BITS_PER_LIMB);
+        let a = limbs_to_biguint(&a_digits, P::NB_BITS_PER_LIMB);
 
         let modulus = P::modulus();
         let c = (&modulus + &a - &b) % &modulus;
-        let p_c = to_u16_le_limbs_polynomial::<F, P>(&c);
+        let p_c = to_le_limbs_polynomial::<F, P>(&c);
 
         writer.write(&self.inner.a, &p_c);
 
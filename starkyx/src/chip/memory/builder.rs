@@ -140,6 +140,20 @@ impl<L: AirParameters> AirBuilder<L> {
         value
     }
 
+    /// Reads the value from the memory at location `ptr` into the trace register `value`.
+    pub(crate) fn get_into<V: MemoryValue>(
+        &mut self,
+        ptr: &Pointer<V>,
+        value: &V,
+        last_write_ts: &Time<L::Field>,
+    ) {
+        assert!(value.is_trace(), "Cannot read into a non-trace register");
+        let instr = MemoryInstruction::Get(GetInstruction::new(ptr.raw, *value.register(), None));
+        self.register_air_instruction_internal(AirInstruction::mem(instr));
+        let read_digest = value.compress(self, ptr.raw, last_write_ts, &ptr.challenges);
+        self.output_from_memory_bus(read_digest);
+    }
+
     fn unsafe_raw_read<V: MemoryValue>(
         &mut self,
         ptr: &Pointer<V>,
@@ -8,6 +8,7 @@ use crate::plonky2::stark::Starky;
 
 pub mod air;
 pub mod arithmetic;
+pub mod bigint;
 pub mod bool;
 pub mod builder;
 pub mod constraint;
@@ -31,10 +32,18 @@ pub trait AirParameters:
 
     /// The number of columns that need to be ranged-checked to range 0..num_rows
     ///
-    /// If NUM_ARITHMETIC_COLUMNS > 0 is used for field operations with 2^16 bit limbs
-    /// the number of rows should be 2^16.
+    /// If NUM_ARITHMETIC_COLUMNS > 0 is used for field operations with limbs of
+    /// `RANGE_CHECK_BITS` bits the number of rows must be at most `2^RANGE_CHECK_BITS`, and
+    /// should be `2^RANGE_CHECK_BITS` for all the limb values to be accepted.
     const NUM_ARITHMETIC_COLUMNS: usize = 0;
 
+    /// The number of bits of the range checks of the arithmetic columns.
+    ///
+    /// This should be the number of bits of the limbs of the field operations. With the internal
+    /// range check, the table is given by the rows, while the `EmulatedBuilder` proves the range
+    /// checks in a separate trace of `2^RANGE_CHECK_BITS` rows.
+    const RANGE_CHECK_BITS: usize = 16;
+
     /// The number of columns that are not range checked.
     const NUM_FREE_COLUMNS: usize = 0;
 
@@ -3,86 +3,142 @@ use num::{BigUint, Zero};
 use crate::math::prelude::*;
 use crate::polynomial::Polynomial;
 
-pub fn bigint_into_u16_digits(x: &BigUint, num_digits: usize) -> Vec<u16> {
-    let mut x_limbs = x
-        .iter_u32_digits()
-        .flat_map(|x| vec![x as u16, (x >> 16) as u16])
-        .collect::<Vec<_>>();
+/// Decomposes `x` into `num_limbs` little-endian limbs of `nb_bits_per_limb` bits.
+pub fn bigint_into_limbs(x: &BigUint, nb_bits_per_limb: usize, num_limbs: usize) -> Vec<u16> {
     assert!(
-        x_limbs.len() <= num_digits,
-        "Number too large to fit in {num_digits} digits"
+        (1..=16).contains(&nb_bits_per_limb),
+        "Limbs of {nb_bits_per_limb} bits are not supported"
     );
-    x_limbs.resize(num_digits, 0);
+    let mask = (1u64 << nb_bits_per_limb) - 1;
+    let mut x_limbs = Vec::with_capacity(num_limbs);
+    let mut acc = 0u64;
+    let mut acc_bits = 0;
+    for digit in x.iter_u32_digits() {
+        acc |= (digit as u64) << acc_bits;
+        acc_bits += 32;
+        while acc_bits >= nb_bits_per_limb {
+            x_limbs.push((acc & mask) as u16);
+            acc >>= nb_bits_per_limb;
+            acc_bits -= nb_bits_per_limb;
+        }
+    }
+    if acc != 0 {
+        x_limbs.push(acc as u16);
+    }
+    while x_limbs.last() == Some(&0) {
+        x_limbs.pop();
+    }
+    assert!(
+        x_limbs.len() <= num_limbs,
+        "Number too large to fit in {num_limbs} limbs"
+    );
+    x_limbs.resize(num_limbs, 0);
     x_limbs
 }
 
-pub fn biguint_to_16_digits_field<F: Field>(x: &BigUint, num_digits: usize) -> Vec<F> {
-    bigint_into_u16_digits(x, num_digits)
+pub fn bigint_into_u16_digits(x: &BigUint, num_digits: usize) -> Vec<u16> {
+    bigint_into_limbs(x, 16, num_digits)
+}
+
+pub fn biguint_to_limbs_field<F: Field>(
+    x: &BigUint,
+    nb_bits_per_limb: usize,
+    num_limbs: usize,
+) -> Vec<F> {
+    bigint_into_limbs(x, nb_bits_per_limb, num_limbs)
         .iter()
         .map(|xi| F::from_canonical_u16(*xi))
         .collect()
 }
 
-pub fn digits_to_biguint(digits: &[u16]) -> BigUint {
+#[deprecated(note = "use `biguint_to_limbs_field` with 16-bit limbs")]
+pub fn biguint_to_16_digits_field<F: Field>(x: &BigUint, num_digits: usize) -> Vec<F> {
+    biguint_to_limbs_field(x, 16, num_digits)
+}
+
+/// The integer given by the little-endian limbs of `nb_bits_per_limb` bits.
+pub fn limbs_to_biguint(limbs: &[u16], nb_bits_per_limb: usize) -> BigUint {
     let mut x = BigUint::zero();
-    for (i, &digit) in digits.iter().enumerate() {
-        x += BigUint::from(digit) << (16 * i);
+    for (i, &limb) in limbs.iter().enumerate() {
+        x += BigUint::from(limb) << (nb_bits_per_limb * i);
     }
     x
 }
 
-#[allow(dead_code)]
-pub fn field_limbs_to_biguint<F: PrimeField64>(limbs: &[F]) -> BigUint {
+pub fn digits_to_biguint(digits: &[u16]) -> BigUint {
+    limbs_to_biguint(digits, 16)
+}
+
+pub fn field_limbs_to_biguint<F: PrimeField64>(limbs: &[F], nb_bits_per_limb: usize) -> BigUint {
     let mut x = BigUint::zero();
     let digits = limbs.iter().map(|x| x.as_canonical_u64());
     for (i, digit) in digits.enumerate() {
-        x += BigUint::from(digit) << (16 * i);
+        x += BigUint::from(digit) << (nb_bits_per_limb * i);
     }
     x
 }
 
+/// Splits the coefficients of a shifted witness polynomial, which are less than
+/// `2^(2 * nb_bits_per_limb)`, into their low and high limbs of `nb_bits_per_limb` bits.
 #[inline]
-pub fn split_u32_limbs_to_u16_limbs<F: PrimeField64>(slice: &[F]) -> (Vec<F>, Vec<F>) {
+pub fn split_witness_limbs<F: PrimeField64>(
+    slice: &[F],
+    nb_bits_per_limb: usize,
+) -> (Vec<F>, Vec<F>) {
+    let mask = (1u64 << nb_bits_per_limb) - 1;
     (
         slice
             .iter()
-            .map(|x| x.as_canonical_u64() as u16)
-            .map(|x| F::from_canonical_u16(x))
+            .map(|x| x.as_canonical_u64() & mask)
+            .map(|x| F::from_canonical_u64(x))
             .collect(),
         slice
             .iter()
-            .map(|x| (x.as_canonical_u64() >> 16) as u16)
-            .map(|x| F::from_canonical_u16(x))
+            .map(|x| x.as_canonical_u64() >> nb_bits_per_limb)
+            .map(|x| {
+                debug_assert!(x <= mask, "Witness coefficient out of range");
+                F::from_canonical_u64(x)
+            })
             .collect(),
     )
 }
 
+#[deprecated(note = "use `split_witness_limbs` with 16-bit limbs")]
+#[inline]
+pub fn split_u32_limbs_to_u16_limbs<F: PrimeField64>(slice: &[F]) -> (Vec<F>, Vec<F>) {
+    split_witness_limbs(slice, 16)
+}
+
 #[inline]
 pub fn compute_root_quotient_and_shift<F: PrimeField64>(
     p_vanishing: &Polynomial<F>,
     offset: usize,
+    nb_bits_per_limb: usize,
 ) -> Vec<F> {
-    // Evaluate the vanishing polynomial at x = 2^16.
+    // Evaluate the vanishing polynomial at x = 2^nb_bits_per_limb.
     let p_vanishing_eval = p_vanishing
         .coefficients()
         .iter()
         .enumerate()
-        .map(|(i, x)| F::from_noncanonical_biguint(BigUint::from(2u32).pow(16 * i as u32)) * *x)
+        .map(|(i, x)| {
+            F::from_noncanonical_biguint(BigUint::from(2u32).pow((nb_bits_per_limb * i) as u32))
+                * *x
+        })
         .sum::<F>();
     debug_assert_eq!(p_vanishing_eval, F::ZERO);
 
-    // Compute the witness polynomial by witness(x) = vanishing(x) / (x - 2^16).
-    let root_monomial = F::from_canonical_u32(2u32.pow(16));
+    // Compute the witness polynomial by witness(x) = vanishing(x) / (x - 2^nb_bits_per_limb).
+    let root_monomial = F::from_canonical_u32(1 << nb_bits_per_limb);
     let p_quotient = p_vanishing.root_quotient(root_monomial);
     debug_assert_eq!(p_quotient.degree(), p_vanishing.degree() - 1);
 
-    // Sanity Check #1: For all i, |w_i| < 2^20 to prevent overflows.
+    // Sanity Check #1: For all i, |w_i| < offset to prevent overflows.
     let offset_u64 = offset as u64;
     for c in p_quotient.coefficients().iter() {
         debug_assert!(c.neg().as_canonical_u64() < offset_u64 || c.as_canonical_u64() < offset_u64);
     }
 
-    // Sanity Check #2: w(x) * (x - 2^16) = vanishing(x).
+    // Sanity Check #2: w(x) * (x - 2^nb_bits_per_limb) = vanishing(x).
     let x_minus_root = Polynomial::<F>::from_coefficients_slice(&[-root_monomial, F::ONE]);
     debug_assert_eq!(
         (&p_quotient * &x_minus_root).coefficients(),
@@ -145,6 +201,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bigint_into_limbs() {
+        let x = BigUint::from(0x1234567890abcdefu64);
+        let x_limbs = bigint_into_limbs(&x, 12, 6);
+        assert_eq!(x_limbs, vec![0xdef, 0xabc, 0x890, 0x567, 0x234, 0x1]);
+
+        let mut rng = thread_rng();
+        for nb_bits_per_limb in [8, 12, 13, 16] {
+            for _ in 0..100 {
+                let x = rng.gen_biguint(256);
+                let num_limbs = (256 + nb_bits_per_limb - 1) / nb_bits_per_limb;
+                let x_limbs = bigint_into_limbs(&x, nb_bits_per_limb, num_limbs);
+                assert!(x_limbs.iter().all(|limb| *limb < 1 << nb_bits_per_limb));
+
+                let x_out = limbs_to_biguint(&x_limbs, nb_bits_per_limb);
+
+                assert_eq!(x, x_out)
+            }
+        }
+    }
+
     #[test]
     fn test_into_bits_le() {
         let mut rng = thread_rng();
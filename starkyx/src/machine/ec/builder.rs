@@ -1,18 +1,25 @@
 use core::borrow::Borrow;
+use std::collections::HashMap;
 
 use itertools::Itertools;
 use log::debug;
 use plonky2::util::log2_ceil;
 
+use super::ecdsa::{field_to_scalar, ECDSASignatureRegister};
+use super::fixed_base::{FixedBaseScalarRegister, FixedBaseTable, FIXED_BASE_WINDOW_BITS};
+use super::msm::{MSMOffsets, MSMRegister, MSM_NB_BUCKETS, MSM_WINDOW_BITS};
 use super::scalar_mul::DoubleAddData;
+use crate::chip::arithmetic::expression::ArithmeticExpression;
 use crate::chip::ec::point::AffinePointRegister;
 use crate::chip::ec::scalar::ECScalarRegister;
-use crate::chip::ec::{ECInstructions, EllipticCurveAir};
+use crate::chip::ec::{ECInstructions, EllipticCurveAir, ProjectiveEllipticCurveAir};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
 use crate::chip::field::register::FieldRegister;
 use crate::chip::memory::time::Time;
 use crate::chip::register::bit::BitRegister;
 use crate::chip::register::element::ElementRegister;
-use crate::chip::register::Register;
+use crate::chip::register::{Register, RegisterSerializable};
 use crate::machine::builder::Builder;
 use crate::math::prelude::*;
 
@@ -58,6 +65,26 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
         self.select_next(flag, &true_value.y, &false_value.y, &result.y);
     }
 
+    fn select_projective_ec_point(
+        &mut self,
+        flag: BitRegister,
+        true_value: &E::ProjectivePointRegister,
+        false_value: &E::ProjectivePointRegister,
+    ) -> E::ProjectivePointRegister
+    where
+        E: ProjectiveEllipticCurveAir<Self::Parameters>,
+    {
+        let coordinates = E::projective_coordinates(true_value)
+            .into_iter()
+            .zip(E::projective_coordinates(false_value))
+            .map(|(true_coordinate, false_coordinate)| {
+                self.select(flag, &true_coordinate, &false_coordinate)
+            })
+            .collect::<Vec<_>>();
+
+        E::projective_from_coordinates(&coordinates)
+    }
+
     fn scalar_mul_batch<I, J, K>(&mut self, points: I, scalars: J, results: K)
     where
         I: IntoIterator,
@@ -66,16 +93,18 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
         I::Item: Borrow<AffinePointRegister<E>>,
         J::Item: Borrow<ECScalarRegister<E>>,
         K::Item: Borrow<AffinePointRegister<E>>,
+        E: ProjectiveEllipticCurveAir<Self::Parameters>,
         Self::Instruction: ECInstructions<E>,
     {
         let nb_scalar_bits = E::nb_scalar_bits();
         let nb_bits_log = nb_scalar_bits.ilog2();
         assert_eq!(
-            E::nb_scalar_bits(),
+            1 << nb_bits_log,
             nb_scalar_bits,
             "Scalar size must be a power of 2"
         );
         assert!(nb_bits_log > 5, "Scalar size must be at least 32 bits");
+        let nb_scalar_limbs = nb_scalar_bits / 32;
 
         let cycle_32_size = self.constant(&Self::Field::from_canonical_u32(32));
         let cycle = self.cycle(nb_bits_log as usize);
@@ -98,14 +127,14 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
                 let result = result.borrow();
 
                 // Store the EC point.
-                let time = Time::constant(256 * i);
+                let time = Time::constant(nb_scalar_bits * i);
                 self.store(&temp_x_ptr.get(i), point.x, &time, None, None, None);
                 self.store(&temp_y_ptr.get(i), point.y, &time, None, None, None);
 
                 // Store and the scalar limbs.
                 for (j, limb) in scalar.limbs.iter().enumerate() {
                     self.store(
-                        &limb_ptr.get(i * 8 + j),
+                        &limb_ptr.get(i * nb_scalar_limbs + j),
                         limb,
                         &zero,
                         Some(cycle_32_size),
@@ -128,17 +157,17 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
         // Insert dummy entries where necessary.
         let generator = self.generator();
         let mut one_scalar_limbs = vec![Self::Field::ONE];
-        one_scalar_limbs.resize(nb_scalar_bits / 32, Self::Field::ZERO);
+        one_scalar_limbs.resize(nb_scalar_limbs, Self::Field::ZERO);
         let one_limbs = self.constant_array::<ElementRegister>(&one_scalar_limbs);
         for i in num_ops..(num_ops + num_dummy_ops) {
-            let time = Time::constant(256 * i);
+            let time = Time::constant(nb_scalar_bits * i);
             self.store(&temp_x_ptr.get(i), generator.x, &time, None, None, None);
             self.store(&temp_y_ptr.get(i), generator.y, &time, None, None, None);
 
             // Store and the scalar limbs.
             for (j, limb) in one_limbs.iter().enumerate() {
                 self.store(
-                    &limb_ptr.get(i * 8 + j),
+                    &limb_ptr.get(i * nb_scalar_limbs + j),
                     limb,
                     &zero,
                     Some(cycle_32_size),
@@ -191,8 +220,609 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
         );
     }
 
+    /// Allocates a public scalar for `fixed_base_scalar_mul_batch`.
+    fn alloc_public_fixed_base_scalar(&mut self) -> FixedBaseScalarRegister<E> {
+        let bits = self.alloc_array_public::<BitRegister>(E::nb_scalar_bits());
+        FixedBaseScalarRegister::new(bits)
+    }
+
+    /// Computes `scalar * G` for a batch of public scalars, where `G` is the generator.
+    ///
+    /// Instead of doubling and adding over every bit of the scalar, each row adds the entry of a
+    /// constant table of multiples of `G` selected by a window of `FIXED_BASE_WINDOW_BITS` bits,
+    /// so that a multiplication takes `nb_scalar_bits / FIXED_BASE_WINDOW_BITS` rows. The table is
+    /// stored in memory, with the number of reads of each entry computed from the scalar bits.
+    ///
+    /// Like `scalar_mul_batch`, this can only be called once per builder and not together with
+    /// `scalar_mul_batch`.
+    fn fixed_base_scalar_mul_batch<J, K>(&mut self, scalars: J, results: K)
+    where
+        J: IntoIterator,
+        K: IntoIterator,
+        J::Item: Borrow<FixedBaseScalarRegister<E>>,
+        K::Item: Borrow<AffinePointRegister<E>>,
+        Self::Instruction: ECInstructions<E>,
+    {
+        let nb_scalar_bits = E::nb_scalar_bits();
+        let nb_windows = nb_scalar_bits / FIXED_BASE_WINDOW_BITS;
+        let nb_windows_log = nb_windows.ilog2() as usize;
+        assert_eq!(
+            1 << nb_windows_log,
+            nb_windows,
+            "Number of windows must be a power of 2"
+        );
+        let nb_digits = 1 << FIXED_BASE_WINDOW_BITS;
+
+        let cycle = self.cycle(nb_windows_log);
+
+        let index_ptr = self.uninit_slice::<ElementRegister>();
+        let table_x_ptr = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let table_y_ptr = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let x_ptr = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let y_ptr = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let zero = Time::zero();
+
+        // The number of reads of each table entry, as an expression of the scalar bits.
+        let mut reads = vec![ArithmeticExpression::<Self::Field>::zero(); nb_windows * nb_digits];
+        let num_ops = scalars
+            .into_iter()
+            .zip_eq(results)
+            .enumerate()
+            .map(|(i, (scalar, result))| {
+                let scalar = scalar.borrow();
+                let result = result.borrow();
+                assert_eq!(scalar.bits.len(), nb_scalar_bits, "Invalid scalar size");
+
+                // Store the table index of each window.
+                for j in 0..nb_windows {
+                    let window = scalar.window(j);
+                    let digit = window
+                        .iter()
+                        .enumerate()
+                        .fold(ArithmeticExpression::zero(), |acc, (k, bit)| {
+                            acc + bit.expr() * Self::Field::from_canonical_u32(1 << k)
+                        });
+                    let index = self.public_expression::<ElementRegister>(
+                        digit + Self::Field::from_canonical_usize(j * nb_digits),
+                    );
+                    self.store(
+                        &index_ptr.get(i * nb_windows + j),
+                        index,
+                        &zero,
+                        None,
+                        None,
+                        None,
+                    );
+
+                    for (d, entry_reads) in reads[j * nb_digits..(j + 1) * nb_digits]
+                        .iter_mut()
+                        .enumerate()
+                    {
+                        let is_digit = window.iter().enumerate().fold(
+                            ArithmeticExpression::one(),
+                            |acc, (k, bit)| {
+                                if (d >> k) & 1 == 1 {
+                                    acc * bit.expr()
+                                } else {
+                                    acc * bit.not_expr()
+                                }
+                            },
+                        );
+                        *entry_reads = entry_reads.clone() + is_digit;
+                    }
+                }
+
+                self.free(&x_ptr.get(i), result.x, &zero);
+                self.free(&y_ptr.get(i), result.y, &zero);
+            })
+            .count();
+
+        debug!("AIR degree before padding: {}", num_ops * nb_windows);
+        let degree_log = log2_ceil(num_ops * nb_windows);
+        assert!(degree_log < 31, "AIR degree is too large");
+        debug!("AIR degree after padding: {}", 1 << degree_log);
+        let num_dummy_ops = (1 << degree_log) / nb_windows - num_ops;
+
+        // Insert dummy entries where necessary, using the scalar one.
+        let generator = self.generator();
+        let one_indices = (0..nb_windows)
+            .map(|j| {
+                let index = j * nb_digits + (j == 0) as usize;
+                reads[index] =
+                    reads[index].clone() + Self::Field::from_canonical_usize(num_dummy_ops);
+                self.constant::<ElementRegister>(&Self::Field::from_canonical_usize(index))
+            })
+            .collect::<Vec<_>>();
+        for i in num_ops..(num_ops + num_dummy_ops) {
+            for (j, index) in one_indices.iter().enumerate() {
+                self.store(
+                    &index_ptr.get(i * nb_windows + j),
+                    *index,
+                    &zero,
+                    None,
+                    None,
+                    None,
+                );
+            }
+
+            self.free(&x_ptr.get(i), generator.x, &zero);
+            self.free(&y_ptr.get(i), generator.y, &zero);
+        }
+
+        // Store the table entries with their number of reads.
+        let table = FixedBaseTable::<E>::new(nb_windows);
+        for (index, (entry, entry_reads)) in
+            table.entries.iter().flatten().zip_eq(reads).enumerate()
+        {
+            let multiplicity = self.public_expression::<ElementRegister>(entry_reads);
+            let x = self.api().fp_constant(&entry.x);
+            let y = self.api().fp_constant(&entry.y);
+            self.store(
+                &table_x_ptr.get(index),
+                x,
+                &zero,
+                Some(multiplicity),
+                None,
+                None,
+            );
+            self.store(
+                &table_y_ptr.get(index),
+                y,
+                &zero,
+                Some(multiplicity),
+                None,
+                None,
+            );
+        }
+
+        // Load the table entry of the current window, the index of which is stored at the row.
+        let process_id = self.process_id(nb_windows, cycle.end_bit);
+        let clk = self.clk();
+        let index = self.load(&index_ptr.get_at(clk), &zero, None, None);
+        let entry_x = self.load(&table_x_ptr.get_at(index), &zero, None, None);
+        let entry_y = self.load(&table_y_ptr.get_at(index), &zero, None, None);
+        let entry = AffinePointRegister::new(entry_x, entry_y);
+
+        // Accumulate the entries, starting from the initial point at the beginning of each cycle.
+        let initial_x = self.api().fp_constant(&table.initial.x);
+        let initial_y = self.api().fp_constant(&table.initial.y);
+        let initial = AffinePointRegister::new(initial_x, initial_y);
+        let result = self.alloc_ec_point();
+        let result_next = self.add(result, entry);
+        self.set_to_expression_first_row(&result.x, initial.x.expr());
+        self.set_to_expression_first_row(&result.y, initial.y.expr());
+        self.select_next_ec_point(cycle.end_bit, &initial, &result_next, &result);
+
+        // Store the result at the end of each cycle.
+        let end_flag = Some(cycle.end_bit.as_element());
+        self.store(
+            &x_ptr.get_at(process_id),
+            result_next.x,
+            &zero,
+            end_flag,
+            None,
+            None,
+        );
+        self.store(
+            &y_ptr.get_at(process_id),
+            result_next.y,
+            &zero,
+            end_flag,
+            None,
+            None,
+        );
+    }
+
+    /// Constrains `result` to be the multi-scalar multiplication `sum_i scalar_i * point_i` of
+    /// public points and scalars, and returns the witness which the prover must write.
+    ///
+    /// The doublings are shared across all scalars using the bucket method with windows of
+    /// `MSM_WINDOW_BITS` bits: each point is added to one bucket of each window, after which the
+    /// buckets of each window are summed with their weights and the windows are combined. Every
+    /// row of the trace performs a single addition `slots[c] = slots[a] + slots[b]` between
+    /// memory slots, with the operands of each row read from a program stored in memory. The
+    /// buckets are slots which are updated in place, using the number of previous updates as the
+    /// write time.
+    ///
+    /// As the rows depend on the results of previous rows, the trace must be written in order.
+    /// Like `scalar_mul_batch`, this can only be called once per builder.
+    fn msm<I, J>(
+        &mut self,
+        points: I,
+        scalars: J,
+        result: &AffinePointRegister<E>,
+    ) -> MSMRegister<E>
+    where
+        I: IntoIterator,
+        J: IntoIterator,
+        I::Item: Borrow<AffinePointRegister<E>>,
+        J::Item: Borrow<ECScalarRegister<E>>,
+        Self::Instruction: ECInstructions<E>,
+    {
+        let nb_scalar_bits = E::nb_scalar_bits();
+        let nb_windows = nb_scalar_bits / MSM_WINDOW_BITS;
+        let nb_buckets = nb_windows * MSM_NB_BUCKETS;
+
+        let x_slots = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let y_slots = self.uninit_slice::<FieldRegister<E::BaseField>>();
+        let zero = Time::zero();
+
+        let mut constants = HashMap::<usize, ElementRegister>::new();
+        let mut constant = |builder: &mut Self, value: usize| {
+            *constants.entry(value).or_insert_with(|| {
+                builder.constant::<ElementRegister>(&Self::Field::from_canonical_usize(value))
+            })
+        };
+
+        // The program of each row, given by the slots and times of the operands `a` and `b`, the
+        // slot and time of the sum `c`, and the multiplicity of the sum.
+        let mut rows: Vec<[ElementRegister; 7]> = Vec::new();
+
+        // Store the points after the buckets, each to be read once per window.
+        let point_slots = nb_buckets;
+        let nb_windows_reg = constant(self, nb_windows);
+        let mut bits = Vec::new();
+        let mut counts = Vec::new();
+        for (i, (point, scalar)) in points.into_iter().zip_eq(scalars).enumerate() {
+            let point = point.borrow();
+            let scalar = scalar.borrow();
+            self.store(
+                &x_slots.get(point_slots + i),
+                point.x,
+                &zero,
+                Some(nb_windows_reg),
+                None,
+                None,
+            );
+            self.store(
+                &y_slots.get(point_slots + i),
+                point.y,
+                &zero,
+                Some(nb_windows_reg),
+                None,
+                None,
+            );
+
+            // Decompose the scalar limbs to bits.
+            let scalar_bits = self.alloc_array_public::<BitRegister>(nb_scalar_bits);
+            assert_eq!(
+                scalar.limbs.len() * 32,
+                nb_scalar_bits,
+                "Invalid scalar size"
+            );
+            for (j, limb) in scalar.limbs.iter().enumerate() {
+                let limb_bits = scalar_bits.get_subarray(j * 32..(j + 1) * 32);
+                let value = limb_bits
+                    .iter()
+                    .enumerate()
+                    .fold(ArithmeticExpression::zero(), |acc, (k, bit)| {
+                        acc + bit.expr() * Self::Field::from_canonical_u64(1 << k)
+                    });
+                self.assert_expression_zero(value - limb.expr());
+            }
+
+            // Add the point to the bucket of its digit in each window.
+            let scalar_counts = self.alloc_array_public::<ElementRegister>(nb_windows);
+            for j in 0..nb_windows {
+                let window =
+                    scalar_bits.get_subarray(j * MSM_WINDOW_BITS..(j + 1) * MSM_WINDOW_BITS);
+                let digit = window
+                    .iter()
+                    .enumerate()
+                    .fold(ArithmeticExpression::zero(), |acc, (k, bit)| {
+                        acc + bit.expr() * Self::Field::from_canonical_u32(1 << k)
+                    });
+                let bucket = self.public_expression::<ElementRegister>(
+                    digit + Self::Field::from_canonical_usize(j * MSM_NB_BUCKETS),
+                );
+                let count = scalar_counts.get(j);
+                let next_count =
+                    self.public_expression::<ElementRegister>(count.expr() + Self::Field::ONE);
+                rows.push([
+                    bucket,
+                    count,
+                    constant(self, point_slots + i),
+                    constant(self, 0),
+                    bucket,
+                    next_count,
+                    constant(self, 1),
+                ]);
+            }
+
+            bits.push(scalar_bits);
+            counts.push(scalar_counts);
+        }
+        let num_points = bits.len();
+
+        // Initialize the buckets and the constant slots.
+        let offsets = MSMOffsets::<E>::new(nb_windows);
+        let bucket_x = self.api().fp_constant(&offsets.bucket.x);
+        let bucket_y = self.api().fp_constant(&offsets.bucket.y);
+        for k in 0..nb_buckets {
+            self.store(&x_slots.get(k), bucket_x, &zero, None, None, None);
+            self.store(&y_slots.get(k), bucket_y, &zero, None, None, None);
+        }
+        let bucket_sizes = self.alloc_array_public::<ElementRegister>(nb_buckets);
+
+        let offset_slot = point_slots + num_points;
+        let generator_slot = offset_slot + 1;
+        let neg_generator_slot = offset_slot + 2;
+        let correction_slot = offset_slot + 3;
+        let junk_slot = offset_slot + 4;
+        let result_slot = offset_slot + 5;
+        let mut next_slot = offset_slot + 6;
+
+        // Sum the buckets of each window with their weights, using the running sums of the
+        // buckets. Each running sum is read by the next one and by the total, except for the
+        // running sum of bucket zero which only consumes the bucket.
+        let mut window_sums = Vec::with_capacity(nb_windows);
+        for j in 0..nb_windows {
+            let mut running = offset_slot;
+            let mut running_sums = Vec::with_capacity(MSM_NB_BUCKETS - 1);
+            for d in (0..MSM_NB_BUCKETS).rev() {
+                let multiplicity = if d == 0 { 0 } else { 2 };
+                rows.push([
+                    constant(self, running),
+                    constant(self, 0),
+                    constant(self, j * MSM_NB_BUCKETS + d),
+                    bucket_sizes.get(j * MSM_NB_BUCKETS + d),
+                    constant(self, next_slot),
+                    constant(self, 0),
+                    constant(self, multiplicity),
+                ]);
+                running = next_slot;
+                if d != 0 {
+                    running_sums.push(next_slot);
+                }
+                next_slot += 1;
+            }
+
+            // The total of the top window is the initial accumulator, read by its doubling.
+            let mut total = running_sums[0];
+            for (k, running_sum) in running_sums.iter().enumerate().skip(1) {
+                let is_last = k == running_sums.len() - 1;
+                let multiplicity = if is_last && j == nb_windows - 1 { 2 } else { 1 };
+                rows.push([
+                    constant(self, total),
+                    constant(self, 0),
+                    constant(self, *running_sum),
+                    constant(self, 0),
+                    constant(self, next_slot),
+                    constant(self, 0),
+                    constant(self, multiplicity),
+                ]);
+                total = next_slot;
+                next_slot += 1;
+            }
+            window_sums.push(total);
+        }
+
+        // Combine the windows from the top, doubling with `2A = (A + G) + (A - G)` to avoid adding
+        // equal points.
+        let mut acc = window_sums[nb_windows - 1];
+        for (j, window_sum) in window_sums.iter().enumerate().rev().skip(1) {
+            for k in 0..MSM_WINDOW_BITS {
+                let sum_slot = next_slot;
+                let diff_slot = next_slot + 1;
+                let double_slot = next_slot + 2;
+                next_slot += 3;
+                for (slot, generator) in
+                    [(sum_slot, generator_slot), (diff_slot, neg_generator_slot)]
+                {
+                    rows.push([
+                        constant(self, acc),
+                        constant(self, 0),
+                        constant(self, generator),
+                        constant(self, 0),
+                        constant(self, slot),
+                        constant(self, 0),
+                        constant(self, 1),
+                    ]);
+                }
+                let multiplicity = if k == MSM_WINDOW_BITS - 1 { 1 } else { 2 };
+                rows.push([
+                    constant(self, sum_slot),
+                    constant(self, 0),
+                    constant(self, diff_slot),
+                    constant(self, 0),
+                    constant(self, double_slot),
+                    constant(self, 0),
+                    constant(self, multiplicity),
+                ]);
+                acc = double_slot;
+            }
+
+            let multiplicity = if j == 0 { 1 } else { 2 };
+            rows.push([
+                constant(self, acc),
+                constant(self, 0),
+                constant(self, *window_sum),
+                constant(self, 0),
+                constant(self, next_slot),
+                constant(self, 0),
+                constant(self, multiplicity),
+            ]);
+            acc = next_slot;
+            next_slot += 1;
+        }
+
+        // Cancel the offsets of the buckets.
+        rows.push([
+            constant(self, acc),
+            constant(self, 0),
+            constant(self, correction_slot),
+            constant(self, 0),
+            constant(self, result_slot),
+            constant(self, 0),
+            constant(self, 1),
+        ]);
+        self.free(&x_slots.get(result_slot), result.x, &zero);
+        self.free(&y_slots.get(result_slot), result.y, &zero);
+
+        debug!("AIR degree before padding: {}", rows.len());
+        let degree_log = log2_ceil(rows.len());
+        assert!(degree_log < 31, "AIR degree is too large");
+        debug!("AIR degree after padding: {}", 1 << degree_log);
+        let num_dummy_rows = (1 << degree_log) - rows.len();
+
+        // Insert dummy rows where necessary, which add two constant points and discard the sum.
+        for _ in 0..num_dummy_rows {
+            rows.push([
+                constant(self, generator_slot),
+                constant(self, 0),
+                constant(self, offset_slot),
+                constant(self, 0),
+                constant(self, junk_slot),
+                constant(self, 0),
+                constant(self, 0),
+            ]);
+        }
+
+        // Store the constant points, each with its number of reads.
+        let generator = self.generator();
+        let neg_generator = E::ec_neg(&E::ec_generator());
+        let neg_generator_x = self.api().fp_constant(&neg_generator.x);
+        let neg_generator_y = self.api().fp_constant(&neg_generator.y);
+        let correction_x = self.api().fp_constant(&offsets.correction.x);
+        let correction_y = self.api().fp_constant(&offsets.correction.y);
+        let nb_doublings = (nb_windows - 1) * MSM_WINDOW_BITS;
+        for (slot, x, y, reads) in [
+            (offset_slot, bucket_x, bucket_y, nb_windows + num_dummy_rows),
+            (
+                generator_slot,
+                generator.x,
+                generator.y,
+                nb_doublings + num_dummy_rows,
+            ),
+            (
+                neg_generator_slot,
+                neg_generator_x,
+                neg_generator_y,
+                nb_doublings,
+            ),
+            (correction_slot, correction_x, correction_y, 1),
+        ] {
+            let multiplicity = constant(self, reads);
+            self.store(&x_slots.get(slot), x, &zero, Some(multiplicity), None, None);
+            self.store(&y_slots.get(slot), y, &zero, Some(multiplicity), None, None);
+        }
+
+        // Store the program.
+        let program = [(); 7].map(|_| self.uninit_slice::<ElementRegister>());
+        for (r, row) in rows.iter().enumerate() {
+            for (ptr, value) in program.iter().zip(row) {
+                self.store(&ptr.get(r), *value, &zero, None, None, None);
+            }
+        }
+
+        // Load the program of the current row and perform the addition.
+        let clk = self.clk();
+        let [a, a_time, b, b_time, c, c_time, multiplicity] =
+            program.map(|ptr| self.load(&ptr.get_at(clk), &zero, None, None));
+        let a_time = Time::from_element(a_time);
+        let b_time = Time::from_element(b_time);
+        let c_time = Time::from_element(c_time);
+        let lhs_x = self.load(&x_slots.get_at(a), &a_time, None, None);
+        let lhs_y = self.load(&y_slots.get_at(a), &a_time, None, None);
+        let rhs_x = self.load(&x_slots.get_at(b), &b_time, None, None);
+        let rhs_y = self.load(&y_slots.get_at(b), &b_time, None, None);
+        let lhs = AffinePointRegister::new(lhs_x, lhs_y);
+        let rhs = AffinePointRegister::new(rhs_x, rhs_y);
+        let sum = self.add(lhs, rhs);
+        self.store(
+            &x_slots.get_at(c),
+            sum.x,
+            &c_time,
+            Some(multiplicity),
+            None,
+            None,
+        );
+        self.store(
+            &y_slots.get_at(c),
+            sum.y,
+            &c_time,
+            Some(multiplicity),
+            None,
+            None,
+        );
+
+        MSMRegister::new(bits, counts, bucket_sizes)
+    }
+
+    /// Allocates the public inputs of an ECDSA signature verification.
+    fn alloc_public_ecdsa_signature<S: FieldParameters>(&mut self) -> ECDSASignatureRegister<E, S> {
+        ECDSASignatureRegister {
+            message_hash: self.alloc_public(),
+            r: self.alloc_public(),
+            s: self.alloc_public(),
+            public_key: self.alloc_public_ec_point(),
+            u_1_g: self.alloc_public_ec_point(),
+            u_2_q: self.alloc_public_ec_point(),
+        }
+    }
+
+    /// Verifies a batch of ECDSA signatures, where `S` is the scalar field of the curve.
+    ///
+    /// For each signature `(r, s)` of a message hash `z` under the public key `Q`, this computes
+    /// `u_1 = z / s` and `u_2 = r / s` in the scalar field and checks that the x-coordinate of
+    /// `u_1 * G + u_2 * Q` is equal to `r` modulo the group order. The scalar multiplications of
+    /// all signatures are done in a single `scalar_mul_batch`, so this can only be called once
+    /// per builder and not together with `scalar_mul_batch`.
+    ///
+    /// The public keys are not checked to be on the curve, which can be done separately with
+    /// `sw_assert_valid`.
+    fn ecdsa_verify_batch<S: FieldParameters>(
+        &mut self,
+        signatures: &[ECDSASignatureRegister<E, S>],
+    ) where
+        E: ProjectiveEllipticCurveAir<Self::Parameters>,
+        Self::Instruction: ECInstructions<E> + FromFieldInstruction<S>,
+    {
+        assert_eq!(
+            S::NB_LIMBS,
+            E::BaseField::NB_LIMBS,
+            "The base and scalar fields must have the same number of limbs"
+        );
+
+        let one = self.api().fp_one::<S>();
+        let generator = self.generator();
+
+        let mut points = Vec::with_capacity(2 * signatures.len());
+        let mut scalars = Vec::with_capacity(2 * signatures.len());
+        let mut results = Vec::with_capacity(2 * signatures.len());
+        for signature in signatures {
+            // The signature must be canonical, with `r` and `s` in `[1, n - 1]`, as otherwise
+            // `(r + n, s)` would also be accepted. The inverse of `s` also proves that `s` is
+            // nonzero, and `r` is checked similarly.
+            self.api().fp_assert_canonical(&signature.r);
+            self.api().fp_assert_canonical(&signature.s);
+            let s_inv = self.api().fp_div(&one, &signature.s);
+            self.api().fp_div(&one, &signature.r);
+
+            let u_1 = self.api().fp_mul(&signature.message_hash, &s_inv);
+            let u_2 = self.api().fp_mul(&signature.r, &s_inv);
+
+            points.push(generator);
+            scalars.push(field_to_scalar::<Self, E, S>(self, &u_1));
+            results.push(signature.u_1_g);
+            points.push(signature.public_key);
+            scalars.push(field_to_scalar::<Self, E, S>(self, &u_2));
+            results.push(signature.u_2_q);
+
+            // Reduce the canonical x-coordinate of `u_1 * G + u_2 * Q` modulo the group order.
+            let point = self.add(signature.u_1_g, signature.u_2_q);
+            self.api().fp_assert_canonical(&point.x);
+            let x = FieldRegister::<S>::from_register_unsafe(*point.x.register());
+            let x_mod_n = self.api().fp_reduce(&x);
+            self.assert_equal(&x_mod_n, &signature.r);
+        }
+
+        self.scalar_mul_batch(&points, &scalars, &results);
+    }
+
     fn double_and_add(&mut self, data: &DoubleAddData<E>) -> AffinePointRegister<E>
     where
+        E: ProjectiveEllipticCurveAir<Self::Parameters>,
         Self::Instruction: ECInstructions<E>,
     {
         // Keep track of whether res is the identity, which is the point at infinity for some
@@ -238,27 +868,39 @@ pub trait EllipticCurveBuilder<E: EllipticCurveAir<Self::Parameters>>: Builder {
             None,
         );
 
-        // Allocate the intermeddiate result.
-        let result = self.alloc_ec_point();
-
-        // Calculate res_next = res + temp if scalar_bit is 1, otherwise res_next = res.
-        let addend = self.select_ec_point(is_res_valid, &result, &temp_next);
-        let sum = self.add(&temp, &addend);
+        // Allocate the intermediate result, kept in projective coordinates across the rows.
+        let temp_projective = E::ec_to_projective_air(self.api(), &temp);
+        let coordinates = E::projective_coordinates(&temp_projective)
+            .iter()
+            .map(|_| self.alloc::<FieldRegister<E::BaseField>>())
+            .collect::<Vec<_>>();
+        let result = E::projective_from_coordinates(&coordinates);
 
-        let res_plus_temp = self.select_ec_point(is_res_valid, &sum, &temp);
-        let result_next = self.select_ec_point(scalar_bit, &res_plus_temp, &result);
+        // Calculate res_next = res + temp if scalar_bit is 1, otherwise res_next = res. The
+        // projective addition has no field division, so the sum can be computed even when res is
+        // not valid.
+        let sum = E::ec_projective_add_air(self.api(), &result, &temp_projective);
 
-        let zero_field = self.zero::<FieldRegister<E::BaseField>>();
-        let dummy_point = AffinePointRegister::new(zero_field, zero_field);
+        let res_plus_temp = self.select_projective_ec_point(is_res_valid, &sum, &temp_projective);
+        let result_next = self.select_projective_ec_point(scalar_bit, &res_plus_temp, &result);
 
-        // Constrain the intermediate result to be (0, 0) in the first row, and at each transition
+        // Constrain the intermediate result to be zero in the first row, and at each transition
         // constrain the result to be equal to `result_next` during each scalar-mul cycle and back
-        // to the dummy point (0, 0) at the beginning of each cycle.
-        self.set_to_expression_first_row(&result.x, zero_field.expr());
-        self.set_to_expression_first_row(&result.y, zero_field.expr());
-        self.select_next_ec_point(end_bit, &dummy_point, &result_next, &result);
+        // to zero at the beginning of each cycle.
+        let zero_field = self.zero::<FieldRegister<E::BaseField>>();
+        for (register, next_value) in E::projective_coordinates(&result)
+            .into_iter()
+            .zip(E::projective_coordinates(&result_next))
+        {
+            self.set_to_expression_first_row(&register, zero_field.expr());
+            self.select_next(end_bit, &zero_field, &next_value, &register);
+        }
 
-        result_next
+        // Convert `result_next` back to affine coordinates. Only the value at the end of each cycle
+        // is used, so `temp` is converted instead in the other rows, where `result_next` may not
+        // be valid yet.
+        let result_last = self.select_projective_ec_point(end_bit, &result_next, &temp_projective);
+        E::ec_projective_to_affine_air(self.api(), &result_last)
     }
 }
 
@@ -280,10 +922,17 @@ mod tests {
     use super::*;
     use crate::chip::ec::edwards::ed25519::params::Ed25519;
     use crate::chip::ec::gadget::EllipticCurveAirWriter;
+    use crate::chip::ec::weierstrass::bls12_381::Bls12381;
+    use crate::chip::ec::weierstrass::p256::instruction::P256Instruction;
+    use crate::chip::ec::weierstrass::p256::tests::rfc6979_vectors;
+    use crate::chip::ec::weierstrass::p256::P256ScalarField;
+    use crate::chip::ec::weierstrass::secp256k1::instruction::Secp256k1Instruction;
+    use crate::chip::ec::weierstrass::secp256k1::{Secp256k1, Secp256k1ScalarField};
     use crate::chip::ec::{ECInstruction, EllipticCurve};
     use crate::chip::trace::writer::data::AirWriterData;
     use crate::chip::trace::writer::AirWriter;
     use crate::chip::AirParameters;
+    use crate::machine::ec::ecdsa::ecdsa_verify;
     use crate::machine::emulated::builder::EmulatedBuilder;
     use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
     use crate::maybe_rayon::*;
@@ -298,9 +947,9 @@ mod tests {
 
         type Instruction = ECInstruction<Ed25519>;
 
-        const NUM_ARITHMETIC_COLUMNS: usize = 1632;
+        const NUM_ARITHMETIC_COLUMNS: usize = 2496;
         const NUM_FREE_COLUMNS: usize = 19;
-        const EXTENDED_COLUMNS: usize = 2502;
+        const EXTENDED_COLUMNS: usize = 3798;
     }
 
     #[test]
@@ -415,4 +1064,429 @@ mod tests {
 
         timing.print();
     }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Bls12381ScalarMulTest;
+
+    impl AirParameters for Bls12381ScalarMulTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = ECInstruction<Bls12381>;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 5936;
+        const NUM_FREE_COLUMNS: usize = 19;
+        const EXTENDED_COLUMNS: usize = 9000;
+    }
+
+    #[test]
+    fn test_bls12_381_scalar_mul() {
+        type F = GoldilocksField;
+        type L = Bls12381ScalarMulTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type E = Bls12381;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("BLS12-381 G1 scalar mul", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        let num_ops = 2;
+        let nb_scalar_limbs = E::nb_scalar_bits() / 32;
+
+        let points = (0..num_ops)
+            .map(|_| builder.alloc_public_ec_point())
+            .collect::<Vec<_>>();
+
+        let scalars = (0..num_ops)
+            .map(|_| builder.alloc_array_public::<ElementRegister>(nb_scalar_limbs))
+            .map(ECScalarRegister::<E>::new)
+            .collect::<Vec<_>>();
+
+        let results = (0..num_ops)
+            .map(|_| builder.alloc_public_ec_point())
+            .collect::<Vec<_>>();
+
+        builder.scalar_mul_batch(&points, &scalars, &results);
+
+        let num_rows = 1 << log2_ceil(num_ops * E::nb_scalar_bits());
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let order = E::prime_group_order();
+        let mut rng = thread_rng();
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+        for ((point_reg, scalar_reg), result_reg) in
+            points.iter().zip(scalars.iter()).zip(results.iter())
+        {
+            let point = E::ec_generator() * rng.gen_biguint(256);
+            let scalar = rng.gen_biguint(256) % &order;
+            let result = &point * &scalar;
+
+            writer.write_ec_point(point_reg, &point);
+            writer.write_ec_point(result_reg, &result);
+
+            let mut limb_values = scalar.to_u32_digits();
+            limb_values.resize(nb_scalar_limbs, 0);
+            for (limb_reg, limb) in scalar_reg.limbs.iter().zip_eq(limb_values) {
+                writer.write(&limb_reg, &F::from_canonical_u32(limb));
+            }
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        writer_data.chunks_par(256).for_each(|mut chunk| {
+            for i in 0..256 {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+        stark.verify(proof, &public).unwrap();
+
+        timing.print();
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Secp256k1FixedBaseScalarMulTest;
+
+    impl AirParameters for Secp256k1FixedBaseScalarMulTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = ECInstruction<Secp256k1>;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 984;
+        const NUM_FREE_COLUMNS: usize = 10;
+        const EXTENDED_COLUMNS: usize = 1527;
+    }
+
+    #[test]
+    fn test_fixed_base_scalar_mul() {
+        type L = Secp256k1FixedBaseScalarMulTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type E = Secp256k1;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("Secp256k1 fixed-base scalar mul", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        // The number of multiplications is not a power of two, so that dummy ones are inserted.
+        let num_ops = 3;
+
+        let scalars = (0..num_ops)
+            .map(|_| builder.alloc_public_fixed_base_scalar())
+            .collect::<Vec<_>>();
+
+        let results = (0..num_ops)
+            .map(|_| builder.alloc_public_ec_point())
+            .collect::<Vec<_>>();
+
+        builder.fixed_base_scalar_mul_batch(&scalars, &results);
+
+        let nb_windows = E::nb_scalar_bits() / FIXED_BASE_WINDOW_BITS;
+        let num_rows = 1 << log2_ceil(num_ops * nb_windows);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let order = E::prime_group_order();
+        let mut rng = thread_rng();
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+        for (scalar_reg, result_reg) in scalars.iter().zip_eq(results.iter()) {
+            let scalar = rng.gen_biguint(256) % &order;
+            let result = E::ec_generator() * scalar.clone();
+
+            scalar_reg.write(&mut writer, &scalar);
+            writer.write_ec_point(result_reg, &result);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        writer_data.chunks_par(256).for_each(|mut chunk| {
+            for i in 0..256 {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+        stark.verify(proof, &public).unwrap();
+
+        timing.print();
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Secp256k1MSMTest;
+
+    impl AirParameters for Secp256k1MSMTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = ECInstruction<Secp256k1>;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 984;
+        const NUM_FREE_COLUMNS: usize = 10;
+        const EXTENDED_COLUMNS: usize = 1563;
+    }
+
+    #[test]
+    fn test_msm() {
+        type F = GoldilocksField;
+        type L = Secp256k1MSMTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type E = Secp256k1;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("Secp256k1 MSM", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        let num_points = 2;
+        let nb_scalar_limbs = E::nb_scalar_bits() / 32;
+
+        let points = (0..num_points)
+            .map(|_| builder.alloc_public_ec_point())
+            .collect::<Vec<_>>();
+
+        let scalars = (0..num_points)
+            .map(|_| builder.alloc_array_public::<ElementRegister>(nb_scalar_limbs))
+            .map(ECScalarRegister::<E>::new)
+            .collect::<Vec<_>>();
+
+        let result = builder.alloc_public_ec_point();
+
+        let msm = builder.msm(&points, &scalars, &result);
+
+        let nb_windows = E::nb_scalar_bits() / MSM_WINDOW_BITS;
+        let num_rows = 1
+            << log2_ceil(
+                num_points * nb_windows
+                    + nb_windows * (2 * MSM_NB_BUCKETS - 2)
+                    + (nb_windows - 1) * (3 * MSM_WINDOW_BITS + 1)
+                    + 1,
+            );
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let order = E::prime_group_order();
+        let mut rng = thread_rng();
+
+        let point_values = (0..num_points)
+            .map(|_| E::ec_generator() * rng.gen_biguint(256))
+            .collect::<Vec<_>>();
+        let scalar_values = (0..num_points)
+            .map(|_| rng.gen_biguint(256) % &order)
+            .collect::<Vec<_>>();
+        let result_value = point_values
+            .iter()
+            .zip(scalar_values.iter())
+            .map(|(point, scalar)| point * scalar)
+            .reduce(|acc, point| E::ec_add(&acc, &point))
+            .unwrap();
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+        for ((point_reg, scalar_reg), (point, scalar)) in points
+            .iter()
+            .zip(scalars.iter())
+            .zip(point_values.iter().zip(scalar_values.iter()))
+        {
+            writer.write_ec_point(point_reg, point);
+
+            let mut limb_values = scalar.to_u32_digits();
+            limb_values.resize(nb_scalar_limbs, 0);
+            for (limb_reg, limb) in scalar_reg.limbs.iter().zip_eq(limb_values) {
+                writer.write(&limb_reg, &F::from_canonical_u32(limb));
+            }
+        }
+        writer.write_ec_point(&result, &result_value);
+        msm.write(&mut writer, &scalar_values);
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        // The rows read the sums of previous rows, so they are written in a single chunk.
+        writer_data.chunks_par(num_rows).for_each(|mut chunk| {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+        stark.verify(proof, &public).unwrap();
+
+        timing.print();
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Secp256k1ECDSATest;
+
+    impl AirParameters for Secp256k1ECDSATest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = Secp256k1Instruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 3904;
+        const NUM_FREE_COLUMNS: usize = 19;
+        const EXTENDED_COLUMNS: usize = 5931;
+    }
+
+    #[test]
+    fn test_ecdsa_verify_batch() {
+        type F = GoldilocksField;
+        type L = Secp256k1ECDSATest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+        type E = Secp256k1;
+        type S = Secp256k1ScalarField;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("Secp256k1 ECDSA verify", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        let num_signatures = 2;
+        let signatures = (0..num_signatures)
+            .map(|_| builder.alloc_public_ecdsa_signature::<S>())
+            .collect::<Vec<_>>();
+        builder.ecdsa_verify_batch(&signatures);
+
+        let degree_log = log2_ceil(2 * num_signatures * 256);
+        let num_rows = 1 << degree_log;
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Sign random message hashes with random keys.
+        let n = S::modulus();
+        let mut rng = thread_rng();
+        let signature_values = (0..num_signatures)
+            .map(|_| {
+                let secret_key = rng.gen_biguint_below(&n);
+                let public_key = E::ec_generator() * secret_key.clone();
+                let message_hash = rng.gen_biguint(256);
+                let k = rng.gen_biguint_below(&n);
+                let r = (E::ec_generator() * k.clone()).x % &n;
+                let k_inv = k.modpow(&(&n - 2u32), &n);
+                let s = (k_inv * (&message_hash + &r * &secret_key)) % &n;
+                assert!(ecdsa_verify::<E, S>(&message_hash, &r, &s, &public_key));
+                (message_hash, r, s, public_key)
+            })
+            .collect::<Vec<_>>();
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (signature, (message_hash, r, s, public_key)) in
+            signatures.iter().zip_eq(signature_values.iter())
+        {
+            signature.write(&mut writer, message_hash, r, s, public_key);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        writer_data.chunks_par(256).for_each(|mut chunk| {
+            for i in 0..256 {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct P256ECDSATest;
+
+    impl AirParameters for P256ECDSATest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = P256Instruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 3904;
+        const NUM_FREE_COLUMNS: usize = 19;
+        const EXTENDED_COLUMNS: usize = 5931;
+    }
+
+    #[test]
+    fn test_p256_ecdsa_verify_batch() {
+        type L = P256ECDSATest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type S = P256ScalarField;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("P-256 ECDSA verify", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        let (public_key, signature_values) = rfc6979_vectors();
+        let signatures = signature_values
+            .iter()
+            .map(|_| builder.alloc_public_ecdsa_signature::<S>())
+            .collect::<Vec<_>>();
+        builder.ecdsa_verify_batch(&signatures);
+
+        let num_rows = 1 << log2_ceil(2 * signatures.len() * 256);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (signature, (message_hash, r, s)) in signatures.iter().zip_eq(signature_values.iter()) {
+            signature.write(&mut writer, message_hash, r, s, &public_key);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        writer_data.chunks_par(256).for_each(|mut chunk| {
+            for i in 0..256 {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+        stark.verify(proof, &public).unwrap();
+
+        timing.print();
+    }
 }
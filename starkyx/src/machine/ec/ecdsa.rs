@@ -0,0 +1,115 @@
+use num::{BigUint, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::ec::gadget::EllipticCurveAirWriter;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::ec::scalar::ECScalarRegister;
+use crate::chip::ec::EllipticCurve;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::AirWriter;
+use crate::machine::builder::Builder;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// The public inputs of an ECDSA signature verification over a curve with scalar field `S`.
+///
+/// The points `u_1_g = u_1 * G` and `u_2_q = u_2 * Q` are computed by the prover and constrained
+/// by the scalar multiplication of the verification.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct ECDSASignatureRegister<E: EllipticCurve, S: FieldParameters> {
+    pub message_hash: FieldRegister<S>,
+    pub r: FieldRegister<S>,
+    pub s: FieldRegister<S>,
+    pub public_key: AffinePointRegister<E>,
+    pub u_1_g: AffinePointRegister<E>,
+    pub u_2_q: AffinePointRegister<E>,
+}
+
+impl<E: EllipticCurve, S: FieldParameters> ECDSASignatureRegister<E, S> {
+    /// Writes the signature `(r, s)` of `message_hash` under `public_key`, together with the
+    /// intermediate points of its verification.
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        message_hash: &BigUint,
+        r: &BigUint,
+        s: &BigUint,
+        public_key: &AffinePoint<E>,
+    ) {
+        let (u_1, u_2) = ecdsa_scalars::<S>(message_hash, r, s);
+
+        writer.write(
+            &self.message_hash,
+            &to_le_limbs_polynomial::<W::Field, S>(message_hash),
+        );
+        writer.write(&self.r, &to_le_limbs_polynomial::<W::Field, S>(r));
+        writer.write(&self.s, &to_le_limbs_polynomial::<W::Field, S>(s));
+        writer.write_ec_point(&self.public_key, public_key);
+        writer.write_ec_point(&self.u_1_g, &(E::ec_generator() * u_1));
+        writer.write_ec_point(&self.u_2_q, &(public_key * u_2));
+    }
+}
+
+/// The scalars `u_1 = z / s` and `u_2 = r / s` of the verification of the signature `(r, s)` of
+/// the message hash `z`.
+pub fn ecdsa_scalars<S: FieldParameters>(
+    message_hash: &BigUint,
+    r: &BigUint,
+    s: &BigUint,
+) -> (BigUint, BigUint) {
+    let n = S::modulus();
+    let s_inv = s.modpow(&(&n - 2u32), &n);
+    let u_1 = (message_hash * &s_inv) % &n;
+    let u_2 = (r * &s_inv) % &n;
+    (u_1, u_2)
+}
+
+/// Verifies the signature `(r, s)` of the message hash `message_hash` under `public_key`.
+pub fn ecdsa_verify<E: EllipticCurve, S: FieldParameters>(
+    message_hash: &BigUint,
+    r: &BigUint,
+    s: &BigUint,
+    public_key: &AffinePoint<E>,
+) -> bool {
+    let n = S::modulus();
+    if r.is_zero() || s.is_zero() || *r >= n || *s >= n {
+        return false;
+    }
+    let (u_1, u_2) = ecdsa_scalars::<S>(message_hash, r, s);
+    let point = E::ec_generator() * u_1 + public_key * u_2;
+    point.x % &n == *r
+}
+
+/// Returns the scalar register whose 32-bit limbs are given by the limbs of `value`.
+pub(crate) fn field_to_scalar<B: Builder, E: EllipticCurve, S: FieldParameters>(
+    builder: &mut B,
+    value: &FieldRegister<S>,
+) -> ECScalarRegister<E> {
+    assert_eq!(S::NB_LIMBS * S::NB_BITS_PER_LIMB, E::nb_scalar_bits());
+    assert_eq!(32 % S::NB_BITS_PER_LIMB, 0);
+    let nb_limbs_per_scalar_limb = 32 / S::NB_BITS_PER_LIMB;
+    let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+    let nb_scalar_limbs = S::NB_LIMBS / nb_limbs_per_scalar_limb;
+    let scalar_limbs = if value.is_trace() {
+        builder.alloc_array::<ElementRegister>(nb_scalar_limbs)
+    } else {
+        builder.alloc_array_public::<ElementRegister>(nb_scalar_limbs)
+    };
+    for (i, scalar_limb) in scalar_limbs.iter().enumerate() {
+        let expression = (0..nb_limbs_per_scalar_limb)
+            .map(|j| {
+                limbs.get(nb_limbs_per_scalar_limb * i + j).expr()
+                    * B::Field::from_canonical_u32(1 << (S::NB_BITS_PER_LIMB * j))
+            })
+            .fold(ArithmeticExpression::zero(), |acc, x| acc + x);
+        builder.set_to_expression(&scalar_limb, expression);
+    }
+    ECScalarRegister::new(scalar_limbs)
+}
@@ -0,0 +1,482 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use num::BigUint;
+use plonky2::util::log2_ceil;
+use serde::{Deserialize, Serialize};
+
+use super::builder::EllipticCurveBuilder;
+use super::ecdsa::field_to_scalar;
+use crate::chip::ec::edwards::ed25519::decompress::decompress;
+use crate::chip::ec::edwards::ed25519::gadget::{CompressedPointAirWriter, CompressedPointGadget};
+use crate::chip::ec::edwards::ed25519::params::{Ed25519, Ed25519ScalarField};
+use crate::chip::ec::edwards::ed25519::point::CompressedPointRegister;
+use crate::chip::ec::edwards::ed25519::sqrt::Ed25519FpSqrtInstruction;
+use crate::chip::ec::gadget::EllipticCurveAirWriter;
+use crate::chip::ec::point::AffinePointRegister;
+use crate::chip::ec::scalar::ECScalarRegister;
+use crate::chip::ec::{ECInstructions, EllipticCurve, EllipticCurveAir};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::machine::builder::Builder;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::machine::hash::sha::builder::SHABuilder;
+use crate::machine::hash::sha::hmac::HMACPure;
+use crate::machine::hash::sha::sha512::register::SHA512DigestRegister;
+use crate::machine::hash::sha::sha512::SHA512;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// The length in bytes of an encoded Ed25519 point.
+const POINT_LENGTH: usize = 32;
+
+/// The cycle length of the SHA-512 AIR.
+const SHA512_CYCLE_LENGTH: usize = 80;
+
+/// The public inputs of an Ed25519 signature verification.
+///
+/// The signature `(R, s)` of the message `M` under the public key `A` is valid if
+/// `s * B = R + k * A`, where `B` is the generator and `k = SHA512(R || A || M) mod L`. The
+/// bytes `R || A || M` are given by `hash_input`, and the digest and the points `s * B` and
+/// `k * A` are computed by the prover and constrained by the verification.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Ed25519SignatureRegister {
+    pub public_key: CompressedPointRegister,
+    pub r: CompressedPointRegister,
+    pub s: FieldRegister<Ed25519ScalarField>,
+    pub hash_input: MessageRegister,
+    pub digest: SHA512DigestRegister,
+    pub s_b: AffinePointRegister<Ed25519>,
+    pub k_a: AffinePointRegister<Ed25519>,
+}
+
+impl Ed25519SignatureRegister {
+    /// The maximal length of a signed message.
+    pub fn max_message_length(&self) -> usize {
+        self.hash_input.max_length() - 2 * POINT_LENGTH
+    }
+
+    /// Writes the signature `(r, s)` of `message` under `public_key`, together with the
+    /// intermediate values of its verification.
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        public_key: &CompressedEdwardsY,
+        r: &CompressedEdwardsY,
+        s: &BigUint,
+        message: &[u8],
+    ) {
+        let hash_input = [
+            r.as_bytes().as_slice(),
+            public_key.as_bytes().as_slice(),
+            message,
+        ]
+        .concat();
+        let digest = SHA512::digest(&hash_input);
+        let k = BigUint::from_bytes_le(&digest) % Ed25519ScalarField::modulus();
+        let (public_key_point, _) = decompress(public_key);
+
+        writer.write_ec_compressed_point(&self.public_key, public_key);
+        writer.write_ec_compressed_point(&self.r, r);
+        writer.write(
+            &self.s,
+            &to_le_limbs_polynomial::<W::Field, Ed25519ScalarField>(s),
+        );
+        self.hash_input.write(writer, &hash_input);
+
+        for (i, byte) in digest.iter().enumerate() {
+            writer.write(
+                &digest_byte(&self.digest, i),
+                &W::Field::from_canonical_u8(*byte),
+            );
+        }
+
+        writer.write_ec_point(&self.s_b, &(Ed25519::ec_generator() * s.clone()));
+        writer.write_ec_point(&self.k_a, &(&public_key_point * k));
+    }
+}
+
+/// The challenge `k = SHA512(R || A || M) mod L` of a signature with commitment `r` of `message`
+/// under `public_key`.
+pub fn ed25519_challenge(
+    r: &CompressedEdwardsY,
+    public_key: &CompressedEdwardsY,
+    message: &[u8],
+) -> BigUint {
+    let hash_input = [
+        r.as_bytes().as_slice(),
+        public_key.as_bytes().as_slice(),
+        message,
+    ]
+    .concat();
+    BigUint::from_bytes_le(&SHA512::digest(&hash_input)) % Ed25519ScalarField::modulus()
+}
+
+/// Verifies the signature `(r, s)` of `message` under `public_key` with the cofactorless
+/// equation `s * B = R + k * A`.
+///
+/// The points `r` and `public_key` must be valid encodings of curve points.
+pub fn ed25519_verify(
+    public_key: &CompressedEdwardsY,
+    r: &CompressedEdwardsY,
+    s: &BigUint,
+    message: &[u8],
+) -> bool {
+    if *s >= Ed25519ScalarField::modulus() {
+        return false;
+    }
+    let (public_key_point, _) = decompress(public_key);
+    let (r_point, _) = decompress(r);
+    let k = ed25519_challenge(r, public_key, message);
+    Ed25519::ec_generator() * s.clone() == r_point + &public_key_point * k
+}
+
+/// The number of rows of the trace of `ed25519_verify_batch` for `signatures`.
+///
+/// The trace has exactly `2^16` rows, since the arithmetic columns are range checked against a
+/// column of the trace. Panics if the batch does not fit in `2^16` rows. Each signature takes two
+/// scalar multiplications of `256` rows, so a batch holds at most `128` signatures, and fewer
+/// with long messages. Larger batches must be split across several proofs.
+pub fn ed25519_verify_num_rows(signatures: &[Ed25519SignatureRegister]) -> usize {
+    let num_sha_rounds = signatures
+        .iter()
+        .map(|signature| (signature.hash_input.max_length() + 16) / 128 + 1)
+        .sum::<usize>();
+    let num_scalar_muls = 2 * signatures.len();
+
+    let degree_log = log2_ceil(num_sha_rounds * SHA512_CYCLE_LENGTH)
+        .max(log2_ceil(num_scalar_muls * Ed25519::nb_scalar_bits()));
+    assert!(degree_log <= 16, "The batch does not fit in 2^16 rows");
+    1 << 16
+}
+
+pub trait Ed25519VerifyBuilder: Builder {
+    /// Allocates the public inputs of the verification of an Ed25519 signature of a message of
+    /// at most `max_message_length` bytes.
+    fn alloc_public_ed25519_signature(
+        &mut self,
+        max_message_length: usize,
+    ) -> Ed25519SignatureRegister {
+        let public_key = self.api().alloc_public_ec_compressed_point();
+        let r = self.api().alloc_public_ec_compressed_point();
+
+        Ed25519SignatureRegister {
+            public_key,
+            r,
+            s: self.alloc_public(),
+            hash_input: self.alloc_public_message(2 * POINT_LENGTH + max_message_length),
+            digest: self.alloc_public(),
+            s_b: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+            k_a: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+        }
+    }
+
+    /// Verifies a batch of Ed25519 signatures.
+    ///
+    /// For each signature, this checks that `R` and `A` are canonical encodings of curve points
+    /// and that `s < L`, computes `k = SHA512(R || A || M) mod L`, and checks the cofactorless
+    /// equation `s * B = R + k * A`. All the hashes are done in a single SHA-512 AIR and all the
+    /// scalar multiplications in a single `scalar_mul_batch`, both padded to the number of rows
+    /// given by `ed25519_verify_num_rows`. Hence, this can only be called once per builder and
+    /// not together with `sha` or `scalar_mul_batch`.
+    fn ed25519_verify_batch(&mut self, signatures: &[Ed25519SignatureRegister])
+    where
+        Ed25519: EllipticCurveAir<Self::Parameters>,
+        Self::Instruction: ECInstructions<Ed25519>
+            + FromFieldInstruction<Ed25519ScalarField>
+            + From<Ed25519FpSqrtInstruction>,
+        SHA512: SHAir<Self, 80>,
+    {
+        let num_rows = ed25519_verify_num_rows(signatures);
+
+        // Bind the points to the first bytes of the hash input and hash `R || A || M`.
+        for signature in signatures {
+            let hash_input = &signature.hash_input;
+            for i in 0..2 * POINT_LENGTH {
+                self.assert_expression_zero(hash_input.length_selector.get(i).expr());
+            }
+            assert_point_bytes(
+                self,
+                &signature.r,
+                &hash_input.bytes.get_subarray(0..POINT_LENGTH),
+            );
+            assert_point_bytes(
+                self,
+                &signature.public_key,
+                &hash_input
+                    .bytes
+                    .get_subarray(POINT_LENGTH..2 * POINT_LENGTH),
+            );
+        }
+        // Hash enough chunks for the SHA-512 AIR to take all of the rows.
+        let hash_inputs = signatures
+            .iter()
+            .map(|signature| signature.hash_input)
+            .collect::<Vec<_>>();
+        let min_rounds = num_rows / (2 * SHA512_CYCLE_LENGTH) + 1;
+        let digests = self.sha_messages_padded::<SHA512, 80>(&hash_inputs, min_rounds);
+
+        let generator = EllipticCurveBuilder::<Ed25519>::generator(self);
+        let two_256 = self.api().fp_constant::<Ed25519ScalarField>(
+            &((BigUint::from(1u32) << 256) % Ed25519ScalarField::modulus()),
+        );
+
+        let mut points = Vec::with_capacity(2 * signatures.len());
+        let mut scalars = Vec::with_capacity(2 * signatures.len());
+        let mut results = Vec::with_capacity(2 * signatures.len());
+        for (signature, digest) in signatures.iter().zip(digests.iter()) {
+            self.set_to_expression(digest, signature.digest.expr());
+
+            // Decompress the points, checking that the encodings and square roots are canonical.
+            let (r, r_root) = self.api().ed25519_decompress(&signature.r);
+            let (public_key, public_key_root) =
+                self.api().ed25519_decompress(&signature.public_key);
+            for value in [
+                &signature.r.y,
+                &r_root,
+                &signature.public_key.y,
+                &public_key_root,
+            ] {
+                self.api().fp_assert_canonical(value);
+            }
+
+            // The digest is read as a little-endian integer `k_low + 2^256 * k_high`.
+            let [k_low, k_high] = [0, 1].map(|half| {
+                let value = self.alloc_public::<FieldRegister<Ed25519ScalarField>>();
+                let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+                for (i, limb) in limbs.iter().enumerate() {
+                    let j = 32 * half + 2 * i;
+                    self.set_to_expression(
+                        &limb,
+                        digest_byte(&signature.digest, j).expr()
+                            + digest_byte(&signature.digest, j + 1).expr()
+                                * Self::Field::from_canonical_u32(1 << 8),
+                    );
+                }
+                value
+            });
+            let k_high_reduced = self.api().fp_mul(&k_high, &two_256);
+            let k = self.api().fp_add(&k_low, &k_high_reduced);
+
+            self.api().fp_assert_canonical(&signature.s);
+            self.api().fp_assert_canonical(&k);
+
+            points.push(generator);
+            scalars.push(field_to_scalar::<Self, Ed25519, Ed25519ScalarField>(
+                self,
+                &signature.s,
+            ));
+            results.push(signature.s_b);
+            points.push(public_key);
+            scalars.push(field_to_scalar::<Self, Ed25519, Ed25519ScalarField>(
+                self, &k,
+            ));
+            results.push(signature.k_a);
+
+            let sum = self.add(r, signature.k_a);
+            self.assert_equal(&sum.x, &signature.s_b.x);
+            self.assert_equal(&sum.y, &signature.s_b.y);
+        }
+
+        // Multiply the generator by one enough times for the scalar multiplications to take all
+        // of the rows.
+        let min_scalar_muls = num_rows / (2 * Ed25519::nb_scalar_bits()) + 1;
+        if points.len() < min_scalar_muls {
+            let mut one_limbs = vec![Self::Field::ONE];
+            one_limbs.resize(Ed25519::nb_scalar_bits() / 32, Self::Field::ZERO);
+            let one_limbs = self.constant_array::<ElementRegister>(&one_limbs);
+            while points.len() < min_scalar_muls {
+                points.push(generator);
+                scalars.push(ECScalarRegister::new(one_limbs));
+                results.push(generator);
+            }
+        }
+
+        EllipticCurveBuilder::<Ed25519>::scalar_mul_batch(self, &points, &scalars, &results);
+    }
+}
+
+impl<B: Builder> Ed25519VerifyBuilder for B {}
+
+/// The register of the byte `i` of the digest.
+///
+/// The digest words are big-endian, while their bytes are stored in little-endian order.
+fn digest_byte(digest: &SHA512DigestRegister, i: usize) -> ElementRegister {
+    let bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(*digest.register());
+    bytes.get(8 * (i / 8) + 7 - i % 8)
+}
+
+/// Constrains `point` to be encoded by the little-endian `bytes` of its y-coordinate, whose most
+/// significant bit is replaced by the sign bit.
+///
+/// The bytes are range checked by the SHA-512 AIR, and the y-coordinate must be checked to be
+/// canonical for the sign bit to be the most significant bit of the last byte.
+fn assert_point_bytes<B: Builder>(
+    builder: &mut B,
+    point: &CompressedPointRegister,
+    bytes: &ArrayRegister<ByteRegister>,
+) {
+    let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*point.y.register());
+    for (i, limb) in limbs.iter().enumerate() {
+        let mut value = bytes.get(2 * i).expr()
+            + bytes.get(2 * i + 1).expr() * B::Field::from_canonical_u32(1 << 8);
+        if i == limbs.len() - 1 {
+            value = value - point.sign.expr() * B::Field::from_canonical_u32(1 << 15);
+        }
+        builder.assert_expression_zero(limb.expr() - value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::util::timing::TimingTree;
+
+    use super::*;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519VerifyInstruction;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::AirParameters;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+
+    /// The signatures of RFC 8032, section 7.1, as `(public_key, message, signature)`.
+    const SIGNATURES: [(&str, &str, &str); 3] = [
+        (
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    fn signature_values() -> Vec<(CompressedEdwardsY, CompressedEdwardsY, BigUint, Vec<u8>)> {
+        SIGNATURES
+            .iter()
+            .map(|(public_key, message, signature)| {
+                let public_key =
+                    CompressedEdwardsY::from_slice(&hex::decode(public_key).unwrap()).unwrap();
+                let signature = hex::decode(signature).unwrap();
+                let r = CompressedEdwardsY::from_slice(&signature[..32]).unwrap();
+                let s = BigUint::from_bytes_le(&signature[32..]);
+                (public_key, r, s, hex::decode(message).unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ed25519_verify_pure() {
+        for (public_key, r, s, message) in signature_values() {
+            assert!(ed25519_verify(&public_key, &r, &s, &message));
+            assert!(!ed25519_verify(&public_key, &r, &s, b"wrong message"));
+            assert!(!ed25519_verify(
+                &public_key,
+                &r,
+                &(&s + Ed25519ScalarField::modulus()),
+                &message
+            ));
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Ed25519VerifyTest;
+
+    impl AirParameters for Ed25519VerifyTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = Ed25519VerifyInstruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 2496;
+        const NUM_FREE_COLUMNS: usize = 836;
+        const EXTENDED_COLUMNS: usize = 5538;
+    }
+
+    #[test]
+    fn test_ed25519_verify_batch() {
+        type F = GoldilocksField;
+        type L = Ed25519VerifyTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("Ed25519 verify", log::Level::Debug);
+
+        let mut builder = BytesBuilder::<L>::new();
+
+        let signature_values = signature_values();
+        let signatures = signature_values
+            .iter()
+            .map(|_| builder.alloc_public_ed25519_signature(8))
+            .collect::<Vec<_>>();
+        builder.ed25519_verify_batch(&signatures);
+
+        let num_rows = ed25519_verify_num_rows(&signatures);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (signature, (public_key, r, s, message)) in
+            signatures.iter().zip(signature_values.iter())
+        {
+            signature.write(&mut writer, public_key, r, s, message);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
@@ -0,0 +1,130 @@
+use core::marker::PhantomData;
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::ec::point::AffinePoint;
+use crate::chip::ec::EllipticCurve;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::utils::biguint_to_bits_le;
+use crate::math::prelude::*;
+
+/// The number of scalar bits processed in each row of a fixed-base scalar multiplication.
+pub const FIXED_BASE_WINDOW_BITS: usize = 4;
+
+/// A public scalar of a fixed-base scalar multiplication, given by its little-endian bits.
+///
+/// The bits are constrained to be boolean when the register is allocated.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FixedBaseScalarRegister<E> {
+    pub bits: ArrayRegister<BitRegister>,
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve> FixedBaseScalarRegister<E> {
+    pub const fn new(bits: ArrayRegister<BitRegister>) -> Self {
+        Self {
+            bits,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The bits of the window `j` of the scalar.
+    pub fn window(&self, j: usize) -> ArrayRegister<BitRegister> {
+        self.bits
+            .get_subarray(j * FIXED_BASE_WINDOW_BITS..(j + 1) * FIXED_BASE_WINDOW_BITS)
+    }
+
+    /// Writes the bits of `scalar`.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, scalar: &BigUint) {
+        let bits = biguint_to_bits_le(scalar, self.bits.len());
+        for (bit_reg, bit) in self.bits.iter().zip(bits) {
+            writer.write(&bit_reg, &W::Field::from_canonical_u8(bit as u8));
+        }
+    }
+}
+
+/// The precomputed multiples of the generator `G` used by a fixed-base scalar multiplication.
+///
+/// The window `j` of a scalar with digit `d` selects the entry `d * 2^(w * j) * G - C`, where `w`
+/// is the window size in bits and `C = x(G) * G` is an offset which keeps every entry, including
+/// those of the zero digit, away from the identity. The offsets are cancelled by starting the
+/// accumulation at `initial = nb_windows * C`.
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable<E> {
+    pub initial: AffinePoint<E>,
+    pub entries: Vec<Vec<AffinePoint<E>>>,
+}
+
+impl<E: EllipticCurve> FixedBaseTable<E> {
+    pub fn new(nb_windows: usize) -> Self {
+        let generator = E::ec_generator();
+        let offset = &generator * &generator.x;
+        let neg_offset = -&offset;
+        let initial = &offset * &BigUint::from(nb_windows);
+
+        let mut base = generator;
+        let entries = (0..nb_windows)
+            .map(|_| {
+                let mut entries = vec![neg_offset.clone()];
+                let mut multiple = base.clone();
+                for _ in 1..(1 << FIXED_BASE_WINDOW_BITS) {
+                    entries.push(&multiple + &neg_offset);
+                    multiple = &multiple + &base;
+                }
+                for _ in 0..FIXED_BASE_WINDOW_BITS {
+                    base = E::ec_double(&base);
+                }
+                entries
+            })
+            .collect();
+
+        Self { initial, entries }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::ec::edwards::ed25519::params::Ed25519;
+    use crate::chip::ec::weierstrass::secp256k1::Secp256k1;
+
+    fn test_fixed_base_table<E: EllipticCurve>() {
+        let nb_windows = E::nb_scalar_bits() / FIXED_BASE_WINDOW_BITS;
+        let table = FixedBaseTable::<E>::new(nb_windows);
+        assert_eq!(table.entries.len(), nb_windows);
+
+        // Adding the entries of the digits of a scalar to the initial point gives its multiple
+        // of the generator.
+        let mut rng = thread_rng();
+        let scalar = rng.gen_biguint(E::nb_scalar_bits() as u64 - 8);
+        let bits = biguint_to_bits_le(&scalar, E::nb_scalar_bits());
+        let result = bits
+            .chunks_exact(FIXED_BASE_WINDOW_BITS)
+            .zip(table.entries.iter())
+            .map(|(digit_bits, entries)| {
+                let digit = digit_bits
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (k, bit)| acc + ((*bit as usize) << k));
+                &entries[digit]
+            })
+            .fold(table.initial.clone(), |acc, entry| E::ec_add(&acc, entry));
+        assert_eq!(result, E::ec_generator() * scalar);
+    }
+
+    #[test]
+    fn test_fixed_base_table_ed25519() {
+        test_fixed_base_table::<Ed25519>();
+    }
+
+    #[test]
+    fn test_fixed_base_table_secp256k1() {
+        test_fixed_base_table::<Secp256k1>();
+    }
+}
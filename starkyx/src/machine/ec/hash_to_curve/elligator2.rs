@@ -0,0 +1,170 @@
+use num::{BigUint, One, Zero};
+
+use super::{assert_public_sign, is_square, sgn0, MapToCurveRegister};
+use crate::chip::ec::edwards::ed25519::params::{Ed25519, Ed25519BaseField};
+use crate::chip::ec::edwards::ed25519::sqrt::sqrt;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::machine::builder::Builder;
+
+/// The coefficient `J` of curve25519, `v^2 = u^3 + J * u^2 + u`.
+const J: u32 = 486662;
+
+/// The non-square `Z` of the Elligator 2 map.
+const Z: u32 = 2;
+
+/// The constant `sqrt(-486664)` of the rational map to edwards25519, with `sgn0` equal to zero.
+fn rational_map_constant() -> BigUint {
+    sqrt(Ed25519BaseField::modulus() - BigUint::from(J + 2))
+}
+
+/// The candidate x-coordinates of the Elligator 2 map of `u` and their images under
+/// `g(x) = x^3 + J * x^2 + x`.
+fn elligator2_candidates(u: &BigUint) -> [(BigUint, BigUint); 2] {
+    let p = Ed25519BaseField::modulus();
+    let j = BigUint::from(J);
+
+    // The denominator `1 + Z * u^2` does not vanish, since `-1 / Z` is not a square.
+    let z_u2 = (BigUint::from(Z) * u * u) % &p;
+    let denominator = (BigUint::one() + &z_u2) % &p;
+    let x_1 = ((&p - &j) * denominator.modpow(&(&p - 2u32), &p)) % &p;
+    let g_x_1 = (&x_1 * ((&x_1 * (&x_1 + &j)) % &p + 1u32)) % &p;
+    let x_2 = (&z_u2 * &x_1) % &p;
+    let g_x_2 = (&z_u2 * &g_x_1) % &p;
+    [(x_1, g_x_1), (x_2, g_x_2)]
+}
+
+/// The witnesses `(is_square, root, y)` of the Elligator 2 map of `u`.
+pub(crate) fn elligator2_witness(u: &BigUint) -> (bool, BigUint, BigUint) {
+    let p = Ed25519BaseField::modulus();
+    let [(_, g_x_1), (_, g_x_2)] = elligator2_candidates(u);
+
+    let is_square = is_square::<Ed25519BaseField>(&g_x_1);
+    let (root, g_x) = if is_square {
+        (sqrt(g_x_1.clone()), g_x_1)
+    } else {
+        (sqrt((BigUint::from(Z) * &g_x_1) % &p), g_x_2)
+    };
+    // The y-coordinate is odd for the first candidate and even for the second.
+    let y = sqrt(g_x);
+    let y = if sgn0(&y) != is_square {
+        (&p - y) % &p
+    } else {
+        y
+    };
+    (is_square, root, y)
+}
+
+/// Computes `map_to_curve_elligator2_edwards25519(u)` of RFC 9380, section 6.8.2.
+pub fn ed25519_map_to_curve_elligator2(u: &BigUint) -> AffinePoint<Ed25519> {
+    let p = Ed25519BaseField::modulus();
+    let [(x_1, _), (x_2, _)] = elligator2_candidates(u);
+    let (is_square, _, t) = elligator2_witness(u);
+    let s = if is_square { x_1 } else { x_2 };
+
+    // The rational map to edwards25519, whose exceptional cases are mapped to the identity.
+    let t_s_1 = (&t * (&s + 1u32)) % &p;
+    if t_s_1.is_zero() {
+        return Ed25519::neutral();
+    }
+    let inverse = t_s_1.modpow(&(&p - 2u32), &p);
+    let x = (rational_map_constant() * &s % &p * (&s + 1u32) % &p * &inverse) % &p;
+    let y = ((&s + &p - 1u32) * &t % &p * &inverse) % &p;
+    AffinePoint::new(x, y)
+}
+
+pub trait Elligator2Builder: Builder {
+    /// Maps `u` to edwards25519 with the Elligator 2 map of curve25519 and the rational map, as in
+    /// `map_to_curve_elligator2_edwards25519`.
+    ///
+    /// The exceptional cases of the rational map, which have negligible probability, are
+    /// rejected.
+    fn ed25519_map_to_curve_elligator2(
+        &mut self,
+        u: &FieldRegister<Ed25519BaseField>,
+        witness: &MapToCurveRegister<Ed25519BaseField>,
+    ) -> AffinePointRegister<Ed25519>
+    where
+        Self::Instruction: FromFieldInstruction<Ed25519BaseField>,
+    {
+        let p = Ed25519BaseField::modulus();
+        let one = self.api().fp_one::<Ed25519BaseField>();
+        let j = self.api().fp_constant(&BigUint::from(J));
+        let minus_j = self.api().fp_constant(&(&p - J));
+
+        // The candidates `x_1 = -J / (1 + Z * u^2)` and `x_2 = Z * u^2 * x_1`.
+        let u_2 = self.api().fp_mul(u, u);
+        let z_u2 = self.api().fp_add(&u_2, &u_2);
+        let denominator = self.api().fp_add(&one, &z_u2);
+        let x_1 = self.api().fp_div(&minus_j, &denominator);
+        let x_1_j = self.api().fp_add(&x_1, &j);
+        let x_1_x_1_j = self.api().fp_mul(&x_1, &x_1_j);
+        let x_1_x_1_j_1 = self.api().fp_add(&x_1_x_1_j, &one);
+        let g_x_1 = self.api().fp_mul(&x_1, &x_1_x_1_j_1);
+        let x_2 = self.api().fp_mul(&z_u2, &x_1);
+        let g_x_2 = self.api().fp_mul(&z_u2, &g_x_1);
+
+        // Either `g(x_1)` or `Z * g(x_1)` is a square, since `Z` is not a square.
+        let z_g_x_1 = self.api().fp_add(&g_x_1, &g_x_1);
+        let square = self.select(witness.is_square, &g_x_1, &z_g_x_1);
+        let root_squared = self.api().fp_mul(&witness.root, &witness.root);
+        self.assert_equal(&root_squared, &square);
+
+        let s = self.select(witness.is_square, &x_1, &x_2);
+        let g_x = self.select(witness.is_square, &g_x_1, &g_x_2);
+        let y_squared = self.api().fp_mul(&witness.y, &witness.y);
+        self.assert_equal(&y_squared, &g_x);
+        assert_public_sign(self, &witness.y, witness.is_square.expr());
+
+        // The rational map `(x, y) = (sqrt(-486664) * s / t, (s - 1) / (s + 1))`.
+        let c = self.api().fp_constant(&rational_map_constant());
+        let c_s = self.api().fp_mul(&c, &s);
+        let x = self.api().fp_div(&c_s, &witness.y);
+        let s_minus_1 = self.api().fp_sub(&s, &one);
+        let s_plus_1 = self.api().fp_add(&s, &one);
+        let y = self.api().fp_div(&s_minus_1, &s_plus_1);
+
+        AffinePointRegister::new(x, y)
+    }
+}
+
+impl<B: Builder> Elligator2Builder for B {}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+
+    use super::*;
+    use crate::chip::ec::edwards::ed25519::params::Ed25519Parameters;
+    use crate::chip::ec::edwards::EdwardsParameters;
+
+    #[test]
+    fn test_ed25519_map_to_curve_elligator2() {
+        let p = Ed25519BaseField::modulus();
+        let d = Ed25519Parameters::d_biguint();
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let u = rng.gen_biguint(256) % &p;
+            let (is_square, root, y) = elligator2_witness(&u);
+            assert_eq!(sgn0(&y), is_square);
+            let [(_, g_x_1), _] = elligator2_candidates(&u);
+            let square = if is_square {
+                g_x_1
+            } else {
+                BigUint::from(Z) * g_x_1 % &p
+            };
+            assert_eq!(&root * &root % &p, square);
+
+            // The point satisfies `-x^2 + y^2 = 1 + d * x^2 * y^2`.
+            let point = ed25519_map_to_curve_elligator2(&u);
+            let x_2 = &point.x * &point.x % &p;
+            let y_2 = &point.y * &point.y % &p;
+            assert_eq!(
+                (&y_2 + &p - &x_2) % &p,
+                (BigUint::one() + &d * &x_2 % &p * &y_2) % &p
+            );
+        }
+    }
+}
@@ -0,0 +1,444 @@
+use num::BigUint;
+use plonky2::util::log2_ceil;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::machine::builder::Builder;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::machine::hash::sha::builder::SHABuilder;
+use crate::machine::hash::sha::hmac::HMACPure;
+use crate::math::prelude::*;
+
+/// The length `L` in bytes of the uniform bytes reduced to a field element by `hash_to_field`,
+/// for fields of at most 256 bits at the 128-bit security level.
+pub const HASH_TO_FIELD_LENGTH: usize = 48;
+
+/// The public inputs and intermediate values of `expand_message_xmd(msg, DST, len_in_bytes)`.
+///
+/// The blocks `b_0, ..., b_ell` are the digests of the messages `hash_inputs`, and the uniform
+/// bytes are the first `len_in_bytes` bytes of `b_1 || ... || b_ell`. The bits of the blocks
+/// `b_0, ..., b_(ell - 1)` are used for the xor of the hash inputs of `b_2, ..., b_ell`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandMessageRegister {
+    pub message: MessageRegister,
+    pub dst: Vec<u8>,
+    pub len_in_bytes: usize,
+    pub hash_inputs: Vec<MessageRegister>,
+    pub blocks: Vec<ArrayRegister<ByteRegister>>,
+    pub block_bits: Vec<ArrayRegister<BitRegister>>,
+}
+
+impl ExpandMessageRegister {
+    /// The maximal length of a message.
+    pub fn max_message_length(&self) -> usize {
+        self.message.max_length()
+    }
+
+    /// The big-endian output bytes of `expand_message_xmd`.
+    pub fn uniform_bytes(&self) -> Vec<ByteRegister> {
+        self.blocks[1..]
+            .iter()
+            .flat_map(|block| block.iter())
+            .take(self.len_in_bytes)
+            .collect()
+    }
+
+    /// Writes `message` together with the intermediate values of its expansion, returning the
+    /// uniform bytes.
+    pub fn write<W: AirWriter, S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &self,
+        writer: &mut W,
+        message: &[u8],
+    ) -> Vec<u8> {
+        let (hash_inputs, blocks) =
+            expand_message_xmd_blocks::<S, CYCLE_LENGTH>(message, &self.dst, self.len_in_bytes);
+
+        self.message.write(writer, message);
+        for (register, hash_input) in self.hash_inputs.iter().zip(hash_inputs.iter()) {
+            register.write(writer, hash_input);
+        }
+        for (register, block) in self.blocks.iter().zip(blocks.iter()) {
+            writer.write_array(
+                register,
+                block.iter().map(|b| W::Field::from_canonical_u8(*b)),
+            );
+        }
+        for (register, block) in self.block_bits.iter().zip(blocks.iter()) {
+            let bits = block
+                .iter()
+                .flat_map(|byte| (0..8).map(move |i| (byte >> i) & 1))
+                .map(W::Field::from_canonical_u8);
+            writer.write_array(register, bits);
+        }
+
+        blocks[1..]
+            .concat()
+            .into_iter()
+            .take(self.len_in_bytes)
+            .collect()
+    }
+}
+
+/// The hash inputs and the blocks `b_0, ..., b_ell` of `expand_message_xmd`.
+fn expand_message_xmd_blocks<S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    message: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> (Vec<Vec<u8>>, Vec<Vec<u8>>) {
+    let digest_length = S::digest_length();
+    let ell = (len_in_bytes + digest_length - 1) / digest_length;
+    assert!(ell <= 255 && len_in_bytes <= 65535 && dst.len() <= 255);
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let mut hash_inputs = vec![[
+        vec![0u8; S::block_length()].as_slice(),
+        message,
+        &(len_in_bytes as u16).to_be_bytes(),
+        &[0u8],
+        &dst_prime,
+    ]
+    .concat()];
+    let mut blocks = vec![S::digest(&hash_inputs[0])];
+    for i in 1..=ell {
+        let previous = if i == 1 {
+            blocks[0].clone()
+        } else {
+            blocks[0]
+                .iter()
+                .zip(blocks[i - 1].iter())
+                .map(|(a, b)| a ^ b)
+                .collect()
+        };
+        let hash_input = [previous.as_slice(), &[i as u8], &dst_prime].concat();
+        blocks.push(S::digest(&hash_input));
+        hash_inputs.push(hash_input);
+    }
+    (hash_inputs, blocks)
+}
+
+/// Computes `expand_message_xmd(msg, DST, len_in_bytes)` of RFC 9380, section 5.3.1.
+pub fn expand_message_xmd<S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    message: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Vec<u8> {
+    let (_, blocks) = expand_message_xmd_blocks::<S, CYCLE_LENGTH>(message, dst, len_in_bytes);
+    blocks[1..]
+        .concat()
+        .into_iter()
+        .take(len_in_bytes)
+        .collect()
+}
+
+/// Computes `hash_to_field(msg, count)` of RFC 9380, section 5.2, for an extension degree of one.
+pub fn hash_to_field<P: FieldParameters, S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    message: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Vec<BigUint> {
+    let uniform_bytes =
+        expand_message_xmd::<S, CYCLE_LENGTH>(message, dst, count * HASH_TO_FIELD_LENGTH);
+    uniform_bytes
+        .chunks_exact(HASH_TO_FIELD_LENGTH)
+        .map(|bytes| BigUint::from_bytes_be(bytes) % P::modulus())
+        .collect()
+}
+
+/// The number of rows of a trace whose SHA AIR hashes the messages of `instances`.
+///
+/// The trace has exactly `2^16` rows, since the arithmetic columns are range checked against a
+/// column of the trace. Panics if the batch does not fit in `2^16` rows.
+pub fn expand_message_xmd_num_rows<S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    instances: &[ExpandMessageRegister],
+) -> usize {
+    let block_length = S::block_length();
+    let length_field_length = block_length / 8;
+    let num_rounds = instances
+        .iter()
+        .flat_map(|instance| instance.hash_inputs.iter())
+        .map(|hash_input| (hash_input.max_length() + length_field_length) / block_length + 1)
+        .sum::<usize>();
+
+    let degree_log = log2_ceil(num_rounds * CYCLE_LENGTH);
+    assert!(degree_log <= 16, "The batch does not fit in 2^16 rows");
+    1 << 16
+}
+
+pub trait ExpandMessageBuilder: Builder {
+    /// Allocates the public inputs of `expand_message_xmd` for messages of at most
+    /// `max_message_length` bytes.
+    fn alloc_public_expand_message<S: HMACPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        max_message_length: usize,
+        dst: &[u8],
+        len_in_bytes: usize,
+    ) -> ExpandMessageRegister {
+        let digest_length = S::digest_length();
+        let ell = (len_in_bytes + digest_length - 1) / digest_length;
+        assert!(ell <= 255, "Output too long");
+        assert!(len_in_bytes <= 65535, "Output too long");
+        assert!(dst.len() <= 255, "Domain separation tag too long");
+        let dst_prime_length = dst.len() + 1;
+
+        let message = self.alloc_public_message(max_message_length);
+        let mut hash_inputs = vec![self
+            .alloc_public_message(S::block_length() + max_message_length + 3 + dst_prime_length)];
+        for _ in 1..=ell {
+            hash_inputs.push(self.alloc_public_message(digest_length + 1 + dst_prime_length));
+        }
+        let blocks = (0..=ell)
+            .map(|_| self.alloc_array_public::<ByteRegister>(digest_length))
+            .collect();
+        let block_bits = (0..ell)
+            .map(|_| self.alloc_array_public::<BitRegister>(8 * digest_length))
+            .collect();
+
+        ExpandMessageRegister {
+            message,
+            dst: dst.to_vec(),
+            len_in_bytes,
+            hash_inputs,
+            blocks,
+            block_bits,
+        }
+    }
+
+    /// Computes `expand_message_xmd` for a batch of messages.
+    ///
+    /// All the hash inputs are hashed in a single SHA AIR, padded with empty messages until there
+    /// are at least `min_rounds` chunks. Hence, this can only be called once per builder and not
+    /// together with `sha`.
+    fn expand_message_xmd_batch<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        instances: &[ExpandMessageRegister],
+        min_rounds: usize,
+    ) {
+        for instance in instances {
+            constrain_hash_inputs::<Self, S, CYCLE_LENGTH>(self, instance);
+        }
+
+        let hash_inputs = instances
+            .iter()
+            .flat_map(|instance| instance.hash_inputs.iter().copied())
+            .collect::<Vec<_>>();
+        let digests = self.sha_messages_padded::<S, CYCLE_LENGTH>(&hash_inputs, min_rounds);
+
+        // The digest words are big-endian, while their bytes are stored in little-endian order.
+        let word_length = S::IntRegister::size_of();
+        let blocks = instances.iter().flat_map(|instance| instance.blocks.iter());
+        for (digest, block) in digests.iter().zip(blocks) {
+            let digest_bytes =
+                ArrayRegister::<ElementRegister>::from_register_unsafe(*digest.register());
+            for (i, byte) in block.iter().enumerate() {
+                let j = word_length * (i / word_length) + word_length - 1 - i % word_length;
+                self.set_to_expression(&digest_bytes.get(j), byte.expr());
+            }
+        }
+    }
+
+    /// Reduces `HASH_TO_FIELD_LENGTH` big-endian uniform bytes to a field element, as in
+    /// `hash_to_field`.
+    ///
+    /// The bytes are split into the 32 least significant bytes and the most significant ones, so
+    /// that the element is `low + high * 2^256`. The result is not necessarily canonical.
+    fn hash_to_field<P: FieldParameters>(&mut self, bytes: &[ByteRegister]) -> FieldRegister<P>
+    where
+        Self::Instruction: FromFieldInstruction<P>,
+    {
+        assert_eq!(bytes.len(), HASH_TO_FIELD_LENGTH);
+        assert_eq!(P::NB_LIMBS * P::NB_BITS_PER_LIMB, 256);
+        let (high_bytes, low_bytes) = bytes.split_at(HASH_TO_FIELD_LENGTH - 32);
+
+        let [low, high] = [low_bytes, high_bytes].map(|bytes| {
+            let value = self.alloc_public::<FieldRegister<P>>();
+            let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+            for (i, limb) in limbs.iter().enumerate() {
+                let limb_expr = if 2 * i < bytes.len() {
+                    let j = bytes.len() - 1 - 2 * i;
+                    bytes[j].expr() + bytes[j - 1].expr() * Self::Field::from_canonical_u32(1 << 8)
+                } else {
+                    ArithmeticExpression::zero()
+                };
+                self.set_to_expression(&limb, limb_expr);
+            }
+            value
+        });
+
+        let two_256 = self
+            .api()
+            .fp_constant::<P>(&((BigUint::from(1u32) << 256) % P::modulus()));
+        let high_reduced = self.api().fp_mul(&high, &two_256);
+        self.api().fp_add(&low, &high_reduced)
+    }
+}
+
+impl<B: Builder> ExpandMessageBuilder for B {}
+
+/// Constrains the hash inputs of `instance` to be those of `expand_message_xmd`.
+///
+/// The input of `b_0` is `Z_pad || msg || I2OSP(len_in_bytes, 2) || I2OSP(0, 1) || DST_prime`,
+/// whose bytes after the message are placed according to the length selector of the message.
+/// The input of `b_i` is `strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime`, where the xor of
+/// the first block is omitted. The bytes of the blocks are range checked by the SHA AIR.
+fn constrain_hash_inputs<B: Builder, S: SHAir<B, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    builder: &mut B,
+    instance: &ExpandMessageRegister,
+) {
+    let block_length = S::block_length();
+    let digest_length = S::digest_length();
+    let dst_prime = [instance.dst.as_slice(), &[instance.dst.len() as u8]].concat();
+    let message = &instance.message;
+    let max_message_length = message.max_length();
+
+    // The input of `b_0`.
+    let suffix = [
+        (instance.len_in_bytes as u16).to_be_bytes().as_slice(),
+        &[0u8],
+        &dst_prime,
+    ]
+    .concat();
+    let hash_input = &instance.hash_inputs[0];
+    builder.assert_expression_zero(
+        hash_input.length.expr()
+            - message.length.expr()
+            - B::Field::from_canonical_usize(block_length + suffix.len()),
+    );
+    for i in 0..block_length {
+        builder.assert_expression_zero(hash_input.bytes.get(i).expr());
+    }
+    let is_message = builder.message_flags(message);
+    for j in 0..max_message_length + suffix.len() {
+        let mut byte_expr = ArithmeticExpression::zero();
+        if j < max_message_length {
+            byte_expr = byte_expr + is_message[j].expr() * message.bytes.get(j).expr();
+        }
+        // The byte `j - len` of the suffix, if the message has length `len`.
+        for len in j.saturating_sub(suffix.len() - 1)..=j.min(max_message_length) {
+            let suffix_byte = suffix[j - len];
+            if suffix_byte != 0 {
+                byte_expr = byte_expr
+                    + message.length_selector.get(len).expr()
+                        * B::Field::from_canonical_u8(suffix_byte);
+            }
+        }
+        builder.assert_expression_zero(hash_input.bytes.get(block_length + j).expr() - byte_expr);
+    }
+
+    // The bits of the blocks used in the xors.
+    for (block, bits) in instance.blocks.iter().zip(instance.block_bits.iter()) {
+        for (k, byte) in block.iter().enumerate() {
+            let mut byte_expr = ArithmeticExpression::zero();
+            for t in 0..8 {
+                let bit = bits.get(8 * k + t);
+                byte_expr = byte_expr + bit.expr() * B::Field::from_canonical_u32(1 << t);
+            }
+            builder.assert_expression_zero(byte.expr() - byte_expr);
+        }
+    }
+
+    // The inputs of `b_1, ..., b_ell`.
+    for (i, hash_input) in instance.hash_inputs.iter().enumerate().skip(1) {
+        builder.assert_expression_zero(
+            hash_input.length.expr()
+                - B::Field::from_canonical_usize(digest_length + 1 + dst_prime.len()),
+        );
+        for k in 0..digest_length {
+            let byte_expr = if i == 1 {
+                instance.blocks[0].get(k).expr()
+            } else {
+                let (lhs, rhs) = (&instance.block_bits[0], &instance.block_bits[i - 1]);
+                (0..8).fold(ArithmeticExpression::zero(), |acc, t| {
+                    let (a, b) = (lhs.get(8 * k + t), rhs.get(8 * k + t));
+                    acc + (a.expr() + b.expr()
+                        - a.expr() * b.expr() * B::Field::from_canonical_u32(2))
+                        * B::Field::from_canonical_u32(1 << t)
+                })
+            };
+            builder.assert_expression_zero(hash_input.bytes.get(k).expr() - byte_expr);
+        }
+        let trailer = [&[i as u8], dst_prime.as_slice()].concat();
+        for (k, byte) in trailer.iter().enumerate() {
+            builder.assert_expression_zero(
+                hash_input.bytes.get(digest_length + k).expr() - B::Field::from_canonical_u8(*byte),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chip::ec::weierstrass::secp256k1::Secp256k1BaseField;
+    use crate::machine::hash::sha::sha256::SHA256;
+    use crate::machine::hash::sha::sha512::SHA512;
+
+    /// The test vectors of RFC 9380, appendix K.1, as `(msg, uniform_bytes)` for a length of 32.
+    const SHA256_VECTORS: [(&str, &str); 3] = [
+        (
+            "",
+            "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+        ),
+        (
+            "abc",
+            "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+        ),
+        (
+            "abcdef0123456789",
+            "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+        ),
+    ];
+
+    /// The test vectors of RFC 9380, appendix K.3, as `(msg, uniform_bytes)` for a length of 32.
+    const SHA512_VECTORS: [(&str, &str); 2] = [
+        (
+            "",
+            "6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba",
+        ),
+        (
+            "abc",
+            "0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc",
+        ),
+    ];
+
+    #[test]
+    fn test_expand_message_xmd() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        for (message, expected) in SHA256_VECTORS {
+            let uniform_bytes = expand_message_xmd::<SHA256, 64>(message.as_bytes(), dst, 32);
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+
+        let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+        for (message, expected) in SHA512_VECTORS {
+            let uniform_bytes = expand_message_xmd::<SHA512, 80>(message.as_bytes(), dst, 32);
+            assert_eq!(hex::encode(uniform_bytes), expected);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_field() {
+        // The field elements `u` of the secp256k1_XMD:SHA-256_SSWU_RO_ vectors of RFC 9380,
+        // appendix J.8.1.
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+        let u = hash_to_field::<Secp256k1BaseField, SHA256, 64>(b"", dst, 2);
+        assert_eq!(
+            u[0].to_str_radix(16),
+            "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3"
+        );
+        assert_eq!(
+            u[1].to_str_radix(16),
+            "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16"
+        );
+    }
+}
@@ -0,0 +1,579 @@
+//! Hashing to elliptic curves as in RFC 9380.
+//!
+//! A message is hashed to a point of the curve by expanding it with `expand_message_xmd` into
+//! two uniformly random field elements `u_0, u_1`, mapping each of them to a curve point, and
+//! clearing the cofactor of their sum. The suites `edwards25519_XMD:SHA-512_ELL2_RO_` and
+//! `secp256k1_XMD:SHA-256_SSWU_RO_` are supported.
+//!
+//! The square roots of the maps are computed by the prover, who also provides a bit indicating
+//! which of the two candidate x-coordinates is used. The bit is constrained by a square root of
+//! either `g(x_1)` or of `g(x_1)` times a non-square. Inputs for which the RFC takes an
+//! exceptional branch, like a vanishing denominator, are rejected by the AIR. These only occur
+//! with negligible probability.
+
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use self::elligator2::{ed25519_map_to_curve_elligator2, elligator2_witness, Elligator2Builder};
+use self::expand::{
+    expand_message_xmd_num_rows, hash_to_field, ExpandMessageBuilder, ExpandMessageRegister,
+    HASH_TO_FIELD_LENGTH,
+};
+use self::sswu::{secp256k1_map_to_curve_sswu, sswu_witness, SimplifiedSWUBuilder};
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::ec::edwards::ed25519::params::{Ed25519, Ed25519BaseField};
+use crate::chip::ec::gadget::EllipticCurveAirWriter;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::ec::weierstrass::secp256k1::{Secp256k1, Secp256k1BaseField};
+use crate::chip::ec::{EllipticCurve, EllipticCurveAir};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::AirWriter;
+use crate::machine::builder::Builder;
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::machine::hash::sha::sha256::SHA256;
+use crate::machine::hash::sha::sha512::SHA512;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+pub mod elligator2;
+pub mod expand;
+pub mod sswu;
+
+/// The cycle length of the SHA-256 AIR.
+const SHA256_CYCLE_LENGTH: usize = 64;
+
+/// The cycle length of the SHA-512 AIR.
+const SHA512_CYCLE_LENGTH: usize = 80;
+
+/// The witnesses of a map to curve computed by the prover.
+///
+/// `is_square` indicates whether `g(x_1)` is a square, in which case `root` is a square root of
+/// `g(x_1)`, and otherwise of `g(x_1)` times the non-square of the map. `y` is the y-coordinate
+/// of the mapped point, before the rational map or isogeny to the target curve.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct MapToCurveRegister<P: FieldParameters> {
+    pub is_square: BitRegister,
+    pub root: FieldRegister<P>,
+    pub y: FieldRegister<P>,
+}
+
+impl<P: FieldParameters> MapToCurveRegister<P> {
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        is_square: bool,
+        root: &BigUint,
+        y: &BigUint,
+    ) {
+        writer.write(
+            &self.is_square,
+            &W::Field::from_canonical_u8(is_square as u8),
+        );
+        writer.write(&self.root, &to_le_limbs_polynomial::<W::Field, P>(root));
+        writer.write(&self.y, &to_le_limbs_polynomial::<W::Field, P>(y));
+    }
+}
+
+/// The public inputs of a hash to curve of a message, where `point` is the resulting point.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct HashToCurveRegister<E: EllipticCurve> {
+    pub expand: ExpandMessageRegister,
+    pub maps: [MapToCurveRegister<E::BaseField>; 2],
+    pub point: AffinePointRegister<E>,
+}
+
+impl HashToCurveRegister<Ed25519> {
+    /// Writes `message` together with the intermediate values of its hash to Ed25519.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, message: &[u8]) {
+        let uniform_bytes = self
+            .expand
+            .write::<W, SHA512, SHA512_CYCLE_LENGTH>(writer, message);
+        for (map, bytes) in self
+            .maps
+            .iter()
+            .zip(uniform_bytes.chunks_exact(HASH_TO_FIELD_LENGTH))
+        {
+            let u = BigUint::from_bytes_be(bytes) % Ed25519BaseField::modulus();
+            let (is_square, root, y) = elligator2_witness(&u);
+            map.write(writer, is_square, &root, &y);
+        }
+        writer.write_ec_point(
+            &self.point,
+            &ed25519_hash_to_curve(message, &self.expand.dst),
+        );
+    }
+}
+
+impl HashToCurveRegister<Secp256k1> {
+    /// Writes `message` together with the intermediate values of its hash to secp256k1.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, message: &[u8]) {
+        let uniform_bytes = self
+            .expand
+            .write::<W, SHA256, SHA256_CYCLE_LENGTH>(writer, message);
+        for (map, bytes) in self
+            .maps
+            .iter()
+            .zip(uniform_bytes.chunks_exact(HASH_TO_FIELD_LENGTH))
+        {
+            let u = BigUint::from_bytes_be(bytes) % Secp256k1BaseField::modulus();
+            let (is_square, root, y) = sswu_witness(&u);
+            map.write(writer, is_square, &root, &y);
+        }
+        writer.write_ec_point(
+            &self.point,
+            &secp256k1_hash_to_curve(message, &self.expand.dst),
+        );
+    }
+}
+
+/// Computes `hash_to_curve(msg)` of the suite `edwards25519_XMD:SHA-512_ELL2_RO_` with the
+/// domain separation tag `dst`.
+pub fn ed25519_hash_to_curve(message: &[u8], dst: &[u8]) -> AffinePoint<Ed25519> {
+    let u = hash_to_field::<Ed25519BaseField, SHA512, SHA512_CYCLE_LENGTH>(message, dst, 2);
+    let q_0 = ed25519_map_to_curve_elligator2(&u[0]);
+    let q_1 = ed25519_map_to_curve_elligator2(&u[1]);
+
+    // Clear the cofactor 8.
+    let mut point = q_0 + q_1;
+    for _ in 0..3 {
+        point = Ed25519::ec_double(&point);
+    }
+    point
+}
+
+/// Computes `hash_to_curve(msg)` of the suite `secp256k1_XMD:SHA-256_SSWU_RO_` with the domain
+/// separation tag `dst`.
+pub fn secp256k1_hash_to_curve(message: &[u8], dst: &[u8]) -> AffinePoint<Secp256k1> {
+    let u = hash_to_field::<Secp256k1BaseField, SHA256, SHA256_CYCLE_LENGTH>(message, dst, 2);
+    secp256k1_map_to_curve_sswu(&u[0]) + secp256k1_map_to_curve_sswu(&u[1])
+}
+
+/// The number of rows of a trace for `ed25519_hash_to_curve_batch`.
+pub fn ed25519_hash_to_curve_num_rows(instances: &[HashToCurveRegister<Ed25519>]) -> usize {
+    let expands = instances
+        .iter()
+        .map(|instance| instance.expand.clone())
+        .collect::<Vec<_>>();
+    expand_message_xmd_num_rows::<SHA512, SHA512_CYCLE_LENGTH>(&expands)
+}
+
+/// The number of rows of a trace for `secp256k1_hash_to_curve_batch`.
+pub fn secp256k1_hash_to_curve_num_rows(instances: &[HashToCurveRegister<Secp256k1>]) -> usize {
+    let expands = instances
+        .iter()
+        .map(|instance| instance.expand.clone())
+        .collect::<Vec<_>>();
+    expand_message_xmd_num_rows::<SHA256, SHA256_CYCLE_LENGTH>(&expands)
+}
+
+pub trait HashToCurveBuilder: Builder {
+    /// Allocates the witnesses of a map to curve.
+    fn alloc_public_map_to_curve<P: FieldParameters>(&mut self) -> MapToCurveRegister<P> {
+        let is_square = self.alloc_public::<BitRegister>();
+        let root = self.alloc_public::<FieldRegister<P>>();
+        let y = self.alloc_public::<FieldRegister<P>>();
+        MapToCurveRegister { is_square, root, y }
+    }
+
+    /// Allocates the public inputs of a hash to Ed25519 of a message of at most
+    /// `max_message_length` bytes with the domain separation tag `dst`.
+    fn alloc_public_ed25519_hash_to_curve(
+        &mut self,
+        max_message_length: usize,
+        dst: &[u8],
+    ) -> HashToCurveRegister<Ed25519> {
+        let expand = self.alloc_public_expand_message::<SHA512, SHA512_CYCLE_LENGTH>(
+            max_message_length,
+            dst,
+            2 * HASH_TO_FIELD_LENGTH,
+        );
+        let maps = [(); 2].map(|_| self.alloc_public_map_to_curve());
+        let point = AffinePointRegister::new(self.alloc_public(), self.alloc_public());
+        HashToCurveRegister {
+            expand,
+            maps,
+            point,
+        }
+    }
+
+    /// Allocates the public inputs of a hash to secp256k1 of a message of at most
+    /// `max_message_length` bytes with the domain separation tag `dst`.
+    fn alloc_public_secp256k1_hash_to_curve(
+        &mut self,
+        max_message_length: usize,
+        dst: &[u8],
+    ) -> HashToCurveRegister<Secp256k1> {
+        let expand = self.alloc_public_expand_message::<SHA256, SHA256_CYCLE_LENGTH>(
+            max_message_length,
+            dst,
+            2 * HASH_TO_FIELD_LENGTH,
+        );
+        let maps = [(); 2].map(|_| self.alloc_public_map_to_curve());
+        let point = AffinePointRegister::new(self.alloc_public(), self.alloc_public());
+        HashToCurveRegister {
+            expand,
+            maps,
+            point,
+        }
+    }
+
+    /// Hashes a batch of messages to Ed25519 with the suite `edwards25519_XMD:SHA-512_ELL2_RO_`.
+    ///
+    /// The messages are expanded in a single SHA-512 AIR, so this can only be called once per
+    /// builder and not together with `sha`.
+    fn ed25519_hash_to_curve_batch(&mut self, instances: &[HashToCurveRegister<Ed25519>])
+    where
+        Ed25519: EllipticCurveAir<Self::Parameters>,
+        Self::Instruction: FromFieldInstruction<Ed25519BaseField>,
+        SHA512: SHAir<Self, SHA512_CYCLE_LENGTH>,
+    {
+        let num_rows = ed25519_hash_to_curve_num_rows(instances);
+        let expands = instances
+            .iter()
+            .map(|instance| instance.expand.clone())
+            .collect::<Vec<_>>();
+        let min_rounds = num_rows / (2 * SHA512_CYCLE_LENGTH) + 1;
+        self.expand_message_xmd_batch::<SHA512, SHA512_CYCLE_LENGTH>(&expands, min_rounds);
+
+        for instance in instances {
+            let uniform_bytes = instance.expand.uniform_bytes();
+            let [q_0, q_1] = [0, 1].map(|i| {
+                let bytes =
+                    &uniform_bytes[i * HASH_TO_FIELD_LENGTH..(i + 1) * HASH_TO_FIELD_LENGTH];
+                let u = self.hash_to_field::<Ed25519BaseField>(bytes);
+                self.ed25519_map_to_curve_elligator2(&u, &instance.maps[i])
+            });
+
+            // Clear the cofactor 8.
+            let mut point = self.add(q_0, q_1);
+            for _ in 0..3 {
+                point = self.double(point);
+            }
+            self.assert_equal(&point.x, &instance.point.x);
+            self.assert_equal(&point.y, &instance.point.y);
+        }
+    }
+
+    /// Hashes a batch of messages to secp256k1 with the suite `secp256k1_XMD:SHA-256_SSWU_RO_`.
+    ///
+    /// The messages are expanded in a single SHA-256 AIR, so this can only be called once per
+    /// builder and not together with `sha`. The sum of the two mapped points is computed with the
+    /// incomplete addition formula, which rejects the negligible case of equal x-coordinates.
+    fn secp256k1_hash_to_curve_batch(&mut self, instances: &[HashToCurveRegister<Secp256k1>])
+    where
+        Secp256k1: EllipticCurveAir<Self::Parameters>,
+        Self::Instruction: FromFieldInstruction<Secp256k1BaseField>,
+        SHA256: SHAir<Self, SHA256_CYCLE_LENGTH>,
+    {
+        let num_rows = secp256k1_hash_to_curve_num_rows(instances);
+        let expands = instances
+            .iter()
+            .map(|instance| instance.expand.clone())
+            .collect::<Vec<_>>();
+        let min_rounds = num_rows / (2 * SHA256_CYCLE_LENGTH) + 1;
+        self.expand_message_xmd_batch::<SHA256, SHA256_CYCLE_LENGTH>(&expands, min_rounds);
+
+        for instance in instances {
+            let uniform_bytes = instance.expand.uniform_bytes();
+            let [q_0, q_1] = [0, 1].map(|i| {
+                let bytes =
+                    &uniform_bytes[i * HASH_TO_FIELD_LENGTH..(i + 1) * HASH_TO_FIELD_LENGTH];
+                let u = self.hash_to_field::<Secp256k1BaseField>(bytes);
+                self.secp256k1_map_to_curve_sswu(&u, &instance.maps[i])
+            });
+
+            let point = self.add(q_0, q_1);
+            self.assert_equal(&point.x, &instance.point.x);
+            self.assert_equal(&point.y, &instance.point.y);
+        }
+    }
+}
+
+impl<B: Builder> HashToCurveBuilder for B {}
+
+/// Returns `sgn0(value)` of RFC 9380, the parity of the canonical representative of `value`.
+pub(crate) fn sgn0(value: &BigUint) -> bool {
+    value.bit(0)
+}
+
+/// Returns whether `value` is a square of the field.
+pub(crate) fn is_square<P: FieldParameters>(value: &BigUint) -> bool {
+    let p = P::modulus();
+    value.modpow(&((&p - 1u32) >> 1), &p) != &p - 1u32
+}
+
+/// Asserts that the public `value` is canonical with `sgn0(value) = sign`.
+pub(crate) fn assert_public_sign<B: Builder, P: FieldParameters>(
+    builder: &mut B,
+    value: &FieldRegister<P>,
+    sign: ArithmeticExpression<B::Field>,
+) where
+    B::Instruction: FromFieldInstruction<P>,
+{
+    builder.api().fp_assert_canonical(value);
+
+    // The least significant limb minus the sign is twice a `u16`.
+    let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+    let half = B::Field::from_canonical_u32(2).inverse();
+    builder.public_expression::<U16Register>((limbs.get(0).expr() - sign) * half);
+}
+
+/// Asserts that the public `lhs` and `rhs` are canonical with `sgn0(lhs) = sgn0(rhs)`.
+pub(crate) fn assert_public_same_sign<B: Builder, P: FieldParameters>(
+    builder: &mut B,
+    lhs: &FieldRegister<P>,
+    rhs: &FieldRegister<P>,
+) where
+    B::Instruction: FromFieldInstruction<P>,
+{
+    builder.api().fp_assert_canonical(lhs);
+    builder.api().fp_assert_canonical(rhs);
+
+    // The difference of the least significant limbs, shifted by `2^NB_BITS_PER_LIMB`, is twice a
+    // limb.
+    let lhs_limbs = ArrayRegister::<U16Register>::from_register_unsafe(*lhs.register());
+    let rhs_limbs = ArrayRegister::<U16Register>::from_register_unsafe(*rhs.register());
+    let half = B::Field::from_canonical_u32(2).inverse();
+    builder.public_expression::<U16Register>(
+        (lhs_limbs.get(0).expr() - rhs_limbs.get(0).expr()
+            + B::Field::from_canonical_u32(1 << P::NB_BITS_PER_LIMB))
+            * half,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::util::timing::TimingTree;
+
+    use super::*;
+    use crate::chip::ec::edwards::ed25519::instruction::Ed25519VerifyInstruction;
+    use crate::chip::ec::weierstrass::secp256k1::instruction::Secp256k1SchnorrInstruction;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::AirParameters;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+
+    const ED25519_DST: &[u8] = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+
+    const SECP256K1_DST: &[u8] = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+    /// The test vectors of RFC 9380, appendix J.5.1, as `(msg, P.x, P.y)`.
+    const ED25519_VECTORS: [(&str, &str, &str); 2] = [
+        (
+            "",
+            "3c3da6925a3c3c268448dcabb47ccde5439559d9599646a8260e47b1e4822fc6",
+            "09a6c8561a0b22bef63124c588ce4c62ea83a3c899763af26d795302e115dc21",
+        ),
+        (
+            "abc",
+            "608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad",
+            "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531",
+        ),
+    ];
+
+    /// The test vectors of RFC 9380, appendix J.8.1, as `(msg, P.x, P.y)`.
+    const SECP256K1_VECTORS: [(&str, &str, &str); 2] = [
+        (
+            "",
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+        ),
+        (
+            "abc",
+            "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+            "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+        ),
+    ];
+
+    fn vector_point<E: EllipticCurve>(x: &str, y: &str) -> AffinePoint<E> {
+        AffinePoint::new(
+            BigUint::parse_bytes(x.as_bytes(), 16).unwrap(),
+            BigUint::parse_bytes(y.as_bytes(), 16).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_ed25519_hash_to_curve_pure() {
+        for (message, x, y) in ED25519_VECTORS {
+            let point = ed25519_hash_to_curve(message.as_bytes(), ED25519_DST);
+            assert_eq!(point, vector_point(x, y));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_hash_to_curve_pure() {
+        for (message, x, y) in SECP256K1_VECTORS {
+            let point = secp256k1_hash_to_curve(message.as_bytes(), SECP256K1_DST);
+            assert_eq!(point, vector_point(x, y));
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Ed25519HashToCurveTest;
+
+    impl AirParameters for Ed25519HashToCurveTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = Ed25519VerifyInstruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 0;
+        const NUM_FREE_COLUMNS: usize = 836;
+        const EXTENDED_COLUMNS: usize = 1830;
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct Secp256k1HashToCurveTest;
+
+    impl AirParameters for Secp256k1HashToCurveTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = Secp256k1SchnorrInstruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 0;
+        const NUM_FREE_COLUMNS: usize = 440;
+        const EXTENDED_COLUMNS: usize = 1000;
+    }
+
+    #[test]
+    fn test_ed25519_hash_to_curve_batch() {
+        type F = GoldilocksField;
+        type L = Ed25519HashToCurveTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("Ed25519 hash to curve", log::Level::Debug);
+
+        let mut builder = BytesBuilder::<L>::new();
+
+        let instances = ED25519_VECTORS
+            .iter()
+            .map(|_| builder.alloc_public_ed25519_hash_to_curve(16, ED25519_DST))
+            .collect::<Vec<_>>();
+        builder.ed25519_hash_to_curve_batch(&instances);
+
+        let num_rows = ed25519_hash_to_curve_num_rows(&instances);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (instance, (message, _, _)) in instances.iter().zip(ED25519_VECTORS.iter()) {
+            instance.write(&mut writer, message.as_bytes());
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+
+    #[test]
+    fn test_secp256k1_hash_to_curve_batch() {
+        type F = GoldilocksField;
+        type L = Secp256k1HashToCurveTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("secp256k1 hash to curve", log::Level::Debug);
+
+        let mut builder = BytesBuilder::<L>::new();
+
+        let instances = SECP256K1_VECTORS
+            .iter()
+            .map(|_| builder.alloc_public_secp256k1_hash_to_curve(16, SECP256K1_DST))
+            .collect::<Vec<_>>();
+        builder.secp256k1_hash_to_curve_batch(&instances);
+
+        let num_rows = secp256k1_hash_to_curve_num_rows(&instances);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (instance, (message, _, _)) in instances.iter().zip(SECP256K1_VECTORS.iter()) {
+            instance.write(&mut writer, message.as_bytes());
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
@@ -0,0 +1,256 @@
+use num::{BigUint, Num, One, Zero};
+
+use super::{assert_public_same_sign, is_square, sgn0, MapToCurveRegister};
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::ec::weierstrass::secp256k1::{Secp256k1, Secp256k1BaseField};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::machine::builder::Builder;
+
+/// The coefficient `A'` of the curve `E': y^2 = x^3 + A' * x + B'` isogenous to secp256k1.
+const ISO_A: &str = "3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533";
+
+/// The coefficient `B'` of the curve `E'` isogenous to secp256k1.
+const ISO_B: u32 = 1771;
+
+/// The absolute value of the non-square `Z = -11` of the simplified SWU map.
+const MINUS_Z: u32 = 11;
+
+/// The coefficients of the numerator of the x-coordinate of the 3-isogeny from `E'` to
+/// secp256k1, starting with the constant coefficient.
+const ISO_X_NUMERATOR: [&str; 4] = [
+    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+    "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+    "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+    "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+];
+
+/// The coefficients of the monic denominator of the x-coordinate of the 3-isogeny.
+const ISO_X_DENOMINATOR: [&str; 3] = [
+    "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+    "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+    "01",
+];
+
+/// The coefficients of the numerator of the y-coordinate of the 3-isogeny.
+const ISO_Y_NUMERATOR: [&str; 4] = [
+    "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+    "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+    "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+    "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+];
+
+/// The coefficients of the monic denominator of the y-coordinate of the 3-isogeny.
+const ISO_Y_DENOMINATOR: [&str; 4] = [
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+    "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+    "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+    "01",
+];
+
+fn hex_biguint(value: &str) -> BigUint {
+    BigUint::from_str_radix(value, 16).unwrap()
+}
+
+fn inverse(value: &BigUint) -> BigUint {
+    let p = Secp256k1BaseField::modulus();
+    value.modpow(&(&p - 2u32), &p)
+}
+
+/// A square root of a square `value`, which is `value^((p + 1) / 4)` since `p = 3 mod 4`.
+fn sqrt(value: &BigUint) -> BigUint {
+    let p = Secp256k1BaseField::modulus();
+    value.modpow(&((&p + 1u32) >> 2), &p)
+}
+
+/// Evaluates the polynomial with the given coefficients at `x`.
+fn evaluate(coefficients: &[&str], x: &BigUint) -> BigUint {
+    let p = Secp256k1BaseField::modulus();
+    coefficients
+        .iter()
+        .rev()
+        .fold(BigUint::zero(), |acc, c| (acc * x + hex_biguint(c)) % &p)
+}
+
+/// The candidate x-coordinates of the simplified SWU map of `u` on `E'` and their images under
+/// `g(x) = x^3 + A' * x + B'`.
+fn sswu_candidates(u: &BigUint) -> [(BigUint, BigUint); 2] {
+    let p = Secp256k1BaseField::modulus();
+    let a = hex_biguint(ISO_A);
+    let b = BigUint::from(ISO_B);
+    let z = &p - MINUS_Z;
+
+    let z_u2 = (&z * u * u) % &p;
+    let tv1 = (&z_u2 * &z_u2 + &z_u2) % &p;
+    let x_1 = if tv1.is_zero() {
+        (&b * inverse(&(&z * &a % &p))) % &p
+    } else {
+        ((&p - &b) * inverse(&a) % &p * (BigUint::one() + inverse(&tv1))) % &p
+    };
+    let g = |x: &BigUint| (x * ((x * x + &a) % &p) + &b) % &p;
+    let g_x_1 = g(&x_1);
+    let x_2 = (&z_u2 * &x_1) % &p;
+    let g_x_2 = g(&x_2);
+    [(x_1, g_x_1), (x_2, g_x_2)]
+}
+
+/// The witnesses `(is_square, root, y)` of the simplified SWU map of `u`.
+pub(crate) fn sswu_witness(u: &BigUint) -> (bool, BigUint, BigUint) {
+    let p = Secp256k1BaseField::modulus();
+    let [(_, g_x_1), (_, g_x_2)] = sswu_candidates(u);
+
+    let is_square = is_square::<Secp256k1BaseField>(&g_x_1);
+    let (root, g_x) = if is_square {
+        (sqrt(&g_x_1), g_x_1)
+    } else {
+        (sqrt(&((&p - MINUS_Z) * &g_x_1 % &p)), g_x_2)
+    };
+    let y = sqrt(&g_x);
+    let y = if sgn0(&y) != sgn0(u) {
+        (&p - y) % &p
+    } else {
+        y
+    };
+    (is_square, root, y)
+}
+
+/// Computes `map_to_curve(u)` of the suite `secp256k1_XMD:SHA-256_SSWU_RO_`, which is the
+/// simplified SWU map to `E'` followed by the 3-isogeny to secp256k1.
+///
+/// Panics in the exceptional cases of the isogeny, which map to the point at infinity.
+pub fn secp256k1_map_to_curve_sswu(u: &BigUint) -> AffinePoint<Secp256k1> {
+    let p = Secp256k1BaseField::modulus();
+    let [(x_1, _), (x_2, _)] = sswu_candidates(u);
+    let (is_square, _, y) = sswu_witness(u);
+    let x = if is_square { x_1 } else { x_2 };
+
+    let x_denominator = evaluate(&ISO_X_DENOMINATOR, &x);
+    let y_denominator = evaluate(&ISO_Y_DENOMINATOR, &x);
+    assert!(!x_denominator.is_zero() && !y_denominator.is_zero());
+    let iso_x = evaluate(&ISO_X_NUMERATOR, &x) * inverse(&x_denominator) % &p;
+    let iso_y = y * evaluate(&ISO_Y_NUMERATOR, &x) % &p * inverse(&y_denominator) % &p;
+    AffinePoint::new(iso_x, iso_y)
+}
+
+pub trait SimplifiedSWUBuilder: Builder {
+    /// Maps `u` to secp256k1 with the simplified SWU map to the isogenous curve `E'` and the
+    /// 3-isogeny, as in `map_to_curve` of the suite `secp256k1_XMD:SHA-256_SSWU_RO_`.
+    ///
+    /// The exceptional cases of the map and of the isogeny, which have negligible probability,
+    /// are rejected.
+    fn secp256k1_map_to_curve_sswu(
+        &mut self,
+        u: &FieldRegister<Secp256k1BaseField>,
+        witness: &MapToCurveRegister<Secp256k1BaseField>,
+    ) -> AffinePointRegister<Secp256k1>
+    where
+        Self::Instruction: FromFieldInstruction<Secp256k1BaseField>,
+    {
+        let p = Secp256k1BaseField::modulus();
+        let one = self.api().fp_one::<Secp256k1BaseField>();
+        let a = self.api().fp_constant(&hex_biguint(ISO_A));
+        let b = self.api().fp_constant(&BigUint::from(ISO_B));
+        let z = self.api().fp_constant(&(&p - MINUS_Z));
+        let minus_b_over_a = self
+            .api()
+            .fp_constant(&((&p - ISO_B) * inverse(&hex_biguint(ISO_A)) % &p));
+
+        // The candidates `x_1 = (-B' / A') * (1 + 1 / tv1)`, where `tv1 = Z^2 * u^4 + Z * u^2`,
+        // and `x_2 = Z * u^2 * x_1`, with `g(x_2) = (Z * u^2)^3 * g(x_1)`.
+        let u_2 = self.api().fp_mul(u, u);
+        let z_u2 = self.api().fp_mul(&z, &u_2);
+        let z_u2_squared = self.api().fp_mul(&z_u2, &z_u2);
+        let tv1 = self.api().fp_add(&z_u2_squared, &z_u2);
+        let tv1_plus_1 = self.api().fp_add(&tv1, &one);
+        let numerator = self.api().fp_mul(&minus_b_over_a, &tv1_plus_1);
+        let x_1 = self.api().fp_div(&numerator, &tv1);
+        let x_1_squared = self.api().fp_mul(&x_1, &x_1);
+        let x_1_squared_a = self.api().fp_add(&x_1_squared, &a);
+        let x_1_cubed_a_x_1 = self.api().fp_mul(&x_1, &x_1_squared_a);
+        let g_x_1 = self.api().fp_add(&x_1_cubed_a_x_1, &b);
+        let x_2 = self.api().fp_mul(&z_u2, &x_1);
+        let z_u2_cubed = self.api().fp_mul(&z_u2_squared, &z_u2);
+        let g_x_2 = self.api().fp_mul(&z_u2_cubed, &g_x_1);
+
+        // Either `g(x_1)` or `Z * g(x_1)` is a square, since `Z` is not a square.
+        let z_g_x_1 = self.api().fp_mul(&z, &g_x_1);
+        let square = self.select(witness.is_square, &g_x_1, &z_g_x_1);
+        let root_squared = self.api().fp_mul(&witness.root, &witness.root);
+        self.assert_equal(&root_squared, &square);
+
+        let x = self.select(witness.is_square, &x_1, &x_2);
+        let g_x = self.select(witness.is_square, &g_x_1, &g_x_2);
+        let y_squared = self.api().fp_mul(&witness.y, &witness.y);
+        self.assert_equal(&y_squared, &g_x);
+        assert_public_same_sign(self, &witness.y, u);
+
+        // The 3-isogeny `(x_num / x_den, y * y_num / y_den)` to secp256k1.
+        let polynomials: [&[&str]; 4] = [
+            &ISO_X_NUMERATOR,
+            &ISO_X_DENOMINATOR,
+            &ISO_Y_NUMERATOR,
+            &ISO_Y_DENOMINATOR,
+        ];
+        let [x_numerator, x_denominator, y_numerator, y_denominator] =
+            polynomials.map(|coefficients| evaluate_polynomial(self, coefficients, &x));
+        let iso_x = self.api().fp_div(&x_numerator, &x_denominator);
+        let y_y_numerator = self.api().fp_mul(&witness.y, &y_numerator);
+        let iso_y = self.api().fp_div(&y_y_numerator, &y_denominator);
+
+        AffinePointRegister::new(iso_x, iso_y)
+    }
+}
+
+impl<B: Builder> SimplifiedSWUBuilder for B {}
+
+/// Evaluates the polynomial with the given coefficients at `x` with Horner's rule.
+fn evaluate_polynomial<B: Builder>(
+    builder: &mut B,
+    coefficients: &[&str],
+    x: &FieldRegister<Secp256k1BaseField>,
+) -> FieldRegister<Secp256k1BaseField>
+where
+    B::Instruction: FromFieldInstruction<Secp256k1BaseField>,
+{
+    let (leading, rest) = coefficients.split_last().unwrap();
+    let mut value = builder.api().fp_constant(&hex_biguint(leading));
+    for coefficient in rest.iter().rev() {
+        let coefficient = builder.api().fp_constant(&hex_biguint(coefficient));
+        let value_x = builder.api().fp_mul(&value, x);
+        value = builder.api().fp_add(&value_x, &coefficient);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+
+    use super::*;
+
+    #[test]
+    fn test_secp256k1_map_to_curve_sswu() {
+        let p = Secp256k1BaseField::modulus();
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            let u = rng.gen_biguint(256) % &p;
+            let (is_square, root, y) = sswu_witness(&u);
+            assert_eq!(sgn0(&y), sgn0(&u));
+            let [(_, g_x_1), _] = sswu_candidates(&u);
+            let square = if is_square {
+                g_x_1
+            } else {
+                (&p - MINUS_Z) * g_x_1 % &p
+            };
+            assert_eq!(&root * &root % &p, square);
+
+            // The point satisfies `y^2 = x^3 + 7`.
+            let point = secp256k1_map_to_curve_sswu(&u);
+            assert_eq!(
+                &point.y * &point.y % &p,
+                (&point.x * &point.x % &p * &point.x + 7u32) % &p
+            );
+        }
+    }
+}
@@ -1,2 +1,9 @@
 pub mod builder;
+pub mod ecdsa;
+pub mod eddsa;
+pub mod fixed_base;
+pub mod hash_to_curve;
+pub mod msm;
 pub mod scalar_mul;
+pub mod schnorr;
+pub mod x25519;
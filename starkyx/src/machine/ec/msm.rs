@@ -0,0 +1,193 @@
+use core::marker::PhantomData;
+
+use itertools::Itertools;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::ec::point::AffinePoint;
+use crate::chip::ec::EllipticCurve;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::utils::biguint_to_bits_le;
+use crate::math::prelude::*;
+
+/// The number of scalar bits of each window of a multi-scalar multiplication.
+pub const MSM_WINDOW_BITS: usize = 4;
+
+/// The number of buckets of each window of a multi-scalar multiplication.
+pub const MSM_NB_BUCKETS: usize = 1 << MSM_WINDOW_BITS;
+
+/// The witness of a multi-scalar multiplication, written by the prover.
+///
+/// The bits of the scalars are checked against their limbs. The bucket counters are not
+/// constrained, as the memory argument only balances if every bucket update reads the value
+/// written by the previous update of the same bucket.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MSMRegister<E> {
+    /// The little-endian bits of each scalar.
+    pub bits: Vec<ArrayRegister<BitRegister>>,
+    /// For each scalar and window, the number of previous scalars with the same digit.
+    pub counts: Vec<ArrayRegister<ElementRegister>>,
+    /// For each window and digit, the number of scalars with that digit.
+    pub bucket_sizes: ArrayRegister<ElementRegister>,
+    _marker: PhantomData<E>,
+}
+
+impl<E: EllipticCurve> MSMRegister<E> {
+    pub fn new(
+        bits: Vec<ArrayRegister<BitRegister>>,
+        counts: Vec<ArrayRegister<ElementRegister>>,
+        bucket_sizes: ArrayRegister<ElementRegister>,
+    ) -> Self {
+        Self {
+            bits,
+            counts,
+            bucket_sizes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Writes the witness of the multiplication with scalars `scalars`.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, scalars: &[BigUint]) {
+        let mut bucket_sizes = vec![0; self.bucket_sizes.len()];
+        for ((bits_reg, counts_reg), scalar) in
+            self.bits.iter().zip_eq(self.counts.iter()).zip_eq(scalars)
+        {
+            let bits = biguint_to_bits_le(scalar, bits_reg.len());
+            for (bit_reg, bit) in bits_reg.iter().zip(bits.iter()) {
+                writer.write(&bit_reg, &W::Field::from_canonical_u8(*bit as u8));
+            }
+
+            for (j, (digit_bits, count_reg)) in bits
+                .chunks_exact(MSM_WINDOW_BITS)
+                .zip_eq(counts_reg.iter())
+                .enumerate()
+            {
+                let digit = digit_bits
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, bit| (acc << 1) | *bit as usize);
+                let size = &mut bucket_sizes[j * MSM_NB_BUCKETS + digit];
+                writer.write(&count_reg, &W::Field::from_canonical_usize(*size));
+                *size += 1;
+            }
+        }
+
+        for (size_reg, size) in self.bucket_sizes.iter().zip(bucket_sizes) {
+            writer.write(&size_reg, &W::Field::from_canonical_usize(size));
+        }
+    }
+}
+
+/// The constant points which keep the partial sums of a multi-scalar multiplication away from
+/// the identity.
+///
+/// Every bucket starts at `bucket`, and the running sums of the buckets of each window start at
+/// `bucket` as well, so that the sum of the running sums of a window is offset by `135 * bucket`.
+/// After combining the windows, the total offset is cancelled by adding `correction`.
+#[derive(Debug, Clone)]
+pub struct MSMOffsets<E> {
+    pub bucket: AffinePoint<E>,
+    pub correction: AffinePoint<E>,
+}
+
+impl<E: EllipticCurve> MSMOffsets<E> {
+    /// The offset of the sum of the running sums of a window, in multiples of `bucket`.
+    pub const WINDOW_OFFSET: usize = MSM_NB_BUCKETS - 1 + (MSM_NB_BUCKETS - 1) * MSM_NB_BUCKETS / 2;
+
+    pub fn new(nb_windows: usize) -> Self {
+        let generator = E::ec_generator();
+        let bucket = &generator * &generator.x;
+        let window_offset = &bucket * &BigUint::from(Self::WINDOW_OFFSET);
+
+        let mut offset = window_offset.clone();
+        for _ in 1..nb_windows {
+            for _ in 0..MSM_WINDOW_BITS {
+                offset = E::ec_double(&offset);
+            }
+            offset = E::ec_add(&offset, &window_offset);
+        }
+        let correction = E::ec_neg(&offset);
+
+        Self { bucket, correction }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::bigint::RandBigInt;
+    use rand::thread_rng;
+
+    use super::*;
+    use crate::chip::ec::edwards::ed25519::params::Ed25519;
+    use crate::chip::ec::weierstrass::secp256k1::Secp256k1;
+
+    /// Runs the bucket method with offsets natively, in the same order as the AIR.
+    fn test_msm_offsets<E: EllipticCurve>() {
+        let nb_windows = E::nb_scalar_bits() / MSM_WINDOW_BITS;
+        let offsets = MSMOffsets::<E>::new(nb_windows);
+
+        let mut rng = thread_rng();
+        let num_points = 5;
+        let points = (0..num_points)
+            .map(|_| E::ec_generator() * rng.gen_biguint(256))
+            .collect::<Vec<_>>();
+        let scalars = (0..num_points)
+            .map(|_| rng.gen_biguint(E::nb_scalar_bits() as u64 - 8))
+            .collect::<Vec<_>>();
+
+        let mut buckets = vec![vec![offsets.bucket.clone(); MSM_NB_BUCKETS]; nb_windows];
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let bits = biguint_to_bits_le(scalar, E::nb_scalar_bits());
+            for (j, digit_bits) in bits.chunks_exact(MSM_WINDOW_BITS).enumerate() {
+                let digit = digit_bits
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, bit| (acc << 1) | *bit as usize);
+                buckets[j][digit] = E::ec_add(&buckets[j][digit], point);
+            }
+        }
+
+        let window_sums = buckets
+            .iter()
+            .map(|window| {
+                let mut running = offsets.bucket.clone();
+                let mut total = None;
+                for bucket in window.iter().skip(1).rev() {
+                    running = E::ec_add(&running, bucket);
+                    total = Some(total.map_or(running.clone(), |t| E::ec_add(&t, &running)));
+                }
+                total.unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let mut result = window_sums.last().unwrap().clone();
+        for window_sum in window_sums.iter().rev().skip(1) {
+            for _ in 0..MSM_WINDOW_BITS {
+                result = E::ec_double(&result);
+            }
+            result = E::ec_add(&result, window_sum);
+        }
+        result = E::ec_add(&result, &offsets.correction);
+
+        let expected = points
+            .iter()
+            .zip(scalars.iter())
+            .map(|(point, scalar)| point * scalar)
+            .reduce(|acc, point| E::ec_add(&acc, &point))
+            .unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_msm_offsets_ed25519() {
+        test_msm_offsets::<Ed25519>();
+    }
+
+    #[test]
+    fn test_msm_offsets_secp256k1() {
+        test_msm_offsets::<Secp256k1>();
+    }
+}
@@ -0,0 +1,567 @@
+use num::{BigUint, Zero};
+use plonky2::util::log2_ceil;
+use serde::{Deserialize, Serialize};
+
+use super::builder::EllipticCurveBuilder;
+use super::ecdsa::field_to_scalar;
+use crate::chip::ec::gadget::EllipticCurveAirWriter;
+use crate::chip::ec::point::{AffinePoint, AffinePointRegister};
+use crate::chip::ec::scalar::ECScalarRegister;
+use crate::chip::ec::weierstrass::secp256k1::{
+    Secp256k1, Secp256k1BaseField, Secp256k1ScalarField,
+};
+use crate::chip::ec::{ECInstructions, EllipticCurve, EllipticCurveAir};
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::parameters::FieldParameters;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::machine::builder::Builder;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::machine::hash::sha::builder::SHABuilder;
+use crate::machine::hash::sha::hmac::HMACPure;
+use crate::machine::hash::sha::sha256::register::SHA256DigestRegister;
+use crate::machine::hash::sha::sha256::SHA256;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// The tag of the challenge hash of BIP-340.
+const CHALLENGE_TAG: &[u8] = b"BIP0340/challenge";
+
+/// The length in bytes of a big-endian field element, as in x-only public keys and signatures.
+const FIELD_LENGTH: usize = 32;
+
+/// The length in bytes of the prefix `SHA256(tag) || SHA256(tag)` of a tagged hash.
+const TAG_PREFIX_LENGTH: usize = 64;
+
+/// The cycle length of the SHA-256 AIR.
+const SHA256_CYCLE_LENGTH: usize = 64;
+
+/// The public inputs of a BIP-340 Schnorr signature verification.
+///
+/// The signature `(r, s)` of the message `m` under the x-only public key `P.x` is valid if
+/// `s * G = R + e * P`, where `P` and `R` are the points of even y-coordinate with x-coordinates
+/// `P.x` and `r`, and `e = SHA256(SHA256(tag) || SHA256(tag) || r || P.x || m) mod n` is the
+/// tagged hash with the tag `BIP0340/challenge`. The y-coordinates of `P` and `R`, the digest and
+/// the points `s * G` and `e * P` are computed by the prover and constrained by the verification.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SchnorrSignatureRegister {
+    pub public_key: AffinePointRegister<Secp256k1>,
+    pub r: AffinePointRegister<Secp256k1>,
+    pub s: FieldRegister<Secp256k1ScalarField>,
+    pub hash_input: MessageRegister,
+    pub digest: SHA256DigestRegister,
+    pub s_g: AffinePointRegister<Secp256k1>,
+    pub e_p: AffinePointRegister<Secp256k1>,
+}
+
+impl SchnorrSignatureRegister {
+    /// The maximal length of a signed message.
+    pub fn max_message_length(&self) -> usize {
+        self.hash_input.max_length() - TAG_PREFIX_LENGTH - 2 * FIELD_LENGTH
+    }
+
+    /// Writes the signature `r || s` of `message` under the x-only `public_key`, together with
+    /// the intermediate values of its verification.
+    ///
+    /// Panics if the public key or `r` is not the x-coordinate of a curve point.
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        public_key: &[u8; 32],
+        message: &[u8],
+        signature: &[u8; 64],
+    ) {
+        let hash_input = challenge_hash_input(&signature[..32], public_key, message);
+        let digest = SHA256::digest(&hash_input);
+        let e = BigUint::from_bytes_be(&digest) % Secp256k1ScalarField::modulus();
+        let s = BigUint::from_bytes_be(&signature[32..]);
+
+        let public_key_point =
+            lift_x(&BigUint::from_bytes_be(public_key)).expect("Invalid public key");
+        let r_point = lift_x(&BigUint::from_bytes_be(&signature[..32])).expect("Invalid signature");
+
+        writer.write_ec_point(&self.public_key, &public_key_point);
+        writer.write_ec_point(&self.r, &r_point);
+        writer.write(
+            &self.s,
+            &to_le_limbs_polynomial::<W::Field, Secp256k1ScalarField>(&s),
+        );
+        self.hash_input.write(writer, &hash_input);
+
+        for (i, byte) in digest.iter().enumerate() {
+            writer.write(
+                &digest_byte(&self.digest, i),
+                &W::Field::from_canonical_u8(*byte),
+            );
+        }
+
+        writer.write_ec_point(&self.s_g, &(Secp256k1::ec_generator() * s));
+        writer.write_ec_point(&self.e_p, &(&public_key_point * e));
+    }
+}
+
+/// The bytes `SHA256(tag) || SHA256(tag) || r || public_key || message` hashed for the challenge.
+fn challenge_hash_input(r: &[u8], public_key: &[u8], message: &[u8]) -> Vec<u8> {
+    let tag_hash = SHA256::digest(CHALLENGE_TAG);
+    [
+        tag_hash.as_slice(),
+        tag_hash.as_slice(),
+        r,
+        public_key,
+        message,
+    ]
+    .concat()
+}
+
+/// The challenge `e` of a signature with commitment `r` of `message` under the x-only
+/// `public_key`.
+pub fn schnorr_bip340_challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> BigUint {
+    let hash_input = challenge_hash_input(r, public_key, message);
+    BigUint::from_bytes_be(&SHA256::digest(&hash_input)) % Secp256k1ScalarField::modulus()
+}
+
+/// Returns the curve point with x-coordinate `x` and an even y-coordinate, if any.
+///
+/// Since the modulus is `3 mod 4`, the square root of `c = x^3 + 7` is `c^((p + 1) / 4)` if `c`
+/// is a square.
+pub fn lift_x(x: &BigUint) -> Option<AffinePoint<Secp256k1>> {
+    let p = Secp256k1BaseField::modulus();
+    if *x >= p {
+        return None;
+    }
+    let c = (x * x * x + Secp256k1::b_int()) % &p;
+    let y = c.modpow(&((&p + 1u32) >> 2), &p);
+    if (&y * &y) % &p != c {
+        return None;
+    }
+    let y = if y.bit(0) { &p - y } else { y };
+    Some(AffinePoint::new(x.clone(), y))
+}
+
+/// Verifies the signature `r || s` of `message` under the x-only `public_key` as in BIP-340.
+///
+/// The verification is done as in `schnorr_bip340_verify_batch`, which also rejects the
+/// signatures with `s = 0`, `e = 0` or `R = e * P` or `R = -e * P`. These only occur with
+/// negligible probability for honestly generated signatures.
+pub fn schnorr_bip340_verify(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let r: &[u8; 32] = signature[..32].try_into().unwrap();
+    let s = BigUint::from_bytes_be(&signature[32..]);
+    let public_key_point = match lift_x(&BigUint::from_bytes_be(public_key)) {
+        Some(point) => point,
+        None => return false,
+    };
+    let r_point = match lift_x(&BigUint::from_bytes_be(r)) {
+        Some(point) => point,
+        None => return false,
+    };
+    if s.is_zero() || s >= Secp256k1ScalarField::modulus() {
+        return false;
+    }
+    let e = schnorr_bip340_challenge(r, public_key, message);
+    if e.is_zero() {
+        return false;
+    }
+    let e_p = &public_key_point * e;
+    if e_p.x == r_point.x {
+        return false;
+    }
+    Secp256k1::ec_generator() * s == r_point + e_p
+}
+
+/// The number of rows of the trace of `schnorr_bip340_verify_batch` for `signatures`.
+///
+/// The trace has exactly `2^16` rows, since the arithmetic columns are range checked against a
+/// column of the trace. Panics if the batch does not fit in `2^16` rows.
+pub fn schnorr_bip340_verify_num_rows(signatures: &[SchnorrSignatureRegister]) -> usize {
+    let num_sha_rounds = signatures
+        .iter()
+        .map(|signature| (signature.hash_input.max_length() + 8) / 64 + 1)
+        .sum::<usize>();
+    let num_scalar_muls = 2 * signatures.len();
+
+    let degree_log = log2_ceil(num_sha_rounds * SHA256_CYCLE_LENGTH)
+        .max(log2_ceil(num_scalar_muls * Secp256k1::nb_scalar_bits()));
+    assert!(degree_log <= 16, "The batch does not fit in 2^16 rows");
+    1 << 16
+}
+
+pub trait SchnorrVerifyBuilder: Builder {
+    /// Allocates the public inputs of the verification of a BIP-340 signature of a message of
+    /// at most `max_message_length` bytes.
+    fn alloc_public_schnorr_signature(
+        &mut self,
+        max_message_length: usize,
+    ) -> SchnorrSignatureRegister {
+        SchnorrSignatureRegister {
+            public_key: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+            r: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+            s: self.alloc_public(),
+            hash_input: self
+                .alloc_public_message(TAG_PREFIX_LENGTH + 2 * FIELD_LENGTH + max_message_length),
+            digest: self.alloc_public(),
+            s_g: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+            e_p: AffinePointRegister::new(self.alloc_public(), self.alloc_public()),
+        }
+    }
+
+    /// Verifies a batch of BIP-340 Schnorr signatures.
+    ///
+    /// For each signature, this lifts the x-only public key and `r` to the curve points `P` and
+    /// `R` of even y-coordinate, checks that `s < n`, computes the tagged hash
+    /// `e = SHA256(SHA256(tag) || SHA256(tag) || r || P.x || m) mod n` and checks that
+    /// `s * G = R + e * P`. All the hashes are done in a single SHA-256 AIR and all the scalar
+    /// multiplications in a single `scalar_mul_batch`, both padded to the number of rows given by
+    /// `schnorr_bip340_verify_num_rows`. Hence, this can only be called once per builder and not
+    /// together with `sha` or `scalar_mul_batch`.
+    ///
+    /// The addition `R + e * P` is not defined if `R = e * P` or `R = -e * P`, and neither are
+    /// the scalar multiplications by zero, so the signatures with `R = e * P`, `s = 0` or `e = 0`
+    /// are rejected. These only occur with negligible probability for honestly generated
+    /// signatures.
+    fn schnorr_bip340_verify_batch(&mut self, signatures: &[SchnorrSignatureRegister])
+    where
+        Secp256k1: EllipticCurveAir<Self::Parameters>,
+        Self::Instruction: ECInstructions<Secp256k1> + FromFieldInstruction<Secp256k1ScalarField>,
+        SHA256: SHAir<Self, 64>,
+    {
+        let num_rows = schnorr_bip340_verify_num_rows(signatures);
+
+        // Bind the tag prefix, `r` and the public key to the first bytes of the hash input.
+        let tag_hash = SHA256::digest(CHALLENGE_TAG);
+        for signature in signatures {
+            let hash_input = &signature.hash_input;
+            let prefix_length = TAG_PREFIX_LENGTH + 2 * FIELD_LENGTH;
+            for i in 0..prefix_length {
+                self.assert_expression_zero(hash_input.length_selector.get(i).expr());
+            }
+            for i in 0..TAG_PREFIX_LENGTH {
+                self.assert_expression_zero(
+                    hash_input.bytes.get(i).expr()
+                        - Self::Field::from_canonical_u8(tag_hash[i % tag_hash.len()]),
+                );
+            }
+            let r_offset = TAG_PREFIX_LENGTH;
+            let public_key_offset = r_offset + FIELD_LENGTH;
+            assert_field_bytes(
+                self,
+                &signature.r.x,
+                &hash_input
+                    .bytes
+                    .get_subarray(r_offset..r_offset + FIELD_LENGTH),
+            );
+            assert_field_bytes(
+                self,
+                &signature.public_key.x,
+                &hash_input
+                    .bytes
+                    .get_subarray(public_key_offset..public_key_offset + FIELD_LENGTH),
+            );
+        }
+        // Hash enough chunks for the SHA-256 AIR to take all of the rows.
+        let hash_inputs = signatures
+            .iter()
+            .map(|signature| signature.hash_input)
+            .collect::<Vec<_>>();
+        let min_rounds = num_rows / (2 * SHA256_CYCLE_LENGTH) + 1;
+        let digests = self.sha_messages_padded::<SHA256, 64>(&hash_inputs, min_rounds);
+
+        let generator = EllipticCurveBuilder::<Secp256k1>::generator(self);
+
+        let mut points = Vec::with_capacity(2 * signatures.len());
+        let mut scalars = Vec::with_capacity(2 * signatures.len());
+        let mut results = Vec::with_capacity(2 * signatures.len());
+        for (signature, digest) in signatures.iter().zip(digests.iter()) {
+            self.set_to_expression(digest, signature.digest.expr());
+
+            // Lift the x-coordinates to the points of even y-coordinate.
+            for point in [&signature.public_key, &signature.r] {
+                self.api().fp_assert_canonical(&point.x);
+                assert_even_y(self, point);
+            }
+
+            // The digest is read as a big-endian integer and reduced modulo the group order.
+            let digest_value = self.alloc_public::<FieldRegister<Secp256k1ScalarField>>();
+            let limbs =
+                ArrayRegister::<U16Register>::from_register_unsafe(*digest_value.register());
+            for (i, limb) in limbs.iter().enumerate() {
+                let j = FIELD_LENGTH - 1 - 2 * i;
+                self.set_to_expression(
+                    &limb,
+                    digest_byte(&signature.digest, j).expr()
+                        + digest_byte(&signature.digest, j - 1).expr()
+                            * Self::Field::from_canonical_u32(1 << 8),
+                );
+            }
+            let e = self.api().fp_reduce(&digest_value);
+
+            self.api().fp_assert_canonical(&signature.s);
+
+            points.push(generator);
+            scalars.push(field_to_scalar::<Self, Secp256k1, Secp256k1ScalarField>(
+                self,
+                &signature.s,
+            ));
+            results.push(signature.s_g);
+            points.push(signature.public_key);
+            scalars.push(field_to_scalar::<Self, Secp256k1, Secp256k1ScalarField>(
+                self, &e,
+            ));
+            results.push(signature.e_p);
+
+            let sum = self.add(signature.r, signature.e_p);
+            self.assert_equal(&sum.x, &signature.s_g.x);
+            self.assert_equal(&sum.y, &signature.s_g.y);
+        }
+
+        // Multiply the generator by one enough times for the scalar multiplications to take all
+        // of the rows.
+        let min_scalar_muls = num_rows / (2 * Secp256k1::nb_scalar_bits()) + 1;
+        if points.len() < min_scalar_muls {
+            let mut one_limbs = vec![Self::Field::ONE];
+            one_limbs.resize(Secp256k1::nb_scalar_bits() / 32, Self::Field::ZERO);
+            let one_limbs = self.constant_array::<ElementRegister>(&one_limbs);
+            while points.len() < min_scalar_muls {
+                points.push(generator);
+                scalars.push(ECScalarRegister::new(one_limbs));
+                results.push(generator);
+            }
+        }
+
+        EllipticCurveBuilder::<Secp256k1>::scalar_mul_batch(self, &points, &scalars, &results);
+    }
+}
+
+impl<B: Builder> SchnorrVerifyBuilder for B {}
+
+/// The register of the byte `i` of the big-endian digest.
+///
+/// The digest words are big-endian, while their bytes are stored in little-endian order.
+fn digest_byte(digest: &SHA256DigestRegister, i: usize) -> ElementRegister {
+    let bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(*digest.register());
+    bytes.get(4 * (i / 4) + 3 - i % 4)
+}
+
+/// Constrains `value` to be given by the big-endian `bytes`.
+///
+/// The bytes are range checked by the SHA-256 AIR.
+fn assert_field_bytes<B: Builder>(
+    builder: &mut B,
+    value: &FieldRegister<Secp256k1BaseField>,
+    bytes: &ArrayRegister<ByteRegister>,
+) {
+    let limbs = ArrayRegister::<U16Register>::from_register_unsafe(*value.register());
+    for (i, limb) in limbs.iter().enumerate() {
+        let j = FIELD_LENGTH - 1 - 2 * i;
+        let value =
+            bytes.get(j).expr() + bytes.get(j - 1).expr() * B::Field::from_canonical_u32(1 << 8);
+        builder.assert_expression_zero(limb.expr() - value);
+    }
+}
+
+/// Asserts that a public point is on the curve and has an even canonical y-coordinate.
+///
+/// The least significant limb of the y-coordinate is constrained to be twice a range checked
+/// limb, which is only possible if it is even.
+fn assert_even_y<B: Builder>(builder: &mut B, point: &AffinePointRegister<Secp256k1>)
+where
+    B::Instruction: FromFieldInstruction<Secp256k1BaseField>,
+{
+    builder.api().sw_assert_valid(point);
+    builder.api().fp_assert_canonical(&point.y);
+
+    let y_limbs = ArrayRegister::<U16Register>::from_register_unsafe(*point.y.register());
+    let half = B::Field::from_canonical_u32(2).inverse();
+    builder.public_expression::<U16Register>(y_limbs.get(0).expr() * half);
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::util::timing::TimingTree;
+
+    use super::*;
+    use crate::chip::ec::weierstrass::secp256k1::instruction::Secp256k1SchnorrInstruction;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::AirParameters;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+
+    /// The valid signatures of the BIP-340 test vectors, as `(public_key, message, signature)`.
+    const SIGNATURES: [(&str, &str, &str); 4] = [
+        (
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+             25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+        ),
+        (
+            "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+            "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+             8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+        ),
+        (
+            "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+            "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+            "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1b\
+             ab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+        ),
+        (
+            "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec\
+             97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+        ),
+    ];
+
+    fn signature_values() -> Vec<([u8; 32], Vec<u8>, [u8; 64])> {
+        SIGNATURES
+            .iter()
+            .map(|(public_key, message, signature)| {
+                let public_key = hex::decode(public_key).unwrap().try_into().unwrap();
+                let signature = hex::decode(signature).unwrap().try_into().unwrap();
+                (public_key, hex::decode(message).unwrap(), signature)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schnorr_bip340_verify_pure() {
+        let n = Secp256k1ScalarField::modulus();
+        for (public_key, message, signature) in signature_values() {
+            assert!(schnorr_bip340_verify(&public_key, &message, &signature));
+            assert!(!schnorr_bip340_verify(
+                &public_key,
+                b"wrong message",
+                &signature
+            ));
+
+            // The signature with a negated `s` and the one with `s + n`.
+            let s = BigUint::from_bytes_be(&signature[32..]);
+            for s in [&n - &s, &s + &n] {
+                let mut modified = signature;
+                let s_bytes = s.to_bytes_be();
+                if s_bytes.len() > 32 {
+                    continue;
+                }
+                modified[32..].fill(0);
+                modified[64 - s_bytes.len()..].copy_from_slice(&s_bytes);
+                assert!(!schnorr_bip340_verify(&public_key, &message, &modified));
+            }
+
+            // A public key which is not the x-coordinate of a curve point.
+            let invalid_key =
+                hex::decode("eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34")
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+            assert!(!schnorr_bip340_verify(&invalid_key, &message, &signature));
+
+            // A commitment `r` equal to the field size.
+            let mut modified = signature;
+            modified[..32].copy_from_slice(&Secp256k1BaseField::modulus().to_bytes_be());
+            assert!(!schnorr_bip340_verify(&public_key, &message, &modified));
+        }
+    }
+
+    #[test]
+    fn test_lift_x() {
+        let p = Secp256k1BaseField::modulus();
+        let generator = Secp256k1::ec_generator();
+        let point = lift_x(&generator.x).unwrap();
+        assert!(!point.y.bit(0));
+        assert!(point.y == generator.y || point.y == &p - &generator.y);
+        assert!(lift_x(&p).is_none());
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct SchnorrVerifyTest;
+
+    impl AirParameters for SchnorrVerifyTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = Secp256k1SchnorrInstruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 3904;
+        const NUM_FREE_COLUMNS: usize = 440;
+        const EXTENDED_COLUMNS: usize = 6870;
+    }
+
+    #[test]
+    fn test_schnorr_bip340_verify_batch() {
+        type F = GoldilocksField;
+        type L = SchnorrVerifyTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("BIP-340 verify", log::Level::Debug);
+
+        let mut builder = BytesBuilder::<L>::new();
+
+        let signature_values = signature_values();
+        let signatures = signature_values
+            .iter()
+            .map(|_| builder.alloc_public_schnorr_signature(32))
+            .collect::<Vec<_>>();
+        builder.schnorr_bip340_verify_batch(&signatures);
+
+        let num_rows = schnorr_bip340_verify_num_rows(&signatures);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (signature, (public_key, message, signature_bytes)) in
+            signatures.iter().zip(signature_values.iter())
+        {
+            signature.write(&mut writer, public_key, message, signature_bytes);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
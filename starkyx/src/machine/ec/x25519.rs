@@ -0,0 +1,308 @@
+use num::BigUint;
+use plonky2::util::log2_ceil;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::ec::edwards::ed25519::params::Ed25519BaseField;
+use crate::chip::ec::montgomery::x25519::{
+    x25519, x25519_decode_u, x25519_projective, Curve25519Parameters, X25519_SCALAR_BITS,
+};
+use crate::chip::ec::montgomery::MontgomeryPointRegister;
+use crate::chip::field::instruction::FromFieldInstruction;
+use crate::chip::field::register::FieldRegister;
+use crate::chip::memory::time::Time;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::Register;
+use crate::chip::trace::writer::AirWriter;
+use crate::machine::builder::Builder;
+use crate::math::prelude::*;
+use crate::polynomial::to_le_limbs_polynomial;
+
+/// The u-coordinate of the base point of Curve25519, used by the dummy operations.
+const X25519_BASE_POINT_U: u32 = 9;
+
+/// The public inputs of an X25519 function evaluation `result = X25519(scalar, u)`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct X25519Register {
+    /// The little-endian bits of the scalar, before clamping.
+    pub scalar: ArrayRegister<BitRegister>,
+    /// The u-coordinate of the input point, reduced modulo `p`.
+    pub u: FieldRegister<Ed25519BaseField>,
+    pub result: FieldRegister<Ed25519BaseField>,
+    /// The projective u-coordinate `(x : z)` at the end of the ladder, with `result = x / z`.
+    pub x: FieldRegister<Ed25519BaseField>,
+    pub z: FieldRegister<Ed25519BaseField>,
+}
+
+impl X25519Register {
+    /// Writes the scalar and the u-coordinate, together with the result of the ladder.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, scalar: &[u8; 32], u: &[u8; 32]) {
+        for (i, bit_reg) in self.scalar.iter().enumerate() {
+            let bit = (scalar[i / 8] >> (i % 8)) & 1;
+            writer.write(&bit_reg, &W::Field::from_canonical_u8(bit));
+        }
+
+        let u_value = x25519_decode_u(u);
+        let (x, z) = x25519_projective(scalar, &u_value);
+        let result = BigUint::from_bytes_le(&x25519(scalar, u));
+        for (register, value) in [
+            (self.u, u_value),
+            (self.result, result),
+            (self.x, x),
+            (self.z, z),
+        ] {
+            writer.write(
+                &register,
+                &to_le_limbs_polynomial::<W::Field, Ed25519BaseField>(&value),
+            );
+        }
+    }
+}
+
+/// The number of rows of the trace of `x25519_batch` for `num_ops` evaluations.
+pub fn x25519_num_rows(num_ops: usize) -> usize {
+    1 << log2_ceil(num_ops * X25519_SCALAR_BITS)
+}
+
+pub trait X25519Builder: Builder {
+    /// Allocates the public inputs of an X25519 function evaluation.
+    fn alloc_public_x25519(&mut self) -> X25519Register {
+        let scalar = self.alloc_array_public::<BitRegister>(X25519_SCALAR_BITS);
+
+        X25519Register {
+            scalar,
+            u: self.alloc_public(),
+            result: self.alloc_public(),
+            x: self.alloc_public(),
+            z: self.alloc_public(),
+        }
+    }
+
+    /// Evaluates the X25519 function of RFC 7748 for a batch of public inputs.
+    ///
+    /// Each evaluation is a Montgomery ladder over the clamped scalar in a cycle of
+    /// `X25519_SCALAR_BITS` rows, one row per ladder step. Every step doubles one point of the
+    /// ladder and adds both, so that the rows do not depend on the scalar bits other than through
+    /// selections. The clamped bits and the u-coordinates are read from memory, and the final
+    /// projective u-coordinate is checked against the result, which must be canonical.
+    ///
+    /// Inputs of small order, for which the result is zero, are rejected. The trace has the number
+    /// of rows given by `x25519_num_rows`, so this can only be called once per builder.
+    fn x25519_batch(&mut self, inputs: &[X25519Register])
+    where
+        Self::Instruction: FromFieldInstruction<Ed25519BaseField>,
+    {
+        let num_ops = inputs.len();
+        let num_rows = x25519_num_rows(num_ops);
+        let num_dummy_ops = num_rows / X25519_SCALAR_BITS - num_ops;
+
+        let bit_ptr = self.uninit_slice::<BitRegister>();
+        let u_ptr = self.uninit_slice::<FieldRegister<Ed25519BaseField>>();
+        let x_ptr = self.uninit_slice::<FieldRegister<Ed25519BaseField>>();
+        let z_ptr = self.uninit_slice::<FieldRegister<Ed25519BaseField>>();
+        let zero = Time::zero();
+
+        let zero_bit = self.constant::<BitRegister>(&Self::Field::ZERO);
+        let one_bit = self.constant::<BitRegister>(&Self::Field::ONE);
+        let nb_steps = self
+            .constant::<ElementRegister>(&Self::Field::from_canonical_usize(X25519_SCALAR_BITS));
+
+        // Store the clamped bits of a scalar, most significant bit first, and its u-coordinate.
+        let store_input = |builder: &mut Self,
+                           i: usize,
+                           scalar: Option<&ArrayRegister<BitRegister>>,
+                           u: FieldRegister<Ed25519BaseField>| {
+            for row in 0..X25519_SCALAR_BITS {
+                let t = X25519_SCALAR_BITS - 1 - row;
+                let bit = match t {
+                    0..=2 | 255 => zero_bit,
+                    254 => one_bit,
+                    _ => scalar.map_or(zero_bit, |scalar| scalar.get(t)),
+                };
+                builder.store(
+                    &bit_ptr.get(i * X25519_SCALAR_BITS + row),
+                    bit,
+                    &zero,
+                    None,
+                    None,
+                    None,
+                );
+            }
+            builder.store(&u_ptr.get(i), u, &zero, Some(nb_steps), None, None);
+        };
+
+        for (i, input) in inputs.iter().enumerate() {
+            store_input(self, i, Some(&input.scalar), input.u);
+            self.free(&x_ptr.get(i), input.x, &zero);
+            self.free(&z_ptr.get(i), input.z, &zero);
+
+            // Check the result against the end of the ladder.
+            let result_mul_z = self.api().fp_mul(&input.result, &input.z);
+            self.assert_equal(&result_mul_z, &input.x);
+            self.api().fp_assert_canonical(&input.result);
+        }
+
+        // Insert dummy evaluations with a zero scalar on the base point.
+        let dummy_u = BigUint::from(X25519_BASE_POINT_U);
+        let (dummy_x, dummy_z) = x25519_projective(&[0; 32], &dummy_u);
+        let dummy_u = self.api().fp_constant(&dummy_u);
+        let dummy_x = self.api().fp_constant(&dummy_x);
+        let dummy_z = self.api().fp_constant(&dummy_z);
+        for i in num_ops..(num_ops + num_dummy_ops) {
+            store_input(self, i, None, dummy_u);
+            self.free(&x_ptr.get(i), dummy_x, &zero);
+            self.free(&z_ptr.get(i), dummy_z, &zero);
+        }
+
+        // Load the bit and the u-coordinate of the current step.
+        let cycle = self.cycle(log2_ceil(X25519_SCALAR_BITS));
+        let process_id = self.process_id(X25519_SCALAR_BITS, cycle.end_bit);
+        let clk = self.clk();
+        let bit = self.load(&bit_ptr.get_at(clk), &zero, None, None);
+        let u = self.load(&u_ptr.get_at(process_id), &zero, None, None);
+
+        // The ladder state `(r0, r1)` starts each cycle at `((1 : 0), (u : 1))`. As `u` changes
+        // between cycles, the first value of `r1` is selected at the start of the cycle.
+        let one = self.api().fp_one::<Ed25519BaseField>();
+        let zero_field = self.api().fp_zero::<Ed25519BaseField>();
+        let x2 = self.alloc::<FieldRegister<Ed25519BaseField>>();
+        let z2 = self.alloc::<FieldRegister<Ed25519BaseField>>();
+        let x3 = self.alloc::<FieldRegister<Ed25519BaseField>>();
+        let z3 = self.alloc::<FieldRegister<Ed25519BaseField>>();
+        self.set_to_expression_first_row(&x2, one.expr());
+        self.set_to_expression_first_row(&z2, zero_field.expr());
+        self.set_to_expression_first_row(&x3, u.expr());
+        self.set_to_expression_first_row(&z3, one.expr());
+        let x3_start = self.select(cycle.start_bit, &u, &x3);
+        let r0 = MontgomeryPointRegister::<Curve25519Parameters>::new(x2, z2);
+        let r1 = MontgomeryPointRegister::<Curve25519Parameters>::new(x3_start, z3);
+
+        // Double `r1` and add if the bit is one, otherwise double `r0` and add.
+        let p = select_montgomery_point(self, bit, &r1, &r0);
+        let q = select_montgomery_point(self, bit, &r0, &r1);
+        let (double, sum) = self.api().montgomery_ladder_step(&u, &p, &q);
+        let r0_next = select_montgomery_point(self, bit, &sum, &double);
+        let r1_next = select_montgomery_point(self, bit, &double, &sum);
+
+        self.select_next(cycle.end_bit, &one, &r0_next.x, &x2);
+        self.select_next(cycle.end_bit, &zero_field, &r0_next.z, &z2);
+        self.select_next(cycle.end_bit, &u, &r1_next.x, &x3);
+        self.select_next(cycle.end_bit, &one, &r1_next.z, &z3);
+
+        // Store the final value of `r0` at the end of the cycle.
+        let end_flag = Some(cycle.end_bit.as_element());
+        self.store(
+            &x_ptr.get_at(process_id),
+            r0_next.x,
+            &zero,
+            end_flag,
+            None,
+            None,
+        );
+        self.store(
+            &z_ptr.get_at(process_id),
+            r0_next.z,
+            &zero,
+            end_flag,
+            None,
+            None,
+        );
+    }
+}
+
+impl<B: Builder> X25519Builder for B {}
+
+fn select_montgomery_point<B: Builder>(
+    builder: &mut B,
+    flag: BitRegister,
+    true_value: &MontgomeryPointRegister<Curve25519Parameters>,
+    false_value: &MontgomeryPointRegister<Curve25519Parameters>,
+) -> MontgomeryPointRegister<Curve25519Parameters> {
+    let x = builder.select(flag, &true_value.x, &false_value.x);
+    let z = builder.select(flag, &true_value.z, &false_value.z);
+
+    MontgomeryPointRegister::new(x, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use curve25519_dalek::montgomery::MontgomeryPoint;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::util::timing::TimingTree;
+    use rand::{thread_rng, Rng};
+
+    use super::*;
+    use crate::chip::field::instruction::FpInstruction;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::AirParameters;
+    use crate::machine::emulated::builder::EmulatedBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::CurtaPoseidonGoldilocksConfig;
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct X25519Test;
+
+    impl AirParameters for X25519Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = FpInstruction<Ed25519BaseField>;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 1880;
+        const NUM_FREE_COLUMNS: usize = 10;
+        const EXTENDED_COLUMNS: usize = 2865;
+    }
+
+    #[test]
+    fn test_x25519_batch() {
+        type L = X25519Test;
+        type C = CurtaPoseidonGoldilocksConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("X25519", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        // The number of evaluations is not a power of two, so that dummy ones are inserted.
+        let num_ops = 3;
+        let inputs = (0..num_ops)
+            .map(|_| builder.alloc_public_x25519())
+            .collect::<Vec<_>>();
+        builder.x25519_batch(&inputs);
+
+        let num_rows = x25519_num_rows(num_ops);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut rng = thread_rng();
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+        for input in inputs.iter() {
+            let scalar = rng.gen::<[u8; 32]>();
+            let u = rng.gen::<[u8; 32]>();
+            assert_eq!(
+                x25519(&scalar, &u),
+                MontgomeryPoint(u).mul_clamped(scalar).to_bytes()
+            );
+            input.write(&mut writer, &scalar, &u);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        // The ladder state is carried between rows, so they are written in order.
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+        stark.verify(proof, &public).unwrap();
+
+        timing.print();
+    }
+}
@@ -10,8 +10,6 @@ use crate::machine::builder::Builder;
 use crate::plonky2::stark::config::{CurtaConfig, StarkyConfig};
 use crate::plonky2::stark::Starky;
 
-pub(crate) const NUM_LOOKUP_ROWS: usize = 1 << 16;
-
 pub struct EmulatedBuilder<L: AirParameters> {
     pub api: AirBuilder<L>,
     pub clk: ElementRegister,
@@ -71,7 +69,7 @@ impl<L: AirParameters> EmulatedBuilder<L> {
         let (air, trace_data) = api.build();
         let stark = Starky::new(air);
 
-        let lookup_config = StarkyConfig::<C, D>::standard_fast_config(NUM_LOOKUP_ROWS);
+        let lookup_config = StarkyConfig::<C, D>::standard_fast_config(1 << L::RANGE_CHECK_BITS);
         let (lookup_air, lookup_trace_data) = lookup_builder.build();
         let lookup_stark = Starky::new(lookup_air);
 
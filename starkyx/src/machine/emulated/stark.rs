@@ -9,7 +9,6 @@ use plonky2::timed;
 use plonky2::util::timing::TimingTree;
 use serde::{Deserialize, Serialize};
 
-use super::builder::NUM_LOOKUP_ROWS;
 use super::proof::{
     EmulatedStarkChallenges, EmulatedStarkChallengesTarget, EmulatedStarkProof,
     EmulatedStarkProofTarget,
@@ -52,6 +51,9 @@ where
     C: CurtaConfig<D, F = L::Field, FE = <L::Field as Extendable<D>>::Extension>,
     Chip<L>: Plonky2Air<L::Field, D>,
 {
+    /// The number of rows of the range check trace, one for each value of the range.
+    pub const NUM_LOOKUP_ROWS: usize = 1 << L::RANGE_CHECK_BITS;
+
     pub const fn stark(&self) -> &Starky<Chip<L>> {
         &self.stark
     }
@@ -80,7 +82,7 @@ where
     ) -> (TraceWriter<L::Field>, TraceWriter<L::Field>) {
         // Initialize writers.
         let main_writer = TraceWriter::new(&self.air_data, execution_trace.height());
-        let lookup_writer = TraceWriter::new(&self.lookup_air_data, NUM_LOOKUP_ROWS);
+        let lookup_writer = TraceWriter::new(&self.lookup_air_data, Self::NUM_LOOKUP_ROWS);
 
         // Insert execution trace and into main writer.
         let execution_trace_length = self.stark.air.execution_trace_length;
@@ -104,16 +106,16 @@ where
             .copy_from_slice(public_values);
 
         // Write lookup table values
-        for i in 0..NUM_LOOKUP_ROWS {
+        for i in 0..Self::NUM_LOOKUP_ROWS {
             lookup_writer.write(&self.lookup_table, &L::Field::from_canonical_usize(i), i);
         }
-        for i in 0..NUM_LOOKUP_ROWS {
+        for i in 0..Self::NUM_LOOKUP_ROWS {
             lookup_writer.write_row_instructions(&self.lookup_air_data, i);
         }
         // Write multiplicities
         let multiplicities = main_writer.get_multiplicities_from_fn(
             1,
-            NUM_LOOKUP_ROWS,
+            Self::NUM_LOOKUP_ROWS,
             &self.lookup_values.trace_values,
             &self.lookup_values.public_values,
             Self::range_fn,
@@ -544,7 +546,7 @@ mod tests {
 
     use super::*;
     use crate::chip::field::instruction::FpInstruction;
-    use crate::chip::field::parameters::tests::Fp25519;
+    use crate::chip::field::parameters::tests::{Fp25519, FpGoldilocks12};
     use crate::chip::field::parameters::FieldParameters;
     use crate::chip::field::register::FieldRegister;
     use crate::chip::trace::writer::data::AirWriterData;
@@ -569,6 +571,21 @@ mod tests {
         const EXTENDED_COLUMNS: usize = 192;
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SmallRangeTest;
+
+    impl AirParameters for SmallRangeTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = FpInstruction<FpGoldilocks12>;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 44;
+        const NUM_FREE_COLUMNS: usize = 1;
+        const EXTENDED_COLUMNS: usize = 72;
+        const RANGE_CHECK_BITS: usize = 12;
+    }
+
     #[test]
     fn test_fp_multi_stark() {
         type L = RangeTest;
@@ -635,4 +652,70 @@ mod tests {
 
         timing.print();
     }
+
+    #[test]
+    fn test_fp_multi_stark_small_limbs() {
+        type L = SmallRangeTest;
+        type F = GoldilocksField;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+        type P = FpGoldilocks12;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("test_fp_multi_stark_small_limbs", log::Level::Debug);
+
+        let mut builder = EmulatedBuilder::<L>::new();
+
+        let a = builder.alloc::<FieldRegister<P>>();
+        let b = builder.alloc::<FieldRegister<P>>();
+        let _ = builder.add(a, b);
+
+        let num_rows = 1 << 5;
+        let stark = builder.build::<C, 2>(num_rows);
+        assert_eq!(EmulatedStark::<L, C, 2>::NUM_LOOKUP_ROWS, 1 << 12);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let p = P::modulus();
+        let air_data = &stark.air_data;
+        air_data.write_global_instructions(&mut writer_data.public_writer());
+
+        writer_data.chunks(1).for_each(|mut chunk| {
+            let mut rng = rand::thread_rng();
+            let mut writer = chunk.row_writer(0);
+            let a_int = rng.gen_biguint_below(&p);
+            let b_int = rng.gen_biguint_below(&p);
+            let p_a = Polynomial::<F>::from_biguint_field(&a_int, 12, 6);
+            let p_b = Polynomial::<F>::from_biguint_field(&b_int, 12, 6);
+            writer.write(&a, &p_a);
+            writer.write(&b, &p_b);
+            air_data.write_trace_instructions(&mut writer);
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
 }
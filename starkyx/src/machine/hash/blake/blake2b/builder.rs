@@ -1,32 +1,4 @@
-use super::air::BLAKEAir;
-use crate::chip::register::array::ArrayRegister;
-use crate::chip::register::bit::BitRegister;
-use crate::chip::register::element::ElementRegister;
-use crate::prelude::Builder;
-
-pub trait BlakeBuilder: Builder {
-    fn blake2b<B: BLAKEAir<Self>>(
-        &mut self,
-        padded_chunks: &[ArrayRegister<B::IntRegister>],
-        t_values: &ArrayRegister<B::IntRegister>,
-        end_bits: &ArrayRegister<BitRegister>,
-        digest_bits: &ArrayRegister<BitRegister>,
-        digest_indices: &ArrayRegister<ElementRegister>,
-        num_messages: &ElementRegister,
-    ) -> Vec<B::DigestRegister> {
-        B::blake2b(
-            self,
-            padded_chunks,
-            t_values,
-            end_bits,
-            digest_bits,
-            digest_indices,
-            num_messages,
-        )
-    }
-}
-
-impl<B: Builder> BlakeBuilder for B {}
+pub use crate::machine::hash::blake::builder::BlakeBuilder;
 
 #[cfg(test)]
 pub mod test_utils {
@@ -41,18 +13,23 @@ pub mod test_utils {
     use plonky2::plonk::circuit_builder::CircuitBuilder;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::timed;
+    use plonky2::util::log2_ceil;
     use plonky2::util::timing::TimingTree;
     use serde::{Deserialize, Serialize};
 
-    use super::*;
+    use crate::chip::register::array::ArrayRegister;
+    use crate::chip::register::bit::BitRegister;
     use crate::chip::uint::operations::instruction::UintInstruction;
     use crate::chip::uint::util::u64_to_le_field_bytes;
     use crate::chip::AirParameters;
     use crate::machine;
+    use crate::machine::builder::Builder;
     use crate::machine::bytes::builder::BytesBuilder;
     use crate::machine::hash::blake::blake2b::pure::BLAKE2BPure;
     use crate::machine::hash::blake::blake2b::utils::BLAKE2BUtil;
     use crate::machine::hash::blake::blake2b::{BLAKE2B, IV};
+    use crate::machine::hash::blake::builder::BlakeBuilder;
+    use crate::machine::hash::message::MessageBuilder;
     use crate::machine::hash::HashDigest;
     use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
     use crate::math::prelude::*;
@@ -169,7 +146,7 @@ pub mod test_utils {
         let digest_bits = builder.alloc_array_public::<BitRegister>(num_rounds);
         let digest_indices = builder.alloc_array_public(17 * msgs.len());
         let num_messages = builder.alloc_public();
-        let hash_state = builder.blake2b::<BLAKE2B>(
+        let hash_state = builder.blake::<BLAKE2B>(
             &padded_chunks,
             &t_values,
             &end_bits,
@@ -275,4 +252,129 @@ pub mod test_utils {
 
         timing.print();
     }
+
+    #[test]
+    pub fn test_blake2b_messages() {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        env::set_var("RUST_LOG", "info");
+        env_logger::try_init().unwrap_or_default();
+        let mut timing = TimingTree::new("test_blake2b_messages", log::Level::Info);
+
+        let long_msg = (0..200).map(|i| (i % 251) as u8).collect_vec();
+        let msgs = [
+            (b"".as_slice(), 100),
+            (b"abc".as_slice(), 3),
+            (long_msg.as_slice(), 256),
+            (b"abc".as_slice(), 300),
+        ];
+        let expected_digests = [
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+            "63c3d97a9f8894d5e043a707b0fee7f7ec4c049a23bbf1079df20b4165f9e22d",
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+        ];
+
+        // Build the stark
+        let mut builder = BytesBuilder::<BLAKE2BTest>::new();
+        let messages = msgs
+            .iter()
+            .map(|(_, max_length)| builder.alloc_public_message(*max_length))
+            .collect::<Vec<_>>();
+        let hash_state = builder.blake_messages::<BLAKE2B>(&messages);
+
+        let num_rounds = msgs
+            .iter()
+            .map(|(_, max_length)| (*max_length as u64).max(1).div_ceil(128))
+            .sum::<u64>() as usize;
+        let num_rows = 1 << log2_ceil(num_rounds * 96);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        for (((msg, max_length), message), (digest, expected_digest)) in msgs
+            .iter()
+            .zip_eq(messages.iter())
+            .zip_eq(hash_state.iter().zip_eq(expected_digests))
+        {
+            message.write(&mut writer, msg);
+
+            let max_num_chunks = (*max_length as u64).max(1).div_ceil(128);
+            let digest_chunk = msg.len().saturating_sub(1) / 128;
+            let mut state = IV;
+            for (i, chunk) in BLAKE2BUtil::pad(msg, max_num_chunks)
+                .chunks_exact(128)
+                .enumerate()
+                .take(digest_chunk + 1)
+            {
+                let at_digest_chunk = i == digest_chunk;
+                let t_value = if at_digest_chunk {
+                    msg.len() as u64
+                } else {
+                    128 * (i as u64 + 1)
+                };
+                BLAKE2B::compress(chunk, &mut state, t_value, at_digest_chunk);
+            }
+            let state_bytes = state[0..4]
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect_vec();
+            assert_eq!(hex::encode(state_bytes), expected_digest);
+
+            let array: ArrayRegister<_> = (*digest).into();
+            writer.write_array(
+                &array,
+                state[0..4].iter().map(|x| u64_to_le_field_bytes(*x)),
+            );
+        }
+
+        timed!(timing, log::Level::Info, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
 }
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use super::MSG_ARRAY_SIZE;
+
 pub mod air;
 pub mod builder;
-pub mod data;
 pub mod pure;
 pub mod register;
 pub mod utils;
@@ -11,13 +12,8 @@ pub mod utils;
 pub struct BLAKE2B;
 
 const NUM_MIX_ROUNDS: usize = 12;
-const MIX_LENGTH: usize = 8;
-const MSG_ARRAY_SIZE: usize = 16;
-const STATE_SIZE: usize = 8;
-const WORK_VECTOR_SIZE: usize = 16;
-const COMPRESS_LENGTH: usize = MIX_LENGTH * NUM_MIX_ROUNDS;
 
-pub const IV: [u64; STATE_SIZE] = [
+pub const IV: [u64; 8] = [
     0x6a09e667f2bdc928,
     0xbb67ae8584caa73b,
     0x3c6ef372fe94f82b,
@@ -32,7 +28,7 @@ pub const IV: [u64; STATE_SIZE] = [
 // we assume that the output is 32 bytes
 // So that means the initial hash entry to be
 // 0x6a09e667f3bcc908 xor 0x01010020
-const COMPRESS_IV: [u64; STATE_SIZE] = [
+const COMPRESS_IV: [u64; 8] = [
     0x6a09e667f3bcc908,
     0xbb67ae8584caa73b,
     0x3c6ef372fe94f82b,
@@ -43,28 +39,6 @@ const COMPRESS_IV: [u64; STATE_SIZE] = [
     0x5be0cd19137e2179,
 ];
 
-const V_INDICES: [[u8; 4]; MIX_LENGTH] = [
-    [0, 4, 8, 12],
-    [1, 5, 9, 13],
-    [2, 6, 10, 14],
-    [3, 7, 11, 15],
-    [0, 5, 10, 15],
-    [1, 6, 11, 12],
-    [2, 7, 8, 13],
-    [3, 4, 9, 14],
-];
-
-const V_LAST_WRITE_AGES: [[u8; 4]; MIX_LENGTH] = [
-    [4, 1, 2, 3],
-    [4, 5, 2, 3],
-    [4, 5, 6, 3],
-    [4, 5, 6, 7],
-    [4, 3, 2, 1],
-    [4, 3, 2, 5],
-    [4, 3, 6, 5],
-    [4, 7, 6, 5],
-];
-
 const SIGMA_PERMUTATIONS: [[u8; MSG_ARRAY_SIZE]; NUM_MIX_ROUNDS] = [
     [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
     [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
@@ -1,5 +1,5 @@
-use super::{BLAKE2B, COMPRESS_IV, STATE_SIZE, WORK_VECTOR_SIZE};
-use crate::machine::hash::blake::blake2b::SIGMA_PERMUTATIONS;
+use super::{BLAKE2B, COMPRESS_IV, SIGMA_PERMUTATIONS};
+use crate::machine::hash::blake::{STATE_SIZE, WORK_VECTOR_SIZE};
 use crate::machine::hash::HashPureInteger;
 
 impl HashPureInteger for BLAKE2B {
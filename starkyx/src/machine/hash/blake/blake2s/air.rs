@@ -0,0 +1,113 @@
+use super::register::BLAKE2SDigestRegister;
+use super::{BLAKE2S, COMPRESS_IV, IV, NUM_MIX_ROUNDS, SIGMA_PERMUTATIONS};
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::Register;
+use crate::chip::uint::operations::instruction::UintInstructions;
+use crate::chip::uint::register::U32Register;
+use crate::chip::uint::util::{u32_from_le_field_bytes, u32_to_le_field_bytes};
+use crate::chip::AirParameters;
+use crate::machine::builder::Builder;
+use crate::machine::bytes::builder::BytesBuilder;
+use crate::machine::hash::blake::air::BLAKEAir;
+use crate::machine::hash::blake::{MSG_ARRAY_SIZE, STATE_SIZE};
+use crate::machine::hash::{HashDigest, HashIntConversion, HashInteger};
+
+impl<B: Builder> HashInteger<B> for BLAKE2S {
+    type Value = <U32Register as Register>::Value<B::Field>;
+    type IntRegister = U32Register;
+}
+
+impl<B: Builder> HashIntConversion<B> for BLAKE2S {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u32_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u32_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for BLAKE2S {
+    type DigestRegister = BLAKE2SDigestRegister;
+}
+
+impl<L: AirParameters> BLAKEAir<BytesBuilder<L>> for BLAKE2S
+where
+    L::Instruction: UintInstructions,
+{
+    const NUM_MIX_ROUNDS: usize = NUM_MIX_ROUNDS;
+    const DIGEST_LENGTH: usize = 8;
+    const T_LENGTH: usize = 1;
+    const IV: [u32; STATE_SIZE] = IV;
+    const COMPRESS_IV: [u32; STATE_SIZE] = COMPRESS_IV;
+    const SIGMA_PERMUTATIONS: &'static [[u8; MSG_ARRAY_SIZE]] = &SIGMA_PERMUTATIONS;
+    const LAST_BLOCK_FLAG: Option<u32> = Some(0xFFFFFFFF);
+    const FEED_FORWARD: bool = true;
+
+    fn cycles_end_bits(
+        builder: &mut BytesBuilder<L>,
+    ) -> (BitRegister, BitRegister, BitRegister, BitRegister) {
+        let cycle_4 = builder.cycle(2);
+        let cycle_8 = builder.cycle(3);
+        let loop_4 = builder.api().loop_instr(4);
+        let loop_5 = builder.api().loop_instr(5);
+        let cycle_80_end_bit = {
+            let cycle_16 = builder.cycle(4);
+            builder.mul(loop_5.get_iteration_reg(4), cycle_16.end_bit)
+        };
+
+        (
+            loop_4.get_iteration_reg(2),
+            cycle_4.end_bit,
+            cycle_8.end_bit,
+            cycle_80_end_bit,
+        )
+    }
+
+    fn xor(
+        builder: &mut BytesBuilder<L>,
+        a: &Self::IntRegister,
+        b: &Self::IntRegister,
+    ) -> Self::IntRegister {
+        builder.xor(a, b)
+    }
+
+    fn mix(
+        builder: &mut BytesBuilder<L>,
+        v_a: &Self::IntRegister,
+        v_b: &Self::IntRegister,
+        v_c: &Self::IntRegister,
+        v_d: &Self::IntRegister,
+        x: &Self::IntRegister,
+        y: &Self::IntRegister,
+    ) -> (
+        Self::IntRegister,
+        Self::IntRegister,
+        Self::IntRegister,
+        Self::IntRegister,
+    ) {
+        let mut v_a_inter = builder.add(*v_a, *v_b);
+        v_a_inter = builder.add(v_a_inter, *x);
+
+        let mut v_d_inter = builder.xor(*v_d, v_a_inter);
+        v_d_inter = builder.rotate_right(v_d_inter, 16);
+
+        let mut v_c_inter = builder.add(*v_c, v_d_inter);
+
+        let mut v_b_inter = builder.xor(*v_b, v_c_inter);
+        v_b_inter = builder.rotate_right(v_b_inter, 12);
+
+        v_a_inter = builder.add(v_a_inter, v_b_inter);
+        v_a_inter = builder.add(v_a_inter, *y);
+
+        v_d_inter = builder.xor(v_d_inter, v_a_inter);
+        v_d_inter = builder.rotate_right(v_d_inter, 8);
+
+        v_c_inter = builder.add(v_c_inter, v_d_inter);
+
+        v_b_inter = builder.xor(v_b_inter, v_c_inter);
+        v_b_inter = builder.rotate_right(v_b_inter, 7);
+
+        (v_a_inter, v_b_inter, v_c_inter, v_d_inter)
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+pub mod test_utils {
+
+    use core::fmt::Debug;
+    use std::env;
+
+    use itertools::Itertools;
+    use log::debug;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::timed;
+    use plonky2::util::log2_ceil;
+    use plonky2::util::timing::TimingTree;
+    use serde::{Deserialize, Serialize};
+
+    use crate::chip::register::array::ArrayRegister;
+    use crate::chip::register::bit::BitRegister;
+    use crate::chip::uint::operations::instruction::UintInstruction;
+    use crate::chip::uint::register::U32Register;
+    use crate::chip::uint::util::u32_to_le_field_bytes;
+    use crate::chip::AirParameters;
+    use crate::machine::builder::Builder;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::machine::hash::blake::blake2s::pure::BLAKE2SPure;
+    use crate::machine::hash::blake::blake2s::utils::BLAKE2SUtil;
+    use crate::machine::hash::blake::blake2s::{BLAKE2S, IV};
+    use crate::machine::hash::blake::builder::BlakeBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::math::prelude::*;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+    use crate::prelude::{AirWriter, AirWriterData};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct BLAKE2STest;
+
+    impl AirParameters for BLAKE2STest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 1024;
+        const EXTENDED_COLUMNS: usize = 1476;
+    }
+
+    #[test]
+    pub fn test_blake2s() {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        env::set_var("RUST_LOG", "info");
+        env_logger::try_init().unwrap_or_default();
+        let mut timing = TimingTree::new("test_blake2s", log::Level::Info);
+
+        let mut padded_chunks_values = Vec::new();
+        let mut t_values_values = Vec::new();
+        let mut end_bits_values = Vec::new();
+        let mut digest_bits_values = Vec::new();
+        let mut digest_indices_values = Vec::new();
+
+        let msgs = [
+            // 1 block
+            b"".to_vec(),
+            // 1 block
+            b"abc".to_vec(),
+            // 1 block
+            (0..64).map(|i| i as u8).collect_vec(),
+            // 4 blocks
+            (0..200).map(|i| (i % 251) as u8).collect_vec(),
+        ];
+        let msg_max_chunk_sizes = [2u64, 1, 1, 5];
+        let num_repetitions = 4;
+
+        let mut start_index = 0;
+        for _i in 0..num_repetitions {
+            for (msg, msg_max_chunk_size) in msgs.iter().zip_eq(msg_max_chunk_sizes.iter()) {
+                let msg_u32_limbs: Vec<[GoldilocksField; 4]> =
+                    BLAKE2SUtil::pad(msg, *msg_max_chunk_size)
+                        .chunks_exact(4)
+                        .map(|x| {
+                            x.iter()
+                                .map(|y| GoldilocksField::from_canonical_u8(*y))
+                                .collect_vec()
+                                .try_into()
+                                .unwrap()
+                        })
+                        .collect_vec();
+
+                let msg_padded_chunks: Vec<[[GoldilocksField; 4]; 16]> = msg_u32_limbs
+                    .chunks_exact(16)
+                    .map(|x| x.try_into().unwrap())
+                    .collect_vec();
+
+                let mut t_value = 0u32;
+                let msg_len = msg.len();
+                let msg_digest_idx = if msg_len == 0 { 0 } else { (msg_len - 1) / 64 };
+                assert!(msg_padded_chunks.len() == *msg_max_chunk_size as usize);
+                for (i, chunk) in msg_padded_chunks.iter().enumerate() {
+                    padded_chunks_values.push(*chunk);
+
+                    t_value += 64;
+
+                    let at_digest_chunk = i == msg_digest_idx;
+                    t_values_values.push(if at_digest_chunk {
+                        msg_len as u32
+                    } else {
+                        t_value
+                    });
+
+                    digest_bits_values.push(GoldilocksField::from_canonical_usize(
+                        at_digest_chunk as usize,
+                    ));
+                    if at_digest_chunk {
+                        digest_indices_values.push(GoldilocksField::from_canonical_usize(
+                            start_index + msg_digest_idx,
+                        ));
+                    }
+
+                    end_bits_values.push(GoldilocksField::from_canonical_usize(
+                        (i == msg_padded_chunks.len() - 1) as usize,
+                    ));
+                }
+
+                start_index += msg_padded_chunks.len();
+            }
+        }
+
+        let num_messages_value =
+            GoldilocksField::from_canonical_usize(num_repetitions * msgs.len());
+
+        // Build the stark
+        let num_rounds = padded_chunks_values.len();
+        let num_rows = 1 << log2_ceil(num_rounds * 80);
+        let mut builder = BytesBuilder::<BLAKE2STest>::new();
+        let padded_chunks = (0..num_rounds)
+            .map(|_| builder.alloc_array_public::<U32Register>(16))
+            .collect::<Vec<_>>();
+        let t_values = builder.alloc_array_public::<U32Register>(num_rounds);
+        let end_bits = builder.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_bits = builder.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_indices = builder.alloc_array_public(num_repetitions * msgs.len());
+        let num_messages = builder.alloc_public();
+        let hash_state = builder.blake::<BLAKE2S>(
+            &padded_chunks,
+            &t_values,
+            &end_bits,
+            &digest_bits,
+            &digest_indices,
+            &num_messages,
+        );
+
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        writer.write(&num_messages, &num_messages_value);
+        let mut hash_state_iter = hash_state.iter();
+        let mut current_state = IV;
+        for i in 0..num_rounds {
+            let padded_chunk = padded_chunks_values[i];
+            writer.write_array(&padded_chunks[i], padded_chunk);
+            writer.write(&end_bits.get(i), &end_bits_values[i]);
+            writer.write(&digest_bits.get(i), &digest_bits_values[i]);
+            writer.write(&t_values.get(i), &u32_to_le_field_bytes(t_values_values[i]));
+
+            let chunk = padded_chunks_values[i];
+            BLAKE2S::compress(
+                &chunk
+                    .iter()
+                    .flatten()
+                    .map(|x| GoldilocksField::as_canonical_u64(x) as u8)
+                    .collect_vec(),
+                &mut current_state,
+                t_values_values[i],
+                digest_bits_values[i] == GoldilocksField::ONE,
+            );
+
+            if digest_bits_values[i] == GoldilocksField::ONE {
+                let array: ArrayRegister<_> = (*hash_state_iter.next().unwrap()).into();
+
+                writer.write_array(
+                    &array,
+                    current_state.iter().map(|x| u32_to_le_field_bytes(*x)),
+                );
+            }
+
+            if end_bits_values[i] == GoldilocksField::ONE {
+                current_state = IV;
+            }
+        }
+
+        for (i, digest_index) in digest_indices_values.iter().enumerate() {
+            writer.write(&digest_indices.get(i), digest_index);
+        }
+
+        timed!(timing, log::Level::Info, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    debug!("writing trace instructions for row {}", i);
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
@@ -0,0 +1,141 @@
+use super::{BLAKE2S, COMPRESS_IV, SIGMA_PERMUTATIONS};
+use crate::machine::hash::blake::{STATE_SIZE, V_INDICES, WORK_VECTOR_SIZE};
+use crate::machine::hash::HashPureInteger;
+
+impl HashPureInteger for BLAKE2S {
+    type Integer = u32;
+}
+
+pub trait BLAKE2SPure: HashPureInteger {
+    fn compress(
+        msg_chunk: &[u8],
+        state: &mut [Self::Integer; STATE_SIZE],
+        bytes_compressed: u32,
+        last_chunk: bool,
+    ) -> [Self::Integer; STATE_SIZE];
+
+    fn mix(
+        v: &mut [Self::Integer; WORK_VECTOR_SIZE],
+        a: usize,
+        b: usize,
+        c: usize,
+        d: usize,
+        x: Self::Integer,
+        y: Self::Integer,
+    );
+}
+
+impl BLAKE2SPure for BLAKE2S {
+    fn compress(
+        msg_chunk: &[u8],
+        state: &mut [Self::Integer; STATE_SIZE],
+        bytes_compressed: u32,
+        last_chunk: bool,
+    ) -> [Self::Integer; STATE_SIZE] {
+        // Set up the work vector V
+        let mut v: [Self::Integer; WORK_VECTOR_SIZE] = [0; WORK_VECTOR_SIZE];
+
+        v[..8].copy_from_slice(&state[..STATE_SIZE]);
+        v[8..16].copy_from_slice(&COMPRESS_IV);
+
+        v[12] ^= bytes_compressed;
+        if last_chunk {
+            v[14] ^= 0xFFFFFFFF;
+        }
+
+        let msg_u32_chunks = msg_chunk
+            .chunks_exact(4)
+            .map(|x| Self::Integer::from_le_bytes(x.try_into().unwrap()))
+            .collect::<Vec<_>>();
+
+        for s in SIGMA_PERMUTATIONS.iter() {
+            for (i, [a, b, c, d]) in V_INDICES.iter().enumerate() {
+                Self::mix(
+                    &mut v,
+                    *a as usize,
+                    *b as usize,
+                    *c as usize,
+                    *d as usize,
+                    msg_u32_chunks[s[2 * i] as usize],
+                    msg_u32_chunks[s[2 * i + 1] as usize],
+                );
+            }
+        }
+
+        for i in 0..STATE_SIZE {
+            state[i] ^= v[i];
+        }
+
+        for i in 0..STATE_SIZE {
+            state[i] ^= v[i + 8];
+        }
+
+        *state
+    }
+
+    fn mix(
+        v: &mut [Self::Integer; WORK_VECTOR_SIZE],
+        a: usize,
+        b: usize,
+        c: usize,
+        d: usize,
+        x: Self::Integer,
+        y: Self::Integer,
+    ) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::machine::hash::blake::blake2s::utils::BLAKE2SUtil;
+    use crate::machine::hash::blake::blake2s::IV;
+
+    #[test]
+    fn test_blake2s_pure() {
+        let msgs: [(Vec<u8>, &str); 3] = [
+            (
+                b"".to_vec(),
+                "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9",
+            ),
+            (
+                b"abc".to_vec(),
+                "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982",
+            ),
+            (
+                (0..200).map(|i| (i % 251) as u8).collect(),
+                "6d244e1a06ce4ef578dd0f63aff0936706735119ca9c8d22d86c801414ab9741",
+            ),
+        ];
+
+        for (msg, expected_digest) in msgs {
+            let num_chunks = if msg.is_empty() {
+                1
+            } else {
+                (msg.len() + 63) / 64
+            };
+            let padded_msg = BLAKE2SUtil::pad(&msg, num_chunks as u64);
+
+            let mut state = IV;
+            for (i, chunk) in padded_msg.chunks_exact(64).enumerate() {
+                let last_chunk = i == num_chunks - 1;
+                let t = if last_chunk { msg.len() } else { 64 * (i + 1) };
+                BLAKE2S::compress(chunk, &mut state, t as u32, last_chunk);
+            }
+
+            let digest = state
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect::<Vec<_>>();
+            assert_eq!(hex::encode(digest), expected_digest);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+pub mod test_utils {
+
+    use core::fmt::Debug;
+    use std::env;
+
+    use itertools::Itertools;
+    use log::debug;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::timed;
+    use plonky2::util::log2_ceil;
+    use plonky2::util::timing::TimingTree;
+    use serde::{Deserialize, Serialize};
+
+    use crate::chip::register::array::ArrayRegister;
+    use crate::chip::uint::bytes::register::ByteRegister;
+    use crate::chip::uint::operations::instruction::UintInstruction;
+    use crate::chip::uint::util::u32_to_le_field_bytes;
+    use crate::chip::AirParameters;
+    use crate::machine::builder::Builder;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::machine::hash::blake::blake3::pure::BLAKE3Pure;
+    use crate::machine::hash::blake::blake3::BLAKE3;
+    use crate::machine::hash::blake::builder::BlakeBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::math::prelude::*;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+    use crate::prelude::{AirWriter, AirWriterData};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct BLAKE3Test;
+
+    impl AirParameters for BLAKE3Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 1171;
+        const EXTENDED_COLUMNS: usize = 1654;
+    }
+
+    #[test]
+    pub fn test_blake3() {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        env::set_var("RUST_LOG", "info");
+        env_logger::try_init().unwrap_or_default();
+        let mut timing = TimingTree::new("test_blake3", log::Level::Info);
+
+        let msgs = [
+            // 1 chunk node
+            b"".to_vec(),
+            // 1 chunk node
+            b"abc".to_vec(),
+            // 1 chunk node
+            (0..1024).map(|i| (i % 251) as u8).collect_vec(),
+            // 2 chunk nodes and 1 parent node
+            (0..1025).map(|i| (i % 251) as u8).collect_vec(),
+            // 3 chunk nodes and 2 parent nodes
+            (0..2049).map(|i| (i % 251) as u8).collect_vec(),
+        ];
+        let expected_digests = [
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+            "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+            "5f4d72f40d7a5f82b15ca2b2e44b1de3c2ef86c426c95c1af0b6879522563030",
+        ];
+        let num_repetitions = 2;
+
+        let mut digest_values = Vec::new();
+        let mut num_rounds = 0;
+        for _i in 0..num_repetitions {
+            for (msg, expected_digest) in msgs.iter().zip_eq(expected_digests.iter()) {
+                let nodes = BLAKE3::nodes(msg);
+                num_rounds += nodes.iter().map(|node| node.blocks.len()).sum::<usize>();
+
+                let digest = BLAKE3::hash(msg);
+                let digest_bytes = digest.iter().flat_map(|x| x.to_le_bytes()).collect_vec();
+                assert_eq!(hex::encode(digest_bytes), *expected_digest);
+                digest_values.push(digest);
+            }
+        }
+
+        // Build the stark
+        let num_rows = 1 << log2_ceil(num_rounds * 56);
+        let mut builder = BytesBuilder::<BLAKE3Test>::new();
+        let messages = (0..num_repetitions)
+            .flat_map(|_| msgs.iter())
+            .map(|msg| builder.alloc_array_public::<ByteRegister>(msg.len()))
+            .collect::<Vec<_>>();
+        let hash_state = builder.blake3_messages::<BLAKE3>(&messages);
+
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        for (message, msg) in messages
+            .iter()
+            .zip_eq((0..num_repetitions).flat_map(|_| msgs.iter()))
+        {
+            writer.write_array(
+                message,
+                msg.iter().map(|b| GoldilocksField::from_canonical_u8(*b)),
+            );
+        }
+        for (digest, digest_value) in hash_state.iter().zip_eq(digest_values.iter()) {
+            let array: ArrayRegister<_> = (*digest).into();
+            writer.write_array(
+                &array,
+                digest_value.iter().map(|x| u32_to_le_field_bytes(*x)),
+            );
+        }
+
+        timed!(timing, log::Level::Info, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    debug!("writing trace instructions for row {}", i);
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            log::Level::Info,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
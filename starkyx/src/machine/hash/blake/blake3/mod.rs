@@ -0,0 +1,132 @@
+//! BLAKE3 in its default hashing mode.
+//!
+//! A message is split into chunks of `CHUNK_LEN` bytes which are compressed block by block, and
+//! the chaining values of the chunks are then merged in a binary tree of parent nodes. Both chunk
+//! and parent nodes are compressed as separate messages of the BLAKE AIR, where the block of a
+//! parent node is the concatenation of the chaining values of its children. The chaining values
+//! are written to the block of the parent through memory, and the counters, block lengths and
+//! flags of every compress are fixed by the length of the message.
+
+use serde::{Deserialize, Serialize};
+
+use super::MSG_ARRAY_SIZE;
+
+pub mod air;
+pub mod builder;
+pub mod pure;
+pub mod register;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BLAKE3;
+
+const NUM_MIX_ROUNDS: usize = 7;
+
+pub const BLOCK_LEN: usize = 64;
+pub const CHUNK_LEN: usize = 1024;
+
+pub const CHUNK_START: u32 = 1 << 0;
+pub const CHUNK_END: u32 = 1 << 1;
+pub const PARENT: u32 = 1 << 2;
+pub const ROOT: u32 = 1 << 3;
+
+/// A node of the hash tree of a message, see [`tree_layout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BLAKE3TreeNode {
+    /// The chunk `index` of the message, of `length` bytes.
+    Chunk {
+        index: usize,
+        length: usize,
+        is_root: bool,
+    },
+    /// A parent of the nodes at positions `left` and `right` of the layout.
+    Parent {
+        left: usize,
+        right: usize,
+        is_root: bool,
+    },
+}
+
+impl BLAKE3TreeNode {
+    /// The number of blocks compressed by the node.
+    pub(crate) fn num_blocks(&self) -> usize {
+        match self {
+            Self::Chunk { length, .. } => (*length).max(1).div_ceil(BLOCK_LEN),
+            Self::Parent { .. } => 1,
+        }
+    }
+
+    pub(crate) fn is_root(&self) -> bool {
+        match self {
+            Self::Chunk { is_root, .. } | Self::Parent { is_root, .. } => *is_root,
+        }
+    }
+}
+
+/// The nodes of the hash tree of a message of `length` bytes, ordered such that children come
+/// before their parents. The last node is the root.
+pub(crate) fn tree_layout(length: usize) -> Vec<BLAKE3TreeNode> {
+    let num_chunks = length.max(1).div_ceil(CHUNK_LEN);
+    let chunk = |index: usize, is_root: bool| BLAKE3TreeNode::Chunk {
+        index,
+        length: (length - index * CHUNK_LEN).min(CHUNK_LEN),
+        is_root,
+    };
+
+    if num_chunks == 1 {
+        return vec![chunk(0, true)];
+    }
+
+    // Merge the complete subtrees as soon as they are available, and the remaining ones from
+    // right to left once the last chunk is added, as in `BLAKE3Pure::nodes`.
+    let mut nodes = Vec::new();
+    let mut stack = Vec::new();
+    for i in 0..num_chunks - 1 {
+        nodes.push(chunk(i, false));
+        let mut right = nodes.len() - 1;
+
+        let mut total_chunks = i + 1;
+        while total_chunks & 1 == 0 {
+            let left = stack.pop().unwrap();
+            nodes.push(BLAKE3TreeNode::Parent {
+                left,
+                right,
+                is_root: false,
+            });
+            right = nodes.len() - 1;
+            total_chunks >>= 1;
+        }
+        stack.push(right);
+    }
+
+    nodes.push(chunk(num_chunks - 1, false));
+    let mut right = nodes.len() - 1;
+    while let Some(left) = stack.pop() {
+        nodes.push(BLAKE3TreeNode::Parent {
+            left,
+            right,
+            is_root: stack.is_empty(),
+        });
+        right = nodes.len() - 1;
+    }
+
+    nodes
+}
+
+pub const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// The last four words of the work vector are set from the counter, block length and flags of
+// each compress.
+const COMPRESS_IV: [u32; 8] = [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0, 0, 0, 0];
+
+// The message schedule of round `r` is given by applying the BLAKE3 message permutation `r` times.
+const SIGMA_PERMUTATIONS: [[u8; MSG_ARRAY_SIZE]; NUM_MIX_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
@@ -0,0 +1,286 @@
+use super::{
+    BLAKE3, BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START, COMPRESS_IV, IV, PARENT, ROOT,
+    SIGMA_PERMUTATIONS,
+};
+use crate::machine::hash::blake::{MSG_ARRAY_SIZE, STATE_SIZE, V_INDICES, WORK_VECTOR_SIZE};
+use crate::machine::hash::HashPureInteger;
+
+impl HashPureInteger for BLAKE3 {
+    type Integer = u32;
+}
+
+/// A node of the BLAKE3 hash tree, given by the blocks it compresses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BLAKE3Node {
+    pub blocks: Vec<[u32; MSG_ARRAY_SIZE]>,
+    /// The words `[counter_low, counter_high, block_len, flags]` of every block.
+    pub t_values: Vec<[u32; 4]>,
+}
+
+pub trait BLAKE3Pure: HashPureInteger {
+    /// The BLAKE3 compression function, returning the full output state.
+    fn compress(
+        chaining_value: &[Self::Integer; STATE_SIZE],
+        block: &[Self::Integer; MSG_ARRAY_SIZE],
+        t: &[Self::Integer; 4],
+    ) -> [Self::Integer; WORK_VECTOR_SIZE];
+
+    fn mix(
+        v: &mut [Self::Integer; WORK_VECTOR_SIZE],
+        a: usize,
+        b: usize,
+        c: usize,
+        d: usize,
+        x: Self::Integer,
+        y: Self::Integer,
+    );
+
+    /// The nodes of the hash tree of a message, ordered such that children come before their
+    /// parents. The last node is the root.
+    fn nodes(msg: &[u8]) -> Vec<BLAKE3Node>;
+
+    /// The chaining value of a node.
+    fn chaining_value(node: &BLAKE3Node) -> [Self::Integer; STATE_SIZE];
+
+    /// Hash a byte message.
+    fn hash(msg: &[u8]) -> [Self::Integer; STATE_SIZE] {
+        let nodes = Self::nodes(msg);
+        Self::chaining_value(nodes.last().unwrap())
+    }
+}
+
+fn chunk_node(chunk: &[u8], counter: u64, is_root: bool) -> BLAKE3Node {
+    let blocks = if chunk.is_empty() {
+        vec![chunk]
+    } else {
+        chunk.chunks(BLOCK_LEN).collect()
+    };
+    let num_blocks = blocks.len();
+
+    let mut node = BLAKE3Node {
+        blocks: Vec::with_capacity(num_blocks),
+        t_values: Vec::with_capacity(num_blocks),
+    };
+    for (i, block) in blocks.into_iter().enumerate() {
+        let mut padded_block = [0u8; BLOCK_LEN];
+        padded_block[..block.len()].copy_from_slice(block);
+        node.blocks.push(core::array::from_fn(|j| {
+            u32::from_le_bytes(padded_block[4 * j..4 * j + 4].try_into().unwrap())
+        }));
+
+        let mut flags = 0;
+        if i == 0 {
+            flags |= CHUNK_START;
+        }
+        if i == num_blocks - 1 {
+            flags |= CHUNK_END;
+            if is_root {
+                flags |= ROOT;
+            }
+        }
+        node.t_values.push([
+            counter as u32,
+            (counter >> 32) as u32,
+            block.len() as u32,
+            flags,
+        ]);
+    }
+    node
+}
+
+fn parent_node(
+    left_child_cv: &[u32; STATE_SIZE],
+    right_child_cv: &[u32; STATE_SIZE],
+    is_root: bool,
+) -> BLAKE3Node {
+    let block = core::array::from_fn(|i| {
+        if i < STATE_SIZE {
+            left_child_cv[i]
+        } else {
+            right_child_cv[i - STATE_SIZE]
+        }
+    });
+    let flags = if is_root { PARENT | ROOT } else { PARENT };
+    BLAKE3Node {
+        blocks: vec![block],
+        t_values: vec![[0, 0, BLOCK_LEN as u32, flags]],
+    }
+}
+
+impl BLAKE3Pure for BLAKE3 {
+    fn compress(
+        chaining_value: &[Self::Integer; STATE_SIZE],
+        block: &[Self::Integer; MSG_ARRAY_SIZE],
+        t: &[Self::Integer; 4],
+    ) -> [Self::Integer; WORK_VECTOR_SIZE] {
+        // Set up the work vector V
+        let mut v: [Self::Integer; WORK_VECTOR_SIZE] = [0; WORK_VECTOR_SIZE];
+
+        v[..8].copy_from_slice(chaining_value);
+        v[8..16].copy_from_slice(&COMPRESS_IV);
+        for (v_i, t_i) in v[12..16].iter_mut().zip(t.iter()) {
+            *v_i ^= t_i;
+        }
+
+        for s in SIGMA_PERMUTATIONS.iter() {
+            for (i, [a, b, c, d]) in V_INDICES.iter().enumerate() {
+                Self::mix(
+                    &mut v,
+                    *a as usize,
+                    *b as usize,
+                    *c as usize,
+                    *d as usize,
+                    block[s[2 * i] as usize],
+                    block[s[2 * i + 1] as usize],
+                );
+            }
+        }
+
+        for i in 0..STATE_SIZE {
+            v[i] ^= v[i + 8];
+            v[i + 8] ^= chaining_value[i];
+        }
+
+        v
+    }
+
+    fn mix(
+        v: &mut [Self::Integer; WORK_VECTOR_SIZE],
+        a: usize,
+        b: usize,
+        c: usize,
+        d: usize,
+        x: Self::Integer,
+        y: Self::Integer,
+    ) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(12);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(8);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(7);
+    }
+
+    fn nodes(msg: &[u8]) -> Vec<BLAKE3Node> {
+        let chunks = if msg.is_empty() {
+            vec![msg]
+        } else {
+            msg.chunks(CHUNK_LEN).collect::<Vec<_>>()
+        };
+        let num_chunks = chunks.len();
+
+        if num_chunks == 1 {
+            return vec![chunk_node(chunks[0], 0, true)];
+        }
+
+        // Merge the chaining values of complete subtrees as soon as they are available, and the
+        // remaining ones from right to left once the last chunk is compressed.
+        let mut nodes = Vec::new();
+        let mut cv_stack: Vec<[u32; STATE_SIZE]> = Vec::new();
+        for (i, chunk) in chunks[..num_chunks - 1].iter().enumerate() {
+            let node = chunk_node(chunk, i as u64, false);
+            let mut cv = Self::chaining_value(&node);
+            nodes.push(node);
+
+            let mut total_chunks = i + 1;
+            while total_chunks & 1 == 0 {
+                let node = parent_node(&cv_stack.pop().unwrap(), &cv, false);
+                cv = Self::chaining_value(&node);
+                nodes.push(node);
+                total_chunks >>= 1;
+            }
+            cv_stack.push(cv);
+        }
+
+        let node = chunk_node(chunks[num_chunks - 1], (num_chunks - 1) as u64, false);
+        let mut cv = Self::chaining_value(&node);
+        nodes.push(node);
+        while let Some(left_child_cv) = cv_stack.pop() {
+            let node = parent_node(&left_child_cv, &cv, cv_stack.is_empty());
+            cv = Self::chaining_value(&node);
+            nodes.push(node);
+        }
+
+        nodes
+    }
+
+    fn chaining_value(node: &BLAKE3Node) -> [Self::Integer; STATE_SIZE] {
+        node.blocks
+            .iter()
+            .zip(node.t_values.iter())
+            .fold(IV, |cv, (block, t)| {
+                let output = Self::compress(&cv, block, t);
+                core::array::from_fn(|i| output[i])
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::machine::hash::blake::blake3::{tree_layout, BLAKE3TreeNode};
+
+    #[test]
+    fn test_blake3_pure() {
+        let msgs: [(Vec<u8>, &str); 7] = [
+            (
+                b"".to_vec(),
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                b"abc".to_vec(),
+                "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85",
+            ),
+            (
+                (0..1024).map(|i| (i % 251) as u8).collect(),
+                "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+            ),
+            (
+                (0..1025).map(|i| (i % 251) as u8).collect(),
+                "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+            ),
+            (
+                (0..2048).map(|i| (i % 251) as u8).collect(),
+                "e776b6028c7cd22a4d0ba182a8bf62205d2ef576467e838ed6f2529b85fba24a",
+            ),
+            (
+                (0..3072).map(|i| (i % 251) as u8).collect(),
+                "b98cb0ff3623be03326b373de6b9095218513e64f1ee2edd2525c7ad1e5cffd2",
+            ),
+            (
+                (0..4097).map(|i| (i % 251) as u8).collect(),
+                "9b4052b38f1c5fc8b1f9ff7ac7b27cd242487b3d890d15c96a1c25b8aa0fb995",
+            ),
+        ];
+
+        for (msg, expected_digest) in msgs {
+            let digest = BLAKE3::hash(&msg)
+                .iter()
+                .flat_map(|x| x.to_le_bytes())
+                .collect::<Vec<_>>();
+            assert_eq!(hex::encode(digest), expected_digest);
+        }
+    }
+
+    #[test]
+    fn test_blake3_tree_layout() {
+        for length in [0, 3, 1024, 1025, 2048, 3072, 4097, 7 * 1024 + 1] {
+            let msg = vec![0u8; length];
+            let nodes = BLAKE3::nodes(&msg);
+            let layout = tree_layout(length);
+            assert_eq!(nodes.len(), layout.len());
+            for (i, (node, layout_node)) in nodes.iter().zip(layout.iter()).enumerate() {
+                assert_eq!(node.blocks.len(), layout_node.num_blocks());
+                assert_eq!(layout_node.is_root(), i == nodes.len() - 1);
+                if let BLAKE3TreeNode::Parent { left, right, .. } = layout_node {
+                    let left_cv = BLAKE3::chaining_value(&nodes[*left]);
+                    let right_cv = BLAKE3::chaining_value(&nodes[*right]);
+                    assert_eq!(node.blocks[0][..STATE_SIZE], left_cv);
+                    assert_eq!(node.blocks[0][STATE_SIZE..], right_cv);
+                }
+            }
+        }
+    }
+}
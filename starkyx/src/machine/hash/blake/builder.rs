@@ -0,0 +1,365 @@
+use super::air::BLAKEAir;
+use super::blake3::{
+    tree_layout, BLAKE3TreeNode, BLOCK_LEN, CHUNK_END, CHUNK_LEN, CHUNK_START, PARENT, ROOT,
+};
+use super::{MSG_ARRAY_SIZE, STATE_SIZE};
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::machine::hash::forward::DigestForwarding;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::math::prelude::*;
+use crate::prelude::Builder;
+
+pub trait BlakeBuilder: Builder {
+    /// Computes the BLAKE digests of a sequence of padded chunks.
+    ///
+    /// Every chunk has `S::T_LENGTH` consecutive words in `t_values`. A message ends at the chunk
+    /// whose `end_bit` is set and `num_messages` must equal the number of set end bits. The
+    /// digest of every chunk whose `digest_bit` is set is returned in the order given by
+    /// `digest_indices`.
+    fn blake<S: BLAKEAir<Self>>(
+        &mut self,
+        padded_chunks: &[ArrayRegister<S::IntRegister>],
+        t_values: &ArrayRegister<S::IntRegister>,
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: &ArrayRegister<ElementRegister>,
+        num_messages: &ElementRegister,
+    ) -> Vec<S::DigestRegister> {
+        S::blake(
+            self,
+            padded_chunks,
+            t_values,
+            end_bits,
+            digest_bits,
+            digest_indices,
+            num_messages,
+        )
+    }
+
+    /// Computes the BLAKE digests of public messages of bounded length, computing their padding
+    /// and the `t` counters in the AIR.
+    ///
+    /// Each message takes the number of chunks of a message of its maximal length. The chunks
+    /// following the digest chunk are compressed but their output is discarded. Only variants
+    /// with a single `t` word per chunk are supported.
+    fn blake_messages<S: BLAKEAir<Self>>(
+        &mut self,
+        messages: &[MessageRegister],
+    ) -> Vec<S::DigestRegister> {
+        assert_eq!(
+            S::T_LENGTH,
+            1,
+            "Only a single t word per chunk is supported"
+        );
+        let word_length = S::IntRegister::size_of();
+        let chunk_length = 16 * word_length;
+        // The chunk containing the last byte of a message of length `len`.
+        let last_chunk = |len: usize| len.saturating_sub(1) / chunk_length;
+
+        let num_chunks = messages
+            .iter()
+            .map(|message| last_chunk(message.max_length()) + 1)
+            .collect::<Vec<_>>();
+        let num_rounds: usize = num_chunks.iter().sum();
+
+        let padded_chunks = (0..num_rounds)
+            .map(|_| self.alloc_array_public::<S::IntRegister>(16))
+            .collect::<Vec<_>>();
+        let t_values = self.alloc_array_public::<S::IntRegister>(num_rounds);
+        let end_bits = self.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_bits = self.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_indices = self.alloc_array_public::<ElementRegister>(messages.len());
+        let num_messages =
+            self.constant::<ElementRegister>(&Self::Field::from_canonical_usize(messages.len()));
+
+        let mut start_index = 0;
+        for ((message, num_message_chunks), digest_index) in
+            messages.iter().zip(num_chunks).zip(digest_indices.iter())
+        {
+            let max_length = message.max_length();
+            let is_message = self.message_flags(message);
+
+            // The little-endian bytes of the message length.
+            let length_bytes = (0..word_length)
+                .map(|k| {
+                    let shift = (8 * k) as u32;
+                    self.message_length_map::<ElementRegister>(message, |len| {
+                        (len as u64).checked_shr(shift).unwrap_or(0) & 0xff
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for i in 0..num_message_chunks {
+                let digest_bit = digest_bits.get(start_index + i);
+                let digest_bit_expr =
+                    self.message_length_expression(message, |len| (last_chunk(len) == i) as u64);
+                self.set_to_expression(&digest_bit, digest_bit_expr);
+
+                let end_bit = if i == num_message_chunks - 1 {
+                    ArithmeticExpression::one()
+                } else {
+                    ArithmeticExpression::zero()
+                };
+                self.set_to_expression(&end_bits.get(start_index + i), end_bit);
+
+                // The counter is the message length at the digest chunk and the number of bytes
+                // of the chunks so far otherwise.
+                let t_value = ((i + 1) * chunk_length) as u64;
+                let t_bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(
+                    *t_values.get(start_index + i).register(),
+                );
+                for (k, (t_byte, length_byte)) in
+                    t_bytes.iter().zip(length_bytes.iter()).enumerate()
+                {
+                    let t_value_byte = t_value.checked_shr((8 * k) as u32).unwrap_or(0) & 0xff;
+                    self.set_to_expression(
+                        &t_byte,
+                        digest_bit.expr() * length_byte.expr()
+                            + digest_bit.not_expr() * Self::Field::from_canonical_u64(t_value_byte),
+                    );
+                }
+
+                for (j, word) in padded_chunks[start_index + i].iter().enumerate() {
+                    let word_bytes =
+                        ArrayRegister::<ElementRegister>::from_register_unsafe(*word.register());
+                    for (k, byte) in word_bytes.iter().enumerate() {
+                        let position = i * chunk_length + j * word_length + k;
+                        let byte_expr = if position < max_length {
+                            is_message[position].expr() * message.bytes.get(position).expr()
+                        } else {
+                            ArithmeticExpression::zero()
+                        };
+                        self.set_to_expression(&byte, byte_expr);
+                    }
+                }
+            }
+
+            let digest_index_expr = self
+                .message_length_expression(message, |len| (start_index + last_chunk(len)) as u64);
+            self.set_to_expression(&digest_index, digest_index_expr);
+
+            start_index += num_message_chunks;
+        }
+
+        S::blake(
+            self,
+            &padded_chunks,
+            &t_values,
+            &end_bits,
+            &digest_bits,
+            &digest_indices,
+            &num_messages,
+        )
+    }
+
+    /// Computes the BLAKE3 digests of public messages of fixed length.
+    ///
+    /// Every node of the hash tree of a message is compressed as a separate message, with the
+    /// chunk nodes first followed by their parents as in `BLAKE3Pure::nodes`. The chaining value
+    /// of a node is forwarded to the block of its parent through memory, and the counters, block
+    /// lengths and flags are constants given by the length of the message, so only the message
+    /// bytes and the digests of the roots are public.
+    fn blake3_messages<S: BLAKEAir<Self>>(
+        &mut self,
+        messages: &[ArrayRegister<ByteRegister>],
+    ) -> Vec<S::DigestRegister> {
+        assert_eq!(S::T_LENGTH, 4, "BLAKE3 needs four t words per chunk");
+        assert_eq!(S::DIGEST_LENGTH, STATE_SIZE);
+        let word_length = S::IntRegister::size_of();
+        assert_eq!(MSG_ARRAY_SIZE * word_length, BLOCK_LEN);
+
+        let layouts = messages
+            .iter()
+            .map(|message| tree_layout(message.len()))
+            .collect::<Vec<_>>();
+        let num_nodes = layouts.iter().map(|layout| layout.len()).sum::<usize>();
+        let num_chunks = layouts
+            .iter()
+            .flat_map(|layout| layout.iter().map(|node| node.num_blocks()))
+            .sum::<usize>();
+
+        let padded_chunks = (0..num_chunks)
+            .map(|_| self.alloc_array_public::<S::IntRegister>(MSG_ARRAY_SIZE))
+            .collect::<Vec<_>>();
+        let t_values = self.alloc_array_public::<S::IntRegister>(4 * num_chunks);
+        let end_bits = self.alloc_array_public::<BitRegister>(num_chunks);
+        let digest_bits = self.alloc_array_public::<BitRegister>(num_chunks);
+        let digest_indices = self.alloc_array_public::<ElementRegister>(messages.len());
+        let public_words = (0..num_chunks)
+            .map(|_| self.alloc_array_public::<BitRegister>(MSG_ARRAY_SIZE))
+            .collect::<Vec<_>>();
+        let forward_bits = self.alloc_array_public::<BitRegister>(num_chunks);
+        let target_chunks = self.alloc_array_public::<ElementRegister>(num_chunks);
+        let target_offsets = self.alloc_array_public::<ElementRegister>(num_chunks);
+        let num_messages =
+            self.constant::<ElementRegister>(&Self::Field::from_canonical_usize(num_nodes));
+
+        let constant = |value: usize| {
+            ArithmeticExpression::from_constant(Self::Field::from_canonical_usize(value))
+        };
+
+        let mut start_chunk = 0;
+        for ((message, layout), digest_index) in messages
+            .iter()
+            .zip(layouts.iter())
+            .zip(digest_indices.iter())
+        {
+            // The first chunk of every node, and the parent chunk and offset of every child.
+            let mut node_starts = Vec::with_capacity(layout.len());
+            for node in layout.iter() {
+                node_starts.push(start_chunk);
+                start_chunk += node.num_blocks();
+            }
+            let mut targets = vec![(0, 0); layout.len()];
+            for (i, node) in layout.iter().enumerate() {
+                if let BLAKE3TreeNode::Parent { left, right, .. } = node {
+                    targets[*left] = (node_starts[i], 0);
+                    targets[*right] = (node_starts[i], STATE_SIZE);
+                }
+            }
+
+            for ((node, node_start), (target_chunk, target_offset)) in
+                layout.iter().zip(node_starts).zip(targets)
+            {
+                let num_blocks = node.num_blocks();
+                for block in 0..num_blocks {
+                    let chunk = node_start + block;
+                    let at_node_end = block == num_blocks - 1;
+                    let is_root = at_node_end && node.is_root();
+
+                    // The t words are `[counter_low, counter_high, block_len, flags]`.
+                    let (counter, block_len, mut flags) = match node {
+                        BLAKE3TreeNode::Chunk { index, length, .. } => {
+                            let mut flags = 0;
+                            if block == 0 {
+                                flags |= CHUNK_START;
+                            }
+                            if at_node_end {
+                                flags |= CHUNK_END;
+                            }
+                            let block_len = length.saturating_sub(block * BLOCK_LEN).min(BLOCK_LEN);
+                            (*index as u64, block_len, flags)
+                        }
+                        BLAKE3TreeNode::Parent { .. } => (0, BLOCK_LEN, PARENT),
+                    };
+                    if is_root {
+                        flags |= ROOT;
+                    }
+                    let t_words = [
+                        counter & 0xffffffff,
+                        counter >> 32,
+                        block_len as u64,
+                        flags as u64,
+                    ];
+                    for (k, t_word) in t_words.iter().enumerate() {
+                        let t = t_values.get(4 * chunk + k);
+                        let t_bytes =
+                            ArrayRegister::<ElementRegister>::from_register_unsafe(*t.register());
+                        for (l, t_byte) in t_bytes.iter().enumerate() {
+                            let t_byte_value =
+                                t_word.checked_shr((8 * l) as u32).unwrap_or(0) & 0xff;
+                            self.set_to_expression(
+                                &t_byte,
+                                ArithmeticExpression::from_constant(
+                                    Self::Field::from_canonical_u64(t_byte_value),
+                                ),
+                            );
+                        }
+                    }
+
+                    // The block of a chunk node is read from the message, while the block of a
+                    // parent node is written by the chaining values of its children.
+                    let chunk_offset = match node {
+                        BLAKE3TreeNode::Chunk { index, .. } => {
+                            Some(index * CHUNK_LEN + block * BLOCK_LEN)
+                        }
+                        BLAKE3TreeNode::Parent { .. } => None,
+                    };
+                    for ((j, word), is_public) in padded_chunks[chunk]
+                        .iter()
+                        .enumerate()
+                        .zip(public_words[chunk].iter())
+                    {
+                        let word_bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(
+                            *word.register(),
+                        );
+                        for (k, byte) in word_bytes.iter().enumerate() {
+                            let position = chunk_offset.map(|offset| offset + j * word_length + k);
+                            let byte_expr = match position {
+                                Some(position) if position < message.len() => {
+                                    message.get(position).expr()
+                                }
+                                _ => ArithmeticExpression::zero(),
+                            };
+                            self.set_to_expression(&byte, byte_expr);
+                        }
+                        self.set_to_expression(
+                            &is_public,
+                            constant(chunk_offset.is_some() as usize),
+                        );
+                    }
+
+                    self.set_to_expression(&end_bits.get(chunk), constant(at_node_end as usize));
+                    self.set_to_expression(&digest_bits.get(chunk), constant(is_root as usize));
+
+                    let forward = at_node_end && !node.is_root();
+                    self.set_to_expression(&forward_bits.get(chunk), constant(forward as usize));
+                    let (target_chunk, target_offset) = if forward {
+                        (target_chunk, target_offset)
+                    } else {
+                        (0, 0)
+                    };
+                    self.set_to_expression(&target_chunks.get(chunk), constant(target_chunk));
+                    self.set_to_expression(&target_offsets.get(chunk), constant(target_offset));
+
+                    if is_root {
+                        self.set_to_expression(&digest_index, constant(chunk));
+                    }
+                }
+            }
+        }
+
+        S::blake_with_forwarding(
+            self,
+            &padded_chunks,
+            &t_values,
+            &end_bits,
+            &digest_bits,
+            &digest_indices,
+            &num_messages,
+            &DigestForwarding {
+                public_words,
+                forward_bits,
+                target_chunks,
+                target_offsets,
+            },
+        )
+    }
+
+    #[deprecated(note = "use `blake`")]
+    fn blake2b<S: BLAKEAir<Self>>(
+        &mut self,
+        padded_chunks: &[ArrayRegister<S::IntRegister>],
+        t_values: &ArrayRegister<S::IntRegister>,
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: &ArrayRegister<ElementRegister>,
+        num_messages: &ElementRegister,
+    ) -> Vec<S::DigestRegister> {
+        self.blake::<S>(
+            padded_chunks,
+            t_values,
+            end_bits,
+            digest_bits,
+            digest_indices,
+            num_messages,
+        )
+    }
+}
+
+impl<B: Builder> BlakeBuilder for B {}
@@ -1,30 +1,29 @@
-use super::{MIX_LENGTH, MSG_ARRAY_SIZE, NUM_MIX_ROUNDS};
+use super::MSG_ARRAY_SIZE;
 use crate::chip::memory::instruction::MemorySliceIndex;
 use crate::chip::memory::pointer::slice::Slice;
 use crate::chip::memory::time::Time;
 use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::bit::BitRegister;
 use crate::chip::register::element::ElementRegister;
-use crate::chip::uint::register::U64Register;
 use crate::machine::builder::Builder;
 use crate::math::field::Field;
 
-pub struct BLAKE2BData<B: Builder> {
-    pub public: BLAKE2BPublicData,
-    pub trace: BLAKE2BTraceData,
-    pub memory: BLAKE2BMemory,
-    pub consts: BLAKE2BConsts<B>,
-    pub const_nums: BLAKE2BConstNums,
+pub struct BLAKEData<B: Builder, T> {
+    pub public: BLAKEPublicData<T>,
+    pub trace: BLAKETraceData,
+    pub memory: BLAKEMemory<T>,
+    pub consts: BLAKEConsts<B, T>,
+    pub const_nums: BLAKEConstNums<T>,
 }
 
-pub struct BLAKE2BPublicData {
-    pub padded_chunks: Vec<ArrayRegister<U64Register>>,
-    pub t_values: ArrayRegister<U64Register>,
+pub struct BLAKEPublicData<T> {
+    pub padded_chunks: Vec<ArrayRegister<T>>,
+    pub t_values: ArrayRegister<T>,
     pub end_bits: ArrayRegister<BitRegister>,
     pub digest_indices: ArrayRegister<ElementRegister>,
 }
 
-pub struct BLAKE2BTraceData {
+pub struct BLAKETraceData {
     pub(crate) clk: ElementRegister,
     pub(crate) is_compress_initialize: BitRegister,
     pub(crate) is_compress_first_row: BitRegister,
@@ -41,55 +40,66 @@ pub struct BLAKE2BTraceData {
     pub(crate) compress_index: ElementRegister,
     pub(crate) mix_id: ElementRegister,
     pub(crate) mix_index: ElementRegister,
+    pub(crate) forward: Option<BLAKEForwardTrace>,
 }
 
-pub struct BLAKE2BMemory {
-    pub(crate) h: Slice<U64Register>,
-    pub(crate) v: Slice<U64Register>,
-    pub(crate) v_final: Slice<U64Register>,
-    pub(crate) m: Slice<U64Register>,
-    pub(crate) t: Slice<U64Register>,
+/// Whether the digest of the current compress is forwarded, and the index in `m` of the first
+/// word receiving it.
+#[derive(Clone, Copy)]
+pub struct BLAKEForwardTrace {
+    pub(crate) at_forward_compress: BitRegister,
+    pub(crate) target: ElementRegister,
 }
 
-pub struct BLAKE2BConsts<B: Builder> {
-    pub(crate) iv: Slice<U64Register>,
-    pub(crate) iv_values: ArrayRegister<U64Register>,
-    pub(crate) compress_iv: Slice<U64Register>,
-    pub(crate) v_indices: MemoryArray<B, MIX_LENGTH, 4>,
-    pub(crate) v_last_write_ages: MemoryArray<B, MIX_LENGTH, 4>,
-    pub(crate) permutations: MemoryArray<B, NUM_MIX_ROUNDS, MSG_ARRAY_SIZE>,
+pub struct BLAKEMemory<T> {
+    pub(crate) h: Slice<T>,
+    pub(crate) v: Slice<T>,
+    pub(crate) v_final: Slice<T>,
+    pub(crate) m: Slice<T>,
+    pub(crate) t: Slice<T>,
+}
+
+pub struct BLAKEConsts<B: Builder, T> {
+    pub(crate) iv: Slice<T>,
+    pub(crate) iv_values: ArrayRegister<T>,
+    pub(crate) compress_iv: Slice<T>,
+    pub(crate) v_indices: MemoryArray<B, 4>,
+    pub(crate) v_last_write_ages: MemoryArray<B, 4>,
+    pub(crate) permutations: MemoryArray<B, MSG_ARRAY_SIZE>,
     pub(crate) dummy_index: ElementRegister,
     pub(crate) dummy_index_2: ElementRegister,
     pub(crate) dummy_ts: ElementRegister,
     pub(crate) first_compress_h_read_ts: ElementRegister,
 }
 
-pub struct BLAKE2BConstNums {
+pub struct BLAKEConstNums<T> {
     pub(crate) const_0: ElementRegister,
-    pub(crate) const_0_u64: U64Register,
+    pub(crate) const_0_word: T,
     pub(crate) const_1: ElementRegister,
     pub(crate) const_2: ElementRegister,
     pub(crate) const_3: ElementRegister,
     pub(crate) const_4: ElementRegister,
     pub(crate) const_8: ElementRegister,
-    pub(crate) const_10: ElementRegister,
-    pub(crate) const_12: ElementRegister,
     pub(crate) const_16: ElementRegister,
-    pub(crate) const_91: ElementRegister,
-    pub(crate) const_96: ElementRegister,
-    pub(crate) const_ffffffffffffffff: U64Register,
+    pub(crate) const_t_length: ElementRegister,
+    pub(crate) const_num_mix_rounds: ElementRegister,
+    pub(crate) const_compress_length: ElementRegister,
 }
 
-pub(crate) struct MemoryArray<B: Builder, const R: usize, const C: usize> {
+/// A two dimensional array of constants stored in memory, with `C` columns and a number of rows
+/// given at construction.
+pub(crate) struct MemoryArray<B: Builder, const C: usize> {
     pub flattened_memory: Slice<ElementRegister>,
+    num_rows: usize,
     c_const: ElementRegister,
     _marker: std::marker::PhantomData<B>,
 }
 
-impl<B: Builder, const R: usize, const C: usize> MemoryArray<B, R, C> {
-    pub(crate) fn new(builder: &mut B) -> Self {
+impl<B: Builder, const C: usize> MemoryArray<B, C> {
+    pub(crate) fn new(builder: &mut B, num_rows: usize) -> Self {
         Self {
             flattened_memory: builder.uninit_slice(),
+            num_rows,
             c_const: builder.constant(&B::Field::from_canonical_usize(C)),
             _marker: core::marker::PhantomData,
         }
@@ -104,7 +114,7 @@ impl<B: Builder, const R: usize, const C: usize> MemoryArray<B, R, C> {
         label: Option<String>,
     ) {
         assert_eq!(values.len(), C);
-        assert!(row < R);
+        assert!(row < self.num_rows);
 
         for (i, value) in values.iter().enumerate() {
             let value_const = builder.constant(&B::Field::from_canonical_u8(*value));
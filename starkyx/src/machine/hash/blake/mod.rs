@@ -1 +1,33 @@
+pub mod air;
 pub mod blake2b;
+pub mod blake2s;
+pub mod blake3;
+pub mod builder;
+pub mod data;
+
+const MIX_LENGTH: usize = 8;
+const MSG_ARRAY_SIZE: usize = 16;
+const STATE_SIZE: usize = 8;
+const WORK_VECTOR_SIZE: usize = 16;
+
+const V_INDICES: [[u8; 4]; MIX_LENGTH] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+const V_LAST_WRITE_AGES: [[u8; 4]; MIX_LENGTH] = [
+    [4, 1, 2, 3],
+    [4, 5, 2, 3],
+    [4, 5, 6, 3],
+    [4, 5, 6, 7],
+    [4, 3, 2, 1],
+    [4, 3, 2, 5],
+    [4, 3, 6, 5],
+    [4, 7, 6, 5],
+];
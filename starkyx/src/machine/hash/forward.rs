@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+
+/// Forwarding of chunk digests to the message words of other chunks through memory.
+///
+/// When the chunk `i` has `forward_bits[i]` set, the digest of the message ending at that chunk
+/// is written to the words `target_offsets[i]..target_offsets[i] + DIGEST_LENGTH` of the chunk
+/// `target_chunks[i]`, without going through the public inputs. The public value of word `j` of
+/// chunk `i` is only used if `public_words[i][j]` is set, so every message word must have exactly
+/// one of the two sources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestForwarding {
+    pub public_words: Vec<ArrayRegister<BitRegister>>,
+    pub forward_bits: ArrayRegister<BitRegister>,
+    pub target_chunks: ArrayRegister<ElementRegister>,
+    pub target_offsets: ArrayRegister<ElementRegister>,
+}
+
+impl DigestForwarding {
+    pub(crate) fn assert_num_chunks(&self, num_chunks: usize) {
+        assert_eq!(self.public_words.len(), num_chunks);
+        assert_eq!(self.forward_bits.len(), num_chunks);
+        assert_eq!(self.target_chunks.len(), num_chunks);
+        assert_eq!(self.target_offsets.len(), num_chunks);
+    }
+}
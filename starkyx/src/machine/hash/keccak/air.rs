@@ -0,0 +1,648 @@
+use log::debug;
+use plonky2::util::log2_ceil;
+
+use super::data::{KeccakData, KeccakMemory, KeccakPublicData, KeccakTraceData};
+use super::pure::KeccakPure;
+use super::register::KeccakDigestRegister;
+use super::{
+    pi_index, DIGEST_LANES, KECCAK256, NUM_ROUNDS, RATE_LANES, RHO_OFFSETS, ROUND_CONSTANTS,
+    SHA3_256, STATE_LANES,
+};
+use crate::chip::memory::time::Time;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::uint::operations::instruction::UintInstructions;
+use crate::chip::uint::register::U64Register;
+use crate::chip::uint::util::{u64_from_le_field_bytes, u64_to_le_field_bytes};
+use crate::chip::AirParameters;
+use crate::machine::builder::Builder;
+use crate::machine::bytes::builder::BytesBuilder;
+use crate::machine::hash::{HashDigest, HashIntConversion, HashInteger};
+use crate::math::prelude::*;
+
+const DUMMY_INDEX: u64 = i32::MAX as u64;
+
+/// Keccak sponge AIR implementation.
+///
+/// An interface for the Keccak sponge as an AIR. Each row of the trace computes one round of the
+/// Keccak-f[1600] permutation, so that every absorbed block takes a cycle of `NUM_ROUNDS` rows.
+pub trait KeccakAir<B: Builder>: KeccakPure + HashIntConversion<B> + HashDigest<B> {
+    /// The clock register, whose value equals the current row.
+    fn clk(builder: &mut B) -> ElementRegister;
+
+    /// Start and end bits for a `NUM_ROUNDS`-cycle.
+    ///
+    /// This function should return two bits `(cycle_start_bit, cycle_end_bit)` that are equal to
+    /// `1` at the first and last row of every `NUM_ROUNDS` cycle respectively and `0` otherwise.
+    fn cycle_bits(builder: &mut B) -> (BitRegister, BitRegister);
+
+    /// Compute the bitwise xor of two lanes.
+    fn xor(builder: &mut B, a: &Self::IntRegister, b: &Self::IntRegister) -> Self::IntRegister;
+
+    /// Apply one round of the Keccak-f[1600] permutation to the state lanes.
+    fn round(
+        builder: &mut B,
+        state: &[Self::IntRegister],
+        round_constant: &Self::IntRegister,
+    ) -> Vec<Self::IntRegister>;
+
+    fn keccak(
+        builder: &mut B,
+        padded_chunks: &[ArrayRegister<Self::IntRegister>],
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Vec<Self::DigestRegister> {
+        let data = Self::data(
+            builder,
+            padded_chunks,
+            end_bits,
+            digest_bits,
+            digest_indices,
+        );
+        Self::permutation(builder, &data)
+    }
+
+    fn data(
+        builder: &mut B,
+        padded_chunks: &[ArrayRegister<Self::IntRegister>],
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> KeccakData<Self::IntRegister> {
+        assert_eq!(padded_chunks.len(), end_bits.len());
+        assert_eq!(padded_chunks.len(), digest_bits.len());
+        let num_real_rounds = padded_chunks.len();
+        debug!(
+            "AIR degree before padding: {}",
+            num_real_rounds * NUM_ROUNDS
+        );
+        let degree_log = log2_ceil(num_real_rounds * NUM_ROUNDS);
+        assert!(degree_log < 31, "AIR degree is too large");
+        debug!("AIR degree after padding: {}", 1 << degree_log);
+        let num_dummy_rounds = (1 << degree_log) / NUM_ROUNDS + 1 - num_real_rounds;
+        // Keep track of the last round length to know how many dummy reads to add.
+        let length_last_round = (1 << degree_log) % NUM_ROUNDS;
+        let num_rounds = num_real_rounds + num_dummy_rounds;
+
+        // Convert the number of rounds to a field element.
+        let num_round_element = builder.constant(&B::Field::from_canonical_usize(num_rounds));
+        let num_round_minus_one = builder.constant(&B::Field::from_canonical_usize(num_rounds - 1));
+
+        // Initialize the round constants and set them to the constant value.
+        let round_constant_values = builder
+            .constant_array::<Self::IntRegister>(&ROUND_CONSTANTS.map(Self::int_to_field_value));
+
+        // Store the round constants in a slice to be able to load them in the trace.
+        let round_constants = builder.uninit_slice();
+
+        for i in 0..length_last_round {
+            builder.store(
+                &round_constants.get(i),
+                round_constant_values.get(i),
+                &Time::zero(),
+                Some(num_round_element),
+                None,
+                None,
+            );
+        }
+
+        for i in length_last_round..NUM_ROUNDS {
+            builder.store(
+                &round_constants.get(i),
+                round_constant_values.get(i),
+                &Time::zero(),
+                Some(num_round_minus_one),
+                None,
+                None,
+            );
+        }
+
+        // Store the message blocks so that lane `j` of block `i` is at index `RATE_LANES * i + j`.
+        let blocks = builder.uninit_slice();
+        for (i, padded_chunk) in padded_chunks.iter().enumerate() {
+            assert_eq!(padded_chunk.len(), RATE_LANES);
+            for (j, lane) in padded_chunk.iter().enumerate() {
+                builder.store(
+                    &blocks.get(RATE_LANES * i + j),
+                    lane,
+                    &Time::zero(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+        }
+
+        // Every row reads `RATE_LANES` lanes, but only the first row of each real round reads the
+        // message block. All other reads are directed to a dummy zero entry.
+        let dummy_entry = builder.constant::<Self::IntRegister>(&Self::int_to_field_value(0));
+
+        assert!(DUMMY_INDEX < B::Field::order());
+        let dummy_index = builder.constant(&B::Field::from_canonical_u64(DUMMY_INDEX));
+
+        let num_dummy_reads = builder.constant::<ElementRegister>(&B::Field::from_canonical_usize(
+            ((1 << degree_log) - num_real_rounds) * RATE_LANES,
+        ));
+        builder.store(
+            &blocks.get(DUMMY_INDEX as usize),
+            dummy_entry,
+            &Time::zero(),
+            Some(num_dummy_reads),
+            None,
+            None,
+        );
+
+        let (cycle_start_bit, cycle_end_bit) = Self::cycle_bits(builder);
+
+        // `process_id` is a register is computed by counting the number of cycles. We do this by
+        // setting `process_id` to be the cumulative sum of the `end_bit` of each cycle.
+        let process_id = builder.process_id(NUM_ROUNDS, cycle_end_bit);
+        // The round index register can be computed as `clock - process_id * NUM_ROUNDS`.
+        let clk = Self::clk(builder);
+        let index = builder.expression(
+            clk.expr() - process_id.expr() * B::Field::from_canonical_usize(NUM_ROUNDS),
+        );
+
+        // Allocate end_bits for public input.
+        let one = builder.constant(&B::Field::ONE);
+        let zero = builder.constant(&B::Field::ZERO);
+        let reg_cycle_length = builder.constant(&B::Field::from_canonical_usize(NUM_ROUNDS));
+        let reg_last_length = builder.constant(&B::Field::from_canonical_usize(length_last_round));
+        let end_bit = builder.uninit_slice();
+        for (i, end_bit_val) in end_bits.iter().enumerate() {
+            builder.store(
+                &end_bit.get(i),
+                end_bit_val,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &end_bit.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &end_bit.get(num_rounds - 1),
+            zero,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+        let digest_bit = builder.uninit_slice();
+        for (i, digest_bit_val) in digest_bits.iter().enumerate() {
+            builder.store(
+                &digest_bit.get(i),
+                digest_bit_val,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &digest_bit.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &digest_bit.get(num_rounds - 1),
+            zero,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+
+        // Initialize a bit slice to commit to `is_dummy` bits.
+        let is_dummy_slice = builder.uninit_slice();
+
+        for i in 0..num_real_rounds {
+            builder.store(
+                &is_dummy_slice.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &is_dummy_slice.get(i),
+                one,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &is_dummy_slice.get(num_rounds - 1),
+            one,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+        let is_dummy = builder.load(
+            &is_dummy_slice.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+
+        let public = KeccakPublicData {
+            padded_chunks: padded_chunks.to_vec(),
+            digest_indices,
+        };
+
+        let trace = KeccakTraceData {
+            process_id,
+            cycle_start_bit,
+            cycle_end_bit,
+            index,
+            is_dummy,
+        };
+
+        let memory = KeccakMemory {
+            round_constants,
+            blocks,
+            end_bit,
+            digest_bit,
+            dummy_index,
+        };
+
+        KeccakData {
+            public,
+            trace,
+            memory,
+            degree: 1 << degree_log,
+        }
+    }
+
+    fn permutation(
+        builder: &mut B,
+        data: &KeccakData<Self::IntRegister>,
+    ) -> Vec<Self::DigestRegister> {
+        // Allocate the public digests and free them from a digest slice at the time given by the
+        // index of the block that produces them.
+        let num_digests = data.public.digest_indices.len();
+        let hash_state_public = (0..num_digests)
+            .map(|_| builder.alloc_public::<Self::DigestRegister>())
+            .collect::<Vec<_>>();
+        let digest_ptr = builder.uninit_slice();
+        for (i, digest) in data
+            .public
+            .digest_indices
+            .iter()
+            .zip(hash_state_public.iter())
+        {
+            let lanes: ArrayRegister<Self::IntRegister> = (*digest).into();
+            assert_eq!(lanes.len(), DIGEST_LANES);
+            for (j, lane) in lanes.iter().enumerate() {
+                builder.free(&digest_ptr.get(j), lane, &Time::from_element(i));
+            }
+        }
+
+        let process_id = data.trace.process_id;
+        let is_dummy = data.trace.is_dummy;
+        let cycle_end_bit = data.trace.cycle_end_bit;
+        let dummy_index = data.memory.dummy_index;
+
+        let round_constant = builder.load(
+            &data.memory.round_constants.get_at(data.trace.index),
+            &Time::zero(),
+            None,
+            None,
+        );
+
+        // Initialize the state lanes and set them to zero in the first row.
+        let zero_lane = builder.constant::<Self::IntRegister>(&Self::int_to_field_value(0));
+        let state = builder.alloc_array::<Self::IntRegister>(STATE_LANES);
+        for lane in state.iter() {
+            builder.set_to_expression_first_row(&lane, zero_lane.expr());
+        }
+
+        // Absorb the message block into the first `RATE_LANES` lanes in the first row of every
+        // real cycle. In all other rows the lanes are xored with the dummy zero entry.
+        let is_absorbing: BitRegister =
+            builder.expression(data.trace.cycle_start_bit.expr() * is_dummy.not_expr());
+        let mut absorbed = Vec::with_capacity(STATE_LANES);
+        for (j, lane) in state.iter().enumerate() {
+            if j < RATE_LANES {
+                let block_index = builder.expression(
+                    is_absorbing.expr()
+                        * (process_id.expr() * B::Field::from_canonical_usize(RATE_LANES)
+                            + B::Field::from_canonical_usize(j))
+                        + is_absorbing.not_expr() * dummy_index.expr(),
+                );
+                let block_lane = builder.load(
+                    &data.memory.blocks.get_at(block_index),
+                    &Time::zero(),
+                    None,
+                    None,
+                );
+                absorbed.push(Self::xor(builder, &lane, &block_lane));
+            } else {
+                absorbed.push(lane);
+            }
+        }
+
+        let state_next = Self::round(builder, &absorbed, &round_constant);
+
+        // Store the digest at the end of the cycle if the digest bit is set.
+        let digest_bit = builder.load(
+            &data.memory.digest_bit.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+        let flag = Some(
+            builder.expression(cycle_end_bit.expr() * is_dummy.not_expr() * digest_bit.expr()),
+        );
+        for (j, lane) in state_next.iter().take(DIGEST_LANES).enumerate() {
+            builder.store(
+                &digest_ptr.get(j),
+                *lane,
+                &Time::from_element(process_id),
+                flag,
+                None,
+                None,
+            );
+        }
+
+        // Set the next row of the state, resetting it to zero after the last block of a message.
+        let end_bit = builder.load(
+            &data.memory.end_bit.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+        let is_reset: BitRegister = builder.expression(cycle_end_bit.expr() * end_bit.expr());
+        for (lane, lane_next) in state.iter().zip(state_next.iter()) {
+            builder
+                .set_to_expression_transition(&lane.next(), lane_next.expr() * is_reset.not_expr());
+        }
+
+        hash_state_public
+    }
+}
+
+impl<B: Builder> HashInteger<B> for KECCAK256 {
+    type Value = <U64Register as Register>::Value<B::Field>;
+    type IntRegister = U64Register;
+}
+
+impl<B: Builder> HashIntConversion<B> for KECCAK256 {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u64_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u64_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for KECCAK256 {
+    type DigestRegister = KeccakDigestRegister;
+}
+
+impl<L: AirParameters> KeccakAir<BytesBuilder<L>> for KECCAK256
+where
+    L::Instruction: UintInstructions,
+{
+    fn clk(builder: &mut BytesBuilder<L>) -> ElementRegister {
+        builder.clk
+    }
+
+    fn cycle_bits(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister) {
+        keccak_cycle_bits(builder)
+    }
+
+    fn xor(
+        builder: &mut BytesBuilder<L>,
+        a: &Self::IntRegister,
+        b: &Self::IntRegister,
+    ) -> Self::IntRegister {
+        builder.xor(a, b)
+    }
+
+    fn round(
+        builder: &mut BytesBuilder<L>,
+        state: &[Self::IntRegister],
+        round_constant: &Self::IntRegister,
+    ) -> Vec<Self::IntRegister> {
+        keccak_round(builder, state, round_constant)
+    }
+}
+
+impl<B: Builder> HashInteger<B> for SHA3_256 {
+    type Value = <U64Register as Register>::Value<B::Field>;
+    type IntRegister = U64Register;
+}
+
+impl<B: Builder> HashIntConversion<B> for SHA3_256 {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u64_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u64_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for SHA3_256 {
+    type DigestRegister = KeccakDigestRegister;
+}
+
+impl<L: AirParameters> KeccakAir<BytesBuilder<L>> for SHA3_256
+where
+    L::Instruction: UintInstructions,
+{
+    fn clk(builder: &mut BytesBuilder<L>) -> ElementRegister {
+        builder.clk
+    }
+
+    fn cycle_bits(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister) {
+        keccak_cycle_bits(builder)
+    }
+
+    fn xor(
+        builder: &mut BytesBuilder<L>,
+        a: &Self::IntRegister,
+        b: &Self::IntRegister,
+    ) -> Self::IntRegister {
+        builder.xor(a, b)
+    }
+
+    fn round(
+        builder: &mut BytesBuilder<L>,
+        state: &[Self::IntRegister],
+        round_constant: &Self::IntRegister,
+    ) -> Vec<Self::IntRegister> {
+        keccak_round(builder, state, round_constant)
+    }
+}
+
+/// A 24-cycle built from a cycle of length 8 and a loop of length 3.
+///
+/// Since the two lengths are coprime, the start and end of both coincide exactly once every 24
+/// rows.
+fn keccak_cycle_bits<L: AirParameters>(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister)
+where
+    L::Instruction: UintInstructions,
+{
+    let cycle_8 = builder.cycle(3);
+    let loop_3 = builder.api.loop_instr(3);
+    let start_bit = builder.mul(loop_3.get_iteration_reg(0), cycle_8.start_bit);
+    let end_bit = builder.mul(loop_3.get_iteration_reg(2), cycle_8.end_bit);
+
+    (start_bit, end_bit)
+}
+
+/// One round of Keccak-f[1600] on `U64Register` lanes using byte lookup operations.
+fn keccak_round<L: AirParameters>(
+    builder: &mut BytesBuilder<L>,
+    state: &[U64Register],
+    round_constant: &U64Register,
+) -> Vec<U64Register>
+where
+    L::Instruction: UintInstructions,
+{
+    assert_eq!(state.len(), STATE_LANES);
+
+    // Theta step: c[x] = a[x, 0] ^ ... ^ a[x, 4] and d[x] = c[x - 1] ^ c[x + 1].rotate_left(1).
+    let mut c = Vec::with_capacity(5);
+    for x in 0..5 {
+        let mut c_x = builder.xor(&state[x], &state[x + 5]);
+        for y in 2..5 {
+            c_x = builder.xor(&c_x, &state[x + 5 * y]);
+        }
+        c.push(c_x);
+    }
+    let mut d = Vec::with_capacity(5);
+    for x in 0..5 {
+        let c_rotate = builder.rotate_right(&c[(x + 1) % 5], 63);
+        d.push(builder.xor(&c[(x + 4) % 5], &c_rotate));
+    }
+    let a = state
+        .iter()
+        .enumerate()
+        .map(|(i, lane)| builder.xor(lane, &d[i % 5]))
+        .collect::<Vec<_>>();
+
+    // Rho and pi steps: b[pi(i)] = a[i].rotate_left(RHO_OFFSETS[i]).
+    let mut b = a.clone();
+    for (i, lane) in a.iter().enumerate() {
+        b[pi_index(i)] = match RHO_OFFSETS[i] {
+            0 => *lane,
+            offset => builder.rotate_right(lane, 64 - offset),
+        };
+    }
+
+    // Chi step: a[x, y] = b[x, y] ^ (!b[x + 1, y] & b[x + 2, y]).
+    let mut state_next = Vec::with_capacity(STATE_LANES);
+    for (i, lane) in b.iter().enumerate() {
+        let (x, y) = (i % 5, i / 5);
+        let not_b = builder.not(&b[(x + 1) % 5 + 5 * y]);
+        let not_b_and_b = builder.and(&not_b, &b[(x + 2) % 5 + 5 * y]);
+        state_next.push(builder.xor(lane, &not_b_and_b));
+    }
+
+    // Iota step.
+    let lane_0 = builder.xor(&state_next[0], round_constant);
+    state_next[0] = lane_0;
+
+    state_next
+}
+
+#[cfg(test)]
+mod tests {
+    use core::iter;
+
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use serde::{Deserialize, Serialize};
+
+    use crate::chip::uint::operations::instruction::UintInstruction;
+    use crate::chip::AirParameters;
+    use crate::machine::hash::keccak::builder::test_utils::test_keccak;
+    use crate::machine::hash::keccak::{KECCAK256, SHA3_256};
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct KeccakTest;
+
+    impl AirParameters for KeccakTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 2350;
+        const EXTENDED_COLUMNS: usize = 6600;
+    }
+
+    #[test]
+    fn test_keccak256_short_message() {
+        let msg = b"abc";
+        let expected_digest = "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45";
+        let num_messages = 2;
+        test_keccak::<KeccakTest, KECCAK256, _, _>(
+            iter::repeat(msg).take(num_messages).map(|x| x.as_slice()),
+            iter::repeat(expected_digest).take(num_messages),
+        )
+    }
+
+    #[test]
+    fn test_keccak256_changing_length_message() {
+        let empty_msg = b"";
+        let empty_expected_digest =
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+        let long_msg = [b'a'; 200];
+        let long_expected_digest =
+            "96ea54061def936c4be90b518992fdc6f12f535068a256229aca54267b4d084d";
+        test_keccak::<KeccakTest, KECCAK256, _, _>(
+            [
+                empty_msg.as_slice(),
+                long_msg.as_slice(),
+                empty_msg.as_slice(),
+            ],
+            [
+                empty_expected_digest,
+                long_expected_digest,
+                empty_expected_digest,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_sha3_256() {
+        let short_msg = b"abc";
+        let short_expected_digest =
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532";
+        let long_msg = [b'a'; 200];
+        let long_expected_digest =
+            "cce34485baf2bf2aca99b94833892a4f52896d3d153f7b840cc4f9fe695f1387";
+        test_keccak::<KeccakTest, SHA3_256, _, _>(
+            [short_msg.as_slice(), long_msg.as_slice()],
+            [short_expected_digest, long_expected_digest],
+        );
+    }
+}
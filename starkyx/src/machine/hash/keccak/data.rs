@@ -0,0 +1,32 @@
+use crate::chip::memory::pointer::slice::Slice;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+
+pub struct KeccakData<T> {
+    pub public: KeccakPublicData<T>,
+    pub trace: KeccakTraceData,
+    pub memory: KeccakMemory<T>,
+    pub degree: usize,
+}
+
+pub struct KeccakPublicData<T> {
+    pub padded_chunks: Vec<ArrayRegister<T>>,
+    pub digest_indices: ArrayRegister<ElementRegister>,
+}
+
+pub struct KeccakTraceData {
+    pub(crate) process_id: ElementRegister,
+    pub(crate) cycle_start_bit: BitRegister,
+    pub(crate) cycle_end_bit: BitRegister,
+    pub index: ElementRegister,
+    pub is_dummy: BitRegister,
+}
+
+pub struct KeccakMemory<T> {
+    pub(crate) round_constants: Slice<T>,
+    pub(crate) blocks: Slice<T>,
+    pub end_bit: Slice<BitRegister>,
+    pub digest_bit: Slice<BitRegister>,
+    pub dummy_index: ElementRegister,
+}
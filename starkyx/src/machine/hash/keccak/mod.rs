@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+pub mod air;
+pub mod builder;
+pub mod data;
+pub mod pure;
+pub mod register;
+
+/// The Keccak-256 hash function, as used by Ethereum.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct KECCAK256;
+
+/// The SHA3-256 hash function, which differs from Keccak-256 only by its padding delimiter.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SHA3_256;
+
+/// The number of rounds of the Keccak-f[1600] permutation.
+pub const NUM_ROUNDS: usize = 24;
+
+/// The number of 64-bit lanes in the Keccak state.
+pub const STATE_LANES: usize = 25;
+
+/// The number of 64-bit lanes absorbed per block, for a capacity of 512 bits.
+pub const RATE_LANES: usize = 17;
+
+/// The number of 64-bit lanes of a 256-bit digest.
+pub const DIGEST_LANES: usize = 4;
+
+pub(crate) const ROUND_CONSTANTS: [u64; NUM_ROUNDS] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Left rotation offsets of the rho step, indexed by the lane index `x + 5 * y`.
+pub(crate) const RHO_OFFSETS: [usize; STATE_LANES] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// The destination of lane `x + 5 * y` under the pi step, i.e. `y + 5 * ((2 * x + 3 * y) % 5)`.
+pub(crate) const fn pi_index(index: usize) -> usize {
+    let (x, y) = (index % 5, index / 5);
+    y + 5 * ((2 * x + 3 * y) % 5)
+}
@@ -0,0 +1,157 @@
+use core::fmt::Debug;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{
+    pi_index, DIGEST_LANES, KECCAK256, RATE_LANES, RHO_OFFSETS, ROUND_CONSTANTS, SHA3_256,
+    STATE_LANES,
+};
+use crate::machine::hash::HashPureInteger;
+
+/// Pure Keccak sponge implementation.
+///
+/// An interface for the Keccak sponge as a Rust function operating on 64-bit lanes.
+pub trait KeccakPure:
+    Debug
+    + Clone
+    + 'static
+    + Serialize
+    + DeserializeOwned
+    + Send
+    + Sync
+    + HashPureInteger<Integer = u64>
+{
+    /// The domain separation byte appended to the message before the `10*1` padding.
+    const DELIMITER: u8;
+
+    /// Pad a byte message to a multiple of `RATE_LANES` little-endian 64-bit lanes.
+    fn pad(msg: &[u8]) -> Vec<u64> {
+        let mut padded_msg = msg.to_vec();
+        padded_msg.push(Self::DELIMITER);
+        let rate_bytes = RATE_LANES * 8;
+        let padlen = (rate_bytes - padded_msg.len() % rate_bytes) % rate_bytes;
+        padded_msg.extend_from_slice(&vec![0u8; padlen]);
+        *padded_msg.last_mut().unwrap() |= 0x80;
+
+        padded_msg
+            .chunks_exact(8)
+            .map(|slice| u64::from_le_bytes(slice.try_into().unwrap()))
+            .collect::<Vec<_>>()
+    }
+
+    /// Absorb a chunk of `RATE_LANES` lanes into the state and apply the permutation.
+    fn process(state: [u64; STATE_LANES], chunk: &[u64]) -> [u64; STATE_LANES] {
+        assert_eq!(chunk.len(), RATE_LANES);
+        let mut state = state;
+        for (lane, word) in state.iter_mut().zip(chunk.iter()) {
+            *lane ^= word;
+        }
+        keccak_f(&mut state);
+        state
+    }
+
+    /// Read the digest lanes from the state.
+    fn squeeze(state: &[u64; STATE_LANES]) -> [u64; DIGEST_LANES] {
+        core::array::from_fn(|i| state[i])
+    }
+
+    /// Hash a byte message to the digest lanes.
+    fn hash(msg: &[u8]) -> [u64; DIGEST_LANES] {
+        let state = Self::pad(msg)
+            .chunks_exact(RATE_LANES)
+            .fold([0u64; STATE_LANES], Self::process);
+        Self::squeeze(&state)
+    }
+
+    /// Decode a digest encoded as a string to its little-endian lanes.
+    fn decode(digest: &str) -> [u64; DIGEST_LANES] {
+        hex::decode(digest)
+            .unwrap()
+            .chunks_exact(8)
+            .map(|x| u64::from_le_bytes(x.try_into().unwrap()))
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap()
+    }
+}
+
+impl HashPureInteger for KECCAK256 {
+    type Integer = u64;
+}
+
+impl KeccakPure for KECCAK256 {
+    const DELIMITER: u8 = 0x01;
+}
+
+impl HashPureInteger for SHA3_256 {
+    type Integer = u64;
+}
+
+impl KeccakPure for SHA3_256 {
+    const DELIMITER: u8 = 0x06;
+}
+
+/// The Keccak-f[1600] permutation.
+pub fn keccak_f(state: &mut [u64; STATE_LANES]) {
+    for round_constant in ROUND_CONSTANTS {
+        *state = keccak_round(*state, round_constant);
+    }
+}
+
+/// A single round of the Keccak-f[1600] permutation.
+pub fn keccak_round(state: [u64; STATE_LANES], round_constant: u64) -> [u64; STATE_LANES] {
+    // Theta step.
+    let c: [u64; 5] = core::array::from_fn(|x| {
+        state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20]
+    });
+    let d: [u64; 5] = core::array::from_fn(|x| c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1));
+    let a: [u64; STATE_LANES] = core::array::from_fn(|i| state[i] ^ d[i % 5]);
+
+    // Rho and pi steps.
+    let mut b = [0u64; STATE_LANES];
+    for (i, lane) in a.iter().enumerate() {
+        b[pi_index(i)] = lane.rotate_left(RHO_OFFSETS[i] as u32);
+    }
+
+    // Chi step.
+    let mut result: [u64; STATE_LANES] = core::array::from_fn(|i| {
+        let (x, y) = (i % 5, i / 5);
+        b[i] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y])
+    });
+
+    // Iota step.
+    result[0] ^= round_constant;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak_pure() {
+        let cases: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a",
+            ),
+            (
+                b"abc",
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+                "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            ),
+            (
+                &[b'a'; 200],
+                "96ea54061def936c4be90b518992fdc6f12f535068a256229aca54267b4d084d",
+                "cce34485baf2bf2aca99b94833892a4f52896d3d153f7b840cc4f9fe695f1387",
+            ),
+        ];
+
+        for (msg, keccak_digest, sha3_digest) in cases {
+            assert_eq!(KECCAK256::hash(msg), KECCAK256::decode(keccak_digest));
+            assert_eq!(SHA3_256::hash(msg), SHA3_256::decode(sha3_digest));
+        }
+    }
+}
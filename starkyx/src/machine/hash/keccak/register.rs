@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chip::register::array::{ArrayIterator, ArrayRegister};
+use crate::chip::register::cell::CellType;
+use crate::chip::register::memory::MemorySlice;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::chip::uint::register::U64Register;
+use crate::machine::hash::keccak::DIGEST_LANES;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct KeccakDigestRegister(ArrayRegister<U64Register>);
+
+impl RegisterSerializable for KeccakDigestRegister {
+    const CELL: CellType = CellType::Element;
+    fn register(&self) -> &MemorySlice {
+        self.0.register()
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(ArrayRegister::from_register_unsafe(register))
+    }
+}
+
+impl RegisterSized for KeccakDigestRegister {
+    fn size_of() -> usize {
+        U64Register::size_of() * DIGEST_LANES
+    }
+}
+
+impl Register for KeccakDigestRegister {
+    type Value<T> = [T; 32];
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+}
+
+impl KeccakDigestRegister {
+    pub fn as_array(&self) -> ArrayRegister<U64Register> {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> U64Register {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> ArrayIterator<U64Register> {
+        self.0.iter()
+    }
+
+    pub fn from_array(array: ArrayRegister<U64Register>) -> Self {
+        assert_eq!(array.len(), DIGEST_LANES);
+        Self(array)
+    }
+}
+
+impl From<KeccakDigestRegister> for ArrayRegister<U64Register> {
+    fn from(register: KeccakDigestRegister) -> Self {
+        register.0
+    }
+}
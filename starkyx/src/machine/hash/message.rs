@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::Register;
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::uint::bytes::register::ByteRegister;
+use crate::machine::builder::Builder;
+use crate::math::prelude::*;
+
+/// A public message of bounded length whose padding is computed in the AIR.
+///
+/// The message is given by `bytes`, of which only the first `length` are part of the message.
+/// The length is also encoded by `length_selector`, a one-hot array of `max_length() + 1` bits
+/// such that `length_selector[i] = 1` if and only if `length = i`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MessageRegister {
+    pub bytes: ArrayRegister<ByteRegister>,
+    pub length: ElementRegister,
+    pub length_selector: ArrayRegister<BitRegister>,
+}
+
+impl MessageRegister {
+    /// The maximal length of a message that fits in the register.
+    pub fn max_length(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Writes the message bytes, its length and its length selector.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, msg: &[u8]) {
+        let max_length = self.max_length();
+        assert!(msg.len() <= max_length, "Message too big");
+
+        for i in 0..max_length {
+            let byte = msg.get(i).copied().unwrap_or(0);
+            writer.write(&self.bytes.get(i), &W::Field::from_canonical_u8(byte));
+        }
+        writer.write(&self.length, &W::Field::from_canonical_usize(msg.len()));
+        for i in 0..=max_length {
+            writer.write(
+                &self.length_selector.get(i),
+                &W::Field::from_canonical_usize((i == msg.len()) as usize),
+            );
+        }
+    }
+}
+
+pub trait MessageBuilder: Builder {
+    /// Allocates a public message of at most `max_length` bytes.
+    fn alloc_public_message(&mut self, max_length: usize) -> MessageRegister {
+        let bytes = self.alloc_array_public::<ByteRegister>(max_length);
+        let length = self.alloc_public::<ElementRegister>();
+        let length_selector = self.alloc_array_public::<BitRegister>(max_length + 1);
+
+        // Constrain the selector to be one-hot and to agree with `length`.
+        let selector_sum = length_selector
+            .iter()
+            .fold(ArithmeticExpression::zero(), |acc, bit| acc + bit.expr());
+        self.assert_expression_zero(selector_sum - Self::Field::ONE);
+        let selector_length = length_selector
+            .iter()
+            .enumerate()
+            .fold(ArithmeticExpression::zero(), |acc, (i, bit)| {
+                acc + bit.expr() * Self::Field::from_canonical_usize(i)
+            });
+        self.assert_expression_zero(selector_length - length.expr());
+
+        MessageRegister {
+            bytes,
+            length,
+            length_selector,
+        }
+    }
+
+    /// Returns public flags `flags[i]` which are `1` if `i < length` and `0` otherwise.
+    fn message_flags(&mut self, message: &MessageRegister) -> Vec<ElementRegister> {
+        let max_length = message.max_length();
+        let mut flags = Vec::with_capacity(max_length);
+
+        // `flags[i]` is the sum of `length_selector[j]` for `j > i`.
+        let mut flag_expr = ArithmeticExpression::zero();
+        for i in (0..max_length).rev() {
+            flag_expr = flag_expr + message.length_selector.get(i + 1).expr();
+            let flag = self.public_expression::<ElementRegister>(flag_expr);
+            flag_expr = flag.expr();
+            flags.push(flag);
+        }
+        flags.reverse();
+        flags
+    }
+
+    /// Returns an expression equal to `f(length)`.
+    ///
+    /// The expression is a linear combination of the length selector bits, so the values of `f`
+    /// need not be range checked.
+    fn message_length_expression(
+        &mut self,
+        message: &MessageRegister,
+        f: impl Fn(usize) -> u64,
+    ) -> ArithmeticExpression<Self::Field> {
+        message
+            .length_selector
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| (f(i), bit))
+            .filter(|(value, _)| *value != 0)
+            .fold(ArithmeticExpression::zero(), |acc, (value, bit)| {
+                acc + bit.expr() * Self::Field::from_canonical_u64(value)
+            })
+    }
+
+    /// Returns a public register equal to `f(length)`.
+    fn message_length_map<T: Register>(
+        &mut self,
+        message: &MessageRegister,
+        f: impl Fn(usize) -> u64,
+    ) -> T {
+        let expression = self.message_length_expression(message, f);
+        self.public_expression(expression)
+    }
+}
+
+impl<B: Builder> MessageBuilder for B {}
@@ -8,6 +8,10 @@ use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::Register;
 
 pub mod blake;
+pub mod forward;
+pub mod keccak;
+pub mod message;
+pub mod poseidon2;
 pub mod sha;
 
 pub trait HashPureInteger {
@@ -0,0 +1,575 @@
+use log::debug;
+use plonky2::util::log2_ceil;
+
+use super::data::{PoseidonData, PoseidonMemory, PoseidonPublicData, PoseidonTraceData};
+use super::pure::{partial_row_linear_maps, row_constants, N_PARTIAL_ROW_VARIABLES};
+use super::register::PoseidonDigestRegister;
+use super::{
+    is_full_row, DIGEST_LENGTH, MDS_MATRIX_CIRC, MDS_MATRIX_DIAG, N_PARTIAL_ROUNDS_PER_ROW, N_ROWS,
+    POSEIDON, RATE, WIDTH,
+};
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::memory::pointer::slice::Slice;
+use crate::chip::memory::time::Time;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::machine::builder::Builder;
+use crate::math::prelude::*;
+
+const DUMMY_INDEX: u64 = i32::MAX as u64;
+
+/// Poseidon AIR implementation.
+///
+/// Each row of the trace computes either a full round of the permutation or
+/// `N_PARTIAL_ROUNDS_PER_ROW` partial rounds on a state of `ElementRegister`s, so that every
+/// absorbed chunk takes a cycle of `N_ROWS` rows. The S-box is constrained directly with
+/// arithmetic expressions and no lookup table is needed.
+pub trait PoseidonAir<B: Builder> {
+    /// Start and end bits for a `N_ROWS`-cycle.
+    ///
+    /// The cycle is built from a cycle of length 2 and a loop of length 5. Since these lengths are
+    /// coprime, their starts and ends coincide exactly once every 10 rows.
+    fn cycle_bits(builder: &mut B) -> (BitRegister, BitRegister) {
+        let cycle_2 = builder.cycle(1);
+        let loop_5 = builder.api().loop_instr(5);
+
+        let start_bit = builder.mul(loop_5.get_iteration_reg(0), cycle_2.start_bit);
+        let end_bit = builder.mul(loop_5.get_iteration_reg(4), cycle_2.end_bit);
+
+        (start_bit, end_bit)
+    }
+
+    fn poseidon(
+        builder: &mut B,
+        padded_chunks: &[ArrayRegister<ElementRegister>],
+        chunk_lengths: &ArrayRegister<ElementRegister>,
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Vec<PoseidonDigestRegister> {
+        let data = Self::data(
+            builder,
+            padded_chunks,
+            chunk_lengths,
+            end_bits,
+            digest_bits,
+            digest_indices,
+        );
+        Self::permutation(builder, &data)
+    }
+
+    fn data(
+        builder: &mut B,
+        padded_chunks: &[ArrayRegister<ElementRegister>],
+        chunk_lengths: &ArrayRegister<ElementRegister>,
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> PoseidonData {
+        assert_eq!(padded_chunks.len(), chunk_lengths.len());
+        assert_eq!(padded_chunks.len(), end_bits.len());
+        assert_eq!(padded_chunks.len(), digest_bits.len());
+        let num_real_rounds = padded_chunks.len();
+        debug!("AIR degree before padding: {}", num_real_rounds * N_ROWS);
+        let degree_log = log2_ceil(num_real_rounds * N_ROWS);
+        assert!(degree_log < 31, "AIR degree is too large");
+        debug!("AIR degree after padding: {}", 1 << degree_log);
+        let num_dummy_rounds = (1 << degree_log) / N_ROWS + 1 - num_real_rounds;
+        // Keep track of the last round length to know how many dummy reads to add.
+        let length_last_round = (1 << degree_log) % N_ROWS;
+        let num_rounds = num_real_rounds + num_dummy_rounds;
+
+        // Convert the number of rounds to a field element.
+        let num_round_element: ElementRegister =
+            builder.constant(&B::Field::from_canonical_usize(num_rounds));
+        let num_round_minus_one: ElementRegister =
+            builder.constant(&B::Field::from_canonical_usize(num_rounds - 1));
+        let round_multiplicity = |i: usize| {
+            if i < length_last_round {
+                num_round_element
+            } else {
+                num_round_minus_one
+            }
+        };
+
+        // Store the constants of each row in slices to be able to load them in the trace.
+        let (input_constant_values, output_constant_values): (Vec<_>, Vec<_>) =
+            (0..N_ROWS).map(row_constants::<B::Field>).unzip();
+        let mut row_constant_slices = |values: &[[B::Field; WIDTH]]| {
+            (0..WIDTH)
+                .map(|i| {
+                    let constant_values = builder.constant_array::<ElementRegister>(
+                        &values.iter().map(|row| row[i]).collect::<Vec<_>>(),
+                    );
+                    let slice = builder.uninit_slice();
+                    for (row, value) in constant_values.iter().enumerate() {
+                        builder.store(
+                            &slice.get(row),
+                            value,
+                            &Time::zero(),
+                            Some(round_multiplicity(row)),
+                            None,
+                            None,
+                        );
+                    }
+                    slice
+                })
+                .collect::<Vec<_>>()
+        };
+        let input_constants = row_constant_slices(&input_constant_values);
+        let output_constants = row_constant_slices(&output_constant_values);
+
+        // Store the flags of the rows computing a full round.
+        let is_full_row_values = builder.constant_array::<BitRegister>(
+            &(0..N_ROWS)
+                .map(|row| B::Field::from_canonical_u8(is_full_row(row) as u8))
+                .collect::<Vec<_>>(),
+        );
+        let is_full_row = builder.uninit_slice();
+        for (row, value) in is_full_row_values.iter().enumerate() {
+            builder.store(
+                &is_full_row.get(row),
+                value,
+                &Time::zero(),
+                Some(round_multiplicity(row)),
+                None,
+                None,
+            );
+        }
+
+        // Store the message chunks so that element `j` of chunk `i` is at index `RATE * i + j`.
+        let blocks = builder.uninit_slice();
+        for (i, padded_chunk) in padded_chunks.iter().enumerate() {
+            assert_eq!(padded_chunk.len(), RATE);
+            for (j, element) in padded_chunk.iter().enumerate() {
+                builder.store(
+                    &blocks.get(RATE * i + j),
+                    element,
+                    &Time::zero(),
+                    None,
+                    None,
+                    None,
+                );
+            }
+        }
+
+        // Every row reads `RATE` elements, but only the first row of each real round reads the
+        // message chunk. All other reads are directed to a dummy zero entry.
+        let dummy_entry = builder.constant::<ElementRegister>(&B::Field::ZERO);
+
+        assert!(DUMMY_INDEX < B::Field::order());
+        let dummy_index = builder.constant(&B::Field::from_canonical_u64(DUMMY_INDEX));
+
+        let num_dummy_reads = builder.constant::<ElementRegister>(&B::Field::from_canonical_usize(
+            ((1 << degree_log) - num_real_rounds) * RATE,
+        ));
+        builder.store(
+            &blocks.get(DUMMY_INDEX as usize),
+            dummy_entry,
+            &Time::zero(),
+            Some(num_dummy_reads),
+            None,
+            None,
+        );
+
+        let (cycle_start_bit, cycle_end_bit) = Self::cycle_bits(builder);
+
+        // `process_id` is a register is computed by counting the number of cycles. We do this by
+        // setting `process_id` to be the cumulative sum of the `end_bit` of each cycle.
+        let process_id = builder.process_id(N_ROWS, cycle_end_bit);
+        // The row index register can be computed as `clock - process_id * N_ROWS`.
+        let clk = builder.clk();
+        let index = builder
+            .expression(clk.expr() - process_id.expr() * B::Field::from_canonical_usize(N_ROWS));
+
+        // Allocate end_bits for public input.
+        let one = builder.constant(&B::Field::ONE);
+        let zero = builder.constant(&B::Field::ZERO);
+        let reg_cycle_length = builder.constant(&B::Field::from_canonical_usize(N_ROWS));
+        let reg_last_length = builder.constant(&B::Field::from_canonical_usize(length_last_round));
+        let end_bit = builder.uninit_slice();
+        for (i, end_bit_val) in end_bits.iter().enumerate() {
+            builder.store(
+                &end_bit.get(i),
+                end_bit_val,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &end_bit.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &end_bit.get(num_rounds - 1),
+            zero,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+        let zero_length = builder.constant(&B::Field::ZERO);
+        let chunk_length = builder.uninit_slice();
+        for (i, length) in chunk_lengths.iter().enumerate() {
+            builder.store(
+                &chunk_length.get(i),
+                length,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &chunk_length.get(i),
+                zero_length,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &chunk_length.get(num_rounds - 1),
+            zero_length,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+        let digest_bit = builder.uninit_slice();
+        for (i, digest_bit_val) in digest_bits.iter().enumerate() {
+            builder.store(
+                &digest_bit.get(i),
+                digest_bit_val,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &digest_bit.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &digest_bit.get(num_rounds - 1),
+            zero,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+
+        // Initialize a bit slice to commit to `is_dummy` bits.
+        let is_dummy_slice = builder.uninit_slice();
+
+        for i in 0..num_real_rounds {
+            builder.store(
+                &is_dummy_slice.get(i),
+                zero,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        for i in num_real_rounds..num_rounds - 1 {
+            builder.store(
+                &is_dummy_slice.get(i),
+                one,
+                &Time::zero(),
+                Some(reg_cycle_length),
+                None,
+                None,
+            );
+        }
+        builder.store(
+            &is_dummy_slice.get(num_rounds - 1),
+            one,
+            &Time::zero(),
+            Some(reg_last_length),
+            None,
+            None,
+        );
+        let is_dummy = builder.load(
+            &is_dummy_slice.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+
+        let public = PoseidonPublicData {
+            padded_chunks: padded_chunks.to_vec(),
+            digest_indices,
+        };
+
+        let trace = PoseidonTraceData {
+            process_id,
+            cycle_start_bit,
+            cycle_end_bit,
+            index,
+            is_dummy,
+        };
+
+        let memory = PoseidonMemory {
+            input_constants,
+            output_constants,
+            is_full_row,
+            blocks,
+            chunk_length,
+            end_bit,
+            digest_bit,
+            dummy_index,
+        };
+
+        PoseidonData {
+            public,
+            trace,
+            memory,
+            degree: 1 << degree_log,
+        }
+    }
+
+    /// Apply one row of the permutation, given the constants of the row and the full round flag.
+    ///
+    /// A full row applies the S-box to every element of the state. A partial row computes
+    /// `N_PARTIAL_ROUNDS_PER_ROW` partial rounds, where the S-box inputs and the state at the end
+    /// of the row are affine functions of the state at the start of the row and of the S-box
+    /// outputs, as given by `partial_row_linear_maps` and `row_constants`.
+    fn row(
+        builder: &mut B,
+        state: &[ElementRegister],
+        input_constants: &[ElementRegister],
+        output_constants: &[ElementRegister],
+        is_full_row: BitRegister,
+    ) -> ArrayRegister<ElementRegister> {
+        assert_eq!(state.len(), WIDTH);
+        assert_eq!(input_constants.len(), WIDTH);
+        assert_eq!(output_constants.len(), WIDTH);
+
+        let (partial_inputs, partial_outputs) = partial_row_linear_maps::<B::Field>();
+        let linear_combination =
+            |form: &[B::Field; N_PARTIAL_ROW_VARIABLES], sbox_outputs: &[ElementRegister]| {
+                state
+                    .iter()
+                    .chain(sbox_outputs.iter())
+                    .zip(form.iter())
+                    .filter(|(_, coefficient)| **coefficient != B::Field::ZERO)
+                    .fold(
+                        ArithmeticExpression::zero(),
+                        |acc, (variable, coefficient)| acc + variable.expr() * *coefficient,
+                    )
+            };
+
+        // Compute the S-box inputs and apply the S-box `x^7`, computing `x^3` and `x^7` in two
+        // degree 3 constraints. In a partial row, only the first `N_PARTIAL_ROUNDS_PER_ROW` S-boxes
+        // are used, one for the first element of each round.
+        let mut sbox_outputs = Vec::with_capacity(WIDTH);
+        for (i, (element, constant)) in state.iter().zip(input_constants.iter()).enumerate() {
+            let partial_input = if i < N_PARTIAL_ROUNDS_PER_ROW {
+                linear_combination(&partial_inputs[i], &sbox_outputs)
+            } else {
+                ArithmeticExpression::zero()
+            };
+            let input: ElementRegister = builder.expression(
+                is_full_row.expr() * element.expr()
+                    + is_full_row.not_expr() * partial_input
+                    + constant.expr(),
+            );
+            let input_cubed: ElementRegister =
+                builder.expression(input.expr() * input.expr() * input.expr());
+            let input_pow_7: ElementRegister =
+                builder.expression(input_cubed.expr() * input_cubed.expr() * input.expr());
+            sbox_outputs.push(input_pow_7);
+        }
+
+        // Multiply by the MDS matrix in a full row, and apply the affine map of the partial rounds
+        // otherwise.
+        let state_next = builder.alloc_array::<ElementRegister>(WIDTH);
+        for (r, element_next) in state_next.iter().enumerate() {
+            let mut acc = sbox_outputs[r].expr() * B::Field::from_canonical_u64(MDS_MATRIX_DIAG[r]);
+            for (i, circ) in MDS_MATRIX_CIRC.iter().enumerate() {
+                acc = acc
+                    + sbox_outputs[(i + r) % WIDTH].expr() * B::Field::from_canonical_u64(*circ);
+            }
+            let partial_output = linear_combination(
+                &partial_outputs[r],
+                &sbox_outputs[..N_PARTIAL_ROUNDS_PER_ROW],
+            );
+            builder.set_to_expression(
+                &element_next,
+                is_full_row.expr() * acc
+                    + is_full_row.not_expr() * partial_output
+                    + output_constants[r].expr(),
+            );
+        }
+
+        state_next
+    }
+
+    fn permutation(builder: &mut B, data: &PoseidonData) -> Vec<PoseidonDigestRegister> {
+        // Allocate the public digests and free them from a digest slice at the time given by the
+        // index of the chunk that produces them.
+        let num_digests = data.public.digest_indices.len();
+        let hash_state_public = (0..num_digests)
+            .map(|_| builder.alloc_public::<PoseidonDigestRegister>())
+            .collect::<Vec<_>>();
+        let digest_ptr = builder.uninit_slice();
+        for (i, digest) in data
+            .public
+            .digest_indices
+            .iter()
+            .zip(hash_state_public.iter())
+        {
+            for (j, element) in digest.iter().enumerate() {
+                builder.free(&digest_ptr.get(j), element, &Time::from_element(i));
+            }
+        }
+
+        let process_id = data.trace.process_id;
+        let is_dummy = data.trace.is_dummy;
+        let cycle_end_bit = data.trace.cycle_end_bit;
+        let dummy_index = data.memory.dummy_index;
+        let index = data.trace.index;
+
+        let mut load_constants = |slices: &[Slice<ElementRegister>]| {
+            slices
+                .iter()
+                .map(|slice| builder.load(&slice.get_at(index), &Time::zero(), None, None))
+                .collect::<Vec<_>>()
+        };
+        let input_constants = load_constants(&data.memory.input_constants);
+        let output_constants = load_constants(&data.memory.output_constants);
+        let is_full_row = builder.load(
+            &data.memory.is_full_row.get_at(index),
+            &Time::zero(),
+            None,
+            None,
+        );
+
+        // Initialize the state and set it to zero in the first row.
+        let state = builder.alloc_array::<ElementRegister>(WIDTH);
+        for element in state.iter() {
+            builder.set_to_expression_first_row(&element, ArithmeticExpression::zero());
+        }
+
+        // The first `chunk_length` elements of the chunk overwrite the state. These are given by
+        // bits `is_overwritten[j]` whose sum is the chunk length and which can only go from one to
+        // zero.
+        let chunk_length = builder.load(
+            &data.memory.chunk_length.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+        let is_overwritten = builder.alloc_array::<BitRegister>(RATE);
+        let sum_overwritten = is_overwritten
+            .iter()
+            .fold(ArithmeticExpression::zero(), |acc, bit| acc + bit.expr());
+        builder.assert_expressions_equal(sum_overwritten, chunk_length.expr());
+        for (bit, bit_next) in is_overwritten.iter().zip(is_overwritten.iter().skip(1)) {
+            builder.assert_expression_zero(bit_next.expr() * bit.not_expr());
+        }
+
+        // Overwrite the state with the message chunk in the first row of every real cycle. In all
+        // other rows the dummy zero entry is read and the state is kept.
+        let is_absorbing: BitRegister =
+            builder.expression(data.trace.cycle_start_bit.expr() * is_dummy.not_expr());
+        let mut row_input = Vec::with_capacity(WIDTH);
+        for (j, element) in state.iter().enumerate() {
+            if j < RATE {
+                let block_index = builder.expression(
+                    is_absorbing.expr()
+                        * (process_id.expr() * B::Field::from_canonical_usize(RATE)
+                            + B::Field::from_canonical_usize(j))
+                        + is_absorbing.not_expr() * dummy_index.expr(),
+                );
+                let block_element = builder.load(
+                    &data.memory.blocks.get_at(block_index),
+                    &Time::zero(),
+                    None,
+                    None,
+                );
+                let overwrite_bit = is_overwritten.get(j);
+                row_input.push(builder.expression(
+                    overwrite_bit.expr() * block_element.expr()
+                        + (ArithmeticExpression::one()
+                            - is_absorbing.expr() * overwrite_bit.expr())
+                            * element.expr(),
+                ));
+            } else {
+                row_input.push(element);
+            }
+        }
+
+        let state_next = Self::row(
+            builder,
+            &row_input,
+            &input_constants,
+            &output_constants,
+            is_full_row,
+        );
+
+        // Store the digest at the end of the cycle if the digest bit is set. The digest of the
+        // empty message, given by a chunk of length zero, is zero.
+        let digest_bit = builder.load(
+            &data.memory.digest_bit.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+        let flag = Some(
+            builder.expression(cycle_end_bit.expr() * is_dummy.not_expr() * digest_bit.expr()),
+        );
+        let is_nonempty = is_overwritten.get(0);
+        for (j, element) in state_next.iter().take(DIGEST_LENGTH).enumerate() {
+            let digest_element: ElementRegister =
+                builder.expression(element.expr() * is_nonempty.expr());
+            builder.store(
+                &digest_ptr.get(j),
+                digest_element,
+                &Time::from_element(process_id),
+                flag,
+                None,
+                None,
+            );
+        }
+
+        // Set the next row of the state, resetting it to zero after the last chunk of a message.
+        let end_bit = builder.load(
+            &data.memory.end_bit.get_at(process_id),
+            &Time::zero(),
+            None,
+            None,
+        );
+        let is_reset: BitRegister = builder.expression(cycle_end_bit.expr() * end_bit.expr());
+        for (element, element_next) in state.iter().zip(state_next.iter()) {
+            builder.set_to_expression_transition(
+                &element.next(),
+                element_next.expr() * is_reset.not_expr(),
+            );
+        }
+
+        hash_state_public
+    }
+}
+
+impl<B: Builder> PoseidonAir<B> for POSEIDON {}
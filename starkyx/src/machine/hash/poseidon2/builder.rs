@@ -0,0 +1,284 @@
+use super::air::PoseidonAir;
+use super::register::PoseidonDigestRegister;
+use super::POSEIDON;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::machine::builder::Builder;
+
+pub trait PoseidonBuilder: Builder {
+    /// Computes the Poseidon sponge digests of a sequence of padded messages.
+    ///
+    /// Each padded chunk consists of `RATE` field elements, of which the first `chunk_lengths[i]`
+    /// overwrite the state as in plonky2's `PoseidonHash::hash_no_pad`. A message ends at the
+    /// chunk whose `end_bit` is set, and the digest of every chunk whose `digest_bit` is set is
+    /// returned in the order given by `digest_indices`. The empty message is given by a single
+    /// chunk of length zero, whose digest is zero.
+    fn poseidon(
+        &mut self,
+        padded_chunks: &[ArrayRegister<ElementRegister>],
+        chunk_lengths: &ArrayRegister<ElementRegister>,
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Vec<PoseidonDigestRegister> {
+        POSEIDON::poseidon(
+            self,
+            padded_chunks,
+            chunk_lengths,
+            end_bits,
+            digest_bits,
+            digest_indices,
+        )
+    }
+}
+
+impl<B: Builder> PoseidonBuilder for B {}
+
+#[cfg(test)]
+pub mod test_utils {
+    use itertools::Itertools;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::timed;
+    use plonky2::util::log2_ceil;
+    use plonky2::util::timing::TimingTree;
+
+    use super::*;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::trace::writer::AirWriter;
+    use crate::chip::{AirParameters, Chip};
+    use crate::machine::hash::poseidon2::pure::{hash, pad};
+    use crate::machine::hash::poseidon2::{DIGEST_LENGTH, N_ROWS, RATE};
+    use crate::machine::stark::builder::StarkBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::math::prelude::*;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+    use crate::plonky2::Plonky2Air;
+
+    pub fn test_poseidon<L>(
+        messages: &[Vec<GoldilocksField>],
+        expected_digests: &[[GoldilocksField; DIGEST_LENGTH]],
+    ) where
+        L: AirParameters<Field = GoldilocksField, CubicParams = GoldilocksCubicParameters>,
+        Chip<L>: Plonky2Air<GoldilocksField, 2>,
+    {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let mut end_bits_values = Vec::new();
+        let mut chunk_lengths_values = Vec::new();
+        let padded_chunks_values = messages
+            .iter()
+            .flat_map(|msg| {
+                let padded_msg = pad(msg);
+                let num_chunks = padded_msg.len();
+                end_bits_values.extend_from_slice(&vec![GoldilocksField::ZERO; num_chunks - 1]);
+                end_bits_values.push(GoldilocksField::ONE);
+                padded_msg
+                    .into_iter()
+                    .map(|(chunk, len)| {
+                        chunk_lengths_values.push(GoldilocksField::from_canonical_usize(len));
+                        chunk
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let num_rounds = end_bits_values.len();
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_poseidon", log::Level::Debug);
+
+        // Build the stark.
+        let mut builder = StarkBuilder::<L>::new();
+        let padded_chunks = (0..num_rounds)
+            .map(|_| builder.alloc_array_public::<ElementRegister>(RATE))
+            .collect::<Vec<_>>();
+        let chunk_lengths = builder.alloc_array_public::<ElementRegister>(num_rounds);
+        let end_bits = builder.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_indices = builder.alloc_array_public(messages.len());
+        let digests = builder.poseidon(
+            &padded_chunks,
+            &chunk_lengths,
+            &end_bits,
+            &end_bits,
+            digest_indices,
+        );
+
+        let num_rows_degree = log2_ceil(N_ROWS * num_rounds);
+        let num_rows = 1 << num_rows_degree;
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        writer.write_array(&chunk_lengths, &chunk_lengths_values);
+        let mut message_iter = messages.iter();
+        let mut digest_iter = digests.iter();
+        let mut digest_indices_iter = digest_indices.iter();
+        for (i, (((chunk, register), end_bit), end_bit_value)) in padded_chunks_values
+            .iter()
+            .zip_eq(padded_chunks.iter())
+            .zip_eq(end_bits.iter())
+            .zip_eq(end_bits_values.iter())
+            .enumerate()
+        {
+            writer.write_array(register, chunk);
+
+            if *end_bit_value == GoldilocksField::ONE {
+                writer.write(
+                    &digest_indices_iter.next().unwrap(),
+                    &GoldilocksField::from_canonical_usize(i),
+                );
+                let digest = digest_iter.next().unwrap();
+                writer.write_array(&digest.as_array(), hash(message_iter.next().unwrap()));
+            }
+
+            writer.write(&end_bit, end_bit_value);
+        }
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        // Compare expected digests with the trace values.
+        let writer = writer_data.public_writer();
+        for (digest, expected) in digests.iter().zip_eq(expected_digests) {
+            let digest = writer.read_array::<_, DIGEST_LENGTH>(&digest.as_array());
+            assert_eq!(digest, *expected);
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+    use serde::{Deserialize, Serialize};
+
+    use super::test_utils::test_poseidon;
+    use crate::chip::instruction::empty::EmptyInstruction;
+    use crate::chip::AirParameters;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::math::prelude::*;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct PoseidonTest;
+
+    impl AirParameters for PoseidonTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = EmptyInstruction<GoldilocksField>;
+
+        const NUM_FREE_COLUMNS: usize = 200;
+        const EXTENDED_COLUMNS: usize = 330;
+    }
+
+    #[test]
+    fn test_poseidon_hash_no_pad() {
+        type F = GoldilocksField;
+
+        let messages = [3, 8, 16, 8, 40]
+            .into_iter()
+            .map(|len| {
+                (0..len)
+                    .map(|i| F::from_canonical_usize(i * 31 + len))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected_digests = messages
+            .iter()
+            .map(|msg| PoseidonHash::hash_no_pad(msg).elements)
+            .collect::<Vec<_>>();
+
+        test_poseidon::<PoseidonTest>(&messages, &expected_digests);
+    }
+
+    #[test]
+    fn test_poseidon_hash_no_pad_partial_chunks() {
+        type F = GoldilocksField;
+
+        let messages = [0, 9, 17]
+            .into_iter()
+            .map(|len| {
+                (0..len)
+                    .map(|i| F::from_canonical_usize(i * 17 + 3))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected_digests = messages
+            .iter()
+            .map(|msg| PoseidonHash::hash_no_pad(msg).elements)
+            .collect::<Vec<_>>();
+
+        test_poseidon::<PoseidonTest>(&messages, &expected_digests);
+    }
+
+    #[test]
+    fn test_poseidon_two_to_one() {
+        type F = GoldilocksField;
+
+        let num_pairs = 10;
+        let (messages, expected_digests): (Vec<_>, Vec<_>) = (0..num_pairs)
+            .map(|i| {
+                let left = HashOut {
+                    elements: core::array::from_fn(|j| F::from_canonical_usize(8 * i + j)),
+                };
+                let right = HashOut {
+                    elements: core::array::from_fn(|j| F::from_canonical_usize(8 * i + j + 4)),
+                };
+                let msg = [left.elements, right.elements].concat();
+                (msg, PoseidonHash::two_to_one(left, right).elements)
+            })
+            .unzip();
+
+        test_poseidon::<PoseidonTest>(&messages, &expected_digests);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::chip::memory::pointer::slice::Slice;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+
+pub struct PoseidonData {
+    pub public: PoseidonPublicData,
+    pub trace: PoseidonTraceData,
+    pub memory: PoseidonMemory,
+    pub degree: usize,
+}
+
+pub struct PoseidonPublicData {
+    pub padded_chunks: Vec<ArrayRegister<ElementRegister>>,
+    pub digest_indices: ArrayRegister<ElementRegister>,
+}
+
+pub struct PoseidonTraceData {
+    pub(crate) process_id: ElementRegister,
+    pub(crate) cycle_start_bit: BitRegister,
+    pub(crate) cycle_end_bit: BitRegister,
+    pub index: ElementRegister,
+    pub is_dummy: BitRegister,
+}
+
+pub struct PoseidonMemory {
+    pub(crate) input_constants: Vec<Slice<ElementRegister>>,
+    pub(crate) output_constants: Vec<Slice<ElementRegister>>,
+    pub(crate) is_full_row: Slice<BitRegister>,
+    pub(crate) blocks: Slice<ElementRegister>,
+    pub chunk_length: Slice<ElementRegister>,
+    pub end_bit: Slice<BitRegister>,
+    pub digest_bit: Slice<BitRegister>,
+    pub dummy_index: ElementRegister,
+}
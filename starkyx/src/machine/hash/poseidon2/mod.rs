@@ -0,0 +1,75 @@
+//! The Poseidon permutation over the Goldilocks field.
+//!
+//! The permutation, constants and sponge mode match those of plonky2's `PoseidonHash`, so that
+//! digests computed in the AIR can be verified against plonky2 hashes. Unlike the byte-oriented
+//! hashes in this module, the state consists of `ElementRegister`s and the permutation does not
+//! require any lookup table.
+
+use serde::{Deserialize, Serialize};
+
+pub mod air;
+pub mod builder;
+pub mod data;
+pub mod pure;
+pub mod register;
+
+/// The Poseidon hash function with the parameters of plonky2's `PoseidonHash`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct POSEIDON;
+
+/// The width of the Poseidon state.
+pub const WIDTH: usize = 12;
+
+/// The number of field elements absorbed per permutation.
+pub const RATE: usize = 8;
+
+/// The number of field elements of a digest.
+pub const DIGEST_LENGTH: usize = 4;
+
+/// The number of full rounds at the beginning and at the end of the permutation.
+pub const HALF_N_FULL_ROUNDS: usize = 4;
+
+/// The number of partial rounds, where the S-box is only applied to the first element.
+pub const N_PARTIAL_ROUNDS: usize = 22;
+
+/// The total number of rounds of the permutation.
+pub const N_ROUNDS: usize = 2 * HALF_N_FULL_ROUNDS + N_PARTIAL_ROUNDS;
+
+/// The number of partial rounds computed in a single row of the AIR.
+pub const N_PARTIAL_ROUNDS_PER_ROW: usize = 11;
+
+/// The number of rows of the AIR computing the partial rounds.
+pub const N_PARTIAL_ROWS: usize = N_PARTIAL_ROUNDS / N_PARTIAL_ROUNDS_PER_ROW;
+
+/// The number of rows of the AIR computing one permutation.
+///
+/// Each full round takes a row, while the partial rounds are grouped by
+/// `N_PARTIAL_ROUNDS_PER_ROW`, since they apply the S-box to the first element only.
+pub const N_ROWS: usize = 2 * HALF_N_FULL_ROUNDS + N_PARTIAL_ROWS;
+
+/// The first row of the circulant MDS matrix.
+pub(crate) const MDS_MATRIX_CIRC: [u64; WIDTH] = [17, 15, 41, 16, 2, 28, 13, 13, 39, 18, 34, 20];
+
+/// The diagonal added to the circulant MDS matrix.
+pub(crate) const MDS_MATRIX_DIAG: [u64; WIDTH] = [8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Returns `true` if the round with index `round` applies the S-box to the entire state.
+pub(crate) const fn is_full_round(round: usize) -> bool {
+    round < HALF_N_FULL_ROUNDS || round >= HALF_N_FULL_ROUNDS + N_PARTIAL_ROUNDS
+}
+
+/// Returns `true` if the row with index `row` computes a full round.
+pub(crate) const fn is_full_row(row: usize) -> bool {
+    row < HALF_N_FULL_ROUNDS || row >= HALF_N_FULL_ROUNDS + N_PARTIAL_ROWS
+}
+
+/// Returns the index of the first round computed in the row with index `row`.
+pub(crate) const fn first_round(row: usize) -> usize {
+    if row < HALF_N_FULL_ROUNDS {
+        row
+    } else if row < HALF_N_FULL_ROUNDS + N_PARTIAL_ROWS {
+        HALF_N_FULL_ROUNDS + (row - HALF_N_FULL_ROUNDS) * N_PARTIAL_ROUNDS_PER_ROW
+    } else {
+        row - N_PARTIAL_ROWS + N_PARTIAL_ROUNDS
+    }
+}
@@ -0,0 +1,202 @@
+use plonky2::hash::poseidon::ALL_ROUND_CONSTANTS;
+
+use super::{
+    first_round, is_full_round, is_full_row, DIGEST_LENGTH, MDS_MATRIX_CIRC, MDS_MATRIX_DIAG,
+    N_PARTIAL_ROUNDS_PER_ROW, N_ROUNDS, RATE, WIDTH,
+};
+use crate::math::prelude::*;
+
+/// The round constant of state element `i` in round `round`.
+pub fn round_constant<F: PrimeField64>(round: usize, i: usize) -> F {
+    F::from_canonical_u64(ALL_ROUND_CONSTANTS[i + WIDTH * round] % F::order())
+}
+
+/// The S-box monomial `x^7`.
+#[inline]
+pub fn sbox<F: Field>(x: F) -> F {
+    let x_3 = x * x * x;
+    x_3 * x_3 * x
+}
+
+/// Multiply the state by the MDS matrix.
+pub fn mds<F: PrimeField64>(state: [F; WIDTH]) -> [F; WIDTH] {
+    core::array::from_fn(|r| {
+        let mut acc = F::from_canonical_u64(MDS_MATRIX_DIAG[r]) * state[r];
+        for (i, circ) in MDS_MATRIX_CIRC.iter().enumerate() {
+            acc += F::from_canonical_u64(*circ) * state[(i + r) % WIDTH];
+        }
+        acc
+    })
+}
+
+/// A single round of the Poseidon permutation.
+pub fn poseidon_round<F: PrimeField64>(state: [F; WIDTH], round: usize) -> [F; WIDTH] {
+    let mut state: [F; WIDTH] = core::array::from_fn(|i| state[i] + round_constant(round, i));
+    if is_full_round(round) {
+        state = state.map(sbox);
+    } else {
+        state[0] = sbox(state[0]);
+    }
+    mds(state)
+}
+
+/// The Poseidon permutation.
+pub fn permute<F: PrimeField64>(state: [F; WIDTH]) -> [F; WIDTH] {
+    (0..N_ROUNDS).fold(state, poseidon_round)
+}
+
+/// The number of variables of the affine maps of a row of partial rounds: the state at the start
+/// of the row followed by the S-box outputs of the rounds.
+pub const N_PARTIAL_ROW_VARIABLES: usize = WIDTH + N_PARTIAL_ROUNDS_PER_ROW;
+
+/// The linear parts of a row of partial rounds.
+///
+/// Denote by `s` the state at the start of the row and by `y_k` the S-box output of the `k`-th
+/// partial round of the row. Then the S-box input of round `k` is `inputs[k] . (s, y)` plus a
+/// constant, and element `i` of the state at the end of the row is `outputs[i] . (s, y)` plus a
+/// constant. The linear parts only depend on the MDS matrix, and the constants are given by
+/// `row_constants`.
+pub fn partial_row_linear_maps<F: PrimeField64>() -> (
+    [[F; N_PARTIAL_ROW_VARIABLES]; N_PARTIAL_ROUNDS_PER_ROW],
+    [[F; N_PARTIAL_ROW_VARIABLES]; WIDTH],
+) {
+    let mut state: [[F; N_PARTIAL_ROW_VARIABLES]; WIDTH] = core::array::from_fn(|i| {
+        let mut form = [F::ZERO; N_PARTIAL_ROW_VARIABLES];
+        form[i] = F::ONE;
+        form
+    });
+    let mut inputs = [[F::ZERO; N_PARTIAL_ROW_VARIABLES]; N_PARTIAL_ROUNDS_PER_ROW];
+    for (k, input) in inputs.iter_mut().enumerate() {
+        *input = state[0];
+        state[0] = [F::ZERO; N_PARTIAL_ROW_VARIABLES];
+        state[0][WIDTH + k] = F::ONE;
+        // The MDS matrix is applied to each variable separately.
+        for j in 0..N_PARTIAL_ROW_VARIABLES {
+            let column = mds(core::array::from_fn(|i| state[i][j]));
+            for (form, value) in state.iter_mut().zip(column) {
+                form[j] = value;
+            }
+        }
+    }
+    (inputs, state)
+}
+
+/// The constants of the row with index `row`.
+///
+/// Returns the constants added to the S-box inputs, and the constants added to the state at the
+/// end of the row. For a full round, these are the round constants and zero.
+pub fn row_constants<F: PrimeField64>(row: usize) -> ([F; WIDTH], [F; WIDTH]) {
+    let round = first_round(row);
+    if is_full_row(row) {
+        return (
+            core::array::from_fn(|i| round_constant(round, i)),
+            [F::ZERO; WIDTH],
+        );
+    }
+    let mut input_constants = [F::ZERO; WIDTH];
+    let mut state = [F::ZERO; WIDTH];
+    for (k, input_constant) in input_constants
+        .iter_mut()
+        .take(N_PARTIAL_ROUNDS_PER_ROW)
+        .enumerate()
+    {
+        state = core::array::from_fn(|i| state[i] + round_constant(round + k, i));
+        *input_constant = state[0];
+        state[0] = F::ZERO;
+        state = mds(state);
+    }
+    (input_constants, state)
+}
+
+/// Split a message into chunks of `RATE` elements and pad the last chunk with zeros.
+///
+/// Returns each chunk together with the number of message elements it contains. The empty
+/// message gives a single chunk of length zero.
+pub fn pad<F: Field>(msg: &[F]) -> Vec<([F; RATE], usize)> {
+    if msg.is_empty() {
+        return vec![([F::ZERO; RATE], 0)];
+    }
+    msg.chunks(RATE)
+        .map(|chunk| {
+            let mut padded_chunk = [F::ZERO; RATE];
+            padded_chunk[..chunk.len()].copy_from_slice(chunk);
+            (padded_chunk, chunk.len())
+        })
+        .collect()
+}
+
+/// Overwrite the first `len` elements of the state with a chunk and apply the permutation.
+pub fn process<F: PrimeField64>(state: [F; WIDTH], chunk: &[F], len: usize) -> [F; WIDTH] {
+    assert_eq!(chunk.len(), RATE);
+    assert!(len <= RATE);
+    let mut state = state;
+    state[..len].copy_from_slice(&chunk[..len]);
+    permute(state)
+}
+
+/// Hash a message of field elements as plonky2's `PoseidonHash::hash_no_pad`.
+///
+/// Each chunk of `RATE` elements overwrites the beginning of the state, so that a shorter last
+/// chunk leaves the rest of the state unchanged. The empty message hashes to the zero digest.
+pub fn hash<F: PrimeField64>(msg: &[F]) -> [F; DIGEST_LENGTH] {
+    let state = msg.chunks(RATE).fold([F::ZERO; WIDTH], |state, chunk| {
+        let mut state = state;
+        state[..chunk.len()].copy_from_slice(chunk);
+        permute(state)
+    });
+    core::array::from_fn(|i| state[i])
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+
+    use super::*;
+    use crate::machine::hash::poseidon2::N_ROWS;
+
+    #[test]
+    fn test_poseidon_pure() {
+        type F = GoldilocksField;
+
+        for len in [0, 1, 5, 8, 9, 16, 17, 24] {
+            let msg = (0..len)
+                .map(|i| F::from_canonical_usize(i * i + 7))
+                .collect::<Vec<_>>();
+            let expected = PoseidonHash::hash_no_pad(&msg).elements;
+            assert_eq!(hash(&msg), expected);
+        }
+    }
+
+    #[test]
+    fn test_poseidon_rows() {
+        type F = GoldilocksField;
+
+        let (inputs, outputs) = partial_row_linear_maps::<F>();
+        let linear_combination = |form: &[F; N_PARTIAL_ROW_VARIABLES], variables: &[F]| -> F {
+            form.iter().zip(variables).map(|(a, b)| *a * *b).sum()
+        };
+
+        let initial_state: [F; WIDTH] = core::array::from_fn(|i| F::from_canonical_usize(i + 1));
+        let mut state = initial_state;
+        for row in 0..N_ROWS {
+            let (input_constants, output_constants) = row_constants::<F>(row);
+            if is_full_row(row) {
+                state = core::array::from_fn(|i| sbox(state[i] + input_constants[i]));
+                state = mds(state);
+            } else {
+                let mut variables = state.to_vec();
+                for (input, input_constant) in inputs.iter().zip(input_constants) {
+                    let sbox_input = linear_combination(input, &variables) + input_constant;
+                    variables.push(sbox(sbox_input));
+                }
+                state = core::array::from_fn(|i| {
+                    linear_combination(&outputs[i], &variables) + output_constants[i]
+                });
+            }
+        }
+
+        assert_eq!(state, permute(initial_state));
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chip::register::array::{ArrayIterator, ArrayRegister};
+use crate::chip::register::cell::CellType;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::memory::MemorySlice;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::machine::hash::poseidon2::DIGEST_LENGTH;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PoseidonDigestRegister(ArrayRegister<ElementRegister>);
+
+impl RegisterSerializable for PoseidonDigestRegister {
+    const CELL: CellType = CellType::Element;
+    fn register(&self) -> &MemorySlice {
+        self.0.register()
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(ArrayRegister::from_register_unsafe(register))
+    }
+}
+
+impl RegisterSized for PoseidonDigestRegister {
+    fn size_of() -> usize {
+        ElementRegister::size_of() * DIGEST_LENGTH
+    }
+}
+
+impl Register for PoseidonDigestRegister {
+    type Value<T> = [T; DIGEST_LENGTH];
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+}
+
+impl PoseidonDigestRegister {
+    pub fn as_array(&self) -> ArrayRegister<ElementRegister> {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> ElementRegister {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> ArrayIterator<ElementRegister> {
+        self.0.iter()
+    }
+
+    pub fn from_array(array: ArrayRegister<ElementRegister>) -> Self {
+        assert_eq!(array.len(), DIGEST_LENGTH);
+        Self(array)
+    }
+}
+
+impl From<PoseidonDigestRegister> for ArrayRegister<ElementRegister> {
+    fn from(register: PoseidonDigestRegister) -> Self {
+        register.0
+    }
+}
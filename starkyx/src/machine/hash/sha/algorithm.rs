@@ -13,7 +13,8 @@ use crate::chip::register::bit::BitRegister;
 use crate::chip::register::element::ElementRegister;
 use crate::chip::register::{Register, RegisterSerializable};
 use crate::machine::builder::Builder;
-use crate::machine::hash::sha::data::{SHAMemory, SHAPublicData, SHATraceData};
+use crate::machine::hash::forward::DigestForwarding;
+use crate::machine::hash::sha::data::{SHAForwardMemory, SHAMemory, SHAPublicData, SHATraceData};
 use crate::machine::hash::{HashDigest, HashIntConversion, HashPureInteger};
 use crate::math::prelude::*;
 
@@ -28,6 +29,11 @@ pub trait SHAPure<const CYCLE_LENGTH: usize>:
     const INITIAL_HASH: [Self::Integer; 8];
     const ROUND_CONSTANTS: [Self::Integer; CYCLE_LENGTH];
 
+    /// The number of words of the final hash state that make up the digest.
+    ///
+    /// Truncated variants such as SHA-224 or SHA-384 only keep the first `DIGEST_LENGTH` words.
+    const DIGEST_LENGTH: usize;
+
     /// Pad a byte message to a vector of `Self::Integer` values.
     fn pad(msg: &[u8]) -> Vec<Self::Integer>;
 
@@ -38,7 +44,10 @@ pub trait SHAPure<const CYCLE_LENGTH: usize>:
     fn process(hash: [Self::Integer; 8], w: &[Self::Integer; CYCLE_LENGTH]) -> [Self::Integer; 8];
 
     /// Decode a digest encoded as a string to a vector of `Self::Integer` values.
-    fn decode(digest: &str) -> [Self::Integer; 8];
+    fn decode(digest: &str) -> Vec<Self::Integer>;
+
+    /// Encode a vector of `Self::Integer` values as big-endian bytes.
+    fn encode(words: &[Self::Integer]) -> Vec<u8>;
 }
 
 /// SHA algorithm AIR implementation.
@@ -76,12 +85,14 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
         round_constant: Self::IntRegister,
     ) -> Vec<Self::IntRegister>;
 
+    /// Free the public digests from a memory pointer at the time given by their digest index.
     fn load_state(
         builder: &mut B,
-        hash_state_public: &[Self::StateVariable],
+        hash_state_public: &[Self::DigestRegister],
         digest_indices: ArrayRegister<ElementRegister>,
     ) -> Self::StatePointer;
 
+    /// Store the digest words of `state_next` to the memory pointer returned by `load_state`.
     fn store_state(
         builder: &mut B,
         state_ptr: &Self::StatePointer,
@@ -102,13 +113,36 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
         end_bits: &ArrayRegister<BitRegister>,
         digest_bits: &ArrayRegister<BitRegister>,
         digest_indices: ArrayRegister<ElementRegister>,
-    ) -> Vec<Self::StateVariable> {
+    ) -> Vec<Self::DigestRegister> {
+        let data = Self::data(
+            builder,
+            padded_chunks,
+            end_bits,
+            digest_bits,
+            digest_indices,
+            None,
+        );
+        let w_i = Self::preprocessing(builder, &data);
+        Self::processing(builder, w_i, &data)
+    }
+
+    /// Like `sha`, but the digests of the chunks selected by `forwarding` are also written to the
+    /// message words of other chunks.
+    fn sha_with_forwarding(
+        builder: &mut B,
+        padded_chunks: &[ArrayRegister<Self::IntRegister>],
+        end_bits: &ArrayRegister<BitRegister>,
+        digest_bits: &ArrayRegister<BitRegister>,
+        digest_indices: ArrayRegister<ElementRegister>,
+        forwarding: &DigestForwarding,
+    ) -> Vec<Self::DigestRegister> {
         let data = Self::data(
             builder,
             padded_chunks,
             end_bits,
             digest_bits,
             digest_indices,
+            Some(forwarding),
         );
         let w_i = Self::preprocessing(builder, &data);
         Self::processing(builder, w_i, &data)
@@ -120,8 +154,12 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
         end_bits: &ArrayRegister<BitRegister>,
         digest_bits: &ArrayRegister<BitRegister>,
         digest_indices: ArrayRegister<ElementRegister>,
+        forwarding: Option<&DigestForwarding>,
     ) -> SHAData<Self::IntRegister, CYCLE_LENGTH> {
         assert_eq!(padded_chunks.len(), end_bits.len());
+        if let Some(forwarding) = forwarding {
+            forwarding.assert_num_chunks(padded_chunks.len());
+        }
         let num_real_rounds = padded_chunks.len();
         debug!(
             "AIR degree before padding: {}",
@@ -231,13 +269,16 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
                 + length_last_round * 5,
         ));
 
+        // Words written by a forwarded digest do not take their public value.
         for (i, padded_chunk) in padded_chunks.iter().enumerate() {
             for (j, word) in padded_chunk.iter().enumerate().take(16) {
+                let multiplicity =
+                    forwarding.map(|forwarding| forwarding.public_words[i].get(j).as_element());
                 builder.store(
                     &w.get(CYCLE_LENGTH * i + j),
                     word,
                     &Time::zero(),
-                    None,
+                    multiplicity,
                     None,
                     None,
                 );
@@ -342,6 +383,67 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
             None,
         );
 
+        let forward = forwarding.map(|forwarding| {
+            let zero_index = builder.constant::<ElementRegister>(&B::Field::ZERO);
+            let forward_bit = builder.uninit_slice();
+            let target = builder.uninit_slice();
+            for (i, ((forward_bit_val, target_chunk), target_offset)) in forwarding
+                .forward_bits
+                .iter()
+                .zip(forwarding.target_chunks.iter())
+                .zip(forwarding.target_offsets.iter())
+                .enumerate()
+            {
+                let target_val = builder.public_expression::<ElementRegister>(
+                    target_chunk.expr() * B::Field::from_canonical_usize(CYCLE_LENGTH)
+                        + target_offset.expr(),
+                );
+                builder.store(
+                    &forward_bit.get(i),
+                    forward_bit_val,
+                    &Time::zero(),
+                    Some(reg_cycle_length),
+                    None,
+                    None,
+                );
+                builder.store(
+                    &target.get(i),
+                    target_val,
+                    &Time::zero(),
+                    Some(reg_cycle_length),
+                    None,
+                    None,
+                );
+            }
+            for i in num_real_rounds..num_rounds {
+                let multiplicity = if i == num_rounds - 1 {
+                    reg_last_length
+                } else {
+                    reg_cycle_length
+                };
+                builder.store(
+                    &forward_bit.get(i),
+                    zero,
+                    &Time::zero(),
+                    Some(multiplicity),
+                    None,
+                    None,
+                );
+                builder.store(
+                    &target.get(i),
+                    zero_index,
+                    &Time::zero(),
+                    Some(multiplicity),
+                    None,
+                    None,
+                );
+            }
+            SHAForwardMemory {
+                forward_bit,
+                target,
+            }
+        });
+
         // Initialize a bit slice to commit to `is_dummy` bits.
         let is_dummy_slice = builder.uninit_slice();
 
@@ -401,6 +503,7 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
             shift_read_mult,
             end_bit,
             digest_bit,
+            forward,
             dummy_index,
         };
         SHAData {
@@ -474,10 +577,10 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
         builder: &mut B,
         w_i: Self::IntRegister,
         data: &SHAData<Self::IntRegister, CYCLE_LENGTH>,
-    ) -> Vec<Self::StateVariable> {
+    ) -> Vec<Self::DigestRegister> {
         let num_digests = data.public.digest_indices.len();
         let hash_state_public = (0..num_digests)
-            .map(|_| builder.alloc_public::<Self::StateVariable>())
+            .map(|_| builder.alloc_public::<Self::DigestRegister>())
             .collect::<Vec<_>>();
         let state_ptr = Self::load_state(builder, &hash_state_public, data.public.digest_indices);
 
@@ -527,6 +630,37 @@ pub trait SHAir<B: Builder, const CYCLE_LENGTH: usize>:
             flag,
         );
 
+        // Write the digest to the message words of its target chunk if it is forwarded.
+        if let Some(forward) = &data.memory.forward {
+            let forward_bit = builder.load(
+                &forward.forward_bit.get_at(process_id),
+                &Time::zero(),
+                None,
+                None,
+            );
+            let target = builder.load(
+                &forward.target.get_at(process_id),
+                &Time::zero(),
+                None,
+                None,
+            );
+            let forward_flag =
+                builder.expression(cycle_end_bit.expr() * is_dummy.not_expr() * forward_bit.expr());
+            let state_next_arr: ArrayRegister<Self::IntRegister> = state_next.into();
+            for (j, word) in state_next_arr.iter().take(Self::DIGEST_LENGTH).enumerate() {
+                let target_j =
+                    builder.expression(target.expr() + B::Field::from_canonical_usize(j));
+                builder.store(
+                    &data.memory.w.get_at(target_j),
+                    word,
+                    &Time::zero(),
+                    Some(forward_flag),
+                    None,
+                    None,
+                );
+            }
+        }
+
         // Set the next row of working variables.
         let end_bit = builder.load(
             &data.memory.end_bit.get_at(data.trace.process_id),
@@ -1,8 +1,17 @@
 use super::algorithm::SHAir;
+use super::hmac::{HMACKeyRegister, IPAD, OPAD};
+use super::sha256::SHA256;
+use crate::chip::arithmetic::expression::ArithmeticExpression;
 use crate::chip::register::array::ArrayRegister;
 use crate::chip::register::bit::BitRegister;
 use crate::chip::register::element::ElementRegister;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::chip::uint::bytes::register::ByteRegister;
 use crate::machine::builder::Builder;
+use crate::machine::hash::forward::DigestForwarding;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::machine::hash::HashDigest;
+use crate::math::prelude::*;
 
 pub trait SHABuilder: Builder {
     fn sha<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
@@ -11,13 +20,496 @@ pub trait SHABuilder: Builder {
         end_bits: &ArrayRegister<BitRegister>,
         digest_bits: &ArrayRegister<BitRegister>,
         digest_indices: ArrayRegister<ElementRegister>,
-    ) -> Vec<S::StateVariable> {
+    ) -> Vec<S::DigestRegister> {
         S::sha(self, padded_chunks, end_bits, digest_bits, digest_indices)
     }
+
+    /// Hashes public messages of bounded length, computing their padding in the AIR.
+    ///
+    /// Each message takes the number of chunks of a padded message of its maximal length. The
+    /// digest is read after the last chunk of the padded message, while the following chunks keep
+    /// updating the same state until the end bit of the last chunk resets it, so their outputs are
+    /// discarded.
+    fn sha_messages<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        messages: &[MessageRegister],
+    ) -> Vec<S::DigestRegister> {
+        self.sha_messages_padded::<S, CYCLE_LENGTH>(messages, 0)
+    }
+
+    /// Like `sha_messages`, but hashes constant empty messages after the given ones until there
+    /// are at least `min_rounds` chunks, whose digests are discarded.
+    ///
+    /// This allows fixing the degree of the SHA AIR when it shares the trace with other machines.
+    fn sha_messages_padded<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        messages: &[MessageRegister],
+        min_rounds: usize,
+    ) -> Vec<S::DigestRegister> {
+        let word_length = S::IntRegister::size_of();
+        let chunk_length = 16 * word_length;
+        let length_field_length = 2 * word_length;
+        // The chunk containing the length field of a message of length `len`.
+        let last_chunk = |len: usize| (len + length_field_length) / chunk_length;
+
+        let num_chunks = messages
+            .iter()
+            .map(|message| last_chunk(message.max_length()) + 1)
+            .collect::<Vec<_>>();
+        let num_message_rounds: usize = num_chunks.iter().sum();
+        let num_rounds = num_message_rounds.max(min_rounds);
+
+        let mut padded_chunks = (0..num_message_rounds)
+            .map(|_| self.alloc_array_public::<S::IntRegister>(16))
+            .collect::<Vec<_>>();
+        if num_rounds > num_message_rounds {
+            // All the chunks of the empty messages are the same constant.
+            let empty_chunk_values = S::pad(&[])
+                .into_iter()
+                .map(S::int_to_field_value)
+                .collect::<Vec<_>>();
+            let empty_chunk = self.constant_array::<S::IntRegister>(&empty_chunk_values);
+            padded_chunks.resize(num_rounds, empty_chunk);
+        }
+        let end_bits = self.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_bits = self.alloc_array_public::<BitRegister>(num_rounds);
+        let digest_indices = self.alloc_array_public::<ElementRegister>(messages.len());
+
+        let mut start_index = 0;
+        for ((message, num_message_chunks), digest_index) in
+            messages.iter().zip(num_chunks).zip(digest_indices.iter())
+        {
+            let message_chunks = start_index..start_index + num_message_chunks;
+            set_padded_message(
+                self,
+                message,
+                &padded_chunks[message_chunks.clone()],
+                &digest_bits.get_subarray(message_chunks),
+                0,
+                0,
+            );
+
+            for i in 0..num_message_chunks {
+                let end_bit = if i == num_message_chunks - 1 {
+                    ArithmeticExpression::one()
+                } else {
+                    ArithmeticExpression::zero()
+                };
+                self.set_to_expression(&end_bits.get(start_index + i), end_bit);
+            }
+
+            let digest_index_expr = self
+                .message_length_expression(message, |len| (start_index + last_chunk(len)) as u64);
+            self.set_to_expression(&digest_index, digest_index_expr);
+
+            start_index += num_message_chunks;
+        }
+
+        for i in num_message_rounds..num_rounds {
+            self.set_to_expression(&end_bits.get(i), ArithmeticExpression::one());
+            self.set_to_expression(&digest_bits.get(i), ArithmeticExpression::zero());
+        }
+
+        S::sha(
+            self,
+            &padded_chunks,
+            &end_bits,
+            &digest_bits,
+            digest_indices,
+        )
+    }
+
+    /// Allocates a public HMAC key of at most one block of `S`.
+    fn alloc_public_hmac_key<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+    ) -> HMACKeyRegister {
+        let block_length = 16 * S::IntRegister::size_of();
+        let bits = self.alloc_array_public::<BitRegister>(8 * block_length);
+        HMACKeyRegister { bits }
+    }
+
+    /// Computes `HMAC(keys[i], messages[i])` for public keys and messages of bounded length.
+    ///
+    /// The digest of the inner hash is written to the message of the outer hash through memory,
+    /// so only the HMAC outputs are public. Like `sha`, this can only be called once per builder.
+    fn hmac<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        keys: &[HMACKeyRegister],
+        messages: &[MessageRegister],
+    ) -> Vec<S::DigestRegister> {
+        assert_eq!(keys.len(), messages.len());
+        let instances = keys
+            .iter()
+            .zip(messages.iter())
+            .map(|(key, message)| HMACInstance {
+                key: *key,
+                message: *message,
+                prefix: None,
+            })
+            .collect::<Vec<_>>();
+        hmac_instances::<Self, S, CYCLE_LENGTH>(self, &instances)
+    }
+
+    /// Computes the pseudorandom keys `HKDF-Extract(salts[i], ikms[i])` of RFC 5869.
+    ///
+    /// Salts longer than a block must be hashed first, and the default salt is a key of zeros.
+    fn hkdf_extract<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        salts: &[HMACKeyRegister],
+        ikms: &[MessageRegister],
+    ) -> Vec<S::DigestRegister> {
+        self.hmac::<S, CYCLE_LENGTH>(salts, ikms)
+    }
+
+    /// Computes the blocks `T(1), ..., T(n)` of `HKDF-Expand(prk, info, length)` of RFC 5869.
+    ///
+    /// The output key material is made of the first `length` bytes of the blocks. Every block
+    /// is written to the message of the next one through memory.
+    fn hkdf_expand<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        prk: &HMACKeyRegister,
+        info: &MessageRegister,
+        length: usize,
+    ) -> Vec<S::DigestRegister> {
+        let block_length = S::DIGEST_LENGTH * S::IntRegister::size_of();
+        let num_blocks = (length + block_length - 1) / block_length;
+        assert!(
+            (1..=255).contains(&num_blocks),
+            "Invalid HKDF output length"
+        );
+
+        let instances = (1..=num_blocks)
+            .map(|i| HMACInstance {
+                key: *prk,
+                message: append_byte(self, info, i as u8),
+                prefix: i.checked_sub(2),
+            })
+            .collect::<Vec<_>>();
+        hmac_instances::<Self, S, CYCLE_LENGTH>(self, &instances)
+    }
+
+    /// HMAC-SHA256 of public keys and messages, see `hmac`.
+    fn hmac_sha256(
+        &mut self,
+        keys: &[HMACKeyRegister],
+        messages: &[MessageRegister],
+    ) -> Vec<<SHA256 as HashDigest<Self>>::DigestRegister>
+    where
+        SHA256: SHAir<Self, 64>,
+    {
+        self.hmac::<SHA256, 64>(keys, messages)
+    }
+
+    /// HKDF-Extract with HMAC-SHA256, see `hkdf_extract`.
+    fn hkdf_extract_sha256(
+        &mut self,
+        salts: &[HMACKeyRegister],
+        ikms: &[MessageRegister],
+    ) -> Vec<<SHA256 as HashDigest<Self>>::DigestRegister>
+    where
+        SHA256: SHAir<Self, 64>,
+    {
+        self.hkdf_extract::<SHA256, 64>(salts, ikms)
+    }
+
+    /// HKDF-Expand with HMAC-SHA256, see `hkdf_expand`.
+    fn hkdf_expand_sha256(
+        &mut self,
+        prk: &HMACKeyRegister,
+        info: &MessageRegister,
+        length: usize,
+    ) -> Vec<<SHA256 as HashDigest<Self>>::DigestRegister>
+    where
+        SHA256: SHAir<Self, 64>,
+    {
+        self.hkdf_expand::<SHA256, 64>(prk, info, length)
+    }
 }
 
 impl<B: Builder> SHABuilder for B {}
 
+/// Sets `chunks` to `message` followed by its padding.
+///
+/// The message starts at byte `message_offset` of the first chunk, whose previous bytes are set
+/// to zero, and the length field also counts `prefix_length` bytes hashed before the chunks.
+/// The bit `digest_bits[i]` is set if the chunk `i` contains the length field.
+fn set_padded_message<B: Builder, T: Register>(
+    builder: &mut B,
+    message: &MessageRegister,
+    chunks: &[ArrayRegister<T>],
+    digest_bits: &ArrayRegister<BitRegister>,
+    message_offset: usize,
+    prefix_length: usize,
+) {
+    let word_length = T::size_of();
+    let chunk_length = 16 * word_length;
+    let length_field_length = 2 * word_length;
+    let length_field_offset = chunk_length - length_field_length;
+    // The chunk containing the length field of a message of length `len`.
+    let last_chunk = |len: usize| (message_offset + len + length_field_length) / chunk_length;
+
+    let max_length = message.max_length();
+    assert_eq!(chunks.len(), last_chunk(max_length) + 1);
+    assert_eq!(digest_bits.len(), chunks.len());
+    let is_message = builder.message_flags(message);
+
+    // The big-endian bytes of the hashed length in bits.
+    let length_bytes = (0..length_field_length)
+        .map(|k| {
+            let shift = (8 * (length_field_length - 1 - k)) as u32;
+            builder.message_length_map::<ElementRegister>(message, |len| {
+                (8 * (prefix_length + message_offset + len) as u64)
+                    .checked_shr(shift)
+                    .unwrap_or(0)
+                    & 0xff
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let digest_bit = digest_bits.get(i);
+        let digest_bit_expr =
+            builder.message_length_expression(message, |len| (last_chunk(len) == i) as u64);
+        builder.set_to_expression(&digest_bit, digest_bit_expr);
+
+        for (j, word) in chunk.iter().enumerate() {
+            let word_bytes =
+                ArrayRegister::<ElementRegister>::from_register_unsafe(*word.register());
+            for (k, byte) in word_bytes.iter().enumerate() {
+                // The words are big-endian, while their bytes are stored in little-endian order.
+                let offset = j * word_length + word_length - 1 - k;
+                let position = i * chunk_length + offset;
+
+                let mut byte_expr = ArithmeticExpression::zero();
+                if let Some(position) = position.checked_sub(message_offset) {
+                    if position < max_length {
+                        byte_expr = byte_expr
+                            + is_message[position].expr() * message.bytes.get(position).expr();
+                    }
+                    if position <= max_length {
+                        byte_expr = byte_expr
+                            + message.length_selector.get(position).expr()
+                                * B::Field::from_canonical_u8(0x80);
+                    }
+                }
+                if offset >= length_field_offset {
+                    byte_expr = byte_expr
+                        + digest_bit.expr() * length_bytes[offset - length_field_offset].expr();
+                }
+                builder.set_to_expression(&byte, byte_expr);
+            }
+        }
+    }
+}
+
+/// An HMAC computation whose message is preceded by the output of the computation `prefix`.
+struct HMACInstance {
+    key: HMACKeyRegister,
+    message: MessageRegister,
+    prefix: Option<usize>,
+}
+
+/// Computes the outputs of HMAC instances with a single SHA AIR.
+///
+/// Every instance takes a chunk for the inner key, the chunks of the padded message, a chunk for
+/// the outer key and a chunk for the inner digest followed by constant padding. The inner digest
+/// is forwarded to the last chunk, and the output is forwarded to the message of the instance
+/// using it as a prefix, if any.
+fn hmac_instances<B: Builder, S: SHAir<B, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+    builder: &mut B,
+    instances: &[HMACInstance],
+) -> Vec<S::DigestRegister> {
+    let word_length = S::IntRegister::size_of();
+    let chunk_length = 16 * word_length;
+    let length_field_length = 2 * word_length;
+    let digest_length = S::DIGEST_LENGTH;
+
+    // The inner digest is followed by the same padding for every instance.
+    let outer_words = S::pad(&vec![0u8; chunk_length + digest_length * word_length]);
+    assert_eq!(outer_words.len(), 32);
+    let outer_padding = outer_words[16 + digest_length..]
+        .iter()
+        .map(|word| builder.constant::<S::IntRegister>(&S::int_to_field_value(*word)))
+        .collect::<Vec<_>>();
+
+    let mut inner_starts = Vec::with_capacity(instances.len());
+    let mut outer_starts = Vec::with_capacity(instances.len());
+    let mut consumers = vec![None; instances.len()];
+    let mut num_chunks = 0;
+    for (i, instance) in instances.iter().enumerate() {
+        assert_eq!(
+            instance.key.block_length(),
+            chunk_length,
+            "The HMAC key must be a block of the hash function"
+        );
+        let message_offset = match instance.prefix {
+            Some(prefix) => {
+                assert!(prefix < i, "An HMAC prefix must be an earlier output");
+                assert!(
+                    consumers[prefix].replace(i).is_none(),
+                    "An HMAC output can only be the prefix of one message"
+                );
+                digest_length * word_length
+            }
+            None => 0,
+        };
+        let num_message_chunks =
+            (message_offset + instance.message.max_length() + length_field_length) / chunk_length
+                + 1;
+        inner_starts.push(num_chunks);
+        outer_starts.push(num_chunks + 1 + num_message_chunks);
+        num_chunks += num_message_chunks + 3;
+    }
+
+    let padded_chunks = (0..num_chunks)
+        .map(|_| builder.alloc_array_public::<S::IntRegister>(16))
+        .collect::<Vec<_>>();
+    let end_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let digest_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let digest_indices = builder.alloc_array_public::<ElementRegister>(instances.len());
+    let public_words = (0..num_chunks)
+        .map(|_| builder.alloc_array_public::<BitRegister>(16))
+        .collect::<Vec<_>>();
+    let forward_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let target_chunks = builder.alloc_array_public::<ElementRegister>(num_chunks);
+    let target_offsets = builder.alloc_array_public::<ElementRegister>(num_chunks);
+
+    let constant =
+        |value: usize| ArithmeticExpression::from_constant(B::Field::from_canonical_usize(value));
+
+    for (i, (instance, digest_index)) in instances.iter().zip(digest_indices.iter()).enumerate() {
+        let (inner_start, outer_start) = (inner_starts[i], outer_starts[i]);
+        let digest_chunk = outer_start + 1;
+        let message_chunks = inner_start + 1..outer_start;
+        let message_offset = instance.prefix.map_or(0, |_| digest_length * word_length);
+
+        set_key_chunk(builder, &instance.key, &padded_chunks[inner_start], IPAD);
+        set_key_chunk(builder, &instance.key, &padded_chunks[outer_start], OPAD);
+
+        // The forward bits of the inner hash are set at the chunk of its length field.
+        set_padded_message(
+            builder,
+            &instance.message,
+            &padded_chunks[message_chunks.clone()],
+            &forward_bits.get_subarray(message_chunks.clone()),
+            message_offset,
+            chunk_length,
+        );
+
+        for (j, word) in padded_chunks[digest_chunk].iter().enumerate() {
+            match j.checked_sub(digest_length) {
+                Some(k) => builder.set_to_expression(&word, outer_padding[k].expr()),
+                None => builder.set_to_expression(&word, ArithmeticExpression::zero()),
+            }
+        }
+
+        for chunk in inner_start..=digest_chunk {
+            let is_forwarded_to =
+                (chunk == digest_chunk) || (chunk == inner_start + 1 && instance.prefix.is_some());
+            for (j, is_public) in public_words[chunk].iter().enumerate() {
+                let is_forwarded = is_forwarded_to && j < digest_length;
+                builder.set_to_expression(&is_public, constant(!is_forwarded as usize));
+            }
+
+            let is_end = chunk == outer_start - 1 || chunk == digest_chunk;
+            builder.set_to_expression(&end_bits.get(chunk), constant(is_end as usize));
+            builder.set_to_expression(
+                &digest_bits.get(chunk),
+                constant((chunk == digest_chunk) as usize),
+            );
+
+            let target_chunk = if message_chunks.contains(&chunk) {
+                digest_chunk
+            } else {
+                let consumer = consumers[i].filter(|_| chunk == digest_chunk);
+                builder.set_to_expression(
+                    &forward_bits.get(chunk),
+                    constant(consumer.is_some() as usize),
+                );
+                consumer.map_or(0, |consumer| inner_starts[consumer] + 1)
+            };
+            builder.set_to_expression(&target_chunks.get(chunk), constant(target_chunk));
+            builder.set_to_expression(&target_offsets.get(chunk), constant(0));
+        }
+
+        builder.set_to_expression(&digest_index, constant(digest_chunk));
+    }
+
+    S::sha_with_forwarding(
+        builder,
+        &padded_chunks,
+        &end_bits,
+        &digest_bits,
+        digest_indices,
+        &DigestForwarding {
+            public_words,
+            forward_bits,
+            target_chunks,
+            target_offsets,
+        },
+    )
+}
+
+/// Sets `chunk` to the bytes of the key block xored with `pad`.
+fn set_key_chunk<B: Builder, T: Register>(
+    builder: &mut B,
+    key: &HMACKeyRegister,
+    chunk: &ArrayRegister<T>,
+    pad: u8,
+) {
+    let word_length = T::size_of();
+    for (j, word) in chunk.iter().enumerate() {
+        let word_bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(*word.register());
+        for (k, byte) in word_bytes.iter().enumerate() {
+            let position = j * word_length + word_length - 1 - k;
+            let byte_expr = (0..8).fold(ArithmeticExpression::zero(), |acc, l| {
+                let bit = key.bits.get(8 * position + l);
+                let bit_expr = if (pad >> l) & 1 == 1 {
+                    bit.not_expr()
+                } else {
+                    bit.expr()
+                };
+                acc + bit_expr * B::Field::from_canonical_u32(1 << l)
+            });
+            builder.set_to_expression(&byte, byte_expr);
+        }
+    }
+}
+
+/// Returns the public message `message || byte`.
+fn append_byte<B: Builder>(
+    builder: &mut B,
+    message: &MessageRegister,
+    byte: u8,
+) -> MessageRegister {
+    let max_length = message.max_length();
+    let is_message = builder.message_flags(message);
+
+    let bytes = builder.alloc_array_public::<ByteRegister>(max_length + 1);
+    let length = builder.alloc_public::<ElementRegister>();
+    let length_selector = builder.alloc_array_public::<BitRegister>(max_length + 2);
+
+    for (i, new_byte) in bytes.iter().enumerate() {
+        let mut byte_expr =
+            message.length_selector.get(i).expr() * B::Field::from_canonical_u8(byte);
+        if i < max_length {
+            byte_expr = byte_expr + is_message[i].expr() * message.bytes.get(i).expr();
+        }
+        builder.set_to_expression(&new_byte, byte_expr);
+    }
+    builder.set_to_expression(&length, message.length.expr() + B::Field::ONE);
+    builder.set_to_expression(&length_selector.get(0), ArithmeticExpression::zero());
+    for (i, bit) in message.length_selector.iter().enumerate() {
+        builder.set_to_expression(&length_selector.get(i + 1), bit.expr());
+    }
+
+    MessageRegister {
+        bytes,
+        length,
+        length_selector,
+    }
+}
+
 #[cfg(test)]
 pub mod test_utils {
     use core::fmt::Debug;
@@ -37,6 +529,7 @@ pub mod test_utils {
     use crate::chip::uint::operations::instruction::UintInstructions;
     use crate::chip::{AirParameters, Chip};
     use crate::machine::bytes::builder::BytesBuilder;
+    use crate::machine::hash::sha::hmac::HMACPure;
     use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
     use crate::math::prelude::*;
     use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
@@ -129,9 +622,9 @@ pub mod test_utils {
                     &digest_indices_iter.next().unwrap(),
                     &GoldilocksField::from_canonical_usize(i),
                 );
-                let h: S::StateVariable = *hash_iter.next().unwrap();
+                let h: S::DigestRegister = *hash_iter.next().unwrap();
                 let array: ArrayRegister<_> = h.into();
-                writer.write_array(&array, &state);
+                writer.write_array(&array, &state[..S::DIGEST_LENGTH]);
                 current_state = S::INITIAL_HASH;
             }
 
@@ -154,8 +647,251 @@ pub mod test_utils {
         for (digest, expected) in hash_state.iter().zip_eq(expected_digests) {
             let array: ArrayRegister<S::IntRegister> = (*digest).into();
             let digest = writer
-                .read_array::<_, 8>(&array)
-                .map(|x| S::field_value_to_int(&x));
+                .read_vec(&array)
+                .iter()
+                .map(S::field_value_to_int)
+                .collect::<Vec<_>>();
+            let expected_digest = S::decode(expected);
+            assert_eq!(digest, expected_digest);
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+
+    pub fn test_sha_messages<
+        'a,
+        L,
+        S,
+        I: IntoIterator<Item = (&'a [u8], usize)>,
+        J: IntoIterator<Item = &'a str>,
+        const CYCLE_LENGTH: usize,
+    >(
+        messages: I,
+        expected_digests: J,
+    ) where
+        L: AirParameters<Field = GoldilocksField, CubicParams = GoldilocksCubicParameters>,
+        L::Instruction: UintInstructions,
+        S: SHAir<BytesBuilder<L>, CYCLE_LENGTH>,
+        Chip<L>: Plonky2Air<GoldilocksField, 2>,
+        S::Integer: PartialEq + Eq + Debug,
+    {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let messages = messages.into_iter().collect::<Vec<_>>();
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_sha_messages", log::Level::Debug);
+
+        // Build the stark.
+        let mut builder = BytesBuilder::<L>::new();
+        let message_registers = messages
+            .iter()
+            .map(|(_, max_length)| builder.alloc_public_message(*max_length))
+            .collect::<Vec<_>>();
+        let hash_state = builder.sha_messages::<S, CYCLE_LENGTH>(&message_registers);
+
+        let word_length = S::IntRegister::size_of();
+        let num_rounds = messages
+            .iter()
+            .map(|(_, max_length)| (max_length + 2 * word_length) / (16 * word_length) + 1)
+            .sum::<usize>();
+        let num_rows = 1 << log2_ceil(CYCLE_LENGTH * num_rounds);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        for (((message, _), register), digest) in messages
+            .iter()
+            .zip_eq(message_registers.iter())
+            .zip_eq(hash_state.iter())
+        {
+            register.write(&mut writer, message);
+
+            let state = S::pad(message)
+                .chunks_exact(16)
+                .fold(S::INITIAL_HASH, |state, chunk| {
+                    S::process(state, &S::pre_process(chunk))
+                })
+                .map(S::int_to_field_value);
+            let array: ArrayRegister<_> = (*digest).into();
+            writer.write_array(&array, &state[..S::DIGEST_LENGTH]);
+        }
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        // Compare expected digests with the trace values.
+        let writer = writer_data.public_writer();
+        for (digest, expected) in hash_state.iter().zip_eq(expected_digests) {
+            let array: ArrayRegister<S::IntRegister> = (*digest).into();
+            let digest = writer
+                .read_vec(&array)
+                .iter()
+                .map(S::field_value_to_int)
+                .collect::<Vec<_>>();
+            let expected_digest = S::decode(expected);
+            assert_eq!(digest, expected_digest);
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+
+    pub fn test_hmac<
+        'a,
+        L,
+        S,
+        I: IntoIterator<Item = (&'a [u8], &'a [u8], usize)>,
+        J: IntoIterator<Item = &'a str>,
+        const CYCLE_LENGTH: usize,
+    >(
+        inputs: I,
+        expected_digests: J,
+    ) where
+        L: AirParameters<Field = GoldilocksField, CubicParams = GoldilocksCubicParameters>,
+        L::Instruction: UintInstructions,
+        S: SHAir<BytesBuilder<L>, CYCLE_LENGTH>,
+        Chip<L>: Plonky2Air<GoldilocksField, 2>,
+        S::Integer: PartialEq + Eq + Debug,
+    {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_hmac", log::Level::Debug);
+
+        // Build the stark.
+        let mut builder = BytesBuilder::<L>::new();
+        let keys = inputs
+            .iter()
+            .map(|_| builder.alloc_public_hmac_key::<S, CYCLE_LENGTH>())
+            .collect::<Vec<_>>();
+        let message_registers = inputs
+            .iter()
+            .map(|(_, _, max_length)| builder.alloc_public_message(*max_length))
+            .collect::<Vec<_>>();
+        let hmacs = builder.hmac::<S, CYCLE_LENGTH>(&keys, &message_registers);
+
+        let word_length = S::IntRegister::size_of();
+        let num_rounds = inputs
+            .iter()
+            .map(|(_, _, max_length)| (max_length + 2 * word_length) / (16 * word_length) + 4)
+            .sum::<usize>();
+        let num_rows = 1 << log2_ceil(CYCLE_LENGTH * num_rounds);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        for ((((key, message, _), key_register), message_register), hmac) in inputs
+            .iter()
+            .zip_eq(keys.iter())
+            .zip_eq(message_registers.iter())
+            .zip_eq(hmacs.iter())
+        {
+            key_register.write(&mut writer, key);
+            message_register.write(&mut writer, message);
+
+            let digest = S::decode(&hex::encode(S::hmac(key, message)));
+            let array: ArrayRegister<_> = (*hmac).into();
+            writer.write_array(&array, digest.into_iter().map(S::int_to_field_value));
+        }
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        // Compare expected digests with the trace values.
+        let writer = writer_data.public_writer();
+        for (digest, expected) in hmacs.iter().zip_eq(expected_digests) {
+            let array: ArrayRegister<S::IntRegister> = (*digest).into();
+            let digest = writer
+                .read_vec(&array)
+                .iter()
+                .map(S::field_value_to_int)
+                .collect::<Vec<_>>();
             let expected_digest = S::decode(expected);
             assert_eq!(digest, expected_digest);
         }
@@ -184,4 +920,116 @@ pub mod test_utils {
 
         timing.print();
     }
+
+    pub fn test_hkdf_expand<L, S, const CYCLE_LENGTH: usize>(
+        prk: &[u8],
+        info: &[u8],
+        max_info_length: usize,
+        length: usize,
+        expected_okm: &str,
+    ) where
+        L: AirParameters<Field = GoldilocksField, CubicParams = GoldilocksCubicParameters>,
+        L::Instruction: UintInstructions,
+        S: SHAir<BytesBuilder<L>, CYCLE_LENGTH>,
+        Chip<L>: Plonky2Air<GoldilocksField, 2>,
+        S::Integer: PartialEq + Eq + Debug,
+    {
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_hkdf_expand", log::Level::Debug);
+
+        // Build the stark.
+        let mut builder = BytesBuilder::<L>::new();
+        let prk_register = builder.alloc_public_hmac_key::<S, CYCLE_LENGTH>();
+        let info_register = builder.alloc_public_message(max_info_length);
+        let blocks = builder.hkdf_expand::<S, CYCLE_LENGTH>(&prk_register, &info_register, length);
+
+        let word_length = S::IntRegister::size_of();
+        let block_length = S::DIGEST_LENGTH * word_length;
+        let num_rounds = (0..blocks.len())
+            .map(|i| {
+                let offset = if i > 0 { block_length } else { 0 };
+                (offset + max_info_length + 1 + 2 * word_length) / (16 * word_length) + 4
+            })
+            .sum::<usize>();
+        let num_rows = 1 << log2_ceil(CYCLE_LENGTH * num_rounds);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        prk_register.write(&mut writer, prk);
+        info_register.write(&mut writer, info);
+        let okm = S::hkdf_expand(prk, info, blocks.len() * block_length);
+        for (block, block_value) in blocks.iter().zip_eq(okm.chunks_exact(block_length)) {
+            let digest = S::decode(&hex::encode(block_value));
+            let array: ArrayRegister<_> = (*block).into();
+            writer.write_array(&array, digest.into_iter().map(S::int_to_field_value));
+        }
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        // Compare the output key material with the trace values.
+        let writer = writer_data.public_writer();
+        let okm = blocks
+            .iter()
+            .flat_map(|block| {
+                let array: ArrayRegister<S::IntRegister> = (*block).into();
+                let words = writer
+                    .read_vec(&array)
+                    .iter()
+                    .map(S::field_value_to_int)
+                    .collect::<Vec<_>>();
+                S::encode(&words)
+            })
+            .take(length)
+            .collect::<Vec<_>>();
+        assert_eq!(hex::encode(okm), expected_okm);
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
 }
@@ -30,5 +30,12 @@ pub struct SHAMemory<T> {
     pub shift_read_mult: Slice<ElementRegister>,
     pub end_bit: Slice<BitRegister>,
     pub digest_bit: Slice<BitRegister>,
+    pub forward: Option<SHAForwardMemory>,
     pub dummy_index: ElementRegister,
 }
+
+/// The forwarding bit and the index in `w` of the first word receiving the digest of every round.
+pub struct SHAForwardMemory {
+    pub forward_bit: Slice<BitRegister>,
+    pub target: Slice<ElementRegister>,
+}
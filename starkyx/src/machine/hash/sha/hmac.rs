@@ -0,0 +1,164 @@
+use num::Zero;
+use serde::{Deserialize, Serialize};
+
+use super::algorithm::SHAPure;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::trace::writer::AirWriter;
+use crate::math::prelude::*;
+
+/// The byte xored with the key of the inner hash of HMAC.
+pub const IPAD: u8 = 0x36;
+
+/// The byte xored with the key of the outer hash of HMAC.
+pub const OPAD: u8 = 0x5c;
+
+/// Pure HMAC and HKDF implementations on top of a SHA algorithm.
+pub trait HMACPure<const CYCLE_LENGTH: usize>: SHAPure<CYCLE_LENGTH> {
+    /// The length in bytes of a chunk of the hash function.
+    fn block_length() -> usize {
+        16 * Self::encode(&[Self::Integer::zero()]).len()
+    }
+
+    /// The length in bytes of the digest of the hash function.
+    fn digest_length() -> usize {
+        Self::encode(&Self::INITIAL_HASH[..Self::DIGEST_LENGTH]).len()
+    }
+
+    /// Hash a byte message, returning the big-endian bytes of the digest.
+    fn digest(msg: &[u8]) -> Vec<u8> {
+        let state = Self::pad(msg)
+            .chunks_exact(16)
+            .fold(Self::INITIAL_HASH, |state, chunk| {
+                Self::process(state, &Self::pre_process(chunk))
+            });
+        Self::encode(&state[..Self::DIGEST_LENGTH])
+    }
+
+    /// The HMAC of `msg` with the key `key`, as defined in RFC 2104.
+    fn hmac(key: &[u8], msg: &[u8]) -> Vec<u8> {
+        let block_length = Self::block_length();
+        let mut key_block = if key.len() > block_length {
+            Self::digest(key)
+        } else {
+            key.to_vec()
+        };
+        key_block.resize(block_length, 0);
+
+        let inner_input = key_block
+            .iter()
+            .map(|b| b ^ IPAD)
+            .chain(msg.iter().copied())
+            .collect::<Vec<_>>();
+        let outer_input = key_block
+            .iter()
+            .map(|b| b ^ OPAD)
+            .chain(Self::digest(&inner_input))
+            .collect::<Vec<_>>();
+        Self::digest(&outer_input)
+    }
+
+    /// The pseudorandom key `HKDF-Extract(salt, ikm)` of RFC 5869.
+    fn hkdf_extract(salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+        Self::hmac(salt, ikm)
+    }
+
+    /// The first `length` bytes of the output `HKDF-Expand(prk, info, length)` of RFC 5869.
+    fn hkdf_expand(prk: &[u8], info: &[u8], length: usize) -> Vec<u8> {
+        let num_blocks = (length + Self::digest_length() - 1) / Self::digest_length();
+        assert!(num_blocks <= 255, "HKDF output too long");
+
+        let mut okm = Vec::with_capacity(num_blocks * Self::digest_length());
+        let mut t = Vec::new();
+        for i in 1..=num_blocks {
+            let input = [t.as_slice(), info, &[i as u8]].concat();
+            t = Self::hmac(prk, &input);
+            okm.extend_from_slice(&t);
+        }
+        okm.truncate(length);
+        okm
+    }
+}
+
+impl<S: SHAPure<CYCLE_LENGTH>, const CYCLE_LENGTH: usize> HMACPure<CYCLE_LENGTH> for S {}
+
+/// A public HMAC key of at most one block.
+///
+/// The key is given by the bits of its bytes padded with zeros to a full block, so that xoring
+/// it with the inner and outer pads is linear in the bits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HMACKeyRegister {
+    pub bits: ArrayRegister<BitRegister>,
+}
+
+impl HMACKeyRegister {
+    /// The length in bytes of the key block.
+    pub fn block_length(&self) -> usize {
+        self.bits.len() / 8
+    }
+
+    /// Writes the bits of `key` padded with zeros.
+    ///
+    /// Keys longer than a block must be hashed first, as in `HMACPure::hmac`.
+    pub fn write<W: AirWriter>(&self, writer: &mut W, key: &[u8]) {
+        let block_length = self.block_length();
+        assert!(key.len() <= block_length, "Key longer than a block");
+
+        for i in 0..block_length {
+            let byte = key.get(i).copied().unwrap_or(0);
+            for k in 0..8 {
+                writer.write(
+                    &self.bits.get(8 * i + k),
+                    &W::Field::from_canonical_u8((byte >> k) & 1),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::machine::hash::sha::sha256::SHA256;
+    use crate::machine::hash::sha::sha512::SHA512;
+
+    #[test]
+    fn test_hmac_pure() {
+        let digest = SHA256::hmac(&[0x0b; 20], b"Hi There");
+        assert_eq!(
+            hex::encode(digest),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+
+        let digest = SHA256::hmac(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(digest),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let digest = SHA512::hmac(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(digest),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn test_hkdf_pure() {
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+
+        let prk = SHA256::hkdf_extract(&salt, &[0x0b; 22]);
+        assert_eq!(
+            hex::encode(&prk),
+            "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"
+        );
+
+        let okm = SHA256::hkdf_expand(&prk, &info, 42);
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod algorithm;
 pub mod builder;
 pub mod data;
+pub mod hmac;
 pub mod sha256;
 pub mod sha512;
@@ -1,5 +1,5 @@
-use super::register::SHA256DigestRegister;
-use super::SHA256;
+use super::register::{SHA224DigestRegister, SHA256DigestRegister};
+use super::{SHA224, SHA256};
 use crate::chip::memory::pointer::slice::Slice;
 use crate::chip::memory::time::Time;
 use crate::chip::register::array::ArrayRegister;
@@ -197,6 +197,103 @@ where
     }
 }
 
+impl<B: Builder> HashInteger<B> for SHA224 {
+    type IntRegister = U32Register;
+    type Value = <U32Register as Register>::Value<B::Field>;
+}
+
+impl<B: Builder> HashIntConversion<B> for SHA224 {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u32_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u32_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for SHA224 {
+    type DigestRegister = SHA224DigestRegister;
+}
+
+impl<L: AirParameters> SHAir<BytesBuilder<L>, 64> for SHA224
+where
+    L::Instruction: UintInstructions,
+{
+    // The state is the full SHA256 state, of which only the first seven words are stored.
+    type StateVariable = SHA256DigestRegister;
+    type StatePointer = Slice<U32Register>;
+
+    fn clk(builder: &mut BytesBuilder<L>) -> ElementRegister {
+        builder.clk
+    }
+
+    fn cycles_end_bits(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister) {
+        <SHA256 as SHAir<BytesBuilder<L>, 64>>::cycles_end_bits(builder)
+    }
+
+    fn load_state(
+        builder: &mut BytesBuilder<L>,
+        hash_state_public: &[Self::DigestRegister],
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Self::StatePointer {
+        let state_ptr = builder.uninit_slice();
+
+        for (i, h_slice) in digest_indices.iter().zip(hash_state_public.iter()) {
+            for (j, h) in h_slice.iter().enumerate() {
+                builder.free(&state_ptr.get(j), h, &Time::from_element(i));
+            }
+        }
+
+        state_ptr
+    }
+
+    fn store_state(
+        builder: &mut BytesBuilder<L>,
+        state_ptr: &Self::StatePointer,
+        state_next: Self::StateVariable,
+        time: &Time<L::Field>,
+        flag: Option<ElementRegister>,
+    ) {
+        for (i, element) in state_next.iter().take(Self::DIGEST_LENGTH).enumerate() {
+            builder.store(&state_ptr.get(i), element, time, flag, None, None);
+        }
+    }
+
+    fn preprocessing_step(
+        builder: &mut BytesBuilder<L>,
+        w_i_minus_15: Self::IntRegister,
+        w_i_minus_2: Self::IntRegister,
+        w_i_mimus_16: Self::IntRegister,
+        w_i_mimus_7: Self::IntRegister,
+    ) -> Self::IntRegister {
+        <SHA256 as SHAir<BytesBuilder<L>, 64>>::preprocessing_step(
+            builder,
+            w_i_minus_15,
+            w_i_minus_2,
+            w_i_mimus_16,
+            w_i_mimus_7,
+        )
+    }
+
+    fn processing_step(
+        builder: &mut BytesBuilder<L>,
+        vars: ArrayRegister<Self::IntRegister>,
+        w_i: Self::IntRegister,
+        round_constant: Self::IntRegister,
+    ) -> Vec<Self::IntRegister> {
+        <SHA256 as SHAir<BytesBuilder<L>, 64>>::processing_step(builder, vars, w_i, round_constant)
+    }
+
+    fn absorb(
+        builder: &mut BytesBuilder<L>,
+        state: ArrayRegister<Self::IntRegister>,
+        vars_next: &[Self::IntRegister],
+    ) -> Self::StateVariable {
+        <SHA256 as SHAir<BytesBuilder<L>, 64>>::absorb(builder, state, vars_next)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::iter;
@@ -206,7 +303,9 @@ mod tests {
 
     use super::*;
     use crate::chip::uint::operations::instruction::UintInstruction;
-    use crate::machine::hash::sha::builder::test_utils::test_sha;
+    use crate::machine::hash::sha::builder::test_utils::{
+        test_hkdf_expand, test_hmac, test_sha, test_sha_messages,
+    };
     use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -222,6 +321,32 @@ mod tests {
         const EXTENDED_COLUMNS: usize = 912;
     }
 
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct SHA224Test;
+
+    impl AirParameters for SHA224Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 418;
+        const EXTENDED_COLUMNS: usize = 930;
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct HMACSHA256Test;
+
+    impl AirParameters for HMACSHA256Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 440;
+        const EXTENDED_COLUMNS: usize = 1000;
+    }
+
     fn test_sha256<'a, I: IntoIterator<Item = &'a [u8]>, J: IntoIterator<Item = &'a str>>(
         messages: I,
         expected_digests: J,
@@ -272,4 +397,85 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_sha224_changing_length_message() {
+        let short_msg = b"abc";
+        let short_expected_digest = "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7";
+        let long_msg = hex::decode("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89452821e638d01377be5466cf34e90c6cc0ac29b7c97c50dd3f84d5b5b5470917").unwrap();
+        let long_expected_digest = "31a026c662d5cca69376d0c36721843e48550eaa44d39bc11132eb3f";
+        test_sha::<SHA224Test, SHA224, _, _, 64>(
+            [
+                short_msg.as_slice(),
+                long_msg.as_slice(),
+                short_msg.as_slice(),
+            ],
+            [
+                short_expected_digest,
+                long_expected_digest,
+                short_expected_digest,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_sha256_padded_in_air() {
+        let long_msg = hex::decode("243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89452821e638d01377be5466cf34e90c6cc0ac29b7c97c50dd3f84d5b5b5470917").unwrap();
+        test_sha_messages::<SHA256Test, SHA256, _, _, 64>(
+            [
+                (b"".as_slice(), 3),
+                (b"abc".as_slice(), 120),
+                (long_msg.as_slice(), 64),
+                (b"abc".as_slice(), 3),
+            ],
+            [
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                "aca16131a2e4c4c49e656d35aac1f0e689b3151bb108fa6cf5bcc3ac08a09bf9",
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_hmac_sha256() {
+        let key = [0x0b; 20];
+        test_hmac::<HMACSHA256Test, SHA256, _, _, 64>(
+            [
+                (key.as_slice(), b"Hi There".as_slice(), 8),
+                (
+                    b"Jefe".as_slice(),
+                    b"what do ya want for nothing?".as_slice(),
+                    100,
+                ),
+                (key.as_slice(), b"Hi There".as_slice(), 64),
+            ],
+            [
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_hkdf_sha256() {
+        let salt = hex::decode("000102030405060708090a0b0c").unwrap();
+        let ikm = [0x0b; 22];
+        test_hmac::<HMACSHA256Test, SHA256, _, _, 64>(
+            [(salt.as_slice(), ikm.as_slice(), 32)],
+            ["077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"],
+        );
+
+        let prk = hex::decode("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+            .unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        test_hkdf_expand::<HMACSHA256Test, SHA256, 64>(
+            &prk,
+            &info,
+            16,
+            42,
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+        );
+    }
 }
@@ -7,6 +7,11 @@ pub mod register;
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct SHA256;
 
+/// SHA-224, which uses the SHA-256 compression function with a different initial hash and
+/// truncates the digest to its first seven words.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SHA224;
+
 pub(crate) const ROUND_CONSTANTS: [u32; 64] = [
     0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
     0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
@@ -21,3 +26,7 @@ pub(crate) const ROUND_CONSTANTS: [u32; 64] = [
 pub(crate) const INITIAL_HASH: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
+
+pub(crate) const SHA224_INITIAL_HASH: [u32; 8] = [
+    0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7, 0xbefa4fa4,
+];
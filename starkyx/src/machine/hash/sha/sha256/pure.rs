@@ -1,4 +1,4 @@
-use super::{INITIAL_HASH, ROUND_CONSTANTS, SHA256};
+use super::{INITIAL_HASH, ROUND_CONSTANTS, SHA224, SHA224_INITIAL_HASH, SHA256};
 use crate::machine::hash::sha::algorithm::SHAPure;
 use crate::machine::hash::HashPureInteger;
 
@@ -9,6 +9,7 @@ impl HashPureInteger for SHA256 {
 impl SHAPure<64> for SHA256 {
     const INITIAL_HASH: [Self::Integer; 8] = INITIAL_HASH;
     const ROUND_CONSTANTS: [Self::Integer; 64] = ROUND_CONSTANTS;
+    const DIGEST_LENGTH: usize = 8;
 
     fn pad(msg: &[u8]) -> Vec<Self::Integer> {
         let mut padded_msg = Vec::new();
@@ -66,14 +67,46 @@ impl SHAPure<64> for SHA256 {
         ]
     }
 
-    fn decode(digest: &str) -> [Self::Integer; 8] {
+    fn decode(digest: &str) -> Vec<Self::Integer> {
         hex::decode(digest)
             .unwrap()
             .chunks_exact(4)
             .map(|x| u32::from_be_bytes(x.try_into().unwrap()))
             .collect::<Vec<_>>()
-            .try_into()
-            .unwrap()
+    }
+
+    fn encode(words: &[Self::Integer]) -> Vec<u8> {
+        words.iter().flat_map(|x| x.to_be_bytes()).collect()
+    }
+}
+
+impl HashPureInteger for SHA224 {
+    type Integer = u32;
+}
+
+impl SHAPure<64> for SHA224 {
+    const INITIAL_HASH: [Self::Integer; 8] = SHA224_INITIAL_HASH;
+    const ROUND_CONSTANTS: [Self::Integer; 64] = ROUND_CONSTANTS;
+    const DIGEST_LENGTH: usize = 7;
+
+    fn pad(msg: &[u8]) -> Vec<Self::Integer> {
+        SHA256::pad(msg)
+    }
+
+    fn pre_process(chunk: &[u32]) -> [Self::Integer; 64] {
+        SHA256::pre_process(chunk)
+    }
+
+    fn process(hash: [Self::Integer; 8], w: &[Self::Integer; 64]) -> [Self::Integer; 8] {
+        SHA256::process(hash, w)
+    }
+
+    fn decode(digest: &str) -> Vec<Self::Integer> {
+        SHA256::decode(digest)
+    }
+
+    fn encode(words: &[Self::Integer]) -> Vec<u8> {
+        SHA256::encode(words)
     }
 }
 
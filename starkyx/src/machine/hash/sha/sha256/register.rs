@@ -66,3 +66,61 @@ impl From<SHA256DigestRegister> for ArrayRegister<U32Register> {
         value.0
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SHA224DigestRegister(ArrayRegister<U32Register>);
+
+impl RegisterSerializable for SHA224DigestRegister {
+    const CELL: CellType = CellType::Element;
+    fn register(&self) -> &MemorySlice {
+        self.0.register()
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(ArrayRegister::from_register_unsafe(register))
+    }
+}
+
+impl RegisterSized for SHA224DigestRegister {
+    fn size_of() -> usize {
+        U32Register::size_of() * 7
+    }
+}
+
+impl Register for SHA224DigestRegister {
+    type Value<T> = [T; 28];
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+}
+
+impl SHA224DigestRegister {
+    pub fn as_array(&self) -> ArrayRegister<U32Register> {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> U32Register {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> ArrayIterator<U32Register> {
+        self.0.iter()
+    }
+
+    pub fn from_array(array: ArrayRegister<U32Register>) -> Self {
+        assert_eq!(array.len(), 7);
+        Self(array)
+    }
+}
+
+impl From<SHA224DigestRegister> for ArrayRegister<U32Register> {
+    fn from(register: SHA224DigestRegister) -> Self {
+        register.0
+    }
+}
@@ -1,5 +1,5 @@
-use super::register::SHA512DigestRegister;
-use super::SHA512;
+use super::register::{SHA384DigestRegister, SHA512DigestRegister, SHA512_256DigestRegister};
+use super::{SHA384, SHA512, SHA512_256};
 use crate::chip::memory::pointer::slice::Slice;
 use crate::chip::memory::time::Time;
 use crate::chip::register::array::ArrayRegister;
@@ -197,6 +197,200 @@ where
     }
 }
 
+impl<B: Builder> HashInteger<B> for SHA384 {
+    type IntRegister = U64Register;
+    type Value = <U64Register as Register>::Value<B::Field>;
+}
+
+impl<B: Builder> HashIntConversion<B> for SHA384 {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u64_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u64_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for SHA384 {
+    type DigestRegister = SHA384DigestRegister;
+}
+
+impl<L: AirParameters> SHAir<BytesBuilder<L>, 80> for SHA384
+where
+    L::Instruction: UintInstructions,
+{
+    // The state is the full SHA512 state, of which only the first six words are stored.
+    type StateVariable = SHA512DigestRegister;
+    type StatePointer = Slice<U64Register>;
+
+    fn clk(builder: &mut BytesBuilder<L>) -> ElementRegister {
+        builder.clk
+    }
+
+    fn cycles_end_bits(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister) {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::cycles_end_bits(builder)
+    }
+
+    fn load_state(
+        builder: &mut BytesBuilder<L>,
+        hash_state_public: &[Self::DigestRegister],
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Self::StatePointer {
+        let state_ptr = builder.uninit_slice();
+
+        for (i, h_slice) in digest_indices.iter().zip(hash_state_public.iter()) {
+            for (j, h) in h_slice.iter().enumerate() {
+                builder.free(&state_ptr.get(j), h, &Time::from_element(i));
+            }
+        }
+
+        state_ptr
+    }
+
+    fn store_state(
+        builder: &mut BytesBuilder<L>,
+        state_ptr: &Self::StatePointer,
+        state_next: Self::StateVariable,
+        time: &Time<L::Field>,
+        flag: Option<ElementRegister>,
+    ) {
+        for (i, element) in state_next.iter().take(Self::DIGEST_LENGTH).enumerate() {
+            builder.store(&state_ptr.get(i), element, time, flag, None, None);
+        }
+    }
+
+    fn preprocessing_step(
+        builder: &mut BytesBuilder<L>,
+        w_i_minus_15: Self::IntRegister,
+        w_i_minus_2: Self::IntRegister,
+        w_i_mimus_16: Self::IntRegister,
+        w_i_mimus_7: Self::IntRegister,
+    ) -> Self::IntRegister {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::preprocessing_step(
+            builder,
+            w_i_minus_15,
+            w_i_minus_2,
+            w_i_mimus_16,
+            w_i_mimus_7,
+        )
+    }
+
+    fn processing_step(
+        builder: &mut BytesBuilder<L>,
+        vars: ArrayRegister<Self::IntRegister>,
+        w_i: Self::IntRegister,
+        round_constant: Self::IntRegister,
+    ) -> Vec<Self::IntRegister> {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::processing_step(builder, vars, w_i, round_constant)
+    }
+
+    fn absorb(
+        builder: &mut BytesBuilder<L>,
+        state: ArrayRegister<Self::IntRegister>,
+        vars_next: &[Self::IntRegister],
+    ) -> Self::StateVariable {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::absorb(builder, state, vars_next)
+    }
+}
+
+impl<B: Builder> HashInteger<B> for SHA512_256 {
+    type IntRegister = U64Register;
+    type Value = <U64Register as Register>::Value<B::Field>;
+}
+
+impl<B: Builder> HashIntConversion<B> for SHA512_256 {
+    fn int_to_field_value(int: Self::Integer) -> Self::Value {
+        u64_to_le_field_bytes(int)
+    }
+
+    fn field_value_to_int(value: &Self::Value) -> Self::Integer {
+        u64_from_le_field_bytes(value)
+    }
+}
+
+impl<B: Builder> HashDigest<B> for SHA512_256 {
+    type DigestRegister = SHA512_256DigestRegister;
+}
+
+impl<L: AirParameters> SHAir<BytesBuilder<L>, 80> for SHA512_256
+where
+    L::Instruction: UintInstructions,
+{
+    // The state is the full SHA512 state, of which only the first four words are stored.
+    type StateVariable = SHA512DigestRegister;
+    type StatePointer = Slice<U64Register>;
+
+    fn clk(builder: &mut BytesBuilder<L>) -> ElementRegister {
+        builder.clk
+    }
+
+    fn cycles_end_bits(builder: &mut BytesBuilder<L>) -> (BitRegister, BitRegister) {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::cycles_end_bits(builder)
+    }
+
+    fn load_state(
+        builder: &mut BytesBuilder<L>,
+        hash_state_public: &[Self::DigestRegister],
+        digest_indices: ArrayRegister<ElementRegister>,
+    ) -> Self::StatePointer {
+        let state_ptr = builder.uninit_slice();
+
+        for (i, h_slice) in digest_indices.iter().zip(hash_state_public.iter()) {
+            for (j, h) in h_slice.iter().enumerate() {
+                builder.free(&state_ptr.get(j), h, &Time::from_element(i));
+            }
+        }
+
+        state_ptr
+    }
+
+    fn store_state(
+        builder: &mut BytesBuilder<L>,
+        state_ptr: &Self::StatePointer,
+        state_next: Self::StateVariable,
+        time: &Time<L::Field>,
+        flag: Option<ElementRegister>,
+    ) {
+        for (i, element) in state_next.iter().take(Self::DIGEST_LENGTH).enumerate() {
+            builder.store(&state_ptr.get(i), element, time, flag, None, None);
+        }
+    }
+
+    fn preprocessing_step(
+        builder: &mut BytesBuilder<L>,
+        w_i_minus_15: Self::IntRegister,
+        w_i_minus_2: Self::IntRegister,
+        w_i_mimus_16: Self::IntRegister,
+        w_i_mimus_7: Self::IntRegister,
+    ) -> Self::IntRegister {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::preprocessing_step(
+            builder,
+            w_i_minus_15,
+            w_i_minus_2,
+            w_i_mimus_16,
+            w_i_mimus_7,
+        )
+    }
+
+    fn processing_step(
+        builder: &mut BytesBuilder<L>,
+        vars: ArrayRegister<Self::IntRegister>,
+        w_i: Self::IntRegister,
+        round_constant: Self::IntRegister,
+    ) -> Vec<Self::IntRegister> {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::processing_step(builder, vars, w_i, round_constant)
+    }
+
+    fn absorb(
+        builder: &mut BytesBuilder<L>,
+        state: ArrayRegister<Self::IntRegister>,
+        vars_next: &[Self::IntRegister],
+    ) -> Self::StateVariable {
+        <SHA512 as SHAir<BytesBuilder<L>, 80>>::absorb(builder, state, vars_next)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::fmt::Debug;
@@ -207,8 +401,8 @@ mod tests {
 
     use crate::chip::uint::operations::instruction::UintInstruction;
     use crate::chip::AirParameters;
-    use crate::machine::hash::sha::builder::test_utils::test_sha;
-    use crate::machine::hash::sha::sha512::SHA512;
+    use crate::machine::hash::sha::builder::test_utils::{test_sha, test_sha_messages};
+    use crate::machine::hash::sha::sha512::{SHA384, SHA512, SHA512_256};
     use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
 
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -272,4 +466,63 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_sha384_changing_length_message() {
+        let short_msg = b"plonky2";
+        let short_expected_digest = "7e4cc34d59b11b2c7db390417a3722112c5c2522d2bc6aab414eca71daf3db08b90bdd9781e374bd2946c926e4e36f31";
+        let long_msg = hex::decode("35c323757c20640a294345c89c0bfcebe3d554fdb0c7b7a0bdb72222c531b1ecf7ec1c43f4de9d49556de87b86b26a98942cb078486fdb44de38b80864c3973153756363696e6374204c616273").unwrap();
+        let long_expected_digest = "39efc652dc1fbdb1db7b39f447ced380e2ebc1e3e3ffff5b45d4b349e0844c0e243c306d2dcc0b52436f06838f10b45a";
+        test_sha::<SHA512Test, SHA384, _, _, 80>(
+            [
+                short_msg.as_slice(),
+                long_msg.as_slice(),
+                short_msg.as_slice(),
+            ],
+            [
+                short_expected_digest,
+                long_expected_digest,
+                short_expected_digest,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_sha512_256_changing_length_message() {
+        let short_msg = b"plonky2";
+        let short_expected_digest =
+            "f897d1b844ca01eff99cb7f1784abe151bde2ddad7786d3c980bdd25032eb03d";
+        let long_msg = hex::decode("35c323757c20640a294345c89c0bfcebe3d554fdb0c7b7a0bdb72222c531b1ecf7ec1c43f4de9d49556de87b86b26a98942cb078486fdb44de38b80864c3973153756363696e6374204c616273").unwrap();
+        let long_expected_digest =
+            "c8edf3df5ccaa7a262ead572ae4bc9ff373390869b3790a426bf318b5e456e51";
+        test_sha::<SHA512Test, SHA512_256, _, _, 80>(
+            [
+                short_msg.as_slice(),
+                long_msg.as_slice(),
+                short_msg.as_slice(),
+            ],
+            [
+                short_expected_digest,
+                long_expected_digest,
+                short_expected_digest,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_sha512_padded_in_air() {
+        let long_msg = hex::decode("35c323757c20640a294345c89c0bfcebe3d554fdb0c7b7a0bdb72222c531b1ecf7ec1c43f4de9d49556de87b86b26a98942cb078486fdb44de38b80864c3973153756363696e6374204c616273").unwrap();
+        test_sha_messages::<SHA512Test, SHA512, _, _, 80>(
+            [
+                (b"plonky2".as_slice(), 7),
+                (long_msg.as_slice(), 200),
+                (b"plonky2".as_slice(), 120),
+            ],
+            [
+                "7c6159dd615db8c15bc76e23d36106e77464759979a0fcd1366e531f552cfa0852dbf5c832f00bb279cbc945b44a132bff3ed0028259813b6a07b57326e88c87",
+                "4388243c4452274402673de881b2f942ff5730fd2c7d8ddb94c3e3d789fb3754380cba8faa40554d9506a0730a681e88ab348a04bc5c41d18926f140b59aed39",
+                "7c6159dd615db8c15bc76e23d36106e77464759979a0fcd1366e531f552cfa0852dbf5c832f00bb279cbc945b44a132bff3ed0028259813b6a07b57326e88c87",
+            ],
+        );
+    }
 }
@@ -7,6 +7,16 @@ pub mod register;
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct SHA512;
 
+/// SHA-384, which uses the SHA-512 compression function with a different initial hash and
+/// truncates the digest to its first six words.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SHA384;
+
+/// SHA-512/256, which uses the SHA-512 compression function with a different initial hash and
+/// truncates the digest to its first four words.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SHA512_256;
+
 pub(crate) const ROUND_CONSTANTS: [u64; 80] = [
     0x428a2f98d728ae22,
     0x7137449123ef65cd,
@@ -100,3 +110,25 @@ pub(crate) const INITIAL_HASH: [u64; 8] = [
     0x1f83d9abfb41bd6b,
     0x5be0cd19137e2179,
 ];
+
+pub(crate) const SHA384_INITIAL_HASH: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+pub(crate) const SHA512_256_INITIAL_HASH: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
@@ -1,4 +1,7 @@
-use super::{INITIAL_HASH, ROUND_CONSTANTS, SHA512};
+use super::{
+    INITIAL_HASH, ROUND_CONSTANTS, SHA384, SHA384_INITIAL_HASH, SHA512, SHA512_256,
+    SHA512_256_INITIAL_HASH,
+};
 use crate::machine::hash::sha::algorithm::SHAPure;
 use crate::machine::hash::HashPureInteger;
 
@@ -9,6 +12,7 @@ impl HashPureInteger for SHA512 {
 impl SHAPure<80> for SHA512 {
     const INITIAL_HASH: [Self::Integer; 8] = INITIAL_HASH;
     const ROUND_CONSTANTS: [Self::Integer; 80] = ROUND_CONSTANTS;
+    const DIGEST_LENGTH: usize = 8;
 
     fn pad(msg: &[u8]) -> Vec<Self::Integer> {
         let mut padded_msg = Vec::new();
@@ -66,14 +70,76 @@ impl SHAPure<80> for SHA512 {
         ]
     }
 
-    fn decode(digest: &str) -> [Self::Integer; 8] {
+    fn decode(digest: &str) -> Vec<Self::Integer> {
         hex::decode(digest)
             .unwrap()
             .chunks_exact(8)
             .map(|x| u64::from_be_bytes(x.try_into().unwrap()))
             .collect::<Vec<_>>()
-            .try_into()
-            .unwrap()
+    }
+
+    fn encode(words: &[Self::Integer]) -> Vec<u8> {
+        words.iter().flat_map(|x| x.to_be_bytes()).collect()
+    }
+}
+
+impl HashPureInteger for SHA384 {
+    type Integer = u64;
+}
+
+impl SHAPure<80> for SHA384 {
+    const INITIAL_HASH: [Self::Integer; 8] = SHA384_INITIAL_HASH;
+    const ROUND_CONSTANTS: [Self::Integer; 80] = ROUND_CONSTANTS;
+    const DIGEST_LENGTH: usize = 6;
+
+    fn pad(msg: &[u8]) -> Vec<Self::Integer> {
+        SHA512::pad(msg)
+    }
+
+    fn pre_process(chunk: &[u64]) -> [Self::Integer; 80] {
+        SHA512::pre_process(chunk)
+    }
+
+    fn process(hash: [Self::Integer; 8], w: &[Self::Integer; 80]) -> [Self::Integer; 8] {
+        SHA512::process(hash, w)
+    }
+
+    fn decode(digest: &str) -> Vec<Self::Integer> {
+        SHA512::decode(digest)
+    }
+
+    fn encode(words: &[Self::Integer]) -> Vec<u8> {
+        SHA512::encode(words)
+    }
+}
+
+impl HashPureInteger for SHA512_256 {
+    type Integer = u64;
+}
+
+impl SHAPure<80> for SHA512_256 {
+    const INITIAL_HASH: [Self::Integer; 8] = SHA512_256_INITIAL_HASH;
+    const ROUND_CONSTANTS: [Self::Integer; 80] = ROUND_CONSTANTS;
+    const DIGEST_LENGTH: usize = 4;
+
+    fn pad(msg: &[u8]) -> Vec<Self::Integer> {
+        SHA512::pad(msg)
+    }
+
+    fn pre_process(chunk: &[u64]) -> [Self::Integer; 80] {
+        SHA512::pre_process(chunk)
+    }
+
+    fn process(hash: [Self::Integer; 8], w: &[Self::Integer; 80]) -> [Self::Integer; 8] {
+        SHA512::process(hash, w)
+    }
+
+    fn decode(digest: &str) -> Vec<Self::Integer> {
+        SHA512::decode(digest)
+    }
+
+    fn encode(words: &[Self::Integer]) -> Vec<u8> {
+        SHA512::encode(words)
     }
 }
 
@@ -63,3 +63,119 @@ impl From<SHA512DigestRegister> for ArrayRegister<U64Register> {
         register.0
     }
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SHA384DigestRegister(ArrayRegister<U64Register>);
+
+impl RegisterSerializable for SHA384DigestRegister {
+    const CELL: CellType = CellType::Element;
+    fn register(&self) -> &MemorySlice {
+        self.0.register()
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(ArrayRegister::from_register_unsafe(register))
+    }
+}
+
+impl RegisterSized for SHA384DigestRegister {
+    fn size_of() -> usize {
+        U64Register::size_of() * 6
+    }
+}
+
+impl Register for SHA384DigestRegister {
+    type Value<T> = [T; 48];
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+}
+
+impl SHA384DigestRegister {
+    pub fn as_array(&self) -> ArrayRegister<U64Register> {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> U64Register {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> ArrayIterator<U64Register> {
+        self.0.iter()
+    }
+
+    pub fn from_array(array: ArrayRegister<U64Register>) -> Self {
+        assert_eq!(array.len(), 6);
+        Self(array)
+    }
+}
+
+impl From<SHA384DigestRegister> for ArrayRegister<U64Register> {
+    fn from(register: SHA384DigestRegister) -> Self {
+        register.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SHA512_256DigestRegister(ArrayRegister<U64Register>);
+
+impl RegisterSerializable for SHA512_256DigestRegister {
+    const CELL: CellType = CellType::Element;
+    fn register(&self) -> &MemorySlice {
+        self.0.register()
+    }
+
+    fn from_register_unsafe(register: MemorySlice) -> Self {
+        Self(ArrayRegister::from_register_unsafe(register))
+    }
+}
+
+impl RegisterSized for SHA512_256DigestRegister {
+    fn size_of() -> usize {
+        U64Register::size_of() * 4
+    }
+}
+
+impl Register for SHA512_256DigestRegister {
+    type Value<T> = [T; 32];
+
+    fn align<T>(value: &Self::Value<T>) -> &[T] {
+        value
+    }
+
+    fn value_from_slice<T: Copy>(slice: &[T]) -> Self::Value<T> {
+        let elem_fn = |i| slice[i];
+        core::array::from_fn(elem_fn)
+    }
+}
+
+impl SHA512_256DigestRegister {
+    pub fn as_array(&self) -> ArrayRegister<U64Register> {
+        self.0
+    }
+
+    pub fn get(&self, index: usize) -> U64Register {
+        self.0.get(index)
+    }
+
+    pub fn iter(&self) -> ArrayIterator<U64Register> {
+        self.0.iter()
+    }
+
+    pub fn from_array(array: ArrayRegister<U64Register>) -> Self {
+        assert_eq!(array.len(), 4);
+        Self(array)
+    }
+}
+
+impl From<SHA512_256DigestRegister> for ArrayRegister<U64Register> {
+    fn from(register: SHA512_256DigestRegister) -> Self {
+        register.0
+    }
+}
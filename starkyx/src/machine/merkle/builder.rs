@@ -0,0 +1,593 @@
+use num::Zero;
+
+use super::MerklePathRegister;
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::{Register, RegisterSerializable, RegisterSized};
+use crate::machine::builder::Builder;
+use crate::machine::hash::blake::air::BLAKEAir;
+use crate::machine::hash::forward::DigestForwarding;
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::math::prelude::*;
+
+/// The inputs of a hash AIR computing the nodes of a set of Merkle paths.
+struct MerkleChunks<T> {
+    padded_chunks: Vec<ArrayRegister<T>>,
+    end_bits: ArrayRegister<BitRegister>,
+    digest_bits: ArrayRegister<BitRegister>,
+    digest_indices: ArrayRegister<ElementRegister>,
+    forwarding: DigestForwarding,
+}
+
+pub trait MerkleBuilder: Builder {
+    /// Allocates a public Merkle path of `depth` siblings whose nodes are `digest_length` words.
+    fn alloc_public_merkle_path<T: Register>(
+        &mut self,
+        digest_length: usize,
+        depth: usize,
+    ) -> MerklePathRegister<T> {
+        let leaf = self.alloc_array_public::<T>(digest_length);
+        let siblings = (0..depth)
+            .map(|_| self.alloc_array_public::<T>(digest_length))
+            .collect();
+        let path_bits = self.alloc_array_public::<BitRegister>(depth);
+
+        MerklePathRegister {
+            leaf,
+            siblings,
+            path_bits,
+        }
+    }
+
+    /// Computes the roots of Merkle paths where every node is the SHA digest of the concatenation
+    /// of its children.
+    ///
+    /// The digest of every level is written to the message of the next level through memory, so
+    /// only the leaves, the siblings, the path bits and the roots are public. Like `sha`, this can
+    /// only be called once per builder, so paths that share their siblings, such as the paths of
+    /// a leaf before and after an update, should be given in the same call.
+    fn sha_merkle_roots<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        paths: &[MerklePathRegister<S::IntRegister>],
+    ) -> Vec<S::DigestRegister> {
+        let word_length = S::IntRegister::size_of();
+        // The padding of a node does not depend on its children, so take it from a zero node.
+        let node_words = S::pad(&vec![0u8; 2 * S::DIGEST_LENGTH * word_length]);
+        let padding = node_words[2 * S::DIGEST_LENGTH..]
+            .iter()
+            .map(|word| self.constant::<S::IntRegister>(&S::int_to_field_value(*word)))
+            .collect::<Vec<_>>();
+
+        let chunks = merkle_chunks(self, paths, S::DIGEST_LENGTH, &padding);
+        S::sha_with_forwarding(
+            self,
+            &chunks.padded_chunks,
+            &chunks.end_bits,
+            &chunks.digest_bits,
+            chunks.digest_indices,
+            &chunks.forwarding,
+        )
+    }
+
+    /// Proves that the leaves of `paths` are included in the SHA Merkle tree with root `root`.
+    fn sha_merkle_inclusion<S: SHAir<Self, CYCLE_LENGTH>, const CYCLE_LENGTH: usize>(
+        &mut self,
+        paths: &[MerklePathRegister<S::IntRegister>],
+        root: &S::DigestRegister,
+    ) {
+        let roots = self.sha_merkle_roots::<S, CYCLE_LENGTH>(paths);
+        for path_root in roots.iter() {
+            self.assert_equal(path_root, root);
+        }
+    }
+
+    /// Computes the roots of Merkle paths where every node is the BLAKE digest of the
+    /// concatenation of its children.
+    ///
+    /// Every node is compressed as a single chunk, and only variants with a single `t` word per
+    /// chunk are supported. See `sha_merkle_roots` for the layout of the computation.
+    fn blake_merkle_roots<S: BLAKEAir<Self>>(
+        &mut self,
+        paths: &[MerklePathRegister<S::IntRegister>],
+    ) -> Vec<S::DigestRegister> {
+        assert_eq!(
+            S::T_LENGTH,
+            1,
+            "Only a single t word per chunk is supported"
+        );
+        let word_length = S::IntRegister::size_of();
+        let node_length = 2 * S::DIGEST_LENGTH;
+        assert!(node_length <= 16, "A node must fit in a single chunk");
+
+        let zero = self.constant::<S::IntRegister>(&S::int_to_field_value(S::Integer::zero()));
+        let padding = vec![zero; 16 - node_length];
+        let chunks = merkle_chunks(self, paths, S::DIGEST_LENGTH, &padding);
+
+        // The counter of every chunk is the byte length of a node.
+        let num_chunks = chunks.padded_chunks.len();
+        let t_value = (node_length * word_length) as u64;
+        let t_values = self.alloc_array_public::<S::IntRegister>(num_chunks);
+        for t in t_values.iter() {
+            let t_bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(*t.register());
+            for (k, t_byte) in t_bytes.iter().enumerate() {
+                let t_value_byte = t_value.checked_shr((8 * k) as u32).unwrap_or(0) & 0xff;
+                self.set_to_expression(
+                    &t_byte,
+                    ArithmeticExpression::from_constant(Self::Field::from_canonical_u64(
+                        t_value_byte,
+                    )),
+                );
+            }
+        }
+        let num_messages =
+            self.constant::<ElementRegister>(&Self::Field::from_canonical_usize(num_chunks));
+
+        S::blake_with_forwarding(
+            self,
+            &chunks.padded_chunks,
+            &t_values,
+            &chunks.end_bits,
+            &chunks.digest_bits,
+            &chunks.digest_indices,
+            &num_messages,
+            &chunks.forwarding,
+        )
+    }
+
+    /// Proves that the leaves of `paths` are included in the BLAKE Merkle tree with root `root`.
+    fn blake_merkle_inclusion<S: BLAKEAir<Self>>(
+        &mut self,
+        paths: &[MerklePathRegister<S::IntRegister>],
+        root: &S::DigestRegister,
+    ) {
+        let roots = self.blake_merkle_roots::<S>(paths);
+        for path_root in roots.iter() {
+            self.assert_equal(path_root, root);
+        }
+    }
+}
+
+impl<B: Builder> MerkleBuilder for B {}
+
+/// Lays out the nodes of `paths` as messages for a hash AIR.
+///
+/// Every node takes `(2 * digest_length + padding.len()) / 16` chunks, its children followed by
+/// the constant `padding`. The nodes of each path are ordered from the leaf upwards, and the
+/// digest of every node but the root is forwarded to the half of the next node given by the path
+/// bit. The public value of that half is the sibling, which is only used for the other half.
+fn merkle_chunks<B: Builder, T: Register>(
+    builder: &mut B,
+    paths: &[MerklePathRegister<T>],
+    digest_length: usize,
+    padding: &[T],
+) -> MerkleChunks<T> {
+    let node_length = 2 * digest_length;
+    assert!(
+        node_length <= 16,
+        "The children of a node must fit in one chunk"
+    );
+    assert_eq!((node_length + padding.len()) % 16, 0);
+    let chunks_per_node = (node_length + padding.len()) / 16;
+    let num_chunks = paths.iter().map(|path| path.depth()).sum::<usize>() * chunks_per_node;
+
+    let padded_chunks = (0..num_chunks)
+        .map(|_| builder.alloc_array_public::<T>(16))
+        .collect::<Vec<_>>();
+    let end_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let digest_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let digest_indices = builder.alloc_array_public::<ElementRegister>(paths.len());
+    let public_words = (0..num_chunks)
+        .map(|_| builder.alloc_array_public::<BitRegister>(16))
+        .collect::<Vec<_>>();
+    let forward_bits = builder.alloc_array_public::<BitRegister>(num_chunks);
+    let target_chunks = builder.alloc_array_public::<ElementRegister>(num_chunks);
+    let target_offsets = builder.alloc_array_public::<ElementRegister>(num_chunks);
+
+    let constant =
+        |value: usize| ArithmeticExpression::from_constant(B::Field::from_canonical_usize(value));
+
+    let mut start_chunk = 0;
+    for (path, digest_index) in paths.iter().zip(digest_indices.iter()) {
+        let depth = path.depth();
+        assert!(depth > 0, "A Merkle path must have at least one sibling");
+        assert_eq!(path.leaf.len(), digest_length);
+        assert_eq!(path.path_bits.len(), depth);
+
+        for (level, (sibling, bit)) in path.siblings.iter().zip(path.path_bits.iter()).enumerate() {
+            assert_eq!(sibling.len(), digest_length);
+            let node_chunks = start_chunk..start_chunk + chunks_per_node;
+            let last_chunk = start_chunk + chunks_per_node - 1;
+            let is_root = level == depth - 1;
+
+            let words = padded_chunks[node_chunks.clone()]
+                .iter()
+                .flat_map(|chunk| chunk.iter())
+                .collect::<Vec<_>>();
+            let is_public = public_words[node_chunks.clone()]
+                .iter()
+                .flat_map(|chunk| chunk.iter())
+                .collect::<Vec<_>>();
+
+            for (j, sibling_word) in sibling.iter().enumerate() {
+                let (left, right) = (words[j], words[digest_length + j]);
+                let (left_is_public, right_is_public) =
+                    (is_public[j], is_public[digest_length + j]);
+                if level == 0 {
+                    let leaf_word = path.leaf.get(j);
+                    builder.set_to_expression(
+                        &left,
+                        bit.expr() * sibling_word.expr() + bit.not_expr() * leaf_word.expr(),
+                    );
+                    builder.set_to_expression(
+                        &right,
+                        bit.expr() * leaf_word.expr() + bit.not_expr() * sibling_word.expr(),
+                    );
+                    builder.set_to_expression(&left_is_public, ArithmeticExpression::one());
+                    builder.set_to_expression(&right_is_public, ArithmeticExpression::one());
+                } else {
+                    // The other half is written by the digest of the previous level.
+                    builder.set_to_expression(&left, sibling_word.expr());
+                    builder.set_to_expression(&right, sibling_word.expr());
+                    builder.set_to_expression(&left_is_public, bit.expr());
+                    builder.set_to_expression(&right_is_public, bit.not_expr());
+                }
+            }
+            for ((word, word_is_public), padding_word) in words[node_length..]
+                .iter()
+                .zip(is_public[node_length..].iter())
+                .zip(padding.iter())
+            {
+                builder.set_to_expression(word, padding_word.expr());
+                builder.set_to_expression(word_is_public, ArithmeticExpression::one());
+            }
+
+            for chunk in node_chunks {
+                let at_node_end = chunk == last_chunk;
+                builder.set_to_expression(&end_bits.get(chunk), constant(at_node_end as usize));
+                builder.set_to_expression(
+                    &digest_bits.get(chunk),
+                    constant((at_node_end && is_root) as usize),
+                );
+
+                let forward = at_node_end && !is_root;
+                builder.set_to_expression(&forward_bits.get(chunk), constant(forward as usize));
+                if forward {
+                    // The digest is the left child of the next node if the next path bit is zero.
+                    let next_bit = path.path_bits.get(level + 1);
+                    builder.set_to_expression(&target_chunks.get(chunk), constant(last_chunk + 1));
+                    builder.set_to_expression(
+                        &target_offsets.get(chunk),
+                        next_bit.expr() * B::Field::from_canonical_usize(digest_length),
+                    );
+                } else {
+                    builder.set_to_expression(&target_chunks.get(chunk), constant(0));
+                    builder.set_to_expression(&target_offsets.get(chunk), constant(0));
+                }
+            }
+
+            if is_root {
+                builder.set_to_expression(&digest_index, constant(last_chunk));
+            }
+            start_chunk += chunks_per_node;
+        }
+    }
+
+    MerkleChunks {
+        padded_chunks,
+        end_bits,
+        digest_bits,
+        digest_indices,
+        forwarding: DigestForwarding {
+            public_words,
+            forward_bits,
+            target_chunks,
+            target_offsets,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::timed;
+    use plonky2::util::log2_ceil;
+    use plonky2::util::timing::TimingTree;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::trace::writer::AirWriter;
+    use crate::chip::uint::operations::instruction::UintInstruction;
+    use crate::chip::uint::register::{U32Register, U64Register};
+    use crate::chip::uint::util::{u32_to_le_field_bytes, u64_to_le_field_bytes};
+    use crate::chip::AirParameters;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::machine::hash::blake::blake2b::pure::BLAKE2BPure;
+    use crate::machine::hash::blake::blake2b::register::BLAKE2BDigestRegister;
+    use crate::machine::hash::blake::blake2b::{BLAKE2B, IV};
+    use crate::machine::hash::sha::algorithm::SHAPure;
+    use crate::machine::hash::sha::sha256::register::SHA256DigestRegister;
+    use crate::machine::hash::sha::sha256::SHA256;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MerkleSHA256Test;
+
+    impl AirParameters for MerkleSHA256Test {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 440;
+        const EXTENDED_COLUMNS: usize = 1000;
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct MerkleBLAKE2BTest;
+
+    impl AirParameters for MerkleBLAKE2BTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = UintInstruction;
+
+        const NUM_FREE_COLUMNS: usize = 1290;
+        const EXTENDED_COLUMNS: usize = 1530;
+    }
+
+    /// The levels of a Merkle tree, from the leaves to the root.
+    fn merkle_levels<I: Clone>(
+        leaves: Vec<Vec<I>>,
+        node: impl Fn(&[I], &[I]) -> Vec<I>,
+    ) -> Vec<Vec<Vec<I>>> {
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next_level = levels
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|children| node(&children[0], &children[1]))
+                .collect();
+            levels.push(next_level);
+        }
+        levels
+    }
+
+    /// The siblings of the path of the leaf at position `index`.
+    fn merkle_siblings<I: Clone>(levels: &[Vec<Vec<I>>], index: usize) -> Vec<Vec<I>> {
+        levels[..levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| nodes[(index >> level) ^ 1].clone())
+            .collect()
+    }
+
+    fn sha256_node(left: &[u32], right: &[u32]) -> Vec<u32> {
+        let bytes = left
+            .iter()
+            .chain(right.iter())
+            .flat_map(|word| word.to_be_bytes())
+            .collect_vec();
+        SHA256::pad(&bytes)
+            .chunks_exact(16)
+            .fold(<SHA256 as SHAPure<64>>::INITIAL_HASH, |state, chunk| {
+                SHA256::process(state, &SHA256::pre_process(chunk))
+            })
+            .to_vec()
+    }
+
+    fn blake2b_node(left: &[u64], right: &[u64]) -> Vec<u64> {
+        let mut chunk = left
+            .iter()
+            .chain(right.iter())
+            .flat_map(|word| word.to_le_bytes())
+            .collect_vec();
+        let node_length = chunk.len() as u64;
+        chunk.resize(128, 0);
+        let mut state = IV;
+        BLAKE2B::compress(&chunk, &mut state, node_length, true);
+        state[..4].to_vec()
+    }
+
+    #[test]
+    fn test_sha256_merkle_inclusion() {
+        type L = MerkleSHA256Test;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_sha256_merkle_inclusion", log::Level::Debug);
+
+        let depth = 4;
+        let leaves = (0..1u32 << depth)
+            .map(|i| (0..8).map(|j| 8 * i + j).collect_vec())
+            .collect_vec();
+        let levels = merkle_levels(leaves, sha256_node);
+        let indices = [0, 5, 6, 15, 5];
+
+        // Build the stark.
+        let mut builder = BytesBuilder::<L>::new();
+        let root = builder.alloc_public::<SHA256DigestRegister>();
+        let paths = indices
+            .iter()
+            .map(|_| builder.alloc_public_merkle_path::<U32Register>(8, depth))
+            .collect_vec();
+        builder.sha_merkle_inclusion::<SHA256, 64>(&paths, &root);
+
+        let num_rows = 1 << log2_ceil(indices.len() * depth * 2 * 64);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        let to_field = |words: &[u32]| {
+            words
+                .iter()
+                .map(|w| u32_to_le_field_bytes(*w))
+                .collect_vec()
+        };
+        let root_array: ArrayRegister<U32Register> = root.into();
+        writer.write_array(&root_array, to_field(&levels[depth][0]));
+        for (path, index) in paths.iter().zip_eq(indices) {
+            let siblings = merkle_siblings(&levels, index)
+                .iter()
+                .map(|sibling| to_field(sibling))
+                .collect_vec();
+            path.write(&mut writer, &to_field(&levels[0][index]), &siblings, index);
+        }
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+
+    #[test]
+    fn test_blake2b_merkle_update() {
+        type L = MerkleBLAKE2BTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+        let mut timing = TimingTree::new("test_blake2b_merkle_update", log::Level::Debug);
+
+        let depth = 3;
+        let leaves = (0..1u64 << depth)
+            .map(|i| (0..4).map(|j| ((4 * i + j) << 40) | 0xabcd).collect_vec())
+            .collect_vec();
+        let index = 3;
+        let new_leaf = vec![1u64, 2, 3, 4];
+        let levels = merkle_levels(leaves.clone(), blake2b_node);
+        let mut new_leaves = leaves;
+        new_leaves[index] = new_leaf.clone();
+        let new_levels = merkle_levels(new_leaves, blake2b_node);
+
+        // Build the stark.
+        let mut builder = BytesBuilder::<L>::new();
+        let old_root = builder.alloc_public::<BLAKE2BDigestRegister>();
+        let path = builder.alloc_public_merkle_path::<U64Register>(4, depth);
+        let new_leaf_register = builder.alloc_array_public::<U64Register>(4);
+        let new_path = path.with_leaf(new_leaf_register);
+        let roots = builder.blake_merkle_roots::<BLAKE2B>(&[path.clone(), new_path]);
+        builder.assert_equal(&roots[0], &old_root);
+        let new_root = roots[1];
+
+        let num_rows = 1 << log2_ceil(2 * depth * 96);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        // Build the recursive circuit.
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<GoldilocksField, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let rec_data = recursive_builder.build::<Config>();
+
+        // Write trace.
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+        let mut writer = writer_data.public_writer();
+
+        let to_field = |words: &[u64]| {
+            words
+                .iter()
+                .map(|w| u64_to_le_field_bytes(*w))
+                .collect_vec()
+        };
+        let old_root_array: ArrayRegister<U64Register> = old_root.into();
+        writer.write_array(&old_root_array, to_field(&levels[depth][0]));
+        let new_root_array: ArrayRegister<U64Register> = new_root.into();
+        writer.write_array(&new_root_array, to_field(&new_levels[depth][0]));
+        let siblings = merkle_siblings(&levels, index)
+            .iter()
+            .map(|sibling| to_field(sibling))
+            .collect_vec();
+        path.write(&mut writer, &to_field(&levels[0][index]), &siblings, index);
+        writer.write_array(&new_leaf_register, to_field(&new_leaf));
+
+        timed!(timing, "write input", {
+            stark.air_data.write_global_instructions(&mut writer);
+
+            for mut chunk in writer_data.chunks(num_rows) {
+                for i in 0..num_rows {
+                    let mut writer = chunk.window_writer(i);
+                    stark.air_data.write_trace_instructions(&mut writer);
+                }
+            }
+        });
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = timed!(
+            timing,
+            "generate stark proof",
+            stark.prove(&trace, &public, &mut timing).unwrap()
+        );
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = timed!(
+            timing,
+            "generate recursive proof",
+            rec_data.prove(pw).unwrap()
+        );
+        rec_data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::bit::BitRegister;
+use crate::chip::register::Register;
+use crate::chip::trace::writer::AirWriter;
+use crate::math::prelude::*;
+
+pub mod builder;
+
+/// A public Merkle path from a leaf to the root of a binary tree.
+///
+/// The siblings are ordered from the leaf level upwards, and `path_bits[i]` is set if the node
+/// at level `i` of the path is the right child of its parent, so that the bits are the binary
+/// decomposition of the index of the leaf.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerklePathRegister<T> {
+    pub leaf: ArrayRegister<T>,
+    pub siblings: Vec<ArrayRegister<T>>,
+    pub path_bits: ArrayRegister<BitRegister>,
+}
+
+impl<T: Register> MerklePathRegister<T> {
+    /// The number of hashes needed to go from the leaf to the root.
+    pub fn depth(&self) -> usize {
+        self.siblings.len()
+    }
+
+    /// The path of `leaf` at the same position, for example to compute the root of the tree
+    /// after updating the leaf.
+    pub fn with_leaf(&self, leaf: ArrayRegister<T>) -> Self {
+        assert_eq!(leaf.len(), self.leaf.len());
+        Self {
+            leaf,
+            siblings: self.siblings.clone(),
+            path_bits: self.path_bits,
+        }
+    }
+
+    /// Writes the leaf, the siblings and the path bits of the leaf at position `index`.
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        leaf: &[T::Value<W::Field>],
+        siblings: &[Vec<T::Value<W::Field>>],
+        index: usize,
+    ) {
+        assert_eq!(siblings.len(), self.depth());
+        assert!(
+            self.depth() >= usize::BITS as usize || index >> self.depth() == 0,
+            "Leaf index out of range"
+        );
+
+        writer.write_array(&self.leaf, leaf);
+        for (register, sibling) in self.siblings.iter().zip(siblings.iter()) {
+            writer.write_array(register, sibling);
+        }
+        for (i, bit) in self.path_bits.iter().enumerate() {
+            writer.write(&bit, &W::Field::from_canonical_usize((index >> i) & 1));
+        }
+    }
+}
@@ -3,4 +3,6 @@ pub mod bytes;
 pub mod ec;
 pub mod emulated;
 pub mod hash;
+pub mod merkle;
+pub mod rsa;
 pub mod stark;
@@ -0,0 +1,756 @@
+//! RSA signature verification.
+
+use num::BigUint;
+use plonky2::util::log2_ceil;
+use serde::{Deserialize, Serialize};
+
+use crate::chip::arithmetic::expression::ArithmeticExpression;
+use crate::chip::bigint::instruction::FromBigIntInstruction;
+use crate::chip::bigint::register::{BigIntBlockRegister, NB_LIMBS_PER_BLOCK};
+use crate::chip::ec::scalar::LimbBitInstruction;
+use crate::chip::memory::time::Time;
+use crate::chip::register::array::ArrayRegister;
+use crate::chip::register::element::ElementRegister;
+use crate::chip::register::u16::U16Register;
+use crate::chip::register::{Register, RegisterSerializable};
+use crate::chip::trace::writer::AirWriter;
+use crate::chip::uint::register::U32Register;
+use crate::machine::builder::Builder;
+use crate::machine::hash::message::{MessageBuilder, MessageRegister};
+use crate::machine::hash::sha::algorithm::SHAir;
+use crate::machine::hash::sha::builder::SHABuilder;
+use crate::machine::hash::sha::hmac::HMACPure;
+use crate::machine::hash::sha::sha256::register::SHA256DigestRegister;
+use crate::machine::hash::sha::sha256::SHA256;
+use crate::math::prelude::*;
+
+/// The DER encoding of the `DigestInfo` prefix of a SHA-256 digest, as in RFC 8017, section 9.2.
+const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+/// The length in bytes of a SHA-256 digest.
+const DIGEST_LENGTH: usize = 32;
+
+/// The number of 32-bit words of a SHA-256 digest.
+const DIGEST_NB_WORDS: usize = DIGEST_LENGTH / 4;
+
+/// The minimal length in bytes of the padding string `PS` of the encoded message.
+const MIN_PADDING_LENGTH: usize = 8;
+
+/// The minimal length in bytes of a modulus, for which the padding string is long enough.
+const MIN_MODULUS_LENGTH: usize =
+    SHA256_DIGEST_INFO_PREFIX.len() + DIGEST_LENGTH + MIN_PADDING_LENGTH + 3;
+
+/// The largest supported public exponent.
+const MAX_EXPONENT: u32 = 65537;
+
+/// The cycle length of the SHA-256 AIR.
+const SHA256_CYCLE_LENGTH: usize = 64;
+
+/// The logarithm of the number of rows of the exponentiation of a signature, each of which does a
+/// single modular multiplication.
+const RSA_CYCLE_LENGTH_LOG: usize = 5;
+
+/// The number of rows of the exponentiation of a signature.
+const RSA_CYCLE_LENGTH: usize = 1 << RSA_CYCLE_LENGTH_LOG;
+
+/// The public inputs of an RSASSA-PKCS1-v1_5 signature verification with SHA-256.
+///
+/// The signature `s` of the message `m` under the public key `(n, e)` is valid if `s < n` and
+/// `s^e mod n = EM`, where `EM = 0x00 || 0x01 || PS || 0x00 || DigestInfo || SHA256(m)` is the
+/// encoded message of the length of the modulus, padded with the bytes `PS = 0xff...0xff`. The
+/// modulus and the signature are given by little-endian 16-bit limbs, and the exponent is fixed
+/// when the verification is built.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RSASignatureRegister {
+    pub modulus: ArrayRegister<U16Register>,
+    pub exponent: u32,
+    pub signature: ArrayRegister<U16Register>,
+    pub message: MessageRegister,
+    pub digest: SHA256DigestRegister,
+}
+
+impl RSASignatureRegister {
+    /// The length in bytes of the modulus and of the signature.
+    pub fn modulus_length(&self) -> usize {
+        2 * self.modulus.len()
+    }
+
+    /// The maximal length of a signed message.
+    pub fn max_message_length(&self) -> usize {
+        self.message.max_length()
+    }
+
+    /// Writes the big-endian signature of `message` under the public key given by the big-endian
+    /// `modulus` and the exponent of the register, together with the digest of the message.
+    ///
+    /// Panics if the modulus or the signature is not of length `modulus_length`.
+    pub fn write<W: AirWriter>(
+        &self,
+        writer: &mut W,
+        modulus: &[u8],
+        message: &[u8],
+        signature: &[u8],
+    ) {
+        let modulus_length = self.modulus_length();
+        assert_eq!(modulus.len(), modulus_length, "Invalid modulus length");
+        assert_eq!(signature.len(), modulus_length, "Invalid signature length");
+
+        for (register, bytes) in [(self.modulus, modulus), (self.signature, signature)] {
+            for (i, limb) in register.iter().enumerate() {
+                let j = modulus_length - 1 - 2 * i;
+                let value = u16::from_be_bytes([bytes[j - 1], bytes[j]]);
+                writer.write(&limb, &W::Field::from_canonical_u16(value));
+            }
+        }
+        self.message.write(writer, message);
+
+        let digest = SHA256::digest(message);
+        for (i, byte) in digest.iter().enumerate() {
+            writer.write(
+                &digest_byte(&self.digest, i),
+                &W::Field::from_canonical_u8(*byte),
+            );
+        }
+    }
+}
+
+/// The encoded message `EM = 0x00 || 0x01 || PS || 0x00 || DigestInfo || digest` of length
+/// `modulus_length` of the EMSA-PKCS1-v1_5 encoding of a SHA-256 digest.
+///
+/// Panics if the modulus is shorter than `MIN_MODULUS_LENGTH` bytes.
+pub fn pkcs1v15_encode(digest: &[u8], modulus_length: usize) -> Vec<u8> {
+    assert_eq!(digest.len(), DIGEST_LENGTH, "Invalid digest length");
+    assert!(
+        modulus_length >= MIN_MODULUS_LENGTH,
+        "Modulus too short for the encoded message"
+    );
+    let padding_length = modulus_length - SHA256_DIGEST_INFO_PREFIX.len() - DIGEST_LENGTH - 3;
+    let padding = vec![0xff; padding_length];
+    [
+        &[0x00, 0x01],
+        padding.as_slice(),
+        &[0x00],
+        &SHA256_DIGEST_INFO_PREFIX,
+        digest,
+    ]
+    .concat()
+}
+
+/// Verifies the big-endian RSASSA-PKCS1-v1_5 signature of `message` with SHA-256 under the public
+/// key given by the big-endian `modulus` and `exponent`.
+pub fn rsa_pkcs1v15_verify(
+    modulus: &[u8],
+    exponent: u32,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let modulus_length = modulus.len();
+    if modulus_length < MIN_MODULUS_LENGTH || signature.len() != modulus_length {
+        return false;
+    }
+    let n = BigUint::from_bytes_be(modulus);
+    let s = BigUint::from_bytes_be(signature);
+    if s >= n {
+        return false;
+    }
+    let encoded_message = pkcs1v15_encode(&SHA256::digest(message), modulus_length);
+    s.modpow(&BigUint::from(exponent), &n) == BigUint::from_bytes_be(&encoded_message)
+}
+
+/// The schedule of the exponentiation by `exponent` in a cycle of `RSA_CYCLE_LENGTH` rows, in
+/// which the bit `k` is set if the row `k` multiplies the accumulator by the signature and unset
+/// if it squares it.
+///
+/// The accumulator starts from one and the bits of the exponent are processed from the most
+/// significant one, with the rows before the first multiplication squaring one.
+fn exponent_schedule(exponent: u32) -> u32 {
+    let nb_bits = 32 - exponent.leading_zeros();
+    let mut operations = vec![true];
+    for i in (0..nb_bits - 1).rev() {
+        operations.push(false);
+        if (exponent >> i) & 1 == 1 {
+            operations.push(true);
+        }
+    }
+    assert!(
+        operations.len() <= RSA_CYCLE_LENGTH,
+        "The exponentiation does not fit in a cycle"
+    );
+
+    let offset = RSA_CYCLE_LENGTH - operations.len();
+    operations
+        .iter()
+        .enumerate()
+        .filter(|(_, is_mul)| **is_mul)
+        .fold(0, |schedule, (k, _)| schedule | (1 << (offset + k)))
+}
+
+/// The number of rows of the trace of `rsa_pkcs1v15_verify_batch` for `signatures`.
+///
+/// The trace has exactly `2^16` rows, since the limbs of the modular arithmetic are range
+/// checked against a column of the trace. Panics if the hashes or the exponentiations of the
+/// batch do not fit in `2^16` rows.
+pub fn rsa_pkcs1v15_verify_num_rows(signatures: &[RSASignatureRegister]) -> usize {
+    let num_sha_rounds = signatures
+        .iter()
+        .map(|signature| (signature.max_message_length() + 8) / 64 + 1)
+        .sum::<usize>();
+
+    let degree_log = log2_ceil(num_sha_rounds * SHA256_CYCLE_LENGTH);
+    assert!(degree_log <= 16, "The batch does not fit in 2^16 rows");
+    assert!(
+        signatures.len() * RSA_CYCLE_LENGTH <= 1 << 16,
+        "The batch does not fit in 2^16 rows"
+    );
+    1 << 16
+}
+
+pub trait RSAVerifyBuilder: Builder {
+    /// Allocates the public inputs of the verification of an RSA signature under a public key
+    /// with a modulus of `modulus_length` bytes and the exponent `exponent`, of a message of at
+    /// most `max_message_length` bytes.
+    ///
+    /// Panics if the modulus length is not a multiple of 32 bytes or too short for the encoded
+    /// message, or if the exponent is not an odd integer between `3` and `65537`.
+    fn alloc_public_rsa_signature(
+        &mut self,
+        modulus_length: usize,
+        exponent: u32,
+        max_message_length: usize,
+    ) -> RSASignatureRegister {
+        assert_eq!(
+            modulus_length % (2 * NB_LIMBS_PER_BLOCK),
+            0,
+            "The modulus length must be a multiple of 32 bytes"
+        );
+        assert!(
+            modulus_length >= MIN_MODULUS_LENGTH,
+            "Modulus too short for the encoded message"
+        );
+        assert!(
+            exponent % 2 == 1 && (3..=MAX_EXPONENT).contains(&exponent),
+            "Unsupported exponent"
+        );
+        let nb_limbs = modulus_length / 2;
+        RSASignatureRegister {
+            modulus: self.alloc_array_public(nb_limbs),
+            exponent,
+            signature: self.alloc_array_public(nb_limbs),
+            message: self.alloc_public_message(max_message_length),
+            digest: self.alloc_public(),
+        }
+    }
+
+    /// Verifies a batch of RSASSA-PKCS1-v1_5 signatures with SHA-256, under moduli of the same
+    /// length.
+    ///
+    /// For each signature, this checks that `s < n`, computes `s^e mod n` by square-and-multiply
+    /// and constrains it to the encoded message of the SHA-256 digest of the message. All the
+    /// hashes are done in a single SHA-256 AIR padded to the number of rows given by
+    /// `rsa_pkcs1v15_verify_num_rows`, so this can only be called once per builder and not
+    /// together with `sha`.
+    ///
+    /// The exponentiation of a signature takes a cycle of 32 rows, each doing a single modular
+    /// multiplication of the accumulator in the trace. The modulus, the signature, the digest and
+    /// the schedule of the exponentiation are stored in memory and loaded by each row of the cycle
+    /// from the index of the signature, which is chained to the next one at the end of the cycle.
+    /// The cycles after the last signature verify it again.
+    ///
+    /// The result of each modular multiplication is only constrained to be congruent to the
+    /// product, but the final one is equal to the encoded message, which is less than `n` for a
+    /// modulus of `modulus_length` bytes.
+    fn rsa_pkcs1v15_verify_batch(&mut self, signatures: &[RSASignatureRegister])
+    where
+        Self::Instruction: FromBigIntInstruction + From<LimbBitInstruction>,
+        SHA256: SHAir<Self, 64>,
+    {
+        assert!(!signatures.is_empty(), "The batch must not be empty");
+        let nb_limbs = signatures[0].modulus.len();
+        assert!(
+            signatures
+                .iter()
+                .all(|signature| signature.modulus.len() == nb_limbs),
+            "The moduli must have the same length"
+        );
+        let num_rows = rsa_pkcs1v15_verify_num_rows(signatures);
+        let num_cycles = num_rows / RSA_CYCLE_LENGTH;
+
+        // Hash enough chunks for the SHA-256 AIR to take all of the rows.
+        let messages = signatures
+            .iter()
+            .map(|signature| signature.message)
+            .collect::<Vec<_>>();
+        let min_rounds = num_rows / (2 * SHA256_CYCLE_LENGTH) + 1;
+        let digests = self.sha_messages_padded::<SHA256, 64>(&messages, min_rounds);
+
+        // Store the values of each signature at an offset of `stride`, with the blocks of the
+        // integers and the words of the digest at consecutive indices.
+        let stride = (nb_limbs / NB_LIMBS_PER_BLOCK).max(DIGEST_NB_WORDS);
+        let modulus_ptr = self.uninit_slice::<BigIntBlockRegister>();
+        let signature_ptr = self.uninit_slice::<BigIntBlockRegister>();
+        let digest_ptr = self.uninit_slice::<U32Register>();
+        let schedule_ptr = self.uninit_slice::<ElementRegister>();
+        let next_ptr = self.uninit_slice::<ElementRegister>();
+        let zero = Time::zero();
+        let cycle_length = self.constant(&Self::Field::from_canonical_usize(RSA_CYCLE_LENGTH));
+        let last = signatures.len() - 1;
+        for (k, (signature, digest)) in signatures.iter().zip(digests.iter()).enumerate() {
+            self.set_to_expression(digest, signature.digest.expr());
+
+            // The values are read by every row of the cycle of the signature, and those of the
+            // last signature by the remaining cycles as well.
+            let (multiplicity, next) = if k == last {
+                let multiplicity = self.constant(&Self::Field::from_canonical_usize(
+                    RSA_CYCLE_LENGTH * (num_cycles - k),
+                ));
+                (multiplicity, k)
+            } else {
+                (cycle_length, k + 1)
+            };
+            let multiplicity = Some(multiplicity);
+
+            let offset = k * stride;
+            let modulus_blocks = BigIntBlockRegister::split(&signature.modulus);
+            let signature_blocks = BigIntBlockRegister::split(&signature.signature);
+            for (j, (modulus_block, signature_block)) in
+                modulus_blocks.into_iter().zip(signature_blocks).enumerate()
+            {
+                self.store(
+                    &modulus_ptr.get(offset + j),
+                    modulus_block,
+                    &zero,
+                    multiplicity,
+                    None,
+                    None,
+                );
+                self.store(
+                    &signature_ptr.get(offset + j),
+                    signature_block,
+                    &zero,
+                    multiplicity,
+                    None,
+                    None,
+                );
+            }
+            for (w, word) in signature.digest.as_array().iter().enumerate() {
+                self.store(
+                    &digest_ptr.get(offset + w),
+                    word,
+                    &zero,
+                    multiplicity,
+                    None,
+                    None,
+                );
+            }
+
+            let schedule = self.constant(&Self::Field::from_canonical_u32(exponent_schedule(
+                signature.exponent,
+            )));
+            self.store(
+                &schedule_ptr.get(offset),
+                schedule,
+                &zero,
+                multiplicity,
+                None,
+                None,
+            );
+            let next = self.constant(&Self::Field::from_canonical_usize(next * stride));
+            self.store(&next_ptr.get(offset), next, &zero, multiplicity, None, None);
+        }
+
+        let cycle = self.cycle(RSA_CYCLE_LENGTH_LOG);
+
+        // Load the values of the signature at `index`, which starts at the first signature and
+        // moves to the next one at the end of each cycle.
+        let index = self.alloc::<ElementRegister>();
+        self.set_to_expression_first_row(&index, ArithmeticExpression::zero());
+        let modulus = self.alloc_array::<U16Register>(nb_limbs);
+        let s = self.alloc_array::<U16Register>(nb_limbs);
+        let modulus_blocks = BigIntBlockRegister::split(&modulus);
+        let signature_blocks = BigIntBlockRegister::split(&s);
+        for (j, (modulus_block, signature_block)) in modulus_blocks
+            .iter()
+            .zip(signature_blocks.iter())
+            .enumerate()
+        {
+            let ptr = modulus_ptr.get_at_shifted(index, j as i32);
+            self.api().get_into(&ptr, modulus_block, &zero);
+            let ptr = signature_ptr.get_at_shifted(index, j as i32);
+            self.api().get_into(&ptr, signature_block, &zero);
+        }
+        let digest = self.alloc::<SHA256DigestRegister>();
+        for (w, word) in digest.as_array().iter().enumerate() {
+            let ptr = digest_ptr.get_at_shifted(index, w as i32);
+            self.api().get_into(&ptr, &word, &zero);
+        }
+        let schedule = self.load(&schedule_ptr.get_at(index), &zero, None, None);
+        let next = self.load(&next_ptr.get_at(index), &zero, None, None);
+        self.select_next(cycle.end_bit, &next, &index, &index);
+
+        self.api().bigint_assert_less_than(&s, &modulus);
+
+        // Multiply the accumulator, which is one at the start of each cycle, by the signature or
+        // by itself as given by the schedule.
+        let is_mul = self.bit_decomposition(schedule, cycle.start_bit, cycle.end_bit);
+        let one_block = |j: usize| -> ArithmeticExpression<Self::Field> {
+            let mut limbs = vec![Self::Field::ZERO; NB_LIMBS_PER_BLOCK];
+            if j == 0 {
+                limbs[0] = Self::Field::ONE;
+            }
+            ArithmeticExpression::from_constant_vec(limbs)
+        };
+        let accumulator = self.alloc_array::<U16Register>(nb_limbs);
+        let accumulator_blocks = BigIntBlockRegister::split(&accumulator);
+        for (j, block) in accumulator_blocks.iter().enumerate() {
+            self.set_to_expression_first_row(block, one_block(j));
+        }
+        let multiplier = self.alloc_array::<U16Register>(nb_limbs);
+        for ((signature_block, accumulator_block), multiplier_block) in signature_blocks
+            .iter()
+            .zip(accumulator_blocks.iter())
+            .zip(BigIntBlockRegister::split(&multiplier))
+        {
+            self.api().set_select(
+                &is_mul,
+                signature_block,
+                accumulator_block,
+                &multiplier_block,
+            );
+        }
+        let product = self
+            .api()
+            .bigint_mul_mod(&accumulator, &multiplier, &modulus);
+        for (j, (accumulator_block, product_block)) in accumulator_blocks
+            .iter()
+            .zip(BigIntBlockRegister::split(&product))
+            .enumerate()
+        {
+            self.set_to_expression_transition(
+                &accumulator_block.next(),
+                cycle.end_bit.expr() * one_block(j)
+                    + cycle.end_bit.not_expr() * product_block.expr(),
+            );
+        }
+
+        // At the end of the cycle, the product is the encoded message, the bytes of which other
+        // than the digest are constants.
+        let modulus_length = 2 * nb_limbs;
+        let digest_offset = modulus_length - DIGEST_LENGTH;
+        let encoded_message = pkcs1v15_encode(&[0; DIGEST_LENGTH], modulus_length);
+        let encoded_byte = |j: usize| -> ArithmeticExpression<Self::Field> {
+            if j >= digest_offset {
+                digest_byte(&digest, j - digest_offset).expr()
+            } else {
+                ArithmeticExpression::from_constant(Self::Field::from_canonical_u8(
+                    encoded_message[j],
+                ))
+            }
+        };
+        for (i, limb) in product.iter().enumerate() {
+            let j = modulus_length - 1 - 2 * i;
+            let value =
+                encoded_byte(j) + encoded_byte(j - 1) * Self::Field::from_canonical_u32(1 << 8);
+            self.assert_expression_zero(cycle.end_bit.expr() * (limb.expr() - value));
+        }
+    }
+}
+
+impl<B: Builder> RSAVerifyBuilder for B {}
+
+/// The register of the byte `i` of the big-endian digest.
+///
+/// The digest words are big-endian, while their bytes are stored in little-endian order.
+fn digest_byte(digest: &SHA256DigestRegister, i: usize) -> ElementRegister {
+    let bytes = ArrayRegister::<ElementRegister>::from_register_unsafe(*digest.register());
+    bytes.get(4 * (i / 4) + 3 - i % 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::util::timing::TimingTree;
+
+    use super::*;
+    use crate::chip::bigint::instruction::RSAInstruction;
+    use crate::chip::trace::writer::data::AirWriterData;
+    use crate::chip::AirParameters;
+    use crate::machine::bytes::builder::BytesBuilder;
+    use crate::math::goldilocks::cubic::GoldilocksCubicParameters;
+    use crate::plonky2::stark::config::{CurtaConfig, CurtaPoseidonGoldilocksConfig};
+
+    /// Signatures under a 2048-bit and a 4096-bit key with `e = 65537` and a 2048-bit key with
+    /// `e = 3`, as `(modulus, exponent, [(message, signature)])`.
+    const SIGNATURES: [(&str, u32, &[(&str, &str)]); 3] = [
+        (
+            "c437125eccb4c442f93462d80ebfc5746a172e508724a90d06e39de031a897f9\
+             0273f07b0ddf4bbb87c5fb84574b0b9181c92d23b80b4a57dcc911c05991997c\
+             5aeec955443ab17b9ba84eddb96b7f8e7f34bcf608397c07029d71f380d57a78\
+             c432e4b734ba5368e4e48b83a7b197eb6ccf9b781f0de22b092b3a556300217b\
+             7e8a7ebdedc93f821ff1728fa856ea7e78fe2e105ebb1ae2d2cc331b2faf6bd7\
+             021e4e42f3ecd820eb6b5ebc8350b68e8f3f541e9df47abb41406daafc6514ec\
+             642ed73ef0c4495dffc9ad0ade8f0db08d74085431ffcf6482db0c164f201383\
+             b9de9f4f6e8289672316d0f85f923f1da19325bf93a7bb7616d14c14e18f2951",
+            65537,
+            &[
+                (
+                    "",
+                    "1e3021a3e98df4b415b0dd4bed4f996d1dc81164713de9313a7fb18b9efa01a5\
+                     ac5703826e1b4e11fd074555349203b2137512bb06a768f450658b2af342cd4e\
+                     a279ac371a3949345ac604273ba5f8fc2ca69ab8b5a3251efc358253fa524782\
+                     c8383e3502eb0ebbfb8db015692c97a53940dab197fbde27a6dd50636165cee4\
+                     881191f66e5993769ce76aa3f309be979e2efa4cff1bac95a1fd4dc9c6b11091\
+                     8a62c9a67a1c020f9b117dc5d9a3d1b342363f600d717382a9be5aa41a9500d5\
+                     7a770cbaae969ede04f89d6bfdadb946c0699a85aa0e828c0b06ca975efef475\
+                     6261b5363c5f1abae90ff212373a6c52e6196836849b880b07703dca93f3ccaf",
+                ),
+                (
+                    "abc",
+                    "1d2eabcfdb30382a2f2de3cff8a03b3ac475a4a79300fbaa30db6f5a0555c10a\
+                     1cc90666dc266006d1431fee8d28cf49f47c911d6537c39f76bd4cef6a591837\
+                     fa5a86fc394082afda02a324378e236ba28dba461e6c545ea80347485d98de54\
+                     28d1c9b11b226c2ac4a8cc890e3678401e9cca424fa4d726203d8cd20153d98a\
+                     633085ed0e7da80f5b2c0d81ae46882172858016237ef4f9aa2997c45ba9cce6\
+                     68bef84139d240afcebcb2427db63eb4bf0160cba70abec39f3a487a7d2e680e\
+                     94e92c957c6965a34d519ca79e6d5c2c95487144a7e62521bb56b259b06fb8f5\
+                     406617378762d7431648fb5b65e03838de8500ee53d3e4b6ac7c992c60718af1",
+                ),
+                (
+                    "The quick brown fox jumps over the lazy dog",
+                    "b8081b9620363f750a25b3c13ca1604a86f56ffc372436d92f4ac5733514a4fd\
+                     d951ce9c34c87695898a30312024a8cfe1944e4ae6d906c3d6dc15c670aeb1a7\
+                     f1f12caa371776051ee548efcbf0be728ab0761ee1f278ddae1f2387dd4bca7a\
+                     fa2af14433222f9c551ae05a1b2f3bbe0575da31e2a61f9feddb3b8999197ece\
+                     fa4e7dfea41a7fa9cab13905780a1bb1ff7baa42fa16c06edcbef8bf246545bc\
+                     90d52a800c5b212cc64115dea7e072fb8c953b33b0793c97cb8e0db8b275fbb3\
+                     0d057acd9b7363ca6494b149ca5acfe1a179ec282b75c24ad764cd99e9727373\
+                     ebc9ec36293544ad9d328225fcaa03467329b160fcefeb310c9012bce6735415",
+                ),
+            ],
+        ),
+        (
+            "d1559df31b1147515d476cebf8a7182ebaf96b6c5271c795c65d7faa75e464da\
+             ed9bd5d4758119613bb3ad1045337699d241242155d4345f133d3840195f6c11\
+             6520f69df7795acde856efa6e5f6ba98ab37163e1cb285c3d5527a99af774320\
+             1d95d0ded1ead16c1b3b3f195b86fd748836bc39ed404d35fbe96b44ba067027\
+             a037bb6875b6a633215e08248ab3e888caa82d978d3f024fd1a15e2482943c58\
+             144eea790d7d23a36c79fa1fbca707f0a79cad471db25d9ab3d30e44c6e44747\
+             bbcf433c3fe697e784c0b89bf739baa67419873167eb12028d09cad9846dde72\
+             441f0519f257e4c80097c00bb504609131114a84c3bb21bcda5c2adf80e1d101\
+             b5529f0810366bfa5adf2895673a42c2e2279cbbc41604c1336cee1d7551b802\
+             4ab8a39f85c9342f02766ee67b9964e4df0ba06cb173c8063f035a01ffa8b6ac\
+             404591956aa9f990e4cf29f100207ee625929a753b22b71e4cff622bad0884f8\
+             f6c3941991fbb3fc53ae3b558250bc4e603e72233358565202685c03452e799b\
+             48f5c1fd2790f781c4b48eb7b66aba03c2b0b47faef9d7da69002c158cdff3fc\
+             baf30a747b5aa40d4c9498ace11ef6006b2cd4b51a5291f4e4d4be1dba3b783f\
+             19b765c69e5eec9a04eb64dc9c52c910afc9442dec79384d20ef129951535b2e\
+             68d3dc0b07a8368a280607b6fd1560975014645c09396d4894621bfb17b7a38b",
+            65537,
+            &[(
+                "abc",
+                "b838727c670e15006966827383a28d068d68e6d606f20769b20fd3bd7c0f6f62\
+                     0ff46c2cb136d4ffb51f65c712442c8b65a59bb703005f4f751ad4afd3d4f649\
+                     c0902e45a27901720fb54cb6f5b994c275750bcb028fe0529d621b6a6eebd661\
+                     6f9a8d401239c5da815828530ad2d4484d1dbc48e62dd544c8a83965fcd6099c\
+                     cb63e9d99e9670a2c2a0ba06296a15a7553fcbef83f691e1d5761f8a8f164a32\
+                     9d0035f6edb936f85c781f33760f3986383e9efd61236a79f8fedbef9ed91807\
+                     a7268cab77d14134aafa895c3dd7db87f1ea744f20065c774cc8e8beac174e3b\
+                     f510d74fe90787cb9c1f1d2eb96e28a96112900c94bf39d2902da57e0fa280a6\
+                     97b227ffeb965b647f203169b852ce33d0f26ebbd4d0b68fe5078a318ee2dedc\
+                     2810e02ffb6732044d248b0911859723c57d2b4c79dac7ca563e8e32a3c220dc\
+                     3a8e3beb5f85ff6266e1dd28b94863169141e0e0ffb8470c0bc4b94a5573403f\
+                     7c15e5ee5d711c377130a3921b57e41801251ea62d9dd8fc743974c5a460baef\
+                     c5f41fefc054cf2808edb08004443b915699078e3ba75fd3b0314ed87af6b107\
+                     6fde896e277e665dd36bc629c194bbfcd2dee6190675ec7641483fa0759ca7c1\
+                     0e466d54ae992287e19960b5bdf51d3693c4184b1f24f14c74465e8d7d3595ec\
+                     dd9ae95ebd77613fb3e740d42fc9afed7d943cb1a5a6d500aaa54b2968163cbb",
+            )],
+        ),
+        (
+            "d6278c42962680645490a772dacf6ecbc2a8d7bfe34b337cdcd3e168deef7dd3\
+             5776ac2a3af1e6707f460e0d991fb026d10d8283987e59ced25911155b55554f\
+             f9a74bc1a1af01601c5432908c1cbb9f5de9710a2965db4c238cc9f0838536de\
+             480f7556d7127bd26cdd8b7e8247b6e745a5081d6bb44b57e4ba95417d7c7c74\
+             534ed5f18eff8a8ce727a7336976ef1ac8b702fa8b93e92fdd9bacb27189fb27\
+             6baeb6b24b776127f7ff7a68b0c3abfd793c8e23bfa7533428bf9e6fb1a84352\
+             0eb7a13f9d6ebbd18cfd97be5766454fb80b8e1461cdb481c7afc2ec0250dcd3\
+             72077c13a87d5ac63f2ec963a34edcd914bf7e9a03062750d3d470a6fd4d8f5b",
+            3,
+            &[(
+                "abc",
+                "52bb89fee2bbe910face9ce2a987d1c504c66167563848f3afbada5cd5bf3a79\
+                     53bfe4849df7330ec3c0a10d7e2bd2316c15c6a8a34f590365355ced17756a20\
+                     c6535ed8ab90a96dba90579c75f75116d90c072fdba54134cb3f0176c4ad9817\
+                     8e7f9880d69a87a354cc99608decb5a3aa814545dbe21cbe1bd0851d4b5101c2\
+                     e1a6ac813912e4695c66c37f11172edf8ab18d4202772babe5d4c91462079e78\
+                     9ef752959c8967736ede032778474c301cc4d4316902f0ad0b2fc9685cc60c3d\
+                     e3fcacaae204e09a7a59588dc9ef17eca5632fe8caa70a7c4eade8b493cc63db\
+                     731096282d8fa0c81d0a73474c12f593a3b676485b2972ddedfc21d32f3d09b2",
+            )],
+        ),
+    ];
+
+    fn signature_values() -> Vec<(Vec<u8>, u32, Vec<(Vec<u8>, Vec<u8>)>)> {
+        SIGNATURES
+            .iter()
+            .map(|(modulus, exponent, signatures)| {
+                let signatures = signatures
+                    .iter()
+                    .map(|(message, signature)| {
+                        (message.as_bytes().to_vec(), hex::decode(signature).unwrap())
+                    })
+                    .collect();
+                (hex::decode(modulus).unwrap(), *exponent, signatures)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rsa_pkcs1v15_verify_pure() {
+        for (modulus, exponent, signatures) in signature_values() {
+            let n = BigUint::from_bytes_be(&modulus);
+            for (message, signature) in signatures {
+                assert!(rsa_pkcs1v15_verify(
+                    &modulus, exponent, &message, &signature
+                ));
+                assert!(!rsa_pkcs1v15_verify(
+                    &modulus,
+                    exponent,
+                    b"wrong message",
+                    &signature
+                ));
+
+                // The signature with a flipped bit and the one with `s + n`, if it fits.
+                let mut modified = signature.clone();
+                modified[modulus.len() - 1] ^= 1;
+                assert!(!rsa_pkcs1v15_verify(
+                    &modulus, exponent, &message, &modified
+                ));
+                let s_plus_n = (BigUint::from_bytes_be(&signature) + &n).to_bytes_be();
+                if s_plus_n.len() == modulus.len() {
+                    assert!(!rsa_pkcs1v15_verify(
+                        &modulus, exponent, &message, &s_plus_n
+                    ));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_exponent_schedule() {
+        let n = BigUint::from(1_000_000_007u32);
+        let s = BigUint::from(123_456_789u32);
+        for exponent in [3, 5, 17, 257, 0xfffd, 0xffff, 65537] {
+            let schedule = exponent_schedule(exponent);
+            let mut accumulator = BigUint::from(1u32);
+            for k in 0..RSA_CYCLE_LENGTH {
+                let multiplier = if (schedule >> k) & 1 == 1 {
+                    s.clone()
+                } else {
+                    accumulator.clone()
+                };
+                accumulator = accumulator * multiplier % &n;
+            }
+            assert_eq!(accumulator, s.modpow(&BigUint::from(exponent), &n));
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+    struct RSAVerifyTest;
+
+    impl AirParameters for RSAVerifyTest {
+        type Field = GoldilocksField;
+        type CubicParams = GoldilocksCubicParameters;
+
+        type Instruction = RSAInstruction;
+
+        const NUM_ARITHMETIC_COLUMNS: usize = 1404;
+        const NUM_FREE_COLUMNS: usize = 620;
+        const EXTENDED_COLUMNS: usize = 3300;
+    }
+
+    #[test]
+    fn test_rsa_pkcs1v15_verify_batch() {
+        type F = GoldilocksField;
+        type L = RSAVerifyTest;
+        type C = CurtaPoseidonGoldilocksConfig;
+        type Config = <C as CurtaConfig<2>>::GenericConfig;
+
+        let _ = env_logger::builder().is_test(true).try_init();
+
+        let mut timing = TimingTree::new("RSA verify", log::Level::Debug);
+
+        let mut builder = BytesBuilder::<L>::new();
+
+        // The 2048-bit signatures of `abc` with `e = 65537` and with `e = 3`.
+        let signature_values = signature_values()
+            .into_iter()
+            .filter(|(modulus, _, _)| modulus.len() == 256)
+            .map(|(modulus, exponent, signatures)| {
+                let (message, signature) = signatures
+                    .into_iter()
+                    .find(|(message, _)| message == b"abc")
+                    .unwrap();
+                (modulus, exponent, message, signature)
+            })
+            .collect::<Vec<_>>();
+        let signatures = signature_values
+            .iter()
+            .map(|(modulus, exponent, _, _)| {
+                builder.alloc_public_rsa_signature(modulus.len(), *exponent, 64)
+            })
+            .collect::<Vec<_>>();
+        builder.rsa_pkcs1v15_verify_batch(&signatures);
+
+        let num_rows = rsa_pkcs1v15_verify_num_rows(&signatures);
+        let stark = builder.build::<C, 2>(num_rows);
+
+        let mut writer_data = AirWriterData::new(&stark.air_data, num_rows);
+
+        let mut writer = writer_data.public_writer();
+        for (signature, (modulus, _, message, signature_bytes)) in
+            signatures.iter().zip(signature_values.iter())
+        {
+            signature.write(&mut writer, modulus, message, signature_bytes);
+        }
+
+        stark.air_data.write_global_instructions(&mut writer);
+
+        for mut chunk in writer_data.chunks(num_rows) {
+            for i in 0..num_rows {
+                let mut writer = chunk.window_writer(i);
+                stark.air_data.write_trace_instructions(&mut writer);
+            }
+        }
+
+        let (trace, public) = (writer_data.trace, writer_data.public);
+
+        let proof = stark.prove(&trace, &public, &mut timing).unwrap();
+
+        stark.verify(proof.clone(), &public).unwrap();
+
+        let config_rec = CircuitConfig::standard_recursion_config();
+        let mut recursive_builder = CircuitBuilder::<F, 2>::new(config_rec);
+
+        let (proof_target, public_input) =
+            stark.add_virtual_proof_with_pis_target(&mut recursive_builder);
+        stark.verify_circuit(&mut recursive_builder, &proof_target, &public_input);
+
+        let data = recursive_builder.build::<Config>();
+
+        let mut pw = PartialWitness::new();
+
+        pw.set_target_arr(&public_input, &public);
+        stark.set_proof_target(&mut pw, &proof_target, proof);
+
+        let rec_proof = data.prove(pw).unwrap();
+        data.verify(rec_proof).unwrap();
+
+        timing.print();
+    }
+}
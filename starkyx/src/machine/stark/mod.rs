@@ -83,6 +83,12 @@ where
             &self.air_data.range_data
         {
             assert_eq!(table.table.len(), 1);
+            // The table is given by the row index, so a trace of more than `2^RANGE_CHECK_BITS`
+            // rows would accept values which are out of range.
+            assert!(
+                num_rows <= 1 << L::RANGE_CHECK_BITS,
+                "The range check table must have at most 2^RANGE_CHECK_BITS rows"
+            );
             let table_column = table.table[0];
             for i in 0..num_rows {
                 writer.write(&table_column, &L::Field::from_canonical_usize(i), i);
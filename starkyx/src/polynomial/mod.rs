@@ -9,7 +9,7 @@ use num::BigUint;
 
 use self::ops::PolynomialOps;
 use crate::chip::field::parameters::FieldParameters;
-use crate::chip::utils::{bigint_into_u16_digits, biguint_to_16_digits_field};
+use crate::chip::utils::biguint_to_limbs_field;
 use crate::math::prelude::*;
 
 /// A wrapper around a vector of elements to represent a polynomial.
@@ -38,12 +38,12 @@ impl<T: Clone> Polynomial<T> {
         &self.coefficients
     }
 
+    /// The polynomial of the little-endian limbs of `num_bits` bits of `num`, for up to 16 bits.
     pub fn from_biguint_field(num: &BigUint, num_bits: usize, num_limbs: usize) -> Self
     where
         T: Field,
     {
-        assert_eq!(num_bits, 16, "Only 16 bit numbers supported");
-        Self::from_coefficients(biguint_to_16_digits_field(num, num_limbs))
+        Self::from_coefficients(biguint_to_limbs_field(num, num_bits, num_limbs))
     }
 }
 
@@ -281,10 +281,12 @@ pub fn get_powers<T>(x: T, one: T) -> PowersIter<T> {
     }
 }
 
+/// The polynomial of the little-endian limbs of `x` as an element of the field `P`.
+pub fn to_le_limbs_polynomial<F: Field, P: FieldParameters>(x: &BigUint) -> Polynomial<F> {
+    Polynomial::from_biguint_field(x, P::NB_BITS_PER_LIMB, P::NB_LIMBS)
+}
+
+#[deprecated(note = "use `to_le_limbs_polynomial`")]
 pub fn to_u16_le_limbs_polynomial<F: Field, P: FieldParameters>(x: &BigUint) -> Polynomial<F> {
-    let num_limbs = bigint_into_u16_digits(x, P::NB_LIMBS)
-        .iter()
-        .map(|x| F::from_canonical_u16(*x))
-        .collect();
-    Polynomial::from_coefficients(num_limbs)
+    to_le_limbs_polynomial::<F, P>(x)
 }